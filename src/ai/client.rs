@@ -7,11 +7,13 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io::Read;
-use std::sync::{mpsc, Arc, Mutex};
+use std::sync::{Arc, Mutex, mpsc};
 use std::time::{Duration, Instant};
 
-use crate::config::AiConfig;
+use crate::ai::redact::{self, Redaction};
+use crate::config::{AiConfig, SecretsConfig};
 use crate::git;
+use crate::git::secrets::SecretRule;
 
 // ─── Constants ─────────────────────────────────────────────────
 
@@ -29,7 +31,7 @@ const CACHE_MAX_ENTRIES: usize = 50;
 
 // ─── Request / Response Types ──────────────────────────────────
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
 pub struct MentorRequest {
     #[serde(rename = "type")]
     pub request_type: String,
@@ -67,6 +69,8 @@ pub struct RepoContext {
     pub detached_head: bool,
 }
 
+/// Totals from `git diff --stat`. Only the counts are kept, never the file
+/// names, so there is nothing in here to redact.
 #[derive(Debug, Serialize, Clone)]
 pub struct DiffStats {
     pub files_changed: usize,
@@ -132,6 +136,10 @@ pub struct AiClient {
     provider_kind: String,
     client: reqwest::blocking::Client,
     cache: ResponseCache,
+    /// Rules run over every request before it leaves the machine.
+    redaction_rules: Vec<SecretRule>,
+    /// Secrets redacted since the last `take_redactions`.
+    redactions: Mutex<Vec<Redaction>>,
 }
 
 impl AiClient {
@@ -161,9 +169,59 @@ impl AiClient {
             provider_kind,
             client,
             cache: Arc::new(Mutex::new(HashMap::new())),
+            redaction_rules: git::secrets::rules_for(&SecretsConfig::default()),
+            redactions: Mutex::new(Vec::new()),
         })
     }
 
+    /// Redact requests with the repository's secret rules (custom patterns,
+    /// rule packs) instead of the defaults.
+    pub fn with_secrets_config(mut self, config: &SecretsConfig) -> Self {
+        self.redaction_rules = git::secrets::rules_for(config);
+        self
+    }
+
+    /// Secrets redacted from requests since the last call, oldest first.
+    pub fn take_redactions(&self) -> Vec<Redaction> {
+        self.redactions
+            .lock()
+            .map(|mut r| std::mem::take(&mut *r))
+            .unwrap_or_default()
+    }
+
+    /// Copy of `request` with every secret replaced by a placeholder. What
+    /// was removed is kept for `take_redactions`.
+    fn redacted(&self, request: &MentorRequest) -> MentorRequest {
+        let mut request = request.clone();
+        let found = redact::redact_request(&mut request, &self.redaction_rules);
+        self.record_redactions(&request.request_type, found);
+        request
+    }
+
+    /// `text` with every secret redacted, cut to `DIFF_TRUNCATE_AT` at a line
+    /// boundary. Redacting before the cut means a secret straddling it can't
+    /// be left as a fragment the rules no longer match.
+    fn redacted_excerpt(&self, text: &str, field: &'static str, request_type: &str) -> String {
+        let mut found = Vec::new();
+        let text = redact::redact_text(text, field, &self.redaction_rules, &mut found);
+        self.record_redactions(request_type, found);
+        truncate_at_line(&text, DIFF_TRUNCATE_AT)
+    }
+
+    fn record_redactions(&self, request_type: &str, found: Vec<Redaction>) {
+        if found.is_empty() {
+            return;
+        }
+        log::info!(
+            "AI request: type={} {}",
+            request_type,
+            redact::summary(&found)
+        );
+        if let Ok(mut redactions) = self.redactions.lock() {
+            redactions.extend(found);
+        }
+    }
+
     /// The active provider's display name (e.g. "Amazon Bedrock", "Ollama").
     pub fn provider_name(&self) -> &str {
         self.provider.name()
@@ -188,10 +246,10 @@ impl AiClient {
     /// Look up a cached response. Returns None if not found or expired.
     fn get_cached(&self, key: &str) -> Option<String> {
         let cache = self.cache.lock().ok()?;
-        if let Some(entry) = cache.get(key) {
-            if entry.created.elapsed() < CACHE_TTL {
-                return Some(entry.response.clone());
-            }
+        if let Some(entry) = cache.get(key)
+            && entry.created.elapsed() < CACHE_TTL
+        {
+            return Some(entry.response.clone());
         }
         None
     }
@@ -202,14 +260,13 @@ impl AiClient {
             // Evict expired entries
             cache.retain(|_, v| v.created.elapsed() < CACHE_TTL);
             // Evict oldest if at capacity
-            if cache.len() >= CACHE_MAX_ENTRIES {
-                if let Some(oldest_key) = cache
+            if cache.len() >= CACHE_MAX_ENTRIES
+                && let Some(oldest_key) = cache
                     .iter()
                     .min_by_key(|(_, v)| v.created)
                     .map(|(k, _)| k.clone())
-                {
-                    cache.remove(&oldest_key);
-                }
+            {
+                cache.remove(&oldest_key);
             }
             cache.insert(
                 key,
@@ -225,6 +282,8 @@ impl AiClient {
     /// For Bedrock, sends the full MentorRequest JSON to Lambda.
     /// For other providers, builds prompts client-side and calls provider.chat().
     fn call(&self, request: &MentorRequest) -> Result<String> {
+        let request = &self.redacted(request);
+
         // Check cache first
         let ckey = cache_key(request);
         if let Some(cached) = self.get_cached(&ckey) {
@@ -332,10 +391,10 @@ impl AiClient {
         resp: reqwest::blocking::Response,
         status: u16,
     ) -> Result<String> {
-        if let Ok(body) = resp.json::<MentorApiResponse>() {
-            if let Some(err) = body.error {
-                anyhow::bail!("{}", err);
-            }
+        if let Ok(body) = resp.json::<MentorApiResponse>()
+            && let Some(err) = body.error
+        {
+            anyhow::bail!("{}", err);
         }
         anyhow::bail!("{}", classify_http_error(status));
     }
@@ -344,6 +403,7 @@ impl AiClient {
     /// Reads the response body incrementally to show tokens as they arrive.
     #[allow(dead_code)]
     fn call_bedrock_with_streaming(&self, request: &MentorRequest) -> Result<String> {
+        let request = &self.redacted(request);
         let body = serde_json::to_value(request).context("Failed to serialize request")?;
 
         let mut last_error = None;
@@ -529,12 +589,7 @@ impl AiClient {
     pub fn review_diff(&self, file_path: &str, diff_content: &str) -> Result<String> {
         let branch = git::branch::BranchOps::current().ok();
 
-        // Truncate diff if too long
-        let diff_text = if diff_content.len() > DIFF_TRUNCATE_AT {
-            format!("{}...(truncated)", &diff_content[..DIFF_TRUNCATE_AT])
-        } else {
-            diff_content.to_string()
-        };
+        let diff_text = self.redacted_excerpt(diff_content, "diff", "review");
 
         let context = RepoContext {
             repo_path: None,
@@ -600,12 +655,8 @@ impl AiClient {
         let conflict_files: Vec<String> = status.conflicts.iter().map(|f| f.path.clone()).collect();
         let merge_state = git::merge::get_merge_state();
 
-        // Truncate if too long
-        let conflict_text = if conflict_content.len() > DIFF_TRUNCATE_AT {
-            format!("{}...(truncated)", &conflict_content[..DIFF_TRUNCATE_AT])
-        } else {
-            conflict_content.to_string()
-        };
+        let conflict_text =
+            self.redacted_excerpt(conflict_content, "conflict_diff", "merge_resolve");
 
         let context = RepoContext {
             repo_path: None,
@@ -683,6 +734,79 @@ impl AiClient {
         self.call(&request)
     }
 
+    /// Draft release notes for `tag` from the commits since `previous`.
+    pub fn draft_release_notes(
+        &self,
+        tag: &str,
+        previous: Option<&str>,
+        commits: &[git::CommitEntry],
+    ) -> Result<String> {
+        let mut listing = match previous {
+            Some(prev) => format!("Release: {} (changes since {})\n\nCommits:\n", tag, prev),
+            None => format!("Release: {} (first release)\n\nCommits:\n", tag),
+        };
+        for (i, c) in commits.iter().enumerate() {
+            // Keep the prompt bounded on long release ranges
+            if listing.len() > DIFF_TRUNCATE_AT * 2 {
+                listing.push_str(&format!("...({} more commits)\n", commits.len() - i));
+                break;
+            }
+            listing.push_str(&format!(
+                "- {} {} ({})\n",
+                c.short_hash, c.message, c.author
+            ));
+        }
+
+        let request = MentorRequest {
+            request_type: "release_notes".to_string(),
+            context: None,
+            query: Some(listing),
+            error: None,
+        };
+        self.call(&request)
+    }
+
+    /// Explain why a commit changed a file, for the blame view. `line` is the
+    /// blamed line the user selected.
+    pub fn explain_change(
+        &self,
+        file_path: &str,
+        commit: &git::blame::BlameCommit,
+        message: &str,
+        diff: &str,
+        line: &str,
+    ) -> Result<String> {
+        let diff_text = self.redacted_excerpt(diff, "diff", "explain_change");
+        let context = RepoContext {
+            repo_path: None,
+            branch: None,
+            staged_files: vec![file_path.to_string()],
+            unstaged_files: vec![],
+            diff_stats: None,
+            diff: Some(diff_text),
+            conflict_files: vec![],
+            conflict_diff: None,
+            has_conflicts: false,
+            merge_type: None,
+            detached_head: false,
+        };
+        let query = format!(
+            "File: {}\nCommit: {} by {}\n\nCommit message:\n{}\n\nSelected line:\n{}",
+            file_path,
+            commit.short_hash(),
+            commit.author,
+            message,
+            line.trim()
+        );
+        let request = MentorRequest {
+            request_type: "explain_change".to_string(),
+            context: Some(context),
+            query: Some(query),
+            error: None,
+        };
+        self.call(&request)
+    }
+
     /// Get AI recommendation for resetting to a specific commit.
     pub fn suggest_reset(
         &self,
@@ -725,12 +849,13 @@ impl AiClient {
                 return rx;
             }
         };
-        let request = MentorRequest {
+        // The transcript carries tool output such as `cat .env`
+        let request = self.redacted(&MentorRequest {
             request_type: "agent".to_string(),
             context: Some(ctx),
             query: Some(user_message.to_string()),
             error: None,
-        };
+        });
 
         let endpoint = self.endpoint.clone();
         let api_key = self.api_key.clone();
@@ -835,7 +960,9 @@ impl AiClient {
 
 /// Build repository context from the current git state.
 fn build_repo_context(include_diff: bool) -> Result<RepoContext> {
-    let repo_path = git::run_git(&["rev-parse", "--show-toplevel"]).ok();
+    let repo_path = git::repo::current()
+        .ok()
+        .map(|repo| repo.root().display().to_string());
     let branch = git::branch::BranchOps::current().ok();
     let detached_head = branch.as_deref() == Some("HEAD");
 
@@ -973,6 +1100,17 @@ fn parse_stat_line(stat: &str) -> (usize, usize, usize) {
     (files, ins, del)
 }
 
+/// `text` cut to at most `limit` bytes, at the last line break before the
+/// limit when there is one.
+fn truncate_at_line(text: &str, limit: usize) -> String {
+    if text.len() <= limit {
+        return text.to_string();
+    }
+    let head = &text[..text.floor_char_boundary(limit)];
+    let cut = head.rfind('\n').map_or(head.len(), |i| i + 1);
+    format!("{}...(truncated)", &text[..cut])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1007,24 +1145,6 @@ mod tests {
     fn test_mentor_request_serialization() {
         let req = MentorRequest {
             request_type: "commit_suggestion".to_string(),
-            context: Some(RepoContext {
-                repo_path: None,
-                branch: Some("main".to_string()),
-                staged_files: vec![],
-                unstaged_files: vec![],
-                diff_stats: None,
-                diff: None,
-                conflict_files: vec![],
-                conflict_diff: None,
-                has_conflicts: false,
-                merge_type: None,
-                detached_head: false,
-            }),
-            query: None,
-            error: None,
-        };
-        let r2 = MentorRequest {
-            request_type: "explain".to_string(),
             context: Some(RepoContext {
                 repo_path: None,
                 branch: Some("main".to_string()),
@@ -1091,6 +1211,7 @@ mod tests {
         let r1 = MentorRequest {
             request_type: "explain".to_string(),
             context: Some(RepoContext {
+                repo_path: None,
                 branch: Some("main".to_string()),
                 staged_files: vec![],
                 unstaged_files: vec![],
@@ -1108,6 +1229,7 @@ mod tests {
         let r2 = MentorRequest {
             request_type: "explain".to_string(),
             context: Some(RepoContext {
+                repo_path: None,
                 branch: Some("feature".to_string()),
                 staged_files: vec![],
                 unstaged_files: vec![],
@@ -1167,6 +1289,49 @@ mod tests {
         assert!(cache.contains_key("overflow"));
     }
 
+    #[test]
+    fn test_redacted_request_records_secrets() {
+        let client = make_test_client();
+        let secret = concat!("AKIA", "IOSFODNN7EXAMPLE");
+        let request = MentorRequest {
+            request_type: "agent".to_string(),
+            context: None,
+            query: Some(format!("Tool: cat .env\nAWS_KEY={}", secret)),
+            error: None,
+        };
+        let sent = client.redacted(&request);
+        assert!(!sent.query.as_deref().unwrap().contains(secret));
+        assert_ne!(cache_key(&sent), cache_key(&request));
+
+        let redactions = client.take_redactions();
+        assert_eq!(redactions.len(), 1);
+        assert_eq!(redactions[0].rule_name, "AWS Access Key ID");
+        assert!(client.take_redactions().is_empty());
+    }
+
+    #[test]
+    fn test_redacted_excerpt_redacts_before_cutting() {
+        let client = make_test_client();
+        let secret = concat!("AKIA", "IOSFODNN7EXAMPLE");
+        // The key straddles the cut, so truncating first would send half of it
+        let padding = "x".repeat(DIFF_TRUNCATE_AT - 20);
+        let diff = format!("+{}\n+AWS_KEY={}\n+more\n", padding, secret);
+        let excerpt = client.redacted_excerpt(&diff, "diff", "review");
+        assert!(!excerpt.contains(&secret[..8]));
+        assert!(excerpt.ends_with("\n...(truncated)"));
+        assert_eq!(client.take_redactions().len(), 1);
+    }
+
+    #[test]
+    fn test_truncate_at_line() {
+        assert_eq!(truncate_at_line("short\n", 10), "short\n");
+        assert_eq!(
+            truncate_at_line("one\ntwo\nthree\n", 10),
+            "one\ntwo\n...(truncated)"
+        );
+        assert_eq!(truncate_at_line("ééééé", 5), "éé...(truncated)");
+    }
+
     // ── classify_http_error tests ────────────────────────────────
 
     #[test]
@@ -1256,9 +1421,11 @@ mod tests {
         assert!(AiClient::from_config(&cfg).is_some());
         // But validate() should flag the bad scheme
         let issues = cfg.validate();
-        assert!(issues
-            .iter()
-            .any(|i| i.contains("must start with https://")));
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.contains("must start with https://"))
+        );
     }
 
     // ── is_configured tests ──────────────────────────────────────
@@ -198,6 +198,42 @@ Use comments (lines starting with #) to organize sections, for example:
 
 Keep the output clean and production-ready."#;
 
+pub const PROMPT_RELEASE_NOTES: &str = r#"You are a release manager writing release notes from a list of commits.
+
+Your role:
+- Group the changes into sections: Features, Fixes, Other Changes (omit empty sections)
+- Rewrite commit subjects into short, user-facing bullet points
+- Merge commits that describe the same change into one bullet
+- Leave out merge commits, version bumps and purely internal chores unless they matter to users
+- Call out breaking changes first under a "Breaking Changes" section
+
+Output Markdown suitable for a GitHub Release body:
+## Features
+- <change>
+
+## Fixes
+- <change>
+
+Do not invent changes that are not in the commit list. Do not add a title or closing remarks.
+Keep the notes under 300 words."#;
+
+pub const PROMPT_EXPLAIN_CHANGE: &str = r#"You are a senior developer explaining the history behind a piece of code.
+
+You are given a commit found through `git blame`: its full message, the diff it made to one file, and the line the user is asking about.
+
+Your role:
+- Explain WHY the change was made, using the commit message as the primary source
+- Describe what the diff changed around the selected line in a sentence or two
+- Point out intent that is implied by the diff but not stated in the message
+- Say plainly when the message and diff do not explain the motivation; do not invent one
+
+Format your response as:
+1. Why (1-3 sentences)
+2. What changed (brief bullets)
+3. Worth knowing (optional: risks, follow-ups, related code to check)
+
+Keep it under 200 words."#;
+
 pub const PROMPT_AGENT: &str = r#"You are a Git operations agent inside the 'zit' terminal tool. The user describes what they want to do in plain English, and you figure out the git commands to make it happen.
 
 Rules:
@@ -232,6 +268,8 @@ pub fn system_prompt_for(request_type: &str) -> &'static str {
         "merge_resolve" => PROMPT_MERGE_RESOLVE,
         "merge_strategy" => PROMPT_MERGE_STRATEGY,
         "generate_gitignore" => PROMPT_GITIGNORE,
+        "release_notes" => PROMPT_RELEASE_NOTES,
+        "explain_change" => PROMPT_EXPLAIN_CHANGE,
         "agent" => PROMPT_AGENT,
         _ => PROMPT_EXPLAIN,
     }
@@ -423,6 +461,19 @@ pub fn build_user_message(
                 file_listing, existing
             )
         }
+        "release_notes" => {
+            let commits = query.unwrap_or("No commits in range.");
+            format!("{}\n\nWrite release notes for this release.", commits)
+        }
+        "explain_change" => {
+            let commit = query.unwrap_or("No commit details provided.");
+            let diff = ctx.diff.as_deref().unwrap_or("No diff provided");
+            let trimmed: String = diff.chars().take(DIFF_TRUNCATE_AT).collect();
+            format!(
+                "{}\n\nDiff:\n{}\n\nExplain why this change was made.",
+                commit, trimmed
+            )
+        }
         _ => {
             // explain, recommend, ask_question
             let q = query.unwrap_or("Explain the current repository state.");
@@ -451,6 +502,8 @@ mod tests {
             "review",
             "merge_resolve",
             "merge_strategy",
+            "release_notes",
+            "explain_change",
         ];
         for t in &types {
             let prompt = system_prompt_for(t);
@@ -466,6 +519,7 @@ mod tests {
     #[test]
     fn test_format_context_empty() {
         let ctx = RepoContext {
+            repo_path: None,
             branch: None,
             staged_files: vec![],
             unstaged_files: vec![],
@@ -483,6 +537,7 @@ mod tests {
     #[test]
     fn test_format_context_with_conflicts() {
         let ctx = RepoContext {
+            repo_path: None,
             branch: Some("main".to_string()),
             staged_files: vec![],
             unstaged_files: vec![],
@@ -502,6 +557,7 @@ mod tests {
     #[test]
     fn test_build_user_message_commit() {
         let ctx = RepoContext {
+            repo_path: None,
             branch: Some("main".to_string()),
             staged_files: vec!["src/main.rs".to_string()],
             unstaged_files: vec![],
@@ -656,7 +656,7 @@ mod tests {
 
     #[test]
     fn test_create_provider_ollama_no_key() {
-        std::env::remove_var("ZIT_AI_API_KEY");
+        unsafe { std::env::remove_var("ZIT_AI_API_KEY") };
         let config = AiConfig {
             enabled: true,
             provider: "ollama".to_string(),
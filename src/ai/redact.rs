@@ -0,0 +1,297 @@
+//! Secret redaction for outbound AI requests. Every string zit sends to a
+//! provider — diffs, file names, questions, agent tool output — is run
+//! through the secret rules first, and each match is replaced with a
+//! placeholder naming the rule that caught it.
+
+use super::client::{MentorRequest, RepoContext};
+use crate::git::secrets::{self, SecretRule};
+
+/// Replacements per line before giving up, in case a rule keeps matching
+/// its own output.
+const MAX_PER_LINE: usize = 16;
+
+/// Rule name recorded for a private key block.
+const PRIVATE_KEY: &str = "Private Key";
+
+/// A secret removed from an AI request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redaction {
+    pub rule_name: String,
+    /// Request field the secret was found in (`diff`, `query`, …).
+    pub field: &'static str,
+    /// Masked secret, as shown in scan findings.
+    pub preview: String,
+}
+
+/// Text that replaces a secret caught by `rule_name`.
+pub fn placeholder(rule_name: &str) -> String {
+    format!("[REDACTED {}]", rule_name)
+}
+
+/// Redact every string of `request` in place.
+pub fn redact_request(request: &mut MentorRequest, rules: &[SecretRule]) -> Vec<Redaction> {
+    let mut found = Vec::new();
+    redact_option(&mut request.query, "query", rules, &mut found);
+    redact_option(&mut request.error, "error", rules, &mut found);
+    if let Some(ctx) = request.context.as_mut() {
+        // Destructured in full so a new text field can't be sent unredacted
+        let RepoContext {
+            repo_path,
+            branch,
+            staged_files,
+            unstaged_files,
+            diff_stats: _, // counts only
+            diff,
+            conflict_files,
+            conflict_diff,
+            has_conflicts: _,
+            merge_type,
+            detached_head: _,
+        } = ctx;
+        redact_option(repo_path, "repo_path", rules, &mut found);
+        redact_option(branch, "branch", rules, &mut found);
+        redact_option(diff, "diff", rules, &mut found);
+        redact_option(conflict_diff, "conflict_diff", rules, &mut found);
+        redact_option(merge_type, "merge_type", rules, &mut found);
+        for (field, files) in [
+            ("staged_files", staged_files),
+            ("unstaged_files", unstaged_files),
+            ("conflict_files", conflict_files),
+        ] {
+            for file in files.iter_mut() {
+                *file = redact_text(file, field, rules, &mut found);
+            }
+        }
+    }
+    found
+}
+
+fn redact_option(
+    value: &mut Option<String>,
+    field: &'static str,
+    rules: &[SecretRule],
+    found: &mut Vec<Redaction>,
+) {
+    if let Some(text) = value.as_mut() {
+        *text = redact_text(text, field, rules, found);
+    }
+}
+
+/// `text` with every secret replaced by its placeholder. Each replaced
+/// secret is pushed to `found`. The body of a PEM private key is dropped
+/// along with its `BEGIN` line, which is all the rules match on.
+pub fn redact_text(
+    text: &str,
+    field: &'static str,
+    rules: &[SecretRule],
+    found: &mut Vec<Redaction>,
+) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_private_key = false;
+    for line in text.split_inclusive('\n') {
+        let newline = if line.ends_with('\n') { "\n" } else { "" };
+        if in_private_key {
+            if line.contains("-----END") && line.contains("PRIVATE KEY-----") {
+                in_private_key = false;
+            }
+            continue;
+        }
+        if line.contains("-----BEGIN") && line.contains("PRIVATE KEY-----") {
+            // A one-line key ends on the same line
+            in_private_key = !line.contains("-----END");
+            found.push(Redaction {
+                rule_name: PRIVATE_KEY.to_string(),
+                field,
+                preview: "----****".to_string(),
+            });
+            out.push_str(&placeholder(PRIVATE_KEY));
+            out.push_str(newline);
+            continue;
+        }
+        out.push_str(&redact_line(line, field, rules, found));
+    }
+    out
+}
+
+fn redact_line(
+    line: &str,
+    field: &'static str,
+    rules: &[SecretRule],
+    found: &mut Vec<Redaction>,
+) -> String {
+    let mut line = line.to_string();
+    let lowercase = line.to_lowercase();
+    for rule in rules.iter().filter(|r| r.may_match(&lowercase)) {
+        for _ in 0..MAX_PER_LINE {
+            let Some((matched, _)) = rule.check_text(&line) else {
+                break;
+            };
+            let secret = matched.to_string();
+            if secret.is_empty() {
+                break;
+            }
+            found.push(Redaction {
+                rule_name: rule.name.clone(),
+                field,
+                preview: secrets::redact_match(&secret),
+            });
+            line = line.replace(&secret, &placeholder(&rule.name));
+        }
+    }
+    line
+}
+
+/// One-line account of `redactions` for the status bar, e.g.
+/// `2 secrets redacted from the AI request (AWS Access Key ID, JWT Token)`.
+pub fn summary(redactions: &[Redaction]) -> String {
+    let mut rules: Vec<&str> = Vec::new();
+    for r in redactions {
+        if !rules.contains(&r.rule_name.as_str()) {
+            rules.push(&r.rule_name);
+        }
+    }
+    let noun = if redactions.len() == 1 {
+        "secret"
+    } else {
+        "secrets"
+    };
+    format!(
+        "{} {} redacted from the AI request ({})",
+        redactions.len(),
+        noun,
+        rules.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::client::RepoContext;
+    use crate::config::SecretsConfig;
+
+    fn rules() -> Vec<SecretRule> {
+        secrets::rules_for(&SecretsConfig::default())
+    }
+
+    const AWS_KEY: &str = concat!("AKIA", "IOSFODNN7EXAMPLE");
+
+    #[test]
+    fn test_redact_text_replaces_secret() {
+        let mut found = Vec::new();
+        let text = format!("+aws_access_key_id = {}\n unchanged line\n", AWS_KEY);
+        let out = redact_text(&text, "diff", &rules(), &mut found);
+        assert!(!out.contains(AWS_KEY));
+        assert!(out.contains("[REDACTED AWS Access Key ID]"));
+        assert!(out.ends_with(" unchanged line\n"));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].field, "diff");
+        assert_eq!(found[0].preview, "AKIA****");
+    }
+
+    #[test]
+    fn test_redact_text_covers_whole_secret() {
+        let mut found = Vec::new();
+        let text = "+password = \"MyS3cureP@ssw0rd!\"\n";
+        let out = redact_text(text, "diff", &rules(), &mut found);
+        assert_eq!(
+            out,
+            "+password = \"[REDACTED Generic Password Assignment]\"\n"
+        );
+
+        let text = format!("AWS_KEY={}\n", AWS_KEY);
+        let out = redact_text(&text, "diff", &rules(), &mut found);
+        assert_eq!(out, "AWS_KEY=[REDACTED AWS Access Key ID]\n");
+    }
+
+    #[test]
+    fn test_redact_text_ignores_rule_path() {
+        let pack = r#"
+[[rules]]
+id = "signing-key"
+regex = '''signing_key:\s*([A-Za-z0-9+/=]{24,})'''
+path = '''\.ya?ml$'''
+"#;
+        let rules = crate::git::gitleaks::parse_rules(pack).unwrap();
+        let mut found = Vec::new();
+        let text = "+signing_key: q8Zr3LmX9vTb2NwKp5YhGd7Fs4Jc+/==\n";
+        let out = redact_text(text, "diff", &rules, &mut found);
+        assert_eq!(out, "+signing_key: [REDACTED signing-key]\n");
+        assert_eq!(found.len(), 1);
+    }
+
+    #[test]
+    fn test_redact_text_drops_private_key_body() {
+        let mut found = Vec::new();
+        let text = concat!(
+            "before\n",
+            "-----BEGIN RSA ",
+            "PRIVATE KEY-----\n",
+            "MIIEowIBAAKCAQEAsecretbody\n",
+            "-----END RSA PRIVATE KEY-----\n",
+            "after\n"
+        );
+        let out = redact_text(text, "query", &rules(), &mut found);
+        assert_eq!(out, "before\n[REDACTED Private Key]\nafter\n");
+        assert_eq!(found.len(), 1);
+    }
+
+    #[test]
+    fn test_redact_text_leaves_clean_text_alone() {
+        let mut found = Vec::new();
+        let text = "fn main() {\n    println!(\"hello\");\n}";
+        assert_eq!(redact_text(text, "diff", &rules(), &mut found), text);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_redact_request_covers_every_field() {
+        let token = concat!("ghp_", "aBcDeFgHiJkLmNoPqRsTuVwXyZ0123456789");
+        let mut request = MentorRequest {
+            request_type: "agent".to_string(),
+            context: Some(RepoContext {
+                repo_path: None,
+                branch: Some("main".to_string()),
+                staged_files: vec![format!("{}.txt", AWS_KEY)],
+                unstaged_files: vec![],
+                diff_stats: None,
+                diff: Some(format!("+key = {}\n", AWS_KEY)),
+                conflict_files: vec![],
+                conflict_diff: None,
+                has_conflicts: false,
+                merge_type: None,
+                detached_head: false,
+            }),
+            query: Some(format!("Tool: cat .env\nGITHUB_TOKEN={}", token)),
+            error: None,
+        };
+        let found = redact_request(&mut request, &rules());
+        let json = serde_json::to_string(&request).unwrap();
+        assert!(!json.contains(AWS_KEY));
+        assert!(!json.contains(token));
+        let fields: Vec<&str> = found.iter().map(|r| r.field).collect();
+        assert!(fields.contains(&"query"));
+        assert!(fields.contains(&"diff"));
+        assert!(fields.contains(&"staged_files"));
+    }
+
+    #[test]
+    fn test_summary_lists_rules_once() {
+        let redaction = |rule: &str| Redaction {
+            rule_name: rule.to_string(),
+            field: "diff",
+            preview: "****".to_string(),
+        };
+        assert_eq!(
+            summary(&[redaction("JWT Token")]),
+            "1 secret redacted from the AI request (JWT Token)"
+        );
+        assert_eq!(
+            summary(&[
+                redaction("JWT Token"),
+                redaction("Private Key"),
+                redaction("JWT Token")
+            ]),
+            "3 secrets redacted from the AI request (JWT Token, Private Key)"
+        );
+    }
+}
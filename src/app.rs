@@ -1,13 +1,15 @@
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
-use std::sync::{mpsc, Arc};
+use std::sync::{Arc, mpsc};
 
 use crate::ai::client::AiClient;
+use crate::ai::redact;
 use crate::config::Config;
 use crate::git;
 use crate::ui::{
-    agent, ai_mentor, bisect, branches, cherry_pick, commit, dashboard, github, merge_resolve,
-    reflog, staging, stash, time_travel, timeline, workflow_builder,
+    agent, ai_mentor, bisect, blame, branches, cherry_pick, commit, dashboard, file_history,
+    github, lfs, merge_resolve, rebase, reflog, secret_audit, staging, stash, submodules, tags,
+    time_travel, timeline, workflow_builder, worktrees,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -26,6 +28,14 @@ pub enum View {
     Bisect,
     CherryPick,
     Agent,
+    Worktrees,
+    Submodules,
+    Tags,
+    Rebase,
+    FileHistory,
+    Blame,
+    SecretAudit,
+    Lfs,
 }
 
 /// Popup dialog state.
@@ -60,6 +70,11 @@ pub enum Popup {
         pending_action: SecretPendingAction,
         selected: usize,
     },
+    /// Staged files that break the commit policy; shown before committing.
+    PolicyWarning {
+        findings: Vec<git::policy::PolicyFinding>,
+        selected: usize,
+    },
 }
 
 /// A follow-up suggestion item shown after AI responses.
@@ -107,11 +122,27 @@ pub enum ConfirmAction {
     ClearStash,
     AbortMerge,
     ContinueMerge,
-    MergePullRequest { number: u64, method: String },
+    MergePullRequest {
+        number: u64,
+        method: String,
+    },
     ClosePullRequest(u64),
     DiscardFile(String),
+    DiscardLines {
+        path: String,
+        hunk: git::diff::Hunk,
+        lines: Vec<usize>,
+    },
     ForceStageWithSecrets(SecretPendingAction),
     ForceCommitWithSecrets,
+    ForceCommitWithPolicyIssues,
+    RemoveWorktree {
+        path: std::path::PathBuf,
+        force: bool,
+    },
+    DeleteTag(String),
+    CreateRelease(String),
+    StartRebase,
 }
 
 #[derive(Debug, Clone)]
@@ -128,6 +159,13 @@ pub enum InputAction {
     AiSetupEndpoint,
     AiSetupApiKey,
     StashPush,
+    AddWorktree,
+    CreateTag,
+    TagMessage,
+    RebaseReword,
+    FileHistory,
+    SecretAuditRange,
+    LfsTrack,
 }
 
 /// Describes which AI action is in flight.
@@ -146,6 +184,8 @@ pub enum AiAction {
     ResetSuggest,
     GenerateGitignore,
     AgentChat,
+    ReleaseNotes(String),  // tag the notes are drafted for
+    ExplainChange(String), // commit hash being explained
 }
 
 pub struct App {
@@ -178,13 +218,25 @@ pub struct App {
     pub bisect_state: bisect::BisectState,
     pub cherry_pick_state: cherry_pick::CherryPickState,
     pub agent_state: agent::AgentState,
+    pub worktrees_state: worktrees::WorktreesState,
+    pub submodules_state: submodules::SubmodulesState,
+    pub tags_state: tags::TagsState,
+    pub rebase_state: rebase::RebaseState,
+    pub file_history_state: file_history::FileHistoryState,
+    pub blame_state: blame::BlameState,
+    pub secret_audit_state: secret_audit::SecretAuditState,
+    pub lfs_state: lfs::LfsState,
+    /// Set when the working directory moved to another repository or
+    /// worktree; the event loop restarts the file watcher.
+    pub repo_switched: bool,
 }
 
 impl App {
     pub fn new(config: Config) -> Self {
         // Validate AI config and warn about issues
         let ai_issues = config.ai.validate();
-        let ai_client = AiClient::from_config(&config.ai);
+        let ai_client =
+            AiClient::from_config(&config.ai).map(|c| c.with_secrets_config(&config.secrets));
         let status_message = if !ai_issues.is_empty() {
             Some(format!("⚠ AI config: {}", ai_issues[0]))
         } else if config.ai.is_ready() && ai_client.is_some() {
@@ -192,6 +244,11 @@ impl App {
         } else {
             None
         };
+        let commit_state = commit::CommitState {
+            conventions: config.commit.clone(),
+            protected: config.branches.clone(),
+            ..commit::CommitState::default()
+        };
         Self {
             running: true,
             view: View::Dashboard,
@@ -206,7 +263,7 @@ impl App {
             ai_setup_provider: None,
             dashboard_state: dashboard::DashboardState::default(),
             staging_state: staging::StagingState::default(),
-            commit_state: commit::CommitState::default(),
+            commit_state,
             branches_state: branches::BranchesState::default(),
             timeline_state: timeline::TimelineState::default(),
             time_travel_state: time_travel::TimeTravelState::default(),
@@ -219,6 +276,15 @@ impl App {
             bisect_state: bisect::BisectState::default(),
             cherry_pick_state: cherry_pick::CherryPickState::default(),
             agent_state: agent::AgentState::default(),
+            worktrees_state: worktrees::WorktreesState::default(),
+            submodules_state: submodules::SubmodulesState::default(),
+            tags_state: tags::TagsState::default(),
+            rebase_state: rebase::RebaseState::default(),
+            file_history_state: file_history::FileHistoryState::default(),
+            blame_state: blame::BlameState::default(),
+            secret_audit_state: secret_audit::SecretAuditState::default(),
+            lfs_state: lfs::LfsState::default(),
+            repo_switched: false,
         }
     }
 
@@ -239,6 +305,15 @@ impl App {
             View::Bisect => self.bisect_state.refresh(),
             View::CherryPick => self.cherry_pick_state.refresh(),
             View::Agent => {} // no auto-refresh for agent
+            View::Worktrees => self.worktrees_state.refresh(),
+            View::Submodules => self.submodules_state.refresh(),
+            View::Tags => self.tags_state.refresh(),
+            View::Rebase => self.rebase_state.refresh(),
+            View::FileHistory => self.file_history_state.refresh(),
+            View::Blame => self.blame_state.refresh(),
+            // Results describe history; rescanning is explicit
+            View::SecretAudit => {}
+            View::Lfs => self.lfs_state.refresh(),
         }
     }
 
@@ -328,10 +403,9 @@ impl App {
                         if let Popup::FollowUp {
                             ref mut selected, ..
                         } = self.popup
+                            && *selected > 0
                         {
-                            if *selected > 0 {
-                                *selected -= 1;
-                            }
+                            *selected -= 1;
                         }
                     }
                     KeyCode::Down | KeyCode::Char('j') => {
@@ -340,10 +414,9 @@ impl App {
                             ref suggestions,
                             ..
                         } = self.popup
+                            && *selected + 1 < suggestions.len()
                         {
-                            if *selected + 1 < suggestions.len() {
-                                *selected += 1;
-                            }
+                            *selected += 1;
                         }
                     }
                     KeyCode::Enter => {
@@ -387,7 +460,9 @@ impl App {
                                 count
                             ),
                             on_confirm: match pending {
-                                SecretPendingAction::Commit => ConfirmAction::ForceCommitWithSecrets,
+                                SecretPendingAction::Commit => {
+                                    ConfirmAction::ForceCommitWithSecrets
+                                }
                                 _ => ConfirmAction::ForceStageWithSecrets(pending),
                             },
                         };
@@ -396,10 +471,9 @@ impl App {
                         if let Popup::SecretWarning {
                             ref mut selected, ..
                         } = self.popup
+                            && *selected > 0
                         {
-                            if *selected > 0 {
-                                *selected -= 1;
-                            }
+                            *selected -= 1;
                         }
                     }
                     KeyCode::Down | KeyCode::Char('j') => {
@@ -408,34 +482,85 @@ impl App {
                             ref findings,
                             ..
                         } = self.popup
+                            && *selected + 1 < findings.len()
                         {
-                            if *selected + 1 < findings.len() {
-                                *selected += 1;
-                            }
+                            *selected += 1;
                         }
                     }
-                    KeyCode::Char('a') => {
-                        // Add the selected finding's file to allowlist
-                        if sel < count {
-                            if let Popup::SecretWarning { ref findings, .. } = self.popup {
-                                let finding = &findings[sel];
-                                let pattern = finding.file.clone();
-                                if !self.config.secrets.allowlist.contains(&pattern) {
-                                    self.config.secrets.allowlist.push(pattern.clone());
-                                    let _ = self.config.save();
-                                    self.set_status(format!(
-                                        "✓ Added '{}' to secret allowlist",
-                                        pattern
-                                    ));
-                                }
+                    // Add the selected finding's file to allowlist
+                    KeyCode::Char('a') if sel < count => {
+                        if let Popup::SecretWarning { ref findings, .. } = self.popup {
+                            let finding = &findings[sel];
+                            let pattern = finding.file.clone();
+                            if !self.config.secrets.allowlist.contains(&pattern) {
+                                self.config.secrets.allowlist.push(pattern.clone());
+                                let _ = self.config.save();
+                                self.set_status(format!(
+                                    "✓ Added '{}' to secret allowlist",
+                                    pattern
+                                ));
                             }
-                            self.popup = Popup::None;
                         }
+                        self.popup = Popup::None;
                     }
+                    KeyCode::Char('b') if sel < count => self.baseline_secret_finding(sel),
                     _ => {}
                 }
                 return Ok(());
             }
+            Popup::PolicyWarning { findings, selected } => {
+                let count = findings.len();
+                let sel = *selected;
+                let suggested = findings.get(sel).map(|f| f.suggested_action());
+                let action = match key.code {
+                    KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('n') => {
+                        self.popup = Popup::None;
+                        self.set_status("Commit policy: commit cancelled");
+                        None
+                    }
+                    KeyCode::Char('f') | KeyCode::Char('F') => {
+                        self.popup = Popup::Confirm {
+                            title: "⚠ Commit Anyway".to_string(),
+                            message: format!(
+                                "{} file(s) break the commit policy. Commit them anyway? (y/n)",
+                                count
+                            ),
+                            on_confirm: ConfirmAction::ForceCommitWithPolicyIssues,
+                        };
+                        None
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        if let Popup::PolicyWarning {
+                            ref mut selected, ..
+                        } = self.popup
+                        {
+                            *selected = sel.saturating_sub(1);
+                        }
+                        None
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        if let Popup::PolicyWarning {
+                            ref mut selected, ..
+                        } = self.popup
+                            && sel + 1 < count
+                        {
+                            *selected = sel + 1;
+                        }
+                        None
+                    }
+                    KeyCode::Enter => suggested,
+                    KeyCode::Char('u') => Some(git::policy::PolicyAction::Unstage),
+                    KeyCode::Char('i') => Some(git::policy::PolicyAction::Gitignore),
+                    KeyCode::Char('l') => Some(git::policy::PolicyAction::TrackWithLfs),
+                    _ => None,
+                };
+                if let Some(action) = action
+                    && sel < count
+                {
+                    self.apply_policy_action(sel, action);
+                }
+                return Ok(());
+            }
             Popup::None => {}
         }
 
@@ -545,6 +670,40 @@ impl App {
                     self.cherry_pick_state.refresh();
                     return Ok(());
                 }
+                KeyCode::Char('W') => {
+                    self.view = View::Worktrees;
+                    self.worktrees_state.refresh();
+                    return Ok(());
+                }
+                KeyCode::Char('S') => {
+                    self.view = View::Submodules;
+                    self.submodules_state.refresh();
+                    return Ok(());
+                }
+                KeyCode::Char('T') => {
+                    self.view = View::Tags;
+                    self.tags_state.refresh();
+                    return Ok(());
+                }
+                KeyCode::Char('R') => {
+                    self.view = View::Rebase;
+                    self.rebase_state.refresh();
+                    return Ok(());
+                }
+                KeyCode::Char('H') => {
+                    self.view = View::SecretAudit;
+                    let state = &mut self.secret_audit_state;
+                    if state.audit.is_none() && !state.is_scanning() {
+                        state.start(&self.config.secrets);
+                    }
+                    return Ok(());
+                }
+                KeyCode::Char('L') => {
+                    self.view = View::Lfs;
+                    self.lfs_state.refresh();
+                    self.lfs_state.refresh_locks();
+                    return Ok(());
+                }
                 KeyCode::Char('A') => {
                     self.view = View::Agent;
                     if self.ai_client.is_none() {
@@ -572,6 +731,14 @@ impl App {
             View::Bisect => bisect::handle_key(self, key)?,
             View::CherryPick => cherry_pick::handle_key(self, key)?,
             View::Agent => agent::handle_key(self, key)?,
+            View::Worktrees => worktrees::handle_key(self, key)?,
+            View::Submodules => submodules::handle_key(self, key)?,
+            View::Tags => tags::handle_key(self, key)?,
+            View::Rebase => rebase::handle_key(self, key)?,
+            View::FileHistory => file_history::handle_key(self, key)?,
+            View::Blame => blame::handle_key(self, key)?,
+            View::SecretAudit => secret_audit::handle_key(self, key)?,
+            View::Lfs => lfs::handle_key(self, key)?,
         }
 
         Ok(())
@@ -633,13 +800,13 @@ impl App {
                 self.time_travel_state.refresh();
             }
             ConfirmAction::RemoveCollaborator(username) => {
-                if let Some(token) = self.config.github.get_token() {
-                    match git::github_auth::remove_collaborator(&token, &username) {
+                match git::github_auth::GithubClient::for_origin(&self.config.github) {
+                    Ok(client) => match client.remove_collaborator(&username) {
                         Ok(()) => {
                             self.github_state.collab_error =
                                 Some(format!("✓ Removed @{}", username));
                             // Refresh collaborator list
-                            if let Ok(collabs) = git::github_auth::list_collaborators(&token) {
+                            if let Ok(collabs) = client.list_collaborators() {
                                 self.github_state.collaborators = collabs;
                                 self.github_state.collab_selected = 0;
                                 self.github_state.collab_list_state.select(
@@ -654,7 +821,8 @@ impl App {
                         Err(e) => {
                             self.github_state.collab_error = Some(format!("Error: {}", e));
                         }
-                    }
+                    },
+                    Err(e) => self.github_state.collab_error = Some(format!("Error: {}", e)),
                 }
             }
             ConfirmAction::ClearStash => {
@@ -668,6 +836,36 @@ impl App {
                 }
                 self.stash_state.refresh();
             }
+            ConfirmAction::RemoveWorktree { path, force } => {
+                match git::worktree::remove(&path, force) {
+                    Ok(()) => {
+                        self.status_message = Some(format!("Removed worktree {}", path.display()))
+                    }
+                    Err(e) => {
+                        let err_str = e.to_string();
+                        self.status_message = Some(format!("Error: {}", err_str));
+                        self.start_ai_error_explain(err_str);
+                    }
+                }
+                self.worktrees_state.refresh();
+            }
+            ConfirmAction::DeleteTag(name) => {
+                match git::tag::delete(&name) {
+                    Ok(()) => self.status_message = Some(format!("Deleted tag '{}'", name)),
+                    Err(e) => {
+                        let err_str = e.to_string();
+                        self.status_message = Some(format!("Error: {}", err_str));
+                        self.start_ai_error_explain(err_str);
+                    }
+                }
+                self.tags_state.refresh();
+            }
+            ConfirmAction::CreateRelease(tag) => self.create_release(&tag),
+            ConfirmAction::StartRebase => {
+                let base = self.rebase_state.base.clone();
+                let outcome = git::rebase::start(base.as_deref(), &self.rebase_state.todo);
+                self.handle_rebase_outcome(outcome);
+            }
             ConfirmAction::AbortMerge => match git::merge::abort_merge() {
                 Ok(()) => {
                     self.set_status("Merge aborted successfully");
@@ -680,6 +878,11 @@ impl App {
                     self.start_ai_error_explain(err_str);
                 }
             },
+            ConfirmAction::ContinueMerge if git::rebase::in_progress() => {
+                // A rebase may stop again on a later step
+                let outcome = git::rebase::continue_rebase();
+                self.handle_rebase_outcome(outcome);
+            }
             ConfirmAction::ContinueMerge => match git::merge::continue_merge() {
                 Ok(()) => {
                     self.set_status("Merge completed successfully!");
@@ -693,31 +896,51 @@ impl App {
                 }
             },
             ConfirmAction::MergePullRequest { number, method } => {
-                if let Some(token) = self.config.github.get_token() {
-                    self.github_state.pr_state.loading = true;
-                    let bg = self.github_state.pr_state.bg_result.clone();
-                    std::thread::spawn(move || {
-                        let result = git::github_auth::merge_pull_request(&token, number, &method)
-                            .map_err(|e| e.to_string());
-                        if let Ok(mut r) = bg.lock() {
-                            *r = Some(github::PrBgResult::MergeResult(result));
-                        }
-                    });
+                match git::github_auth::GithubClient::for_origin(&self.config.github) {
+                    Ok(client) => {
+                        self.github_state.pr_state.loading = true;
+                        let bg = self.github_state.pr_state.bg_result.clone();
+                        std::thread::spawn(move || {
+                            let result = client
+                                .merge_pull_request(number, &method)
+                                .map_err(|e| e.to_string());
+                            if let Ok(mut r) = bg.lock() {
+                                *r = Some(github::PrBgResult::MergeResult(result));
+                            }
+                        });
+                    }
+                    Err(e) => self.github_state.pr_state.error = Some(e.to_string()),
                 }
             }
             ConfirmAction::ClosePullRequest(number) => {
-                if let Some(token) = self.config.github.get_token() {
-                    self.github_state.pr_state.loading = true;
-                    let bg = self.github_state.pr_state.bg_result.clone();
-                    std::thread::spawn(move || {
-                        let result = git::github_auth::close_pull_request(&token, number)
-                            .map_err(|e| e.to_string());
-                        if let Ok(mut r) = bg.lock() {
-                            *r = Some(github::PrBgResult::CloseResult(result));
-                        }
-                    });
+                match git::github_auth::GithubClient::for_origin(&self.config.github) {
+                    Ok(client) => {
+                        self.github_state.pr_state.loading = true;
+                        let bg = self.github_state.pr_state.bg_result.clone();
+                        std::thread::spawn(move || {
+                            let result =
+                                client.close_pull_request(number).map_err(|e| e.to_string());
+                            if let Ok(mut r) = bg.lock() {
+                                *r = Some(github::PrBgResult::CloseResult(result));
+                            }
+                        });
+                    }
+                    Err(e) => self.github_state.pr_state.error = Some(e.to_string()),
                 }
             }
+            ConfirmAction::DiscardLines { path, hunk, lines } => {
+                match git::diff::discard_lines(&path, &hunk, &lines) {
+                    Ok(_) => {
+                        self.set_status(format!("Discarded {} line(s) in '{}'", lines.len(), path));
+                    }
+                    Err(e) => {
+                        let err_str = e.to_string();
+                        self.set_status(format!("Failed to discard: {}", err_str));
+                        self.start_ai_error_explain(err_str);
+                    }
+                }
+                self.staging_state.reload_hunks();
+            }
             ConfirmAction::DiscardFile(path) => {
                 match git::run_git(&["restore", &path]) {
                     Ok(_) => {
@@ -770,6 +993,9 @@ impl App {
                     }
                 }
             }
+            ConfirmAction::ForceCommitWithPolicyIssues => {
+                crate::ui::commit::scan_and_commit(self)?;
+            }
             ConfirmAction::ForceCommitWithSecrets => {
                 let msg = self.commit_state.message.trim().to_string();
                 match git::run_git(&["commit", "-m", &msg]) {
@@ -796,7 +1022,7 @@ impl App {
     }
 
     fn execute_input(&mut self, action: InputAction, value: String) -> Result<()> {
-        // AI setup steps handle empty values themselves
+        // AI setup steps handle empty values themselves; an empty search clears it
         if value.trim().is_empty()
             && !matches!(
                 action,
@@ -805,6 +1031,8 @@ impl App {
                     | InputAction::AiSetupEndpoint
                     | InputAction::AiSetupApiKey
                     | InputAction::StashPush
+                    | InputAction::SearchCommits
+                    | InputAction::SecretAuditRange
             )
         {
             return Ok(());
@@ -820,6 +1048,56 @@ impl App {
                 }
                 self.branches_state.refresh();
             }
+            InputAction::AddWorktree => {
+                let branch = value.trim();
+                self.add_worktree(branch, true);
+            }
+            InputAction::CreateTag => {
+                let name = value.trim().to_string();
+                let kind = self
+                    .tags_state
+                    .pending_kind
+                    .unwrap_or(git::tag::TagKind::Lightweight);
+                if kind.needs_message() {
+                    self.popup = Popup::Input {
+                        title: format!("Tag message for '{}'", name),
+                        prompt: "Message: ".to_string(),
+                        value: String::new(),
+                        on_submit: InputAction::TagMessage,
+                    };
+                    self.tags_state.pending_name = Some(name);
+                } else {
+                    self.create_tag(&name, kind, "");
+                }
+            }
+            InputAction::RebaseReword => {
+                let message = value.trim().to_string();
+                if let Some(entry) = self.rebase_state.selected_entry_mut() {
+                    entry.action = git::rebase::RebaseAction::Reword;
+                    entry.new_message = (message != entry.summary).then_some(message);
+                }
+                self.rebase_state.update_preview();
+            }
+            InputAction::FileHistory => {
+                self.open_file_history(value.trim());
+            }
+            InputAction::LfsTrack => {
+                self.lfs_track(value.trim());
+            }
+            InputAction::SecretAuditRange => {
+                self.secret_audit_state.range = value.trim().to_string();
+                self.secret_audit_state.start(&self.config.secrets);
+                self.set_status("Scanning history for secrets…");
+            }
+            InputAction::TagMessage => {
+                let kind = self
+                    .tags_state
+                    .pending_kind
+                    .unwrap_or(git::tag::TagKind::Annotated);
+                if let Some(name) = self.tags_state.pending_name.take() {
+                    self.create_tag(&name, kind, value.trim());
+                }
+            }
             InputAction::RenameBranch => {
                 match git::BranchOps::rename(value.trim()) {
                     Ok(()) => self.status_message = Some(format!("Renamed to '{}'", value.trim())),
@@ -829,7 +1107,9 @@ impl App {
             }
             InputAction::SearchCommits => {
                 self.timeline_state.search_query = value;
-                self.timeline_state.do_search();
+                if let Err(e) = self.timeline_state.do_search() {
+                    self.set_status(format!("Search: {}", e));
+                }
             }
             InputAction::SearchFiles => {
                 self.staging_state.filter = value;
@@ -839,12 +1119,12 @@ impl App {
             }
             InputAction::AddCollaborator => {
                 let username = value.trim().to_string();
-                if let Some(token) = self.config.github.get_token() {
-                    match git::github_auth::add_collaborator(&token, &username) {
+                match git::github_auth::GithubClient::for_origin(&self.config.github) {
+                    Ok(client) => match client.add_collaborator(&username) {
                         Ok(msg) => {
                             self.github_state.collab_error = Some(format!("✓ {}", msg));
                             // Refresh collaborator list
-                            if let Ok(collabs) = git::github_auth::list_collaborators(&token) {
+                            if let Ok(collabs) = client.list_collaborators() {
                                 self.github_state.collaborators = collabs;
                                 self.github_state.collab_selected = 0;
                                 self.github_state.collab_list_state.select(
@@ -859,7 +1139,8 @@ impl App {
                         Err(e) => {
                             self.github_state.collab_error = Some(format!("Error: {}", e));
                         }
-                    }
+                    },
+                    Err(e) => self.github_state.collab_error = Some(format!("Error: {}", e)),
                 }
             }
             InputAction::AiSetupProvider => {
@@ -965,7 +1246,8 @@ impl App {
                         Ok(()) => {}
                         Err(e) => self.set_status(format!("⚠ Config save failed: {}", e)),
                     }
-                    self.ai_client = AiClient::from_config(&self.config.ai).map(Arc::new);
+                    self.ai_client = AiClient::from_config(&self.config.ai)
+                        .map(|c| Arc::new(c.with_secrets_config(&self.config.secrets)));
                     if self.ai_client.is_some() {
                         self.set_status("✓ Ollama configured! Testing connection...");
                         self.start_ai_query("health_check".to_string(), None);
@@ -1001,7 +1283,8 @@ impl App {
                     Ok(()) => {}
                     Err(e) => self.set_status(format!("⚠ Config in memory but save failed: {}", e)),
                 }
-                self.ai_client = AiClient::from_config(&self.config.ai).map(Arc::new);
+                self.ai_client = AiClient::from_config(&self.config.ai)
+                    .map(|c| Arc::new(c.with_secrets_config(&self.config.secrets)));
                 if self.ai_client.is_some() {
                     let pname = self.ai_setup_provider.take().unwrap_or("AI".to_string());
                     self.set_status(format!("✓ {} configured! Testing connection...", pname));
@@ -1378,6 +1661,102 @@ impl App {
         });
     }
 
+    /// Start async AI release notes for `tag` from its commit range — non-blocking.
+    pub fn start_ai_release_notes(
+        &mut self,
+        tag: String,
+        previous: Option<String>,
+        commits: Vec<git::CommitEntry>,
+    ) {
+        if self.ai_loading {
+            self.set_status("⏳ AI is already generating...");
+            return;
+        }
+        let client = match self.ai_client {
+            Some(ref c) => Arc::clone(c),
+            None => {
+                self.set_status("AI not configured. Set [ai] in ~/.config/zit/config.toml or export ZIT_AI_API_KEY + ZIT_AI_ENDPOINT");
+                return;
+            }
+        };
+        if commits.is_empty() {
+            self.set_status(format!("No commits in '{}' to write notes for", tag));
+            return;
+        }
+
+        self.ai_loading = true;
+        self.ai_action = Some(AiAction::ReleaseNotes(tag.clone()));
+        self.ai_mentor_state.last_action = Some("Release Notes".to_string());
+        self.tags_state.notes_loading = true;
+        self.set_status(format!("⏳ Drafting release notes for {}...", tag));
+
+        let (tx, rx) = mpsc::channel();
+        self.ai_receiver = Some(rx);
+
+        std::thread::spawn(move || {
+            let result = client
+                .draft_release_notes(&tag, previous.as_deref(), &commits)
+                .map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Ask the AI why the commit behind the selected blame line made its change.
+    pub fn start_ai_explain_change(&mut self) {
+        if self.ai_loading {
+            self.set_status("⏳ AI is already generating...");
+            return;
+        }
+        let client = match self.ai_client {
+            Some(ref c) => Arc::clone(c),
+            None => {
+                self.set_status("AI not configured. Set [ai] in ~/.config/zit/config.toml or export ZIT_AI_API_KEY + ZIT_AI_ENDPOINT");
+                return;
+            }
+        };
+        let Some(commit) = self.blame_state.selected_commit().cloned() else {
+            return;
+        };
+        if commit.is_uncommitted() {
+            self.set_status("This line is not committed yet");
+            return;
+        }
+        let path = if commit.filename.is_empty() {
+            self.blame_state.blame.path.clone()
+        } else {
+            commit.filename.clone()
+        };
+        let line = self
+            .blame_state
+            .blame
+            .lines
+            .get(self.blame_state.selected)
+            .map(|l| l.content.clone())
+            .unwrap_or_default();
+
+        self.ai_loading = true;
+        self.ai_action = Some(AiAction::ExplainChange(commit.hash.clone()));
+        self.ai_mentor_state.last_action = Some("Explain Change".to_string());
+        self.blame_state.explain_loading = true;
+        self.set_status(format!(
+            "⏳ Asking AI why {} changed this...",
+            commit.short_hash()
+        ));
+
+        let (tx, rx) = mpsc::channel();
+        self.ai_receiver = Some(rx);
+
+        std::thread::spawn(move || {
+            let result = (|| {
+                let message = git::blame::commit_message(&commit.hash)?;
+                let diff = git::blame::commit_file_patch(&commit.hash, &path)?;
+                client.explain_change(&path, &commit, &message, &diff, &line)
+            })()
+            .map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+    }
+
     // ── Agent Mode ─────────────────────────────────────────────
 
     /// Start an async AI agent chat — non-blocking.
@@ -1492,30 +1871,7 @@ impl App {
         self.agent_state.command_receiver = Some(rx);
 
         std::thread::spawn(move || {
-            let (output, success) = if is_git {
-                let args_refs: Vec<&str> = args_str[1..].iter().map(|s| s.as_str()).collect();
-                match git::run_git(&args_refs) {
-                    Ok(out) => (out, true),
-                    Err(e) => (e.to_string(), false),
-                }
-            } else {
-                // File-reading commands: cat, head, tail, grep, find, ls, wc
-                let (cmd, cmd_args) = args_str.split_first().unwrap();
-                let cmd_args: Vec<&str> = cmd_args.iter().map(|s| s.as_str()).collect();
-                match std::process::Command::new(cmd).args(&cmd_args).output() {
-                    Ok(out) => {
-                        let stdout = String::from_utf8_lossy(&out.stdout).to_string();
-                        let stderr = String::from_utf8_lossy(&out.stderr).to_string();
-                        let combined = if !stderr.is_empty() {
-                            format!("{}\n{}", stdout, stderr)
-                        } else {
-                            stdout
-                        };
-                        (combined, out.status.success())
-                    }
-                    Err(e) => (e.to_string(), false),
-                }
-            };
+            let (output, success) = agent::run_tool_command(&args_str);
             let _ = tx.send((cmd_str, output, success));
         });
     }
@@ -1553,11 +1909,7 @@ impl App {
 
                     // Show proceed/revise/stop prompt
                     self.agent_state.tool_result_prompt = Some(agent::ToolResultPrompt {
-                        tool_name: format!(
-                            "{} {}",
-                            if cmd_str.starts_with("git ") { "" } else { "" },
-                            cmd_str
-                        ),
+                        tool_name: format!(" {}", cmd_str),
                         output_preview,
                     });
                 }
@@ -1643,7 +1995,7 @@ impl App {
 
     /// Shell-style argument splitting that respects double and single quotes.
     /// e.g. `commit -m "Update zit codebase"` → ["commit", "-m", "Update zit codebase"]
-    fn shell_split(input: &str) -> Vec<String> {
+    pub fn shell_split(input: &str) -> Vec<String> {
         let mut args = Vec::new();
         let mut current = String::new();
         let mut chars = input.chars().peekable();
@@ -1683,7 +2035,7 @@ impl App {
     /// Parse [TOOL_USE] blocks from an AI agent response.
     /// Supports: git <args>, cat <path>, head <path>, tail <path>, grep <pattern> <path>, find <path>, ls <path>, wc <path>
     /// Returns (text_before_tools, tool_uses, text_after_tools).
-    fn parse_agent_response(response: &str) -> (String, Vec<(String, Vec<String>)>, String) {
+    pub fn parse_agent_response(response: &str) -> (String, Vec<(String, Vec<String>)>, String) {
         let mut text_before = Vec::new();
         let mut text_after = Vec::new();
         let mut tool_uses = Vec::new();
@@ -1839,6 +2191,8 @@ impl App {
                     let action = self.ai_action.take();
                     self.ai_loading = false;
                     self.ai_receiver = None;
+                    let redacted =
+                        self.record_ai_redactions(matches!(action, Some(AiAction::AgentChat)));
 
                     match action {
                         Some(AiAction::CommitSuggest) => {
@@ -1866,12 +2220,11 @@ impl App {
                                 // Fallback: numbered list like "1. feat: ..."
                                 if let Some(msg) =
                                     trimmed.strip_prefix(|c: char| c.is_ascii_digit())
+                                    && let Some(msg) = msg.strip_prefix(['.', ')', ':'])
                                 {
-                                    if let Some(msg) = msg.strip_prefix(['.', ')', ':']) {
-                                        let msg = msg.trim();
-                                        if !msg.is_empty() && !msg.starts_with('[') {
-                                            raw_lines.push(msg.to_string());
-                                        }
+                                    let msg = msg.trim();
+                                    if !msg.is_empty() && !msg.starts_with('[') {
+                                        raw_lines.push(msg.to_string());
                                     }
                                 }
 
@@ -2110,6 +2463,26 @@ impl App {
                             self.ai_mentor_state
                                 .add_history("Generate .gitignore".to_string(), clean);
                         }
+                        Some(AiAction::ReleaseNotes(tag)) => {
+                            let notes = response.trim().to_string();
+                            self.tags_state.notes_loading = false;
+                            self.tags_state.release_notes = Some((tag.clone(), notes.clone()));
+                            self.set_status(format!(
+                                "✓ Release notes drafted for {} — press R to publish",
+                                tag
+                            ));
+                            // Store in history
+                            self.ai_mentor_state
+                                .add_history(format!("Release Notes: {}", tag), notes);
+                        }
+                        Some(AiAction::ExplainChange(hash)) => {
+                            let text = response.trim().to_string();
+                            self.blame_state.explain_loading = false;
+                            self.blame_state.explanation = Some((hash.clone(), text.clone()));
+                            self.set_status("✓ AI explained the change");
+                            self.ai_mentor_state
+                                .add_history(format!("Why: {}", &hash[..hash.len().min(7)]), text);
+                        }
                         Some(AiAction::AgentChat) => {
                             self.agent_state.thinking = false;
 
@@ -2157,6 +2530,9 @@ impl App {
                             self.set_status(format!("AI: {}", response));
                         }
                     }
+                    if let Some(note) = redacted {
+                        self.append_status(&note);
+                    }
                 }
                 Ok(Err(e)) => {
                     log::debug!(
@@ -2164,6 +2540,8 @@ impl App {
                         self.ai_action,
                         e
                     );
+                    let redacted = self
+                        .record_ai_redactions(matches!(self.ai_action, Some(AiAction::AgentChat)));
                     // Reset agent thinking state on error
                     if matches!(self.ai_action, Some(AiAction::AgentChat)) {
                         self.agent_state.thinking = false;
@@ -2172,7 +2550,12 @@ impl App {
                             content: format!("Error: {}", e),
                         });
                     }
+                    self.tags_state.notes_loading = false;
+                    self.blame_state.explain_loading = false;
                     self.set_status(format!("AI error: {}", e));
+                    if let Some(note) = redacted {
+                        self.append_status(&note);
+                    }
                     self.ai_loading = false;
                     self.ai_receiver = None;
                     self.ai_action = None;
@@ -2185,6 +2568,8 @@ impl App {
                         "[AI] poll_ai_result: DISCONNECTED action={:?}",
                         self.ai_action
                     );
+                    self.tags_state.notes_loading = false;
+                    self.blame_state.explain_loading = false;
                     self.set_status("AI request was interrupted");
                     self.ai_loading = false;
                     self.ai_receiver = None;
@@ -2199,6 +2584,300 @@ impl App {
         self.status_message = Some(msg.into());
     }
 
+    /// Add `note` to the current status message.
+    fn append_status(&mut self, note: &str) {
+        let status = match self.status_message.take() {
+            Some(status) if !status.is_empty() => format!("{} · {}", status, note),
+            _ => note.to_string(),
+        };
+        self.set_status(status);
+    }
+
+    /// Collect the secrets the AI client redacted from the request that
+    /// just finished. In agent mode they're also noted in the conversation.
+    /// Returns a status note, `None` when nothing was redacted.
+    fn record_ai_redactions(&mut self, agent: bool) -> Option<String> {
+        let redactions = self.ai_client.as_ref()?.take_redactions();
+        if redactions.is_empty() {
+            return None;
+        }
+        let summary = redact::summary(&redactions);
+        if agent {
+            let details: Vec<String> = redactions
+                .iter()
+                .map(|r| format!("  {} in {} ({})", r.rule_name, r.field, r.preview))
+                .collect();
+            self.agent_state.messages.push(agent::AgentMessage {
+                role: agent::MessageRole::System,
+                content: format!("🛡 {}:\n{}", summary, details.join("\n")),
+            });
+            self.agent_state.dirty = true;
+        }
+        Some(format!("🛡 {}", summary))
+    }
+
+    /// Show the history of `path`, returning to the current view on Esc.
+    pub fn open_file_history(&mut self, path: &str) {
+        match self.file_history_state.open(path) {
+            Ok(()) => {
+                if self.view != View::FileHistory {
+                    self.file_history_state.origin = Some(self.view);
+                }
+                self.view = View::FileHistory;
+            }
+            Err(e) => self.set_status(format!("Error: {}", e)),
+        }
+    }
+
+    /// Record finding `index` of the secret warning in the repository's
+    /// baseline and drop it from the popup; closes it once none are left.
+    fn baseline_secret_finding(&mut self, index: usize) {
+        let Popup::SecretWarning { findings, .. } = &self.popup else {
+            return;
+        };
+        let finding = findings[index].clone();
+        let saved = git::secret_baseline::Baseline::load().and_then(|mut baseline| {
+            baseline.add(&finding);
+            baseline.save()
+        });
+        if let Err(e) = saved {
+            self.set_status(format!("Error: {:#}", e));
+            return;
+        }
+
+        let Popup::SecretWarning {
+            findings, selected, ..
+        } = &mut self.popup
+        else {
+            return;
+        };
+        findings.remove(index);
+        *selected = index.min(findings.len().saturating_sub(1));
+        if findings.is_empty() {
+            self.popup = Popup::None;
+            self.set_status(format!(
+                "✓ All findings added to {} — retry to continue",
+                git::secret_baseline::BASELINE_FILE
+            ));
+        } else {
+            self.set_status(format!(
+                "✓ Added {}:{} to {}",
+                finding.file,
+                finding.line,
+                git::secret_baseline::BASELINE_FILE
+            ));
+        }
+    }
+
+    /// Apply `action` to finding `index` of the commit policy popup and drop
+    /// it; closes the popup once none are left.
+    fn apply_policy_action(&mut self, index: usize, action: git::policy::PolicyAction) {
+        let Popup::PolicyWarning { findings, .. } = &self.popup else {
+            return;
+        };
+        let finding = findings[index].clone();
+        if let Err(e) = git::policy::apply(&finding, action) {
+            self.set_status(format!("Error: {:#}", e));
+            return;
+        }
+        self.commit_state.refresh();
+        self.staging_state.refresh();
+
+        let Popup::PolicyWarning { findings, selected } = &mut self.popup else {
+            return;
+        };
+        findings.remove(index);
+        *selected = index.min(findings.len().saturating_sub(1));
+        if findings.is_empty() {
+            self.popup = Popup::None;
+            self.set_status(format!(
+                "✓ {}: {} — all files resolved, retry to commit",
+                action.label(),
+                finding.path
+            ));
+        } else {
+            self.set_status(format!("✓ {}: {}", action.label(), finding.path));
+        }
+    }
+
+    /// Blame `path` at `rev` (working tree when `None`), returning to the
+    /// current view on Esc.
+    pub fn open_blame(&mut self, path: &str, rev: Option<&str>) {
+        match self.blame_state.open(path, rev) {
+            Ok(()) => {
+                if self.view != View::Blame {
+                    self.blame_state.origin = Some(self.view);
+                }
+                self.view = View::Blame;
+            }
+            Err(e) => self.set_status(format!("Error: {}", e)),
+        }
+    }
+
+    /// Route the result of a rebase step: conflicts go to Merge Resolve,
+    /// `edit` stops and errors to the Rebase view.
+    pub fn handle_rebase_outcome(&mut self, outcome: Result<git::rebase::RebaseOutcome>) {
+        use git::rebase::RebaseOutcome;
+        match outcome {
+            Ok(RebaseOutcome::Done) => {
+                self.view = View::Rebase;
+                self.rebase_state.reset();
+                self.set_status("✓ Rebase complete");
+            }
+            Ok(RebaseOutcome::Conflict) => {
+                self.rebase_state.refresh();
+                self.view = View::MergeResolve;
+                self.merge_resolve_state.refresh();
+                let step = self
+                    .rebase_state
+                    .progress
+                    .map(|(done, total)| format!(" at step {}/{}", done, total))
+                    .unwrap_or_default();
+                self.set_status(format!(
+                    "Rebase stopped on conflicts{} — resolve them, then continue",
+                    step
+                ));
+            }
+            Ok(RebaseOutcome::Stopped) => {
+                self.view = View::Rebase;
+                self.rebase_state.refresh();
+                self.set_status("Stopped for amending — stage changes and press c to continue");
+            }
+            Err(e) => {
+                self.rebase_state.refresh();
+                let err_str = e.to_string();
+                self.set_status(format!("Rebase error: {}", err_str));
+                self.start_ai_error_explain(err_str);
+            }
+        }
+    }
+
+    /// Create a tag on HEAD and select it in the Tags view.
+    pub fn create_tag(&mut self, name: &str, kind: git::tag::TagKind, message: &str) {
+        self.tags_state.pending_kind = None;
+        match git::tag::create(name, kind, message, None) {
+            Ok(()) => {
+                self.tags_state.refresh();
+                self.tags_state.select_name(name);
+                self.set_status(format!("Created {} tag '{}'", kind.label(), name));
+            }
+            Err(e) => {
+                let err_str = e.to_string();
+                self.set_status(format!("Error: {}", err_str));
+                self.start_ai_error_explain(err_str);
+            }
+        }
+    }
+
+    /// Push `tag` to origin and publish a GitHub Release for it, using the
+    /// AI-drafted notes when present and the commit list otherwise.
+    pub fn create_release(&mut self, tag: &str) {
+        let client = match git::github_auth::GithubClient::for_origin(&self.config.github) {
+            Ok(client) => client,
+            Err(e) => {
+                self.set_status(e.to_string());
+                return;
+            }
+        };
+        if let Err(e) = git::tag::push("origin", tag) {
+            let err_str = e.to_string();
+            self.set_status(format!("Error: {}", err_str));
+            self.start_ai_error_explain(err_str);
+            return;
+        }
+
+        let body = match self.tags_state.notes_for(tag) {
+            Some(notes) => notes.to_string(),
+            None => {
+                let previous = git::tag::previous(tag);
+                let range = git::tag::range_spec(previous.as_deref(), tag);
+                let commits = git::log::get_range(&range, tags::RANGE_LIMIT).unwrap_or_default();
+                tags::default_release_body(previous.as_deref(), &commits)
+            }
+        };
+        let prerelease = git::tag::is_prerelease(tag);
+
+        match client.create_release(tag, tag, &body, false, prerelease) {
+            Ok(release) => self.set_status(format!("✓ Published release {}", release.html_url)),
+            Err(e) => self.set_status(format!("Release failed: {}", e)),
+        }
+    }
+
+    /// Track `pattern` with Git LFS from the LFS panel or Staging view.
+    pub fn lfs_track(&mut self, pattern: &str) {
+        match git::lfs::track(pattern) {
+            Ok(true) => self.set_status(format!(
+                "Tracking '{}' with LFS — stage .gitattributes and re-add matching files",
+                pattern
+            )),
+            Ok(false) => self.set_status(format!("'{}' is already tracked by LFS", pattern)),
+            Err(e) => self.set_status(format!("Error: {}", e)),
+        }
+        self.lfs_state.refresh();
+        self.staging_state.refresh();
+    }
+
+    /// Show `path` in the Staging view, if it has changes.
+    pub fn open_in_staging(&mut self, path: &str) {
+        self.staging_state.refresh();
+        if self.staging_state.select_path(path) {
+            self.view = View::Staging;
+        } else {
+            self.set_status(format!("{} has no changes to stage", path));
+        }
+    }
+
+    /// Create a worktree for `branch` next to the main worktree and show it
+    /// in the Worktrees view. `new_branch` creates the branch from HEAD.
+    pub fn add_worktree(&mut self, branch: &str, new_branch: bool) {
+        if branch.is_empty() {
+            self.set_status("Branch name cannot be empty");
+            return;
+        }
+        let main_root = match git::worktree::list() {
+            Ok(list) => list.into_iter().next().map(|wt| wt.path),
+            Err(e) => {
+                self.set_status(format!("Error: {}", e));
+                return;
+            }
+        };
+        let Some(main_root) = main_root else {
+            self.set_status("No worktrees found");
+            return;
+        };
+        let path = git::worktree::default_path(&main_root, branch);
+
+        match git::worktree::add(&path.to_string_lossy(), branch, new_branch) {
+            Ok(()) => {
+                self.view = View::Worktrees;
+                self.worktrees_state.refresh();
+                self.worktrees_state.select_path(&path);
+                self.set_status(format!(
+                    "Created worktree {} — Enter to jump in",
+                    path.display()
+                ));
+            }
+            Err(e) => {
+                let err_str = e.to_string();
+                self.set_status(format!("Error: {}", err_str));
+                self.start_ai_error_explain(err_str);
+            }
+        }
+    }
+
+    /// Move zit into another worktree: change directory, restart watching
+    /// and go back to the dashboard.
+    pub fn enter_worktree(&mut self, path: &std::path::Path) {
+        if let Err(e) = std::env::set_current_dir(path) {
+            self.set_status(format!("Cannot enter {}: {}", path.display(), e));
+            return;
+        }
+        self.repo_switched = true;
+        self.view = View::Dashboard;
+        self.dashboard_state.refresh();
+        self.set_status(format!("Now in worktree {}", path.display()));
+    }
+
     /// Clear the status message.
     pub fn clear_status(&mut self) {
         self.status_message = None;
@@ -2217,12 +2896,7 @@ impl App {
                             .select(Some(self.staging_state.selected));
                     }
                 }
-                View::Timeline => {
-                    let len = self.timeline_state.commits.len();
-                    if len > 0 && self.timeline_state.selected < len - 1 {
-                        self.timeline_state.selected += 1;
-                    }
-                }
+                View::Timeline => self.timeline_state.move_selection(1),
                 View::Branches => {
                     let len = self.branches_state.branches.len();
                     if len > 0 && self.branches_state.selected < len - 1 {
@@ -2250,36 +2924,24 @@ impl App {
                 _ => {}
             },
             MouseEventKind::ScrollUp => match self.view {
-                View::Staging => {
-                    if self.staging_state.selected > 0 {
-                        self.staging_state.selected -= 1;
-                        self.staging_state
-                            .list_state
-                            .select(Some(self.staging_state.selected));
-                    }
+                View::Staging if self.staging_state.selected > 0 => {
+                    self.staging_state.selected -= 1;
+                    self.staging_state
+                        .list_state
+                        .select(Some(self.staging_state.selected));
                 }
-                View::Timeline => {
-                    if self.timeline_state.selected > 0 {
-                        self.timeline_state.selected -= 1;
-                    }
+                View::Timeline => self.timeline_state.move_selection(-1),
+                View::Branches if self.branches_state.selected > 0 => {
+                    self.branches_state.selected -= 1;
                 }
-                View::Branches => {
-                    if self.branches_state.selected > 0 {
-                        self.branches_state.selected -= 1;
-                    }
+                View::Reflog if self.reflog_state.selected > 0 => {
+                    self.reflog_state.selected -= 1;
                 }
-                View::Reflog => {
-                    if self.reflog_state.selected > 0 {
-                        self.reflog_state.selected -= 1;
-                    }
-                }
-                View::Stash => {
-                    if self.stash_state.selected > 0 {
-                        self.stash_state.selected -= 1;
-                        self.stash_state
-                            .list_state
-                            .select(Some(self.stash_state.selected));
-                    }
+                View::Stash if self.stash_state.selected > 0 => {
+                    self.stash_state.selected -= 1;
+                    self.stash_state
+                        .list_state
+                        .select(Some(self.stash_state.selected));
                 }
                 View::Agent => {
                     agent::handle_mouse(self, mouse);
@@ -2464,7 +3126,7 @@ fn generate_strategy_follow_ups(response: &str) -> Vec<FollowUpItem> {
 }
 
 /// Heuristic check: does this string look like a reasonable commit message?
-fn looks_like_commit_msg(s: &str) -> bool {
+pub fn looks_like_commit_msg(s: &str) -> bool {
     if s.len() > 120 || s.len() < 3 {
         return false;
     }
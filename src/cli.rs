@@ -0,0 +1,809 @@
+//! Non-interactive subcommands (`zit status`, `zit commit`, `zit scan`, `zit hooks`, ...).
+//!
+//! These run the same git / AI / GitHub logic as the TUI but print to
+//! stdout and return an exit code, so they can be used from scripts and CI.
+
+use anyhow::{Context, Result, bail};
+
+use crate::ai::client::AiClient;
+use crate::ai::redact;
+use crate::app::App;
+use crate::config::{self, Config};
+use crate::git;
+use crate::git::hooks::Hook;
+use crate::git::secret_report::{self, ReportFormat};
+use crate::ui::agent;
+
+/// Maximum number of AI round-trips for `zit agent` before giving up.
+const AGENT_MAX_TURNS: usize = 10;
+
+/// A parsed subcommand.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// `zit status [--json]`
+    Status { json: bool },
+    /// `zit commit [-m <msg>] [--ai] [--force]`
+    Commit {
+        message: Option<String>,
+        ai: bool,
+        force: bool,
+    },
+    /// `zit scan [--format text|json|sarif] [--history [<range>]]`
+    Scan {
+        history: Option<String>,
+        format: ReportFormat,
+    },
+    /// `zit hooks install`
+    HooksInstall,
+    /// `zit hooks uninstall`
+    HooksUninstall,
+    /// `zit hooks run <hook>`, invoked by the installed hooks
+    HooksRun { hook: Hook },
+    /// `zit pr list [--state open|closed|all]`
+    PrList { state: String },
+    /// `zit agent [--yes] "<task>"`
+    Agent { task: String, yes: bool },
+    /// `zit config`
+    Config,
+}
+
+/// Parse subcommand arguments (everything from the subcommand name onwards).
+pub fn parse(args: &[String]) -> Result<Command> {
+    let Some((name, rest)) = args.split_first() else {
+        bail!("missing subcommand");
+    };
+
+    match name.as_str() {
+        "status" => {
+            let mut json = false;
+            for arg in rest {
+                match arg.as_str() {
+                    "--json" => json = true,
+                    other => bail!("unknown option for 'status': {}", other),
+                }
+            }
+            Ok(Command::Status { json })
+        }
+        "commit" => {
+            let mut message = None;
+            let mut ai = false;
+            let mut force = false;
+            let mut iter = rest.iter();
+            while let Some(arg) = iter.next() {
+                match arg.as_str() {
+                    "-m" | "--message" => match iter.next() {
+                        Some(msg) => message = Some(msg.clone()),
+                        None => bail!("'{}' requires a value", arg),
+                    },
+                    "--ai" => ai = true,
+                    "--force" => force = true,
+                    other => bail!("unknown option for 'commit': {}", other),
+                }
+            }
+            if message.is_some() && ai {
+                bail!("'commit' accepts either -m <msg> or --ai, not both");
+            }
+            if message.is_none() && !ai {
+                bail!("'commit' needs a message: use -m <msg> or --ai");
+            }
+            Ok(Command::Commit { message, ai, force })
+        }
+        "scan" => {
+            let mut history: Option<Vec<&str>> = None;
+            let mut format = ReportFormat::Text;
+            let mut iter = rest.iter();
+            while let Some(arg) = iter.next() {
+                match arg.as_str() {
+                    "--format" => match iter.next() {
+                        Some(name) => format = ReportFormat::from_name(name)?,
+                        None => bail!("'--format' requires a value"),
+                    },
+                    "--history" => history = Some(Vec::new()),
+                    // Everything else after --history is the range, e.g. `v1.0..main --tags`
+                    range if history.is_some() => history.as_mut().unwrap().push(range),
+                    other => bail!("unknown option for 'scan': {}", other),
+                }
+            }
+            Ok(Command::Scan {
+                history: history.map(|range| range.join(" ")),
+                format,
+            })
+        }
+        "hooks" => {
+            let Some((action, rest)) = rest.split_first() else {
+                bail!("'hooks' needs an action (install, uninstall)");
+            };
+            match (action.as_str(), rest) {
+                ("install", []) => Ok(Command::HooksInstall),
+                ("uninstall", []) => Ok(Command::HooksUninstall),
+                ("run", [name, ..]) => match Hook::from_name(name) {
+                    Some(hook) => Ok(Command::HooksRun { hook }),
+                    None => bail!("unknown hook: {}", name),
+                },
+                ("install" | "uninstall", [other, ..]) => {
+                    bail!("unknown option for 'hooks {}': {}", action, other)
+                }
+                ("run", []) => bail!("'hooks run' needs a hook name"),
+                (other, _) => bail!("unknown 'hooks' action: {}", other),
+            }
+        }
+        "pr" => {
+            let Some((action, rest)) = rest.split_first() else {
+                bail!("'pr' needs an action (list)");
+            };
+            if action != "list" {
+                bail!("unknown 'pr' action: {}", action);
+            }
+            let mut state = "open".to_string();
+            let mut iter = rest.iter();
+            while let Some(arg) = iter.next() {
+                match arg.as_str() {
+                    "--state" => match iter.next().map(|s| s.as_str()) {
+                        Some(s @ ("open" | "closed" | "all")) => state = s.to_string(),
+                        Some(other) => bail!("invalid --state '{}' (open, closed, all)", other),
+                        None => bail!("'--state' requires a value"),
+                    },
+                    other => bail!("unknown option for 'pr list': {}", other),
+                }
+            }
+            Ok(Command::PrList { state })
+        }
+        "agent" => {
+            let mut yes = false;
+            let mut words = Vec::new();
+            for arg in rest {
+                match arg.as_str() {
+                    "-y" | "--yes" => yes = true,
+                    _ => words.push(arg.as_str()),
+                }
+            }
+            let task = words.join(" ");
+            if task.trim().is_empty() {
+                bail!("'agent' needs a task, e.g. zit agent \"squash my last two commits\"");
+            }
+            Ok(Command::Agent { task, yes })
+        }
+        "config" => {
+            if let Some(other) = rest.first() {
+                bail!("unknown option for 'config': {}", other);
+            }
+            Ok(Command::Config)
+        }
+        other => bail!("unknown subcommand: {}", other),
+    }
+}
+
+/// Run a subcommand. Returns the process exit code.
+pub fn run(command: Command, config: &Config) -> Result<i32> {
+    match command {
+        Command::Status { json } => run_status(json),
+        Command::Commit { message, ai, force } => run_commit(config, message, ai, force),
+        Command::Scan {
+            history: None,
+            format,
+        } => run_scan(config, format),
+        Command::Scan {
+            history: Some(range),
+            format,
+        } => run_scan_history(config, &range, format),
+        Command::HooksInstall => run_hooks_install(),
+        Command::HooksUninstall => run_hooks_uninstall(),
+        Command::HooksRun { hook } => run_hook(config, hook),
+        Command::PrList { state } => run_pr_list(config, &state),
+        Command::Agent { task, yes } => run_agent(config, &task, yes),
+        Command::Config => run_config(config),
+    }
+}
+
+// ─── status ────────────────────────────────────────────────────
+
+fn run_status(json: bool) -> Result<i32> {
+    let status = git::status::get_status()?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&status)?);
+        return Ok(0);
+    }
+
+    let mut header = format!("## {}", status.branch);
+    if let Some(ref upstream) = status.upstream {
+        header.push_str(&format!("...{}", upstream));
+    }
+    if status.ahead > 0 || status.behind > 0 {
+        header.push_str(&format!(
+            " [ahead {}, behind {}]",
+            status.ahead, status.behind
+        ));
+    }
+    println!("{}", header);
+
+    let sections = [
+        ("staged", &status.staged),
+        ("unstaged", &status.unstaged),
+        ("untracked", &status.untracked),
+        ("conflicts", &status.conflicts),
+    ];
+    for (label, files) in sections {
+        for file in files {
+            println!("{:<10} {:?}  {}", label, file.status, file.path);
+        }
+    }
+
+    if status.is_clean() {
+        println!("nothing to commit, working tree clean");
+    }
+    Ok(0)
+}
+
+// ─── commit ────────────────────────────────────────────────────
+
+fn run_commit(config: &Config, message: Option<String>, ai: bool, force: bool) -> Result<i32> {
+    let status = git::status::get_status()?;
+    if status.staged.is_empty() {
+        bail!("No files staged for commit");
+    }
+
+    if config.branches.is_protected(&status.branch) {
+        eprintln!(
+            "Warning: committing directly to protected branch '{}'",
+            status.branch
+        );
+    }
+
+    if !force {
+        let issues = git::policy::check_staged(&config.commit)?;
+        if !issues.is_empty() {
+            eprintln!("Files breaking the commit policy ({}):", issues.len());
+            for issue in &issues {
+                eprintln!("  {}", issue);
+            }
+            eprintln!("Commit aborted. Re-run with --force to commit anyway.");
+            return Ok(1);
+        }
+    }
+
+    if config.secrets.enabled && !force {
+        let rules = git::secrets::rules_for(&config.secrets);
+        let findings = git::secrets::scan_staged_additions(&rules, &config.secrets.allowlist)?;
+        if !findings.is_empty() {
+            print_findings(&findings);
+            eprintln!("Commit aborted. Re-run with --force to commit anyway.");
+            return Ok(1);
+        }
+    }
+
+    let message = match message {
+        Some(msg) => msg,
+        None if ai => {
+            let client = ai_client(config)?;
+            let response = client.suggest_commit_message();
+            report_redactions(&client);
+            let response = response?;
+            pick_commit_suggestion(&response)
+                .context("AI did not return a usable commit message")?
+        }
+        None => bail!("Commit message cannot be empty"),
+    };
+
+    let message = message.trim();
+    if message.is_empty() {
+        bail!("Commit message cannot be empty");
+    }
+
+    let output = git::run_git(&["commit", "-m", message])?;
+    print!("{}", output);
+    Ok(0)
+}
+
+/// Pick the first commit message from an AI suggestion response.
+/// Prefers `[SUGGESTION]` lines, then anything that looks like a subject line.
+fn pick_commit_suggestion(response: &str) -> Option<String> {
+    let lines = || response.lines().map(|l| l.trim());
+
+    if let Some(msg) = lines()
+        .filter_map(|l| l.strip_prefix("[SUGGESTION] "))
+        .map(|m| m.trim())
+        .find(|m| !m.is_empty())
+    {
+        return Some(msg.to_string());
+    }
+
+    lines()
+        .map(|l| l.trim_start_matches(['-', '*', ' ']))
+        .map(|l| {
+            l.trim_start_matches(|c: char| c.is_ascii_digit())
+                .trim_start_matches(['.', ')', ':'])
+                .trim()
+        })
+        .find(|l| crate::app::looks_like_commit_msg(l))
+        .map(|l| l.to_string())
+}
+
+// ─── config ────────────────────────────────────────────────────
+
+fn run_config(config: &Config) -> Result<i32> {
+    print!("{}", describe_config(config));
+    Ok(0)
+}
+
+/// Effective settings, one per line, each followed by the layers that set it.
+fn describe_config(config: &Config) -> String {
+    let mut out = String::from("Config files:\n");
+    if config.sources.files.is_empty() {
+        out.push_str("  (none — using defaults)\n");
+    }
+    for (layer, path) in &config.sources.files {
+        out.push_str(&format!("  {:<7} {}\n", layer, path.display()));
+    }
+    out.push('\n');
+
+    let width = config::REPORTED_KEYS
+        .iter()
+        .map(|k| k.len())
+        .max()
+        .unwrap_or(0);
+    for key in config::REPORTED_KEYS {
+        let value = config
+            .display_value(key)
+            .unwrap_or_else(|| "(unset)".to_string());
+        let layers: Vec<String> = config
+            .sources
+            .layers(key)
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        out.push_str(&format!(
+            "{:<width$} = {}  ({})\n",
+            key,
+            value,
+            layers.join(" + "),
+            width = width
+        ));
+    }
+    out
+}
+
+// ─── scan ──────────────────────────────────────────────────────
+
+fn run_scan(config: &Config, format: ReportFormat) -> Result<i32> {
+    let status = git::status::get_status()?;
+    let rules = git::secrets::rules_for(&config.secrets);
+    let findings =
+        git::secrets::scan_staged_files(&status.staged, &rules, &config.secrets.allowlist);
+
+    match format {
+        ReportFormat::Text => {}
+        ReportFormat::Json => println!("{}", secret_report::json(&findings)?),
+        ReportFormat::Sarif => println!("{}", secret_report::sarif(&findings, &rules)?),
+    }
+    if format != ReportFormat::Text {
+        return Ok(if findings.is_empty() { 0 } else { 1 });
+    }
+
+    if findings.is_empty() {
+        println!("No secrets found in {} staged file(s)", status.staged.len());
+        return Ok(0);
+    }
+
+    print_findings(&findings);
+    Ok(1)
+}
+
+fn run_scan_history(config: &Config, range: &str, format: ReportFormat) -> Result<i32> {
+    let rules = git::secrets::rules_for(&config.secrets);
+    let range = Some(range).filter(|r| !r.trim().is_empty());
+    let audit =
+        git::secret_audit::audit_history(range, &rules, &config.secrets.allowlist, |_, _| true)?;
+
+    match format {
+        ReportFormat::Text => {}
+        ReportFormat::Json => println!("{}", secret_report::json_history(&audit.findings)?),
+        ReportFormat::Sarif => {
+            println!("{}", secret_report::sarif_history(&audit.findings, &rules)?)
+        }
+    }
+    if format != ReportFormat::Text {
+        return Ok(if audit.findings.is_empty() { 0 } else { 1 });
+    }
+
+    if audit.findings.is_empty() {
+        println!("No secrets found in {} commit(s)", audit.commits_scanned);
+        return Ok(0);
+    }
+
+    eprintln!(
+        "Secrets introduced in history ({}, {} still at HEAD, {} commit(s) scanned):",
+        audit.findings.len(),
+        audit.at_head(),
+        audit.commits_scanned
+    );
+    for finding in &audit.findings {
+        eprintln!("  {}", finding);
+    }
+    Ok(1)
+}
+
+fn print_findings(findings: &[git::SecretFinding]) {
+    eprintln!("Potential secrets detected ({}):", findings.len());
+    for finding in findings {
+        eprintln!("  {}", finding);
+    }
+}
+
+// ─── hooks ─────────────────────────────────────────────────────
+
+fn run_hooks_install() -> Result<i32> {
+    let exe = std::env::current_exe().context("Cannot locate the zit binary")?;
+    let changes = git::hooks::install(&exe)?;
+    print_hook_changes(&changes);
+    println!("Commits and pushes from any client are now scanned for secrets");
+    Ok(0)
+}
+
+fn run_hooks_uninstall() -> Result<i32> {
+    let changes = git::hooks::uninstall()?;
+    print_hook_changes(&changes);
+    Ok(0)
+}
+
+fn print_hook_changes(changes: &[(Hook, git::hooks::HookChange)]) {
+    for (hook, change) in changes {
+        println!("{:<10} {}", hook.name(), change);
+    }
+}
+
+/// Headless scan run by the installed hooks.
+fn run_hook(config: &Config, hook: Hook) -> Result<i32> {
+    if !config.secrets.enabled {
+        return Ok(0);
+    }
+    let rules = git::secrets::rules_for(&config.secrets);
+    match hook {
+        Hook::PreCommit => {
+            let findings = git::secrets::scan_staged_additions(&rules, &config.secrets.allowlist)?;
+            if findings.is_empty() {
+                return Ok(0);
+            }
+            print_findings(&findings);
+            eprintln!("Commit blocked by zit.");
+        }
+        Hook::PrePush => {
+            let mut input = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)?;
+            let mut findings: Vec<git::secret_audit::HistoryFinding> = Vec::new();
+            for pushed in git::hooks::parse_push_refs(&input) {
+                let Some(range) = pushed.range() else {
+                    continue;
+                };
+                let audit = git::secret_audit::audit_history(
+                    Some(&range),
+                    &rules,
+                    &config.secrets.allowlist,
+                    |_, _| true,
+                )?;
+                // Branches pushed together share commits
+                for found in audit.findings {
+                    let duplicate = findings.iter().any(|f| {
+                        f.commit == found.commit
+                            && f.finding.file == found.finding.file
+                            && f.finding.line == found.finding.line
+                    });
+                    if !duplicate {
+                        findings.push(found);
+                    }
+                }
+            }
+            if findings.is_empty() {
+                return Ok(0);
+            }
+            eprintln!("Potential secrets in pushed commits ({}):", findings.len());
+            for finding in &findings {
+                eprintln!("  {}", finding);
+            }
+            eprintln!("Push blocked by zit.");
+        }
+    }
+    eprintln!(
+        "Mark intended values with a `{}` comment, add them to {} from zit, or bypass with --no-verify.",
+        git::secrets::ALLOW_MARKER,
+        git::secret_baseline::BASELINE_FILE
+    );
+    Ok(1)
+}
+
+// ─── pr list ───────────────────────────────────────────────────
+
+fn run_pr_list(config: &Config, state: &str) -> Result<i32> {
+    let client = git::github_auth::GithubClient::for_origin(&config.github)
+        .map_err(|e| anyhow::anyhow!("{}. Run zit and press 'g' to log in, or set a PAT", e))?;
+    let prs = client.list_pull_requests(state)?;
+    if prs.is_empty() {
+        println!("No {} pull requests", state);
+        return Ok(0);
+    }
+
+    for pr in prs {
+        let state = if pr.draft { "draft" } else { pr.state.as_str() };
+        println!(
+            "#{}\t{}\t{}\t{} → {}\t{}",
+            pr.number, state, pr.user.login, pr.head.ref_name, pr.base.ref_name, pr.title
+        );
+    }
+    Ok(0)
+}
+
+// ─── agent ─────────────────────────────────────────────────────
+
+fn run_agent(config: &Config, task: &str, yes: bool) -> Result<i32> {
+    let client = ai_client(config)?;
+    let mut transcript = vec![format!("User: {}", task)];
+
+    for _ in 0..AGENT_MAX_TURNS {
+        let response = client
+            .agent_chat(&transcript.join("\n\n"))
+            .recv()
+            .map_err(|_| anyhow::anyhow!("AI request channel disconnected"))?;
+        report_redactions(&client);
+        let response = response.map_err(|e| anyhow::anyhow!(e))?;
+
+        let (text_before, tool_uses, text_after) = App::parse_agent_response(&response);
+        print_agent_text(&text_before);
+        transcript.push(format!("Agent: {}", response.trim()));
+
+        if tool_uses.is_empty() {
+            return Ok(0);
+        }
+
+        for (_description, args) in tool_uses {
+            let is_git = !args.is_empty() && args[0] == "git";
+            let is_safe = if is_git {
+                agent::is_safe_command(&args[1..])
+            } else {
+                agent::is_safe_file_command(&args)
+            };
+            let command = if is_git {
+                args[1..].join(" ")
+            } else {
+                args.join(" ")
+            };
+
+            if !is_safe && !yes {
+                println!("$ {}  (skipped: needs --yes)", args.join(" "));
+                transcript.push(format!(
+                    "[DENIED] git {} -- user denied this command, suggest alternative",
+                    command
+                ));
+                continue;
+            }
+
+            println!("$ {}", args.join(" "));
+            let (output, success) = agent::run_tool_command(&args);
+            print!("{}", output);
+            if !output.is_empty() && !output.ends_with('\n') {
+                println!();
+            }
+            let status = if success { "OK" } else { "FAILED" };
+            transcript.push(format!(
+                "[TOOL_RESULT] git {} ({})\n{}",
+                command, status, output
+            ));
+        }
+
+        print_agent_text(&text_after);
+    }
+
+    eprintln!("Agent stopped after {} turns", AGENT_MAX_TURNS);
+    Ok(1)
+}
+
+fn print_agent_text(text: &str) {
+    let trimmed = text.trim();
+    if !trimmed.is_empty() {
+        println!("{}", trimmed);
+    }
+}
+
+fn ai_client(config: &Config) -> Result<AiClient> {
+    AiClient::from_config(&config.ai)
+        .map(|c| c.with_secrets_config(&config.secrets))
+        .context(
+            "AI not configured. Set [ai] in ~/.config/zit/config.toml or export ZIT_AI_API_KEY + ZIT_AI_ENDPOINT",
+        )
+}
+
+/// Tell the user on stderr about secrets kept out of the last AI request.
+fn report_redactions(client: &AiClient) {
+    let redactions = client.take_redactions();
+    if !redactions.is_empty() {
+        eprintln!("zit: {}", redact::summary(&redactions));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_status() {
+        assert_eq!(
+            parse(&args(&["status"])).unwrap(),
+            Command::Status { json: false }
+        );
+        assert_eq!(
+            parse(&args(&["status", "--json"])).unwrap(),
+            Command::Status { json: true }
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_message() {
+        assert_eq!(
+            parse(&args(&["commit", "-m", "Fix bug"])).unwrap(),
+            Command::Commit {
+                message: Some("Fix bug".to_string()),
+                ai: false,
+                force: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_ai() {
+        assert_eq!(
+            parse(&args(&["commit", "--ai", "--force"])).unwrap(),
+            Command::Commit {
+                message: None,
+                ai: true,
+                force: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_requires_message_or_ai() {
+        assert!(parse(&args(&["commit"])).is_err());
+        assert!(parse(&args(&["commit", "-m"])).is_err());
+        assert!(parse(&args(&["commit", "--ai", "-m", "x"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_pr_list_state() {
+        assert_eq!(
+            parse(&args(&["pr", "list"])).unwrap(),
+            Command::PrList {
+                state: "open".to_string()
+            }
+        );
+        assert_eq!(
+            parse(&args(&["pr", "list", "--state", "all"])).unwrap(),
+            Command::PrList {
+                state: "all".to_string()
+            }
+        );
+        assert!(parse(&args(&["pr", "list", "--state", "merged"])).is_err());
+        assert!(parse(&args(&["pr"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_agent_joins_words() {
+        assert_eq!(
+            parse(&args(&["agent", "--yes", "undo", "last", "commit"])).unwrap(),
+            Command::Agent {
+                task: "undo last commit".to_string(),
+                yes: true,
+            }
+        );
+        assert!(parse(&args(&["agent"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_scan_history() {
+        assert_eq!(
+            parse(&args(&["scan"])).unwrap(),
+            Command::Scan {
+                history: None,
+                format: ReportFormat::Text
+            }
+        );
+        assert_eq!(
+            parse(&args(&["scan", "--history"])).unwrap(),
+            Command::Scan {
+                history: Some(String::new()),
+                format: ReportFormat::Text
+            }
+        );
+        assert_eq!(
+            parse(&args(&["scan", "--history", "v1.0..main", "--tags"])).unwrap(),
+            Command::Scan {
+                history: Some("v1.0..main --tags".to_string()),
+                format: ReportFormat::Text
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_scan_format() {
+        assert_eq!(
+            parse(&args(&["scan", "--format", "sarif"])).unwrap(),
+            Command::Scan {
+                history: None,
+                format: ReportFormat::Sarif
+            }
+        );
+        assert_eq!(
+            parse(&args(&["scan", "--history", "main", "--format", "json"])).unwrap(),
+            Command::Scan {
+                history: Some("main".to_string()),
+                format: ReportFormat::Json
+            }
+        );
+        assert!(parse(&args(&["scan", "--format"])).is_err());
+        assert!(parse(&args(&["scan", "--format", "xml"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_hooks() {
+        assert_eq!(
+            parse(&args(&["hooks", "install"])).unwrap(),
+            Command::HooksInstall
+        );
+        assert_eq!(
+            parse(&args(&["hooks", "uninstall"])).unwrap(),
+            Command::HooksUninstall
+        );
+        assert_eq!(
+            parse(&args(&["hooks", "run", "pre-push", "origin", "url"])).unwrap(),
+            Command::HooksRun {
+                hook: Hook::PrePush
+            }
+        );
+        assert!(parse(&args(&["hooks"])).is_err());
+        assert!(parse(&args(&["hooks", "run", "post-merge"])).is_err());
+        assert!(parse(&args(&["hooks", "install", "--force"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_config() {
+        assert_eq!(parse(&args(&["config"])).unwrap(), Command::Config);
+        assert!(parse(&args(&["config", "--json"])).is_err());
+    }
+
+    #[test]
+    fn test_describe_config_shows_layers() {
+        let config = Config::default();
+        let text = describe_config(&config);
+        assert!(text.contains("using defaults"));
+        assert!(text.contains("commit.max_subject_length"));
+        assert!(text.contains("= 72  (default)"));
+        assert!(text.contains("ai.model"));
+    }
+
+    #[test]
+    fn test_parse_unknown() {
+        assert!(parse(&args(&["frobnicate"])).is_err());
+        assert!(parse(&args(&["scan", "--what"])).is_err());
+    }
+
+    #[test]
+    fn test_pick_commit_suggestion_prefers_tag() {
+        let response =
+            "Here are some options:\n[SUGGESTION] feat: add login\n[SUGGESTION] fix: typo";
+        assert_eq!(
+            pick_commit_suggestion(response),
+            Some("feat: add login".to_string())
+        );
+    }
+
+    #[test]
+    fn test_pick_commit_suggestion_numbered_fallback() {
+        let response = "Here are some options:\n1. fix: handle empty input\n2. chore: tidy";
+        assert_eq!(
+            pick_commit_suggestion(response),
+            Some("fix: handle empty input".to_string())
+        );
+    }
+}
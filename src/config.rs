@@ -1,6 +1,9 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::git::github_auth::GITHUB_COM;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Config {
@@ -14,6 +17,13 @@ pub struct Config {
     pub ai: AiConfig,
     #[serde(default)]
     pub secrets: SecretsConfig,
+    #[serde(default)]
+    pub commit: CommitConfig,
+    #[serde(default)]
+    pub branches: BranchesConfig,
+    /// Where the effective values came from. Never written to disk.
+    #[serde(skip)]
+    pub sources: ConfigSources,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -32,11 +42,49 @@ pub struct GithubConfig {
     pub oauth_token: Option<String>,
     #[serde(default)]
     pub username: Option<String>,
+    /// Host used when `origin` doesn't name one, e.g. for logging in or
+    /// creating a repository. Defaults to github.com.
+    #[serde(default)]
+    pub host: Option<String>,
+    /// GitHub Enterprise Server instances.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hosts: Vec<GithubHostConfig>,
+}
+
+/// A GitHub Enterprise Server instance, matched against the host of the
+/// `origin` remote URL.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct GithubHostConfig {
+    /// Host name as it appears in remote URLs, e.g. `github.example.com`.
+    pub host: String,
+    /// REST API root. Defaults to `<web_url>/api/v3`.
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// Web root. Defaults to `https://<host>`.
+    #[serde(default)]
+    pub web_url: Option<String>,
+    /// Client ID of an OAuth App registered on this instance, for the
+    /// device login flow. Without one, use a personal access token.
+    #[serde(default)]
+    pub client_id: Option<String>,
+    /// Token for this instance, when it isn't in the OS keychain. Only ever
+    /// sent to this host.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Account the token belongs to, filled in on login.
+    #[serde(default)]
+    pub username: Option<String>,
 }
 
 impl GithubConfig {
-    /// Get the best available token: keychain first, then config file (OAuth preferred over PAT).
-    pub fn get_token(&self) -> Option<String> {
+    /// Token issued by `host`. github.com uses the keychain first, then the
+    /// config file (OAuth preferred over PAT); an Enterprise host only its
+    /// own keychain entry and `token` under `[[github.hosts]]`.
+    pub fn token_for(&self, host: &str) -> Option<String> {
+        if !host.eq_ignore_ascii_case(GITHUB_COM) {
+            return crate::keychain::get_github_host_token(host)
+                .or_else(|| self.host_entry(host).and_then(|h| h.token.clone()));
+        }
         // Try OS keychain first
         if let Some(token) = crate::keychain::get_github_token() {
             return Some(token);
@@ -47,6 +95,91 @@ impl GithubConfig {
         // Fallback to plaintext config
         self.oauth_token.clone().or_else(|| self.pat.clone())
     }
+
+    /// Store the token `host` issued in the keychain, falling back to the
+    /// config file if the keychain is unavailable.
+    pub fn set_token(&mut self, host: &str, token: String) {
+        let stored = if host.eq_ignore_ascii_case(GITHUB_COM) {
+            crate::keychain::store_github_token(&token)
+        } else {
+            crate::keychain::store_github_host_token(host, &token)
+        };
+        if stored.is_ok() {
+            return;
+        }
+        log::warn!("Keychain unavailable, storing token in config file");
+        if host.eq_ignore_ascii_case(GITHUB_COM) {
+            self.oauth_token = Some(token);
+            return;
+        }
+        match self
+            .hosts
+            .iter_mut()
+            .find(|h| h.host.eq_ignore_ascii_case(host))
+        {
+            Some(entry) => entry.token = Some(token),
+            None => self.hosts.push(GithubHostConfig {
+                host: host.to_string(),
+                token: Some(token),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Forget every token stored for `host`.
+    pub fn clear_token(&mut self, host: &str) {
+        if host.eq_ignore_ascii_case(GITHUB_COM) {
+            crate::keychain::delete_github_token();
+            crate::keychain::delete_github_pat();
+            self.oauth_token = None;
+            self.pat = None;
+            return;
+        }
+        crate::keychain::delete_github_host_token(host);
+        if let Some(entry) = self
+            .hosts
+            .iter_mut()
+            .find(|h| h.host.eq_ignore_ascii_case(host))
+        {
+            entry.token = None;
+        }
+    }
+
+    /// Account logged in to `host`: `username` for github.com, the
+    /// `[[github.hosts]]` entry's otherwise.
+    pub fn username_for(&self, host: &str) -> Option<&str> {
+        if host.eq_ignore_ascii_case(GITHUB_COM) {
+            return self.username.as_deref();
+        }
+        self.host_entry(host).and_then(|h| h.username.as_deref())
+    }
+
+    /// Record the account logged in to `host`, or forget it with `None`.
+    pub fn set_username(&mut self, host: &str, username: Option<String>) {
+        if host.eq_ignore_ascii_case(GITHUB_COM) {
+            self.username = username;
+            return;
+        }
+        match self
+            .hosts
+            .iter_mut()
+            .find(|h| h.host.eq_ignore_ascii_case(host))
+        {
+            Some(entry) => entry.username = username,
+            None if username.is_some() => self.hosts.push(GithubHostConfig {
+                host: host.to_string(),
+                username,
+                ..Default::default()
+            }),
+            None => {}
+        }
+    }
+
+    fn host_entry(&self, host: &str) -> Option<&GithubHostConfig> {
+        self.hosts
+            .iter()
+            .find(|h| h.host.eq_ignore_ascii_case(host))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -88,7 +221,7 @@ impl Default for UiConfig {
 }
 
 /// Configuration for the built-in secret scanner.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct SecretsConfig {
     /// Enable/disable secret scanning on stage and commit (default: true).
     #[serde(default = "default_true")]
@@ -100,9 +233,56 @@ pub struct SecretsConfig {
     /// Additional custom regex patterns to scan for.
     #[serde(default)]
     pub custom_patterns: Vec<CustomSecretPattern>,
+    /// gitleaks-format TOML rule files to load. Relative paths are resolved
+    /// from the repository root.
+    #[serde(default)]
+    pub rule_packs: Vec<String>,
+    /// Generic high-entropy value detection near key/secret/token/password.
+    #[serde(default)]
+    pub entropy: EntropyConfig,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Thresholds for the entropy detector. Raise them to cut noise, lower
+/// them to catch shorter or less random credentials.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EntropyConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Shortest value considered, in characters.
+    #[serde(default = "default_entropy_min_length")]
+    pub min_length: usize,
+    /// Minimum Shannon entropy (bits per character) for base64-like values.
+    #[serde(default = "default_base64_threshold")]
+    pub base64_threshold: f64,
+    /// Minimum Shannon entropy (bits per character) for hex values.
+    #[serde(default = "default_hex_threshold")]
+    pub hex_threshold: f64,
+}
+
+fn default_entropy_min_length() -> usize {
+    20
+}
+
+fn default_base64_threshold() -> f64 {
+    4.0
+}
+
+fn default_hex_threshold() -> f64 {
+    3.0
+}
+
+impl Default for EntropyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_length: default_entropy_min_length(),
+            base64_threshold: default_base64_threshold(),
+            hex_threshold: default_hex_threshold(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct CustomSecretPattern {
     pub name: String,
     pub pattern: String,
@@ -114,10 +294,286 @@ impl Default for SecretsConfig {
             enabled: true,
             allowlist: Vec::new(),
             custom_patterns: Vec::new(),
+            rule_packs: Vec::new(),
+            entropy: EntropyConfig::default(),
         }
     }
 }
 
+/// Commit message conventions and the file policy checked by the commit view.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CommitConfig {
+    /// Message convention to enforce: "conventional" or unset for none.
+    #[serde(default)]
+    pub convention: Option<String>,
+    /// Recommended maximum subject line length.
+    #[serde(default = "default_max_subject_length")]
+    pub max_subject_length: usize,
+    /// Staged files larger than this (KiB) are flagged; 0 disables.
+    #[serde(default = "default_max_file_size_kb")]
+    pub max_file_size_kb: u64,
+    /// Flag binary files that aren't stored through Git LFS.
+    #[serde(default = "default_true")]
+    pub flag_binaries: bool,
+    /// gitignore-style patterns of paths that should never be committed.
+    #[serde(default = "default_forbidden_paths")]
+    pub forbidden_paths: Vec<String>,
+}
+
+fn default_max_subject_length() -> usize {
+    72
+}
+
+fn default_max_file_size_kb() -> u64 {
+    5 * 1024
+}
+
+fn default_forbidden_paths() -> Vec<String> {
+    [".env", "*.pem", "node_modules/"]
+        .map(str::to_string)
+        .to_vec()
+}
+
+impl Default for CommitConfig {
+    fn default() -> Self {
+        Self {
+            convention: None,
+            max_subject_length: default_max_subject_length(),
+            max_file_size_kb: default_max_file_size_kb(),
+            flag_binaries: true,
+            forbidden_paths: default_forbidden_paths(),
+        }
+    }
+}
+
+impl CommitConfig {
+    /// Whether Conventional Commits (`type(scope): subject`) are required.
+    pub fn is_conventional(&self) -> bool {
+        self.convention
+            .as_deref()
+            .is_some_and(|c| c.eq_ignore_ascii_case("conventional"))
+    }
+}
+
+/// Branch protection settings.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BranchesConfig {
+    /// Branches that zit refuses to delete or rename and warns about when
+    /// committing directly. Supports `*` wildcards, e.g. `release/*`.
+    #[serde(default)]
+    pub protected: Vec<String>,
+}
+
+impl BranchesConfig {
+    /// Whether `branch` matches one of the protected patterns.
+    pub fn is_protected(&self, branch: &str) -> bool {
+        self.protected.iter().any(|p| wildcard_match(p, branch))
+    }
+}
+
+/// Match `text` against a pattern where `*` stands for any run of characters.
+pub(crate) fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+// ─── Layering ──────────────────────────────────────────────────────────
+
+/// Name of the repo-local config file at the worktree root (shared, committed).
+pub const REPO_CONFIG_FILE: &str = ".zit.toml";
+
+/// Name of the private per-repo config file inside the git dir.
+pub const LOCAL_CONFIG_FILE: &str = "zit.toml";
+
+/// Keys reported by `zit config`, in display order.
+pub const REPORTED_KEYS: &[&str] = &[
+    "general.tick_rate_ms",
+    "general.confirm_destructive",
+    "github.host",
+    "github.hosts",
+    "ai.enabled",
+    "ai.provider",
+    "ai.model",
+    "secrets.enabled",
+    "secrets.allowlist",
+    "secrets.custom_patterns",
+    "secrets.rule_packs",
+    "secrets.entropy",
+    "commit.convention",
+    "commit.max_subject_length",
+    "commit.max_file_size_kb",
+    "commit.flag_binaries",
+    "commit.forbidden_paths",
+    "branches.protected",
+];
+
+/// A source of configuration values, lowest precedence first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigLayer {
+    /// Built-in default.
+    Default,
+    /// `~/.config/zit/config.toml`.
+    Global,
+    /// `.zit.toml` at the repository root.
+    Repo,
+    /// `zit.toml` inside the git dir.
+    Local,
+}
+
+impl std::fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ConfigLayer::Default => "default",
+            ConfigLayer::Global => "global",
+            ConfigLayer::Repo => "repo",
+            ConfigLayer::Local => "local",
+        };
+        f.pad(name)
+    }
+}
+
+/// Repo-local overrides. Only settings that make sense per repository are
+/// accepted — credentials and endpoints stay in the global file.
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RepoConfig {
+    secrets: RepoSecretsConfig,
+    ai: RepoAiConfig,
+    commit: RepoCommitConfig,
+    branches: BranchesConfig,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RepoSecretsConfig {
+    allowlist: Vec<String>,
+    custom_patterns: Vec<CustomSecretPattern>,
+    rule_packs: Vec<String>,
+    entropy: RepoEntropyConfig,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RepoEntropyConfig {
+    enabled: Option<bool>,
+    min_length: Option<usize>,
+    base64_threshold: Option<f64>,
+    hex_threshold: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RepoAiConfig {
+    model: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RepoCommitConfig {
+    convention: Option<String>,
+    max_subject_length: Option<usize>,
+    max_file_size_kb: Option<u64>,
+    flag_binaries: Option<bool>,
+    forbidden_paths: Vec<String>,
+}
+
+/// The settings a repo layer can touch, captured so `save()` can write
+/// back only what belongs in the global file.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct LayeredValues {
+    allowlist: Vec<String>,
+    custom_patterns: Vec<CustomSecretPattern>,
+    rule_packs: Vec<String>,
+    entropy: EntropyConfig,
+    model: Option<String>,
+    commit: CommitConfig,
+    protected: Vec<String>,
+}
+
+/// Provenance of the effective configuration.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSources {
+    /// Config files that were read, lowest precedence first.
+    pub files: Vec<(ConfigLayer, PathBuf)>,
+    /// Layers that set each key. Missing keys come from the defaults.
+    origins: BTreeMap<String, Vec<ConfigLayer>>,
+    /// Global values before any repo layer was applied.
+    global: Option<Box<LayeredValues>>,
+    /// Effective values right after the repo layers were applied.
+    merged: Option<Box<LayeredValues>>,
+}
+
+impl ConfigSources {
+    /// Layers that contributed to `key`, e.g. `[Global, Repo]` for a list
+    /// extended by `.zit.toml`.
+    pub fn layers(&self, key: &str) -> Vec<ConfigLayer> {
+        match self.origins.get(key) {
+            Some(layers) if !layers.is_empty() => layers.clone(),
+            _ => vec![ConfigLayer::Default],
+        }
+    }
+
+    /// Record every key in `table` (two levels: `section.key`) as set by `layer`.
+    fn record(&mut self, table: &toml::Table, layer: ConfigLayer) {
+        for (section, value) in table {
+            let Some(inner) = value.as_table() else {
+                continue;
+            };
+            for key in inner.keys() {
+                let layers = self
+                    .origins
+                    .entry(format!("{}.{}", section, key))
+                    .or_default();
+                if !layers.contains(&layer) {
+                    layers.push(layer);
+                }
+            }
+        }
+    }
+}
+
+/// Append the items of `extra` that `list` does not already contain.
+fn extend_unique<T: PartialEq + Clone>(list: &mut Vec<T>, extra: &[T]) {
+    for item in extra {
+        if !list.contains(item) {
+            list.push(item.clone());
+        }
+    }
+}
+
+/// Undo a repo layer on a scalar: keep runtime edits, otherwise restore the
+/// global value.
+fn unlayer_value<T: PartialEq + Clone>(current: &T, merged: &T, global: &T) -> T {
+    if current == merged {
+        global.clone()
+    } else {
+        current.clone()
+    }
+}
+
+/// Undo a repo layer on a list: drop items that only a repo layer added.
+fn unlayer_list<T: PartialEq + Clone>(current: &[T], merged: &[T], global: &[T]) -> Vec<T> {
+    current
+        .iter()
+        .filter(|item| global.contains(item) || !merged.contains(item))
+        .cloned()
+        .collect()
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AiConfig {
     /// Enable AI mentor features.
@@ -160,10 +616,10 @@ impl AiConfig {
 
     /// Get the effective model name, falling back to a per-provider default.
     pub fn effective_model(&self) -> String {
-        if let Some(ref m) = self.model {
-            if !m.is_empty() {
-                return m.clone();
-            }
+        if let Some(ref m) = self.model
+            && !m.is_empty()
+        {
+            return m.clone();
         }
         match self.effective_provider() {
             "openai" => "gpt-4o".to_string(),
@@ -312,10 +768,10 @@ impl AiConfig {
                     "API key required for '{}' — add 'api_key' to [ai] config or set ZIT_AI_API_KEY",
                     provider
                 ));
-            } else if let Some(ref key) = resolved_key {
-                if key.len() < 8 {
-                    issues.push("AI API key seems too short (< 8 chars)".to_string());
-                }
+            } else if let Some(ref key) = resolved_key
+                && key.len() < 8
+            {
+                issues.push("AI API key seems too short (< 8 chars)".to_string());
             }
         }
 
@@ -369,21 +825,190 @@ impl Config {
         let path = Self::path();
         if path.exists() {
             let content = std::fs::read_to_string(&path)?;
-            let config: Config = toml::from_str(&content)?;
-            Ok(config)
+            Self::from_global_str(&content, path)
         } else {
             Ok(Config::default())
         }
     }
 
+    /// Parse the global config file contents, recording which keys it sets.
+    fn from_global_str(content: &str, path: PathBuf) -> Result<Self> {
+        let mut config: Config = toml::from_str(content)?;
+        let table: toml::Table = toml::from_str(content)?;
+        config.sources.record(&table, ConfigLayer::Global);
+        config.sources.files.push((ConfigLayer::Global, path));
+        Ok(config)
+    }
+
+    /// Merge the repo-local layers over this (global) config:
+    /// `<root>/.zit.toml`, then `<git_dir>/zit.toml`. Lists are extended,
+    /// scalars are overridden. Missing files are skipped.
+    pub fn apply_repo_layers(&mut self, root: &Path, git_dir: &Path) -> Result<()> {
+        let global = self.layered_values();
+        let mut applied = false;
+        let mut first_error = None;
+
+        // A broken file is skipped; the other layer still applies
+        for (layer, path) in [
+            (ConfigLayer::Repo, root.join(REPO_CONFIG_FILE)),
+            (ConfigLayer::Local, git_dir.join(LOCAL_CONFIG_FILE)),
+        ] {
+            if !path.is_file() {
+                continue;
+            }
+            let result = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))
+                .and_then(|content| {
+                    self.apply_layer(&content, layer)
+                        .with_context(|| format!("Invalid {}", path.display()))
+                });
+            match result {
+                Ok(()) => {
+                    self.sources.files.push((layer, path));
+                    applied = true;
+                }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        if applied {
+            self.sources.merged = Some(Box::new(self.layered_values()));
+            self.sources.global = Some(Box::new(global));
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Merge one repo-layer TOML document into this config.
+    fn apply_layer(&mut self, content: &str, layer: ConfigLayer) -> Result<()> {
+        let repo: RepoConfig = toml::from_str(content)?;
+        let table: toml::Table = toml::from_str(content)?;
+
+        let allowlist: Vec<String> = repo
+            .secrets
+            .allowlist
+            .into_iter()
+            .filter(|a| !a.is_empty())
+            .collect();
+        extend_unique(&mut self.secrets.allowlist, &allowlist);
+        extend_unique(
+            &mut self.secrets.custom_patterns,
+            &repo.secrets.custom_patterns,
+        );
+        extend_unique(&mut self.secrets.rule_packs, &repo.secrets.rule_packs);
+        let entropy = repo.secrets.entropy;
+        if let Some(enabled) = entropy.enabled {
+            self.secrets.entropy.enabled = enabled;
+        }
+        if let Some(min_length) = entropy.min_length {
+            self.secrets.entropy.min_length = min_length;
+        }
+        if let Some(threshold) = entropy.base64_threshold {
+            self.secrets.entropy.base64_threshold = threshold;
+        }
+        if let Some(threshold) = entropy.hex_threshold {
+            self.secrets.entropy.hex_threshold = threshold;
+        }
+        if let Some(model) = repo.ai.model {
+            self.ai.model = Some(model);
+        }
+        if let Some(convention) = repo.commit.convention {
+            self.commit.convention = Some(convention);
+        }
+        if let Some(max) = repo.commit.max_subject_length {
+            self.commit.max_subject_length = max;
+        }
+        if let Some(max) = repo.commit.max_file_size_kb {
+            self.commit.max_file_size_kb = max;
+        }
+        if let Some(flag) = repo.commit.flag_binaries {
+            self.commit.flag_binaries = flag;
+        }
+        extend_unique(
+            &mut self.commit.forbidden_paths,
+            &repo.commit.forbidden_paths,
+        );
+        extend_unique(&mut self.branches.protected, &repo.branches.protected);
+
+        self.sources.record(&table, layer);
+        Ok(())
+    }
+
+    fn layered_values(&self) -> LayeredValues {
+        LayeredValues {
+            allowlist: self.secrets.allowlist.clone(),
+            custom_patterns: self.secrets.custom_patterns.clone(),
+            rule_packs: self.secrets.rule_packs.clone(),
+            entropy: self.secrets.entropy.clone(),
+            model: self.ai.model.clone(),
+            commit: self.commit.clone(),
+            protected: self.branches.protected.clone(),
+        }
+    }
+
+    /// The config as it should be written to the global file: values that
+    /// came from a repo layer are stripped, edits made since loading are kept.
+    pub fn global_layer(&self) -> Config {
+        let mut out = self.clone();
+        out.sources = ConfigSources::default();
+        let (Some(global), Some(merged)) = (&self.sources.global, &self.sources.merged) else {
+            return out;
+        };
+
+        out.secrets.allowlist = unlayer_list(
+            &self.secrets.allowlist,
+            &merged.allowlist,
+            &global.allowlist,
+        );
+        out.secrets.custom_patterns = unlayer_list(
+            &self.secrets.custom_patterns,
+            &merged.custom_patterns,
+            &global.custom_patterns,
+        );
+        out.secrets.rule_packs = unlayer_list(
+            &self.secrets.rule_packs,
+            &merged.rule_packs,
+            &global.rule_packs,
+        );
+        out.secrets.entropy =
+            unlayer_value(&self.secrets.entropy, &merged.entropy, &global.entropy);
+        out.ai.model = unlayer_value(&self.ai.model, &merged.model, &global.model);
+        out.commit = unlayer_value(&self.commit, &merged.commit, &global.commit);
+        out.branches.protected = unlayer_list(
+            &self.branches.protected,
+            &merged.protected,
+            &global.protected,
+        );
+        out
+    }
+
+    /// Effective value of a dotted key (e.g. `ai.model`) rendered as TOML,
+    /// or `None` if unset.
+    pub fn display_value(&self, key: &str) -> Option<String> {
+        let value = toml::Value::try_from(self).ok()?;
+        let (section, name) = key.split_once('.')?;
+        let mut v = value.get(section)?.get(name)?.clone();
+        // Host tokens are credentials, not settings
+        if key == "github.hosts"
+            && let Some(hosts) = v.as_array_mut()
+        {
+            for host in hosts.iter_mut().filter_map(|h| h.as_table_mut()) {
+                host.remove("token");
+            }
+        }
+        Some(v.to_string())
+    }
+
     /// Save config to file, creating directories if needed.
     /// On Unix, sets file permissions to 0o600 (owner-only read/write).
+    /// Only the global layer is written; repo-local values stay in their files.
     pub fn save(&self) -> Result<()> {
         let path = Self::path();
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
         }
-        let content = toml::to_string_pretty(self)?;
+        let content = toml::to_string_pretty(&self.global_layer())?;
         std::fs::write(&path, content)?;
 
         // Restrict permissions on Unix (config may contain tokens as fallback)
@@ -497,7 +1122,7 @@ mod tests {
 
     #[test]
     fn test_ai_not_ready_missing_endpoint() {
-        std::env::remove_var("ZIT_AI_ENDPOINT");
+        unsafe { std::env::remove_var("ZIT_AI_ENDPOINT") };
         let a = AiConfig {
             enabled: true,
             provider: "bedrock".to_string(),
@@ -511,7 +1136,7 @@ mod tests {
 
     #[test]
     fn test_ai_not_ready_missing_key() {
-        std::env::remove_var("ZIT_AI_API_KEY");
+        unsafe { std::env::remove_var("ZIT_AI_API_KEY") };
         let a = AiConfig {
             enabled: true,
             provider: "bedrock".to_string(),
@@ -525,7 +1150,7 @@ mod tests {
 
     #[test]
     fn test_ollama_ready_without_key() {
-        std::env::remove_var("ZIT_AI_API_KEY");
+        unsafe { std::env::remove_var("ZIT_AI_API_KEY") };
         let a = AiConfig {
             enabled: true,
             provider: "ollama".to_string(),
@@ -559,7 +1184,7 @@ mod tests {
 
     #[test]
     fn test_validate_bedrock_no_endpoint() {
-        std::env::remove_var("ZIT_AI_ENDPOINT");
+        unsafe { std::env::remove_var("ZIT_AI_ENDPOINT") };
         let a = AiConfig {
             enabled: true,
             provider: "bedrock".to_string(),
@@ -583,9 +1208,11 @@ mod tests {
             timeout_secs: Some(30),
         };
         let issues = a.validate();
-        assert!(issues
-            .iter()
-            .any(|i| i.contains("must start with https://")));
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.contains("must start with https://"))
+        );
     }
 
     #[test]
@@ -639,7 +1266,7 @@ mod tests {
 
     #[test]
     fn test_validate_ollama_no_key_ok() {
-        std::env::remove_var("ZIT_AI_API_KEY");
+        unsafe { std::env::remove_var("ZIT_AI_API_KEY") };
         let a = AiConfig {
             enabled: true,
             provider: "ollama".to_string(),
@@ -655,7 +1282,7 @@ mod tests {
     #[test]
     fn test_github_config_no_token() {
         let g = GithubConfig::default();
-        assert!(g.get_token().is_none());
+        assert!(g.token_for(GITHUB_COM).is_none());
     }
 
     #[test]
@@ -664,8 +1291,9 @@ mod tests {
             pat: Some("pat-token".to_string()),
             oauth_token: Some("oauth-token".to_string()),
             username: None,
+            ..Default::default()
         };
-        assert_eq!(g.get_token(), Some("oauth-token".to_string()));
+        assert_eq!(g.token_for(GITHUB_COM), Some("oauth-token".to_string()));
     }
 
     #[test]
@@ -674,8 +1302,58 @@ mod tests {
             pat: Some("pat-token".to_string()),
             oauth_token: None,
             username: None,
+            ..Default::default()
         };
-        assert_eq!(g.get_token(), Some("pat-token".to_string()));
+        assert_eq!(g.token_for(GITHUB_COM), Some("pat-token".to_string()));
+    }
+
+    #[test]
+    fn test_github_tokens_stay_with_their_host() {
+        let g = GithubConfig {
+            pat: Some("dotcom-token".to_string()),
+            hosts: vec![GithubHostConfig {
+                host: "GitHub.Example.com".to_string(),
+                token: Some("ghe-token".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(g.token_for(GITHUB_COM), Some("dotcom-token".to_string()));
+        assert_eq!(
+            g.token_for("github.example.com"),
+            Some("ghe-token".to_string())
+        );
+        assert!(g.token_for("git.corp.internal").is_none());
+    }
+
+    #[test]
+    fn test_github_usernames_stay_with_their_host() {
+        let mut g = GithubConfig::default();
+        g.set_username(GITHUB_COM, Some("octocat".to_string()));
+        g.set_username("GitHub.Example.com", Some("jdoe".to_string()));
+        assert_eq!(g.username, Some("octocat".to_string()));
+        assert_eq!(g.username_for(GITHUB_COM), Some("octocat"));
+        assert_eq!(g.username_for("github.example.com"), Some("jdoe"));
+        assert!(g.username_for("git.corp.internal").is_none());
+
+        g.set_username("github.example.com", None);
+        assert!(g.username_for("github.example.com").is_none());
+        assert_eq!(g.username_for(GITHUB_COM), Some("octocat"));
+        g.set_username("git.corp.internal", None);
+        assert_eq!(g.hosts.len(), 1);
+    }
+
+    #[test]
+    fn test_display_value_hides_host_tokens() {
+        let mut config = Config::default();
+        config.github.hosts.push(GithubHostConfig {
+            host: "github.example.com".to_string(),
+            token: Some("ghe-secret".to_string()),
+            ..Default::default()
+        });
+        let shown = config.display_value("github.hosts").unwrap();
+        assert!(shown.contains("github.example.com"));
+        assert!(!shown.contains("ghe-secret"));
     }
 
     // ── Config serialization roundtrip ──────────────────────────────
@@ -690,6 +1368,12 @@ mod tests {
                 pat: Some("ghp_test".to_string()),
                 oauth_token: None,
                 username: Some("user".to_string()),
+                host: Some("github.example.com".to_string()),
+                hosts: vec![GithubHostConfig {
+                    host: "github.example.com".to_string(),
+                    client_id: Some("Iv1.abc123".to_string()),
+                    ..Default::default()
+                }],
             },
             ui: UiConfig {
                 color_scheme: "dark".to_string(),
@@ -703,12 +1387,18 @@ mod tests {
                 api_key: Some("key123456".to_string()),
                 timeout_secs: Some(60),
             },
+            secrets: SecretsConfig::default(),
+            commit: CommitConfig::default(),
+            branches: BranchesConfig::default(),
+            sources: ConfigSources::default(),
         };
         let toml_str = toml::to_string_pretty(&config).unwrap();
         let parsed: Config = toml::from_str(&toml_str).unwrap();
         assert_eq!(parsed.general.tick_rate_ms, 500);
         assert!(!parsed.general.confirm_destructive);
         assert_eq!(parsed.github.pat, Some("ghp_test".to_string()));
+        assert_eq!(parsed.github.host.as_deref(), Some("github.example.com"));
+        assert_eq!(parsed.github.hosts, config.github.hosts);
         assert_eq!(parsed.ui.color_scheme, "dark");
         assert!(parsed.ai.enabled);
         assert_eq!(parsed.ai.provider, "openai");
@@ -729,29 +1419,29 @@ mod tests {
     // ── AiConfig::resolved_endpoint env fallback ────────────────────
     #[test]
     fn test_resolved_endpoint_prefers_config() {
-        std::env::set_var("ZIT_AI_ENDPOINT", "https://env.example.com");
+        unsafe { std::env::set_var("ZIT_AI_ENDPOINT", "https://env.example.com") };
         let a = AiConfig {
             endpoint: Some("https://config.example.com".to_string()),
             ..AiConfig::default()
         };
         assert_eq!(a.resolved_endpoint().unwrap(), "https://config.example.com");
-        std::env::remove_var("ZIT_AI_ENDPOINT");
+        unsafe { std::env::remove_var("ZIT_AI_ENDPOINT") };
     }
 
     #[test]
     fn test_resolved_endpoint_env_fallback() {
-        std::env::set_var("ZIT_AI_ENDPOINT", "https://env.example.com");
+        unsafe { std::env::set_var("ZIT_AI_ENDPOINT", "https://env.example.com") };
         let a = AiConfig {
             endpoint: None,
             ..AiConfig::default()
         };
         assert_eq!(a.resolved_endpoint().unwrap(), "https://env.example.com");
-        std::env::remove_var("ZIT_AI_ENDPOINT");
+        unsafe { std::env::remove_var("ZIT_AI_ENDPOINT") };
     }
 
     #[test]
     fn test_effective_endpoint_provider_defaults() {
-        std::env::remove_var("ZIT_AI_ENDPOINT");
+        unsafe { std::env::remove_var("ZIT_AI_ENDPOINT") };
         let a = AiConfig {
             provider: "openai".to_string(),
             ..AiConfig::default()
@@ -768,4 +1458,162 @@ mod tests {
         };
         assert!(a.effective_endpoint().is_none()); // bedrock requires explicit
     }
+
+    // ── Repo layers ─────────────────────────────────────────────────
+    fn layered(global: &str, repo: Option<&str>, local: Option<&str>) -> Config {
+        let dir = tempfile::TempDir::new().unwrap();
+        let git_dir = dir.path().join(".git");
+        std::fs::create_dir_all(&git_dir).unwrap();
+        if let Some(repo) = repo {
+            std::fs::write(dir.path().join(REPO_CONFIG_FILE), repo).unwrap();
+        }
+        if let Some(local) = local {
+            std::fs::write(git_dir.join(LOCAL_CONFIG_FILE), local).unwrap();
+        }
+        let mut config = Config::from_global_str(global, PathBuf::from("config.toml")).unwrap();
+        config.apply_repo_layers(dir.path(), &git_dir).unwrap();
+        config
+    }
+
+    #[test]
+    fn test_repo_layer_extends_lists_and_overrides_scalars() {
+        let config = layered(
+            "[secrets]\nallowlist = [\"EXAMPLE\"]\n[ai]\nmodel = \"gpt-4o\"\n",
+            Some(
+                "[secrets]\nallowlist = [\"fixtures/\", \"EXAMPLE\"]\n[commit]\nconvention = \"conventional\"\n",
+            ),
+            Some("[ai]\nmodel = \"gpt-4o-mini\"\n[branches]\nprotected = [\"main\"]\n"),
+        );
+        assert_eq!(config.secrets.allowlist, vec!["EXAMPLE", "fixtures/"]);
+        assert_eq!(config.ai.model.as_deref(), Some("gpt-4o-mini"));
+        assert!(config.commit.is_conventional());
+        assert!(config.branches.is_protected("main"));
+    }
+
+    #[test]
+    fn test_repo_layer_provenance() {
+        let config = layered(
+            "[secrets]\nallowlist = [\"EXAMPLE\"]\n",
+            Some("[secrets]\nallowlist = [\"fixtures/\"]\n"),
+            Some("[ai]\nmodel = \"llama3\"\n"),
+        );
+        let layers = |key| config.sources.layers(key);
+        assert_eq!(
+            layers("secrets.allowlist"),
+            vec![ConfigLayer::Global, ConfigLayer::Repo]
+        );
+        assert_eq!(layers("ai.model"), vec![ConfigLayer::Local]);
+        assert_eq!(layers("general.tick_rate_ms"), vec![ConfigLayer::Default]);
+        assert_eq!(config.sources.files.len(), 3);
+    }
+
+    #[test]
+    fn test_repo_layer_rejects_global_only_keys() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join(REPO_CONFIG_FILE),
+            "[ai]\nendpoint = \"http://example.com\"\n",
+        )
+        .unwrap();
+        let mut config = Config::default();
+        let err = config
+            .apply_repo_layers(dir.path(), &dir.path().join(".git"))
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("endpoint"));
+        assert!(config.ai.endpoint.is_none());
+    }
+
+    #[test]
+    fn test_global_layer_strips_repo_values() {
+        let mut config = layered(
+            "[secrets]\nallowlist = [\"EXAMPLE\"]\n",
+            Some(
+                "[secrets]\nallowlist = [\"fixtures/\"]\n[ai]\nmodel = \"repo-model\"\n[branches]\nprotected = [\"main\"]\n",
+            ),
+            None,
+        );
+        // Edits made at runtime must survive the save
+        config.secrets.allowlist.push("added-in-ui".to_string());
+
+        let global = config.global_layer();
+        assert_eq!(global.secrets.allowlist, vec!["EXAMPLE", "added-in-ui"]);
+        assert!(global.ai.model.is_none());
+        assert!(global.branches.protected.is_empty());
+    }
+
+    #[test]
+    fn test_repo_layer_adds_rule_packs() {
+        let config = layered(
+            "[secrets]\nrule_packs = [\"~/gitleaks.toml\"]\n",
+            Some("[secrets]\nrule_packs = [\".gitleaks.toml\"]\n"),
+            None,
+        );
+        assert_eq!(
+            config.secrets.rule_packs,
+            vec!["~/gitleaks.toml", ".gitleaks.toml"]
+        );
+        assert_eq!(
+            config.global_layer().secrets.rule_packs,
+            vec!["~/gitleaks.toml"]
+        );
+    }
+
+    #[test]
+    fn test_global_layer_keeps_changed_scalar() {
+        let mut config = layered("", Some("[ai]\nmodel = \"repo-model\"\n"), None);
+        config.ai.model = Some("picked-in-setup".to_string());
+        assert_eq!(
+            config.global_layer().ai.model.as_deref(),
+            Some("picked-in-setup")
+        );
+    }
+
+    #[test]
+    fn test_repo_layer_overrides_entropy_thresholds() {
+        let config = layered(
+            "[secrets.entropy]\nhex_threshold = 3.5\n",
+            Some("[secrets.entropy]\nmin_length = 32\n"),
+            None,
+        );
+        let entropy = &config.secrets.entropy;
+        assert_eq!(entropy.min_length, 32);
+        assert_eq!(entropy.hex_threshold, 3.5);
+        assert_eq!(entropy.base64_threshold, 4.0);
+        assert_eq!(
+            config.sources.layers("secrets.entropy"),
+            vec![ConfigLayer::Global, ConfigLayer::Repo]
+        );
+        assert_eq!(config.global_layer().secrets.entropy.min_length, 20);
+    }
+
+    #[test]
+    fn test_display_value() {
+        let config = layered("", Some("[commit]\nmax_subject_length = 50\n"), None);
+        assert_eq!(
+            config.display_value("commit.max_subject_length").unwrap(),
+            "50"
+        );
+        assert!(config.display_value("ai.model").is_none());
+    }
+
+    // ── Protected branches ──────────────────────────────────────────
+    #[test]
+    fn test_protected_branch_patterns() {
+        let branches = BranchesConfig {
+            protected: vec!["main".to_string(), "release/*".to_string()],
+        };
+        assert!(branches.is_protected("main"));
+        assert!(branches.is_protected("release/1.2"));
+        assert!(!branches.is_protected("mainline"));
+        assert!(!branches.is_protected("feature/release"));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*", "anything"));
+        assert!(wildcard_match("hotfix-*-rc", "hotfix-12-rc"));
+        assert!(!wildcard_match("hotfix-*-rc", "hotfix-12"));
+        assert!(wildcard_match("a*b*c", "abc"));
+        assert!(!wildcard_match("ab*ba", "aba"));
+    }
 }
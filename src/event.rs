@@ -3,17 +3,22 @@ use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
 
+use crate::watcher::RepoWatcher;
+
 pub enum AppEvent {
     Key(KeyEvent),
     Mouse(MouseEvent),
     Tick,
+    /// Files in the worktree or `.git` changed (debounced).
+    RepoChanged,
     #[allow(dead_code)] // dispatched from event loop but fields not read in match arm
     Resize(u16, u16),
 }
 
 pub struct EventHandler {
     rx: mpsc::Receiver<AppEvent>,
-    _tx: mpsc::Sender<AppEvent>,
+    tx: mpsc::Sender<AppEvent>,
+    watcher: Option<RepoWatcher>,
 }
 
 impl EventHandler {
@@ -28,20 +33,21 @@ impl EventHandler {
                 // Poll for crossterm events with tick_rate timeout
                 if event::poll(tick_rate).unwrap_or(false) {
                     match event::read() {
-                        Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
-                            if event_tx.send(AppEvent::Key(key)).is_err() {
-                                return;
-                            }
+                        Ok(Event::Key(key))
+                            if key.kind == KeyEventKind::Press
+                                && event_tx.send(AppEvent::Key(key)).is_err() =>
+                        {
+                            return;
                         }
-                        Ok(Event::Mouse(mouse)) => {
-                            if event_tx.send(AppEvent::Mouse(mouse)).is_err() {
-                                return;
-                            }
+                        Ok(Event::Mouse(mouse))
+                            if event_tx.send(AppEvent::Mouse(mouse)).is_err() =>
+                        {
+                            return;
                         }
-                        Ok(Event::Resize(w, h)) => {
-                            if event_tx.send(AppEvent::Resize(w, h)).is_err() {
-                                return;
-                            }
+                        Ok(Event::Resize(w, h))
+                            if event_tx.send(AppEvent::Resize(w, h)).is_err() =>
+                        {
+                            return;
                         }
                         _ => {}
                     }
@@ -54,7 +60,37 @@ impl EventHandler {
             }
         });
 
-        Self { rx, _tx: tx }
+        Self {
+            rx,
+            tx,
+            watcher: None,
+        }
+    }
+
+    /// Start emitting `AppEvent::RepoChanged` when the current repository changes.
+    /// Replaces any previous watcher. Returns false if the watcher could not be started.
+    pub fn watch_repo(&mut self) -> bool {
+        let started =
+            crate::git::repo::current().and_then(|repo| RepoWatcher::start(&repo, self.tx.clone()));
+        match started {
+            Ok(watcher) => {
+                self.watcher = Some(watcher);
+                true
+            }
+            Err(e) => {
+                log::warn!(
+                    "File watcher unavailable, falling back to tick refresh: {}",
+                    e
+                );
+                self.watcher = None;
+                false
+            }
+        }
+    }
+
+    /// Whether repository changes are being watched (vs. polled on every tick).
+    pub fn is_watching(&self) -> bool {
+        self.watcher.is_some()
     }
 
     /// Receive the next event (blocking).
@@ -2,7 +2,6 @@
 
 use super::runner::run_git;
 use anyhow::Result;
-use std::path::Path;
 
 /// Current phase of a bisect session.
 #[derive(Debug, Clone, PartialEq)]
@@ -38,13 +37,9 @@ pub struct BisectLogEntry {
 /// Check whether a bisect session is currently active.
 pub fn is_bisecting() -> bool {
     // Git stores bisect state in .git/BISECT_LOG
-    let output = run_git(&["rev-parse", "--git-dir"]);
-    if let Ok(git_dir) = output {
-        let bisect_log = Path::new(git_dir.trim()).join("BISECT_LOG");
-        bisect_log.exists()
-    } else {
-        false
-    }
+    super::repo::current()
+        .map(|repo| repo.git_dir().join("BISECT_LOG").exists())
+        .unwrap_or(false)
 }
 
 /// Start a bisect session.
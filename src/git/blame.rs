@@ -0,0 +1,252 @@
+//! Line-by-line blame built on `git blame --porcelain`.
+
+use super::runner::run_git;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Hash git uses for lines that are not committed yet.
+pub const UNCOMMITTED: &str = "0000000000000000000000000000000000000000";
+
+/// Metadata for a commit that owns one or more blamed lines.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlameCommit {
+    pub hash: String,
+    pub author: String,
+    pub author_mail: String,
+    /// Unix timestamp of the authoring.
+    pub author_time: i64,
+    pub summary: String,
+    /// Parent commit and the file's path there, for blaming one step back.
+    pub previous: Option<(String, String)>,
+    /// Path of the file in this commit (differs from the blamed path after renames).
+    pub filename: String,
+    /// Root commit or the edge of a shallow/limited range.
+    pub boundary: bool,
+}
+
+impl BlameCommit {
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+
+    pub fn is_uncommitted(&self) -> bool {
+        self.hash == UNCOMMITTED
+    }
+}
+
+/// One line of the blamed file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlameLine {
+    pub hash: String,
+    /// Line number in the commit that introduced it.
+    pub orig_line: u32,
+    /// Line number in the blamed revision.
+    pub final_line: u32,
+    pub content: String,
+}
+
+/// Blame output for one file at one revision.
+#[derive(Debug, Clone, Default)]
+pub struct Blame {
+    pub path: String,
+    /// Revision blamed, or `None` for the working tree.
+    pub rev: Option<String>,
+    pub commits: HashMap<String, BlameCommit>,
+    pub lines: Vec<BlameLine>,
+}
+
+impl Blame {
+    pub fn commit_for(&self, line: usize) -> Option<&BlameCommit> {
+        self.lines.get(line).and_then(|l| self.commits.get(&l.hash))
+    }
+
+    /// Runs of consecutive lines that come from the same commit.
+    pub fn groups(&self) -> Vec<Range<usize>> {
+        let mut groups: Vec<Range<usize>> = Vec::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            match groups.last_mut() {
+                Some(group) if self.lines[group.start].hash == line.hash => group.end = i + 1,
+                _ => groups.push(i..i + 1),
+            }
+        }
+        groups
+    }
+}
+
+/// Blame `path` at `rev`, or the working tree copy when `rev` is `None`.
+pub fn blame(path: &str, rev: Option<&str>) -> Result<Blame> {
+    let mut args = vec!["blame", "--porcelain"];
+    if let Some(rev) = rev {
+        args.push(rev);
+    }
+    args.extend(["--", path]);
+    let output = run_git(&args)?;
+    let (commits, lines) = parse_porcelain(&output);
+    Ok(Blame {
+        path: path.to_string(),
+        rev: rev.map(str::to_string),
+        commits,
+        lines,
+    })
+}
+
+/// Parse `--porcelain` output. Commit metadata is only printed the first time
+/// a commit appears; later lines from it carry just the header and content.
+fn parse_porcelain(output: &str) -> (HashMap<String, BlameCommit>, Vec<BlameLine>) {
+    let mut commits: HashMap<String, BlameCommit> = HashMap::new();
+    let mut lines = Vec::new();
+    let mut current: Option<(String, u32, u32)> = None;
+
+    for line in output.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            if let Some((hash, orig_line, final_line)) = current.take() {
+                lines.push(BlameLine {
+                    hash,
+                    orig_line,
+                    final_line,
+                    content: content.to_string(),
+                });
+            }
+            continue;
+        }
+
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        if key.len() == 40 && key.bytes().all(|b| b.is_ascii_hexdigit()) {
+            let mut numbers = value.split_whitespace().map(|n| n.parse().unwrap_or(0));
+            let orig_line = numbers.next().unwrap_or(0);
+            let final_line = numbers.next().unwrap_or(0);
+            commits
+                .entry(key.to_string())
+                .or_insert_with(|| BlameCommit {
+                    hash: key.to_string(),
+                    ..Default::default()
+                });
+            current = Some((key.to_string(), orig_line, final_line));
+            continue;
+        }
+
+        let Some(commit) = current
+            .as_ref()
+            .and_then(|(hash, _, _)| commits.get_mut(hash))
+        else {
+            continue;
+        };
+        match key {
+            "author" => commit.author = value.to_string(),
+            "author-mail" => commit.author_mail = value.to_string(),
+            "author-time" => commit.author_time = value.parse().unwrap_or(0),
+            "summary" => commit.summary = value.to_string(),
+            "filename" => commit.filename = value.to_string(),
+            "boundary" => commit.boundary = true,
+            "previous" => {
+                if let Some((hash, path)) = value.split_once(' ') {
+                    commit.previous = Some((hash.to_string(), path.to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+    (commits, lines)
+}
+
+/// Full commit message, for explaining why a change was made.
+pub fn commit_message(hash: &str) -> Result<String> {
+    Ok(run_git(&["log", "-1", "--format=%B", hash])?
+        .trim()
+        .to_string())
+}
+
+/// Patch `hash` made to `path`.
+pub fn commit_file_patch(hash: &str, path: &str) -> Result<String> {
+    run_git(&["show", "--format=", "--patch", hash, "--", path])
+}
+
+/// Coarse age bucket of a timestamp relative to `now`: 0 = this week … 4 = over a year.
+pub fn age_bucket(time: i64, now: i64) -> usize {
+    const DAY: i64 = 24 * 60 * 60;
+    match now.saturating_sub(time) / DAY {
+        0..=7 => 0,
+        8..=31 => 1,
+        32..=182 => 2,
+        183..=365 => 3,
+        _ => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: &str = "1111111111111111111111111111111111111111";
+    const B: &str = "2222222222222222222222222222222222222222";
+
+    fn sample() -> String {
+        format!(
+            "{A} 1 1 2\n\
+             author Alice\n\
+             author-mail <alice@example.com>\n\
+             author-time 1700000000\n\
+             summary First\n\
+             boundary\n\
+             filename src/lib.rs\n\
+             \tfn one() {{}}\n\
+             {A} 2 2\n\
+             \tfn two() {{}}\n\
+             {B} 3 3 1\n\
+             author Bob\n\
+             author-time 1700100000\n\
+             summary Second\n\
+             previous {A} src/old.rs\n\
+             filename src/lib.rs\n\
+             \t\tindented\n\
+             {A} 3 4 1\n\
+             \tfn four() {{}}\n"
+        )
+    }
+
+    #[test]
+    fn test_parse_porcelain() {
+        let (commits, lines) = parse_porcelain(&sample());
+        assert_eq!(commits.len(), 2);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1].hash, A);
+        assert_eq!(lines[1].content, "fn two() {}");
+        assert_eq!(lines[2].content, "\tindented");
+        assert_eq!(lines[3].final_line, 4);
+
+        let a = &commits[A];
+        assert_eq!(a.author, "Alice");
+        assert_eq!(a.author_time, 1_700_000_000);
+        assert!(a.boundary);
+        assert_eq!(a.short_hash(), "1111111");
+
+        let b = &commits[B];
+        assert_eq!(b.summary, "Second");
+        assert_eq!(b.previous, Some((A.to_string(), "src/old.rs".to_string())));
+    }
+
+    #[test]
+    fn test_groups() {
+        let (commits, lines) = parse_porcelain(&sample());
+        let blame = Blame {
+            path: "src/lib.rs".to_string(),
+            rev: None,
+            commits,
+            lines,
+        };
+        assert_eq!(blame.groups(), vec![0..2, 2..3, 3..4]);
+        assert_eq!(blame.commit_for(2).unwrap().author, "Bob");
+    }
+
+    #[test]
+    fn test_age_bucket() {
+        let now = 1_700_000_000;
+        let day = 24 * 60 * 60;
+        assert_eq!(age_bucket(now, now), 0);
+        assert_eq!(age_bucket(now - 20 * day, now), 1);
+        assert_eq!(age_bucket(now - 100 * day, now), 2);
+        assert_eq!(age_bucket(now - 300 * day, now), 3);
+        assert_eq!(age_bucket(now - 800 * day, now), 4);
+    }
+}
@@ -3,7 +3,6 @@
 use super::log::CommitEntry;
 use super::runner::run_git;
 use anyhow::Result;
-use std::path::Path;
 
 /// Cherry-pick a single commit onto the current branch.
 pub fn cherry_pick(commit_hash: &str) -> Result<String> {
@@ -29,12 +28,9 @@ pub fn cherry_pick_continue() -> Result<String> {
 
 /// Check if a cherry-pick is currently in progress.
 pub fn is_cherry_picking() -> bool {
-    if let Ok(git_dir) = run_git(&["rev-parse", "--git-dir"]) {
-        let cp_head = Path::new(git_dir.trim()).join("CHERRY_PICK_HEAD");
-        cp_head.exists()
-    } else {
-        false
-    }
+    super::repo::current()
+        .map(|repo| repo.git_dir().join("CHERRY_PICK_HEAD").exists())
+        .unwrap_or(false)
 }
 
 /// Get local branch names (excluding the current one) for source selection.
@@ -85,18 +81,12 @@ pub fn get_cherry_candidates(source_branch: &str, count: usize) -> Result<Vec<Co
                 date_iso: String::new(),
                 parents: Vec::new(),
                 refs: String::new(),
-                graph: String::new(),
             });
         }
     }
     Ok(entries)
 }
 
-/// Show the diff for a specific commit (for preview).
-pub fn commit_diff(commit_hash: &str) -> Result<String> {
-    run_git(&["show", "--stat", "--patch", commit_hash])
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
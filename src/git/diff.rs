@@ -32,6 +32,9 @@ pub struct FileDiff {
     pub path: String,
     pub old_path: Option<String>,
     pub hunks: Vec<Hunk>,
+    /// The hunks describe Git LFS objects in place of pointer file text,
+    /// so they can't be applied as patches.
+    pub lfs: bool,
 }
 
 /// Get diff of unstaged changes (working tree vs index).
@@ -59,9 +62,28 @@ pub fn get_staged_diff_for_file(path: &str) -> Result<Vec<FileDiff>> {
     Ok(parse_diff_output(&output))
 }
 
-/// Get diff for a specific commit.
+/// Get diff for a specific commit against its first parent.
+/// Root commits are shown in full.
 pub fn get_commit_diff(hash: &str) -> Result<Vec<FileDiff>> {
-    let output = run_git(&["diff", &format!("{}^..{}", hash, hash)])?;
+    let parents = super::repo::current()?.commit_parents(hash)?;
+    let output = match parents.first() {
+        Some(parent) => run_git(&["diff", parent, hash])?,
+        None => run_git(&["show", "--format=", "--patch", hash])?,
+    };
+    Ok(parse_diff_output(&output))
+}
+
+/// Get the diff a commit made to specific paths against its first parent.
+/// Pass both the old and new path of a rename to see it as one file.
+pub fn get_commit_file_diff(hash: &str, paths: &[&str]) -> Result<Vec<FileDiff>> {
+    let parents = super::repo::current()?.commit_parents(hash)?;
+    let mut args = match parents.first() {
+        Some(parent) => vec!["diff", "-M", parent.as_str(), hash],
+        None => vec!["show", "--format=", "--patch", hash],
+    };
+    args.push("--");
+    args.extend(paths);
+    let output = run_git(&args)?;
     Ok(parse_diff_output(&output))
 }
 
@@ -79,7 +101,200 @@ pub fn stage_hunk(file_path: &str, hunk: &Hunk) -> Result<()> {
 /// Unstage a single hunk via `git apply --cached -R` (reverse apply).
 pub fn unstage_hunk(file_path: &str, hunk: &Hunk) -> Result<()> {
     let patch = build_hunk_patch(file_path, hunk);
-    apply_patch_reverse(&patch)
+    apply_patch_reverse(&patch, true)
+}
+
+/// Stage only the selected lines of a hunk (indices into `hunk.lines`).
+/// Unselected removals stay in the index as context; unselected additions are left out.
+pub fn stage_lines(file_path: &str, hunk: &Hunk, selected: &[usize]) -> Result<()> {
+    let patch = build_lines_patch(file_path, hunk, selected, false)
+        .context("No added or removed lines selected")?;
+    apply_patch(&patch, true)
+}
+
+/// Unstage only the selected lines of a staged hunk.
+pub fn unstage_lines(file_path: &str, hunk: &Hunk, selected: &[usize]) -> Result<()> {
+    let patch = build_lines_patch(file_path, hunk, selected, true)
+        .context("No added or removed lines selected")?;
+    apply_patch_reverse(&patch, true)
+}
+
+/// Discard the selected lines of an unstaged hunk from the working tree.
+pub fn discard_lines(file_path: &str, hunk: &Hunk, selected: &[usize]) -> Result<()> {
+    let patch = build_lines_patch(file_path, hunk, selected, true)
+        .context("No added or removed lines selected")?;
+    apply_patch_reverse(&patch, false)
+}
+
+/// Split a hunk into smaller hunks at the context lines between runs of changes,
+/// like `git add -p`'s split. Context between two runs is shared by both pieces.
+/// A hunk with a single run of changes is returned unchanged.
+pub fn split_hunk(hunk: &Hunk) -> Vec<Hunk> {
+    // Runs of added/removed lines as [start, end) indices into hunk.lines
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for (i, line) in hunk.lines.iter().enumerate() {
+        let is_change = matches!(line.line_type, DiffLineType::Added | DiffLineType::Removed);
+        if is_change || line.content.starts_with('\\') {
+            match runs.last_mut() {
+                Some(run) if run.1 == i => run.1 = i + 1,
+                _ if is_change => runs.push((i, i + 1)),
+                _ => {}
+            }
+        }
+    }
+    if runs.len() < 2 {
+        return vec![hunk.clone()];
+    }
+
+    let body_start = usize::from(
+        hunk.lines
+            .first()
+            .is_some_and(|l| l.line_type == DiffLineType::Header),
+    );
+    let mut pieces = Vec::with_capacity(runs.len());
+    for r in 0..runs.len() {
+        let from = if r == 0 { body_start } else { runs[r - 1].1 };
+        let to = runs.get(r + 1).map_or(hunk.lines.len(), |next| next.0);
+
+        // Old/new line numbers at `from`
+        let mut old_start = hunk.old_start;
+        let mut new_start = hunk.new_start;
+        for line in &hunk.lines[body_start..from] {
+            let (old, new) = line_sides(line);
+            old_start += old;
+            new_start += new;
+        }
+
+        let body = &hunk.lines[from..to];
+        let old_count: u32 = body.iter().map(|l| line_sides(l).0).sum();
+        let new_count: u32 = body.iter().map(|l| line_sides(l).1).sum();
+
+        let header = format_hunk_header(old_start, old_count, new_start, new_count);
+        let mut lines = vec![DiffLine {
+            line_type: DiffLineType::Header,
+            content: header.clone(),
+        }];
+        lines.extend(body.iter().cloned());
+        pieces.push(Hunk {
+            header,
+            old_start,
+            old_count,
+            new_start,
+            new_count,
+            lines,
+        });
+    }
+    pieces
+}
+
+/// How many old-side and new-side lines a diff line accounts for.
+fn line_sides(line: &DiffLine) -> (u32, u32) {
+    match line.line_type {
+        DiffLineType::Added => (0, 1),
+        DiffLineType::Removed => (1, 0),
+        DiffLineType::Header => (0, 0),
+        // "\ No newline at end of file" annotates the previous line
+        DiffLineType::Context if line.content.starts_with('\\') => (0, 0),
+        DiffLineType::Context => (1, 1),
+    }
+}
+
+fn format_hunk_header(old_start: u32, old_count: u32, new_start: u32, new_count: u32) -> String {
+    format!(
+        "@@ -{},{} +{},{} @@",
+        old_start, old_count, new_start, new_count
+    )
+}
+
+/// Build a patch containing only the `selected` lines of `hunk`, with a header
+/// recomputed from the lines that remain.
+///
+/// Forward patches (staging) are applied on top of the hunk's old side: unselected
+/// removals turn into context and unselected additions are dropped. Reverse patches
+/// (unstaging, discarding) are applied backwards from the new side, so it's the
+/// other way round. Returns `None` when no added or removed line is selected.
+fn build_lines_patch(
+    file_path: &str,
+    hunk: &Hunk,
+    selected: &[usize],
+    reverse: bool,
+) -> Option<String> {
+    let mut body: Vec<String> = Vec::new();
+    let mut old_count = 0u32;
+    let mut new_count = 0u32;
+    let mut any_change = false;
+    // Whether the previous line made it into the patch, for "\ No newline" markers
+    let mut kept_previous = false;
+
+    for (i, line) in hunk.lines.iter().enumerate() {
+        let is_selected = selected.contains(&i);
+        let (kept, old, new) = match line.line_type {
+            DiffLineType::Header => continue,
+            DiffLineType::Context if line.content.starts_with('\\') => {
+                if kept_previous {
+                    body.push(line.content.clone());
+                }
+                continue;
+            }
+            DiffLineType::Context => (Some(line.content.clone()), 1, 1),
+            DiffLineType::Added if is_selected => {
+                any_change = true;
+                (Some(line.content.clone()), 0, 1)
+            }
+            DiffLineType::Removed if is_selected => {
+                any_change = true;
+                (Some(line.content.clone()), 1, 0)
+            }
+            // Unselected lines on the side the patch starts from become context
+            DiffLineType::Added if reverse => (Some(as_context(&line.content)), 1, 1),
+            DiffLineType::Removed if !reverse => (Some(as_context(&line.content)), 1, 1),
+            DiffLineType::Added | DiffLineType::Removed => (None, 0, 0),
+        };
+        kept_previous = kept.is_some();
+        if let Some(content) = kept {
+            body.push(content);
+            old_count += old;
+            new_count += new;
+        }
+    }
+
+    if !any_change {
+        return None;
+    }
+
+    // The untouched side keeps its position; the other side is derived from it.
+    // Zero-length ranges point at the line before the change, as git writes them.
+    let anchor = if reverse {
+        hunk.new_start + u32::from(hunk.new_count == 0)
+    } else {
+        hunk.old_start + u32::from(hunk.old_count == 0)
+    };
+    let at = |count: u32| {
+        if count == 0 {
+            anchor.saturating_sub(1)
+        } else {
+            anchor
+        }
+    };
+    let header = format_hunk_header(at(old_count), old_count, at(new_count), new_count);
+
+    let mut patch = String::new();
+    patch.push_str(&format!("--- a/{}\n", file_path));
+    patch.push_str(&format!("+++ b/{}\n", file_path));
+    patch.push_str(&header);
+    patch.push('\n');
+    for line in body {
+        patch.push_str(&line);
+        patch.push('\n');
+    }
+    Some(patch)
+}
+
+/// Turn a `+`/`-` diff line into a context line.
+fn as_context(content: &str) -> String {
+    let mut chars = content.chars();
+    chars.next();
+    format!(" {}", chars.as_str())
 }
 
 /// Build a minimal unified-diff patch for a single hunk.
@@ -127,13 +342,20 @@ fn apply_patch(patch: &str, cached: bool) -> Result<()> {
     Ok(())
 }
 
-/// Reverse-apply a patch from the index (unstage a hunk).
-fn apply_patch_reverse(patch: &str) -> Result<()> {
+/// Reverse-apply a patch to the index (unstage) or, without `cached`, to the
+/// working tree (discard).
+fn apply_patch_reverse(patch: &str, cached: bool) -> Result<()> {
     use std::io::Write;
     use std::process::{Command, Stdio};
 
+    let mut args = vec!["apply", "--reverse", "--unidiff-zero"];
+    if cached {
+        args.push("--cached");
+    }
+    args.push("-");
+
     let mut child = Command::new("git")
-        .args(["apply", "--cached", "--reverse", "--unidiff-zero", "-"])
+        .args(&args)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -154,7 +376,9 @@ fn apply_patch_reverse(patch: &str) -> Result<()> {
     Ok(())
 }
 
-fn parse_diff_output(output: &str) -> Vec<FileDiff> {
+/// Parse `git diff`-style output into per-file hunks. Text before the first
+/// `diff --git` line (e.g. a `--stat` summary) is ignored.
+pub fn parse_diff_output(output: &str) -> Vec<FileDiff> {
     let mut files: Vec<FileDiff> = Vec::new();
     let mut current_file: Option<FileDiff> = None;
     let mut current_hunk: Option<Hunk> = None;
@@ -166,6 +390,7 @@ fn parse_diff_output(output: &str) -> Vec<FileDiff> {
                 if let Some(h) = current_hunk.take() {
                     f.hunks.push(h);
                 }
+                super::lfs::summarize_pointer_diff(f);
                 files.push(f.clone());
             }
 
@@ -176,6 +401,7 @@ fn parse_diff_output(output: &str) -> Vec<FileDiff> {
                 path,
                 old_path: None,
                 hunks: Vec::new(),
+                lfs: false,
             });
             current_hunk = None;
         } else if line.starts_with("rename from ") {
@@ -184,10 +410,10 @@ fn parse_diff_output(output: &str) -> Vec<FileDiff> {
             }
         } else if line.starts_with("@@") {
             // Save previous hunk
-            if let Some(ref mut f) = current_file {
-                if let Some(h) = current_hunk.take() {
-                    f.hunks.push(h);
-                }
+            if let Some(ref mut f) = current_file
+                && let Some(h) = current_hunk.take()
+            {
+                f.hunks.push(h);
             }
 
             let (old_start, old_count, new_start, new_count) = parse_hunk_header(line);
@@ -223,13 +449,15 @@ fn parse_diff_output(output: &str) -> Vec<FileDiff> {
         if let Some(h) = current_hunk.take() {
             f.hunks.push(h);
         }
+        super::lfs::summarize_pointer_diff(f);
         files.push(f.clone());
     }
 
     files
 }
 
-fn parse_hunk_header(header: &str) -> (u32, u32, u32, u32) {
+/// Parse `@@ -a,b +c,d @@` into `(a, b, c, d)`; a missing count means 1.
+pub fn parse_hunk_header(header: &str) -> (u32, u32, u32, u32) {
     // Format: @@ -old_start,old_count +new_start,new_count @@
     let mut old_start = 0u32;
     let mut old_count = 1u32;
@@ -260,10 +488,84 @@ fn parse_hunk_header(header: &str) -> (u32, u32, u32, u32) {
     (old_start, old_count, new_start, new_count)
 }
 
+/// Undo git's C-style quoting of a path: `"cl\303\251.env"` → `clé.env`.
+/// Paths git didn't quote are returned unchanged.
+pub fn unquote_path(path: &str) -> String {
+    let Some(inner) = path.strip_prefix('"').and_then(|p| p.strip_suffix('"')) else {
+        return path.to_string();
+    };
+    let mut out = Vec::with_capacity(inner.len());
+    let mut bytes = inner.bytes().peekable();
+    while let Some(byte) = bytes.next() {
+        if byte != b'\\' {
+            out.push(byte);
+            continue;
+        }
+        let Some(escaped) = bytes.next() else {
+            break;
+        };
+        out.push(match escaped {
+            b'a' => 0x07,
+            b'b' => 0x08,
+            b't' => b'\t',
+            b'n' => b'\n',
+            b'v' => 0x0b,
+            b'f' => 0x0c,
+            b'r' => b'\r',
+            // Three octal digits, one byte of the UTF-8 name
+            b'0'..=b'7' => {
+                let mut value = escaped - b'0';
+                for _ in 0..2 {
+                    match bytes.peek() {
+                        Some(&d @ b'0'..=b'7') => {
+                            value = value.wrapping_mul(8).wrapping_add(d - b'0');
+                            bytes.next();
+                        }
+                        _ => break,
+                    }
+                }
+                value
+            }
+            // `\\` and `\"`
+            other => other,
+        });
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Path of the file a `+++ ` diff header adds lines to, from the text after
+/// `+++ `, without the `b/` prefix. Handles quoted names and the TAB git
+/// appends to names containing a space. `None` for `/dev/null`.
+pub fn new_file_path(target: &str) -> Option<String> {
+    let target = target.strip_suffix('\t').unwrap_or(target);
+    unquote_path(target).strip_prefix("b/").map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_unquote_path() {
+        assert_eq!(unquote_path("src/main.rs"), "src/main.rs");
+        assert_eq!(unquote_path("\"cl\\303\\251.env\""), "clé.env");
+        assert_eq!(unquote_path("\"a\\\"b\\\\c\\td\""), "a\"b\\c\td");
+    }
+
+    #[test]
+    fn test_new_file_path() {
+        assert_eq!(
+            new_file_path("b/src/main.rs").as_deref(),
+            Some("src/main.rs")
+        );
+        assert_eq!(new_file_path("b/a b.env\t").as_deref(), Some("a b.env"));
+        assert_eq!(
+            new_file_path("\"b/cl\\303\\251 x.env\"").as_deref(),
+            Some("clé x.env")
+        );
+        assert_eq!(new_file_path("/dev/null"), None);
+    }
+
     #[test]
     fn test_parse_diff_output() {
         let sample = "\
@@ -392,4 +694,111 @@ index abc..def 100644
         assert_eq!(files[0].hunks[0].old_start, 1);
         assert_eq!(files[0].hunks[1].old_start, 10);
     }
+
+    fn sample_hunk() -> Hunk {
+        let sample = "\
+diff --git a/f.txt b/f.txt
+--- a/f.txt
++++ b/f.txt
+@@ -1,6 +1,6 @@
+ a
+-b
++B
+ c
+ d
+-e
++E
+ f
+";
+        parse_diff_output(sample).remove(0).hunks.remove(0)
+    }
+
+    #[test]
+    fn test_build_lines_patch_stage_selected_addition_only() {
+        let hunk = sample_hunk();
+        // lines: 0 header, 1 " a", 2 "-b", 3 "+B", 4 " c", 5 " d", 6 "-e", 7 "+E", 8 " f"
+        let patch = build_lines_patch("f.txt", &hunk, &[3], false).unwrap();
+        assert_eq!(
+            patch,
+            "--- a/f.txt\n+++ b/f.txt\n@@ -1,6 +1,7 @@\n a\n b\n+B\n c\n d\n e\n f\n"
+        );
+    }
+
+    #[test]
+    fn test_build_lines_patch_reverse_keeps_new_side() {
+        let hunk = sample_hunk();
+        let patch = build_lines_patch("f.txt", &hunk, &[6, 7], true).unwrap();
+        assert_eq!(
+            patch,
+            "--- a/f.txt\n+++ b/f.txt\n@@ -1,6 +1,6 @@\n a\n B\n c\n d\n-e\n+E\n f\n"
+        );
+    }
+
+    #[test]
+    fn test_build_lines_patch_needs_a_change() {
+        let hunk = sample_hunk();
+        assert!(build_lines_patch("f.txt", &hunk, &[], false).is_none());
+        assert!(build_lines_patch("f.txt", &hunk, &[0, 1, 4], false).is_none());
+    }
+
+    #[test]
+    fn test_build_lines_patch_pure_addition_header() {
+        let sample = "\
+diff --git a/f.txt b/f.txt
+--- a/f.txt
++++ b/f.txt
+@@ -3,0 +4,2 @@
++x
++y
+";
+        let hunk = parse_diff_output(sample).remove(0).hunks.remove(0);
+        let patch = build_lines_patch("f.txt", &hunk, &[2], false).unwrap();
+        assert!(patch.contains("@@ -3,0 +4,1 @@\n+y\n"));
+        // Unstaging one of two staged additions leaves the other as context
+        let patch = build_lines_patch("f.txt", &hunk, &[2], true).unwrap();
+        assert!(patch.contains("@@ -4,1 +4,2 @@\n x\n+y\n"));
+    }
+
+    #[test]
+    fn test_build_lines_patch_no_newline_marker_follows_its_line() {
+        let sample = "\
+diff --git a/f.txt b/f.txt
+--- a/f.txt
++++ b/f.txt
+@@ -1,2 +1,2 @@
+ a
+-b
+\\ No newline at end of file
++c
+\\ No newline at end of file
+";
+        let hunk = parse_diff_output(sample).remove(0).hunks.remove(0);
+        let patch = build_lines_patch("f.txt", &hunk, &[2, 4], false).unwrap();
+        assert!(
+            patch.ends_with("-b\n\\ No newline at end of file\n+c\n\\ No newline at end of file\n")
+        );
+        // Dropping the addition drops its marker too
+        let patch = build_lines_patch("f.txt", &hunk, &[2], false).unwrap();
+        assert!(patch.ends_with("@@ -1,2 +1,1 @@\n a\n-b\n\\ No newline at end of file\n"));
+    }
+
+    #[test]
+    fn test_split_hunk_at_context() {
+        let hunk = sample_hunk();
+        let pieces = split_hunk(&hunk);
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].header, "@@ -1,4 +1,4 @@");
+        assert_eq!(pieces[0].lines.len(), 6); // header, a, -b, +B, c, d
+        assert_eq!(pieces[1].header, "@@ -3,4 +3,4 @@");
+        assert_eq!(pieces[1].lines[1].content, " c");
+        assert_eq!(pieces[1].lines.last().unwrap().content, " f");
+    }
+
+    #[test]
+    fn test_split_hunk_single_run_unchanged() {
+        let pieces = split_hunk(&sample_hunk().clone());
+        let single = split_hunk(&pieces[0]);
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].header, pieces[0].header);
+    }
 }
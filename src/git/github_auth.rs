@@ -1,9 +1,17 @@
 use anyhow::{Context, Result};
+use reqwest::Method;
+use reqwest::blocking::{RequestBuilder, Response};
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
 
-/// GitHub OAuth App Client ID for zit.
+use crate::config::{GithubConfig, GithubHostConfig};
+
+/// GitHub OAuth App Client ID for zit on github.com.
 pub const CLIENT_ID: &str = "Ov23liMBOn6cAuIPFslq";
 
+/// Host name of the public GitHub.
+pub const GITHUB_COM: &str = "github.com";
+
 /// Scopes to request — repo access for push/pull/create, read:user for username.
 const SCOPES: &str = "repo,read:user";
 
@@ -54,262 +62,441 @@ pub enum PollResult {
     Error(String),
 }
 
-/// Step 1: Request device and user verification codes from GitHub.
-pub fn request_device_code() -> Result<DeviceCodeResponse> {
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .post("https://github.com/login/device/code")
-        .header("Accept", "application/json")
-        .form(&[("client_id", CLIENT_ID), ("scope", SCOPES)])
-        .send()
-        .context("Failed to contact GitHub for device code")?;
+// ─── Hosts ───────────────────────────────────────────────────────
 
-    let status = resp.status();
-    let body = resp.text().context("Failed to read GitHub response")?;
+/// Where a GitHub instance serves its web UI and REST API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GithubHost {
+    /// Host name as it appears in remote URLs.
+    pub name: String,
+    /// Web root, e.g. `https://github.com`.
+    pub web_url: String,
+    /// REST API root, e.g. `https://api.github.com`.
+    pub api_url: String,
+    /// OAuth App used for the device login flow.
+    pub client_id: Option<String>,
+}
 
-    if !status.is_success() {
-        anyhow::bail!("GitHub returned {}: {}", status, body);
+impl GithubHost {
+    pub fn github_com() -> Self {
+        GithubHost {
+            name: GITHUB_COM.to_string(),
+            web_url: "https://github.com".to_string(),
+            api_url: "https://api.github.com".to_string(),
+            client_id: Some(CLIENT_ID.to_string()),
+        }
     }
 
-    let response: DeviceCodeResponse =
-        serde_json::from_str(&body).context("Failed to parse device code response")?;
+    /// A GitHub Enterprise Server instance. Unset URLs follow the GHES
+    /// layout: `https://<host>` and `https://<host>/api/v3`.
+    pub fn enterprise(entry: &GithubHostConfig) -> Self {
+        let web_url = match &entry.web_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None => format!("https://{}", entry.host),
+        };
+        let api_url = match &entry.api_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None => format!("{}/api/v3", web_url),
+        };
+        GithubHost {
+            name: entry.host.to_lowercase(),
+            web_url,
+            api_url,
+            client_id: entry.client_id.clone(),
+        }
+    }
 
-    Ok(response)
-}
+    /// The host called `name` if zit knows it as GitHub: a `[[github.hosts]]`
+    /// entry, the `github.host` default, or github.com itself.
+    pub fn lookup(config: &GithubConfig, name: &str) -> Option<Self> {
+        if let Some(entry) = config
+            .hosts
+            .iter()
+            .find(|h| h.host.eq_ignore_ascii_case(name))
+        {
+            return Some(Self::enterprise(entry));
+        }
+        let name = name.to_lowercase();
+        if matches!(
+            name.as_str(),
+            GITHUB_COM | "www.github.com" | "ssh.github.com"
+        ) {
+            return Some(Self::github_com());
+        }
+        // `github.host` alone is enough for an instance with the GHES layout
+        config
+            .host
+            .as_deref()
+            .filter(|h| h.eq_ignore_ascii_case(&name))
+            .map(|h| {
+                Self::enterprise(&GithubHostConfig {
+                    host: h.to_string(),
+                    ..Default::default()
+                })
+            })
+    }
 
-/// Step 3: Poll GitHub to check if the user has authorized the device.
-pub fn poll_for_token(device_code: &str) -> PollResult {
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .post("https://github.com/login/oauth/access_token")
-        .header("Accept", "application/json")
-        .form(&[
-            ("client_id", CLIENT_ID),
-            ("device_code", device_code),
-            ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
-        ])
-        .send();
+    /// Host for requests that aren't about a remote: `github.host`, else github.com.
+    pub fn default_for(config: &GithubConfig) -> Self {
+        config
+            .host
+            .as_deref()
+            .and_then(|h| Self::lookup(config, h))
+            .unwrap_or_else(Self::github_com)
+    }
 
-    let resp = match resp {
-        Ok(r) => r,
-        Err(e) => return PollResult::Error(format!("Network error: {}", e)),
-    };
+    /// Host of the `origin` remote, or the default host when `origin` isn't
+    /// on a known GitHub host.
+    pub fn current(config: &GithubConfig) -> Self {
+        resolve(config, origin_url().as_deref()).0
+    }
 
-    let body = match resp.text() {
-        Ok(b) => b,
-        Err(e) => return PollResult::Error(format!("Read error: {}", e)),
-    };
+    fn client_id(&self) -> Result<&str> {
+        self.client_id.as_deref().with_context(|| {
+            format!(
+                "No OAuth client ID for {} — set client_id under [[github.hosts]] or use a personal access token",
+                self.name
+            )
+        })
+    }
 
-    // Try parsing as success first
-    if let Ok(token) = serde_json::from_str::<TokenResponse>(&body) {
-        if !token.access_token.is_empty() {
-            return PollResult::Success(token);
+    /// Step 1: Request device and user verification codes from GitHub.
+    pub fn request_device_code(&self) -> Result<DeviceCodeResponse> {
+        let client_id = self.client_id()?;
+        let client = reqwest::blocking::Client::new();
+        let resp = client
+            .post(format!("{}/login/device/code", self.web_url))
+            .header("Accept", "application/json")
+            .form(&[("client_id", client_id), ("scope", SCOPES)])
+            .send()
+            .with_context(|| format!("Failed to contact {} for device code", self.name))?;
+
+        let status = resp.status();
+        let body = resp.text().context("Failed to read GitHub response")?;
+
+        if !status.is_success() {
+            anyhow::bail!("GitHub returned {}: {}", status, body);
         }
+
+        let response: DeviceCodeResponse =
+            serde_json::from_str(&body).context("Failed to parse device code response")?;
+
+        Ok(response)
     }
 
-    // Try parsing as error
-    if let Ok(err) = serde_json::from_str::<ErrorResponse>(&body) {
-        return match err.error.as_str() {
-            "authorization_pending" => PollResult::Pending,
-            "slow_down" => PollResult::SlowDown(err.interval.unwrap_or(10)),
-            "expired_token" => PollResult::Expired,
-            "access_denied" => PollResult::AccessDenied,
-            _ => PollResult::Error(err.error_description),
+    /// Step 3: Poll GitHub to check if the user has authorized the device.
+    pub fn poll_for_token(&self, device_code: &str) -> PollResult {
+        let client_id = match self.client_id() {
+            Ok(id) => id,
+            Err(e) => return PollResult::Error(e.to_string()),
+        };
+        let client = reqwest::blocking::Client::new();
+        let resp = client
+            .post(format!("{}/login/oauth/access_token", self.web_url))
+            .header("Accept", "application/json")
+            .form(&[
+                ("client_id", client_id),
+                ("device_code", device_code),
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+            ])
+            .send();
+
+        let resp = match resp {
+            Ok(r) => r,
+            Err(e) => return PollResult::Error(format!("Network error: {}", e)),
+        };
+
+        let body = match resp.text() {
+            Ok(b) => b,
+            Err(e) => return PollResult::Error(format!("Read error: {}", e)),
         };
+
+        // Try parsing as success first
+        if let Ok(token) = serde_json::from_str::<TokenResponse>(&body)
+            && !token.access_token.is_empty()
+        {
+            return PollResult::Success(token);
+        }
+
+        // Try parsing as error
+        if let Ok(err) = serde_json::from_str::<ErrorResponse>(&body) {
+            return match err.error.as_str() {
+                "authorization_pending" => PollResult::Pending,
+                "slow_down" => PollResult::SlowDown(err.interval.unwrap_or(10)),
+                "expired_token" => PollResult::Expired,
+                "access_denied" => PollResult::AccessDenied,
+                _ => PollResult::Error(err.error_description),
+            };
+        }
+
+        PollResult::Error(format!("Unexpected response: {}", body))
     }
+}
 
-    PollResult::Error(format!("Unexpected response: {}", body))
-}
-
-/// Fetch the authenticated user's username.
-pub fn get_username(token: &str) -> Result<String> {
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .get("https://api.github.com/user")
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .send()
-        .context("Failed to fetch user info")?;
-
-    let body: serde_json::Value = resp.json().context("Failed to parse user response")?;
-    let login = body["login"]
-        .as_str()
-        .context("Missing login field")?
-        .to_string();
-
-    Ok(login)
-}
-
-/// Create a GitHub repository using the API.
-pub fn create_repo(token: &str, name: &str, description: &str, private: bool) -> Result<String> {
-    let client = reqwest::blocking::Client::new();
-    let body = serde_json::json!({
-        "name": name,
-        "description": description,
-        "private": private,
-        "auto_init": false,
-    });
-
-    let resp = client
-        .post("https://api.github.com/user/repos")
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .json(&body)
-        .send()
-        .context("Failed to create repository")?;
+/// A repository on some host, parsed from a remote URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRepo {
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+}
 
-    let status = resp.status();
-    let resp_body: serde_json::Value = resp.json().context("Failed to parse response")?;
+/// Parse a remote URL of the form `https://host[:port]/owner/repo.git`,
+/// `ssh://git@host[:port]/owner/repo.git` or `git@host:owner/repo.git`.
+pub fn parse_remote_url(url: &str) -> Option<RemoteRepo> {
+    let url = url.trim();
+    let (authority, path) = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?,
+        // scp-like syntax
+        None => url.split_once(':')?,
+    };
+    let host = authority.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, repo) = path.split_once('/')?;
+    if host.is_empty() || owner.is_empty() || repo.is_empty() || repo.contains('/') {
+        return None;
+    }
+    Some(RemoteRepo {
+        host: host.to_lowercase(),
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+    })
+}
 
-    if status.is_success() {
-        let clone_url = resp_body["clone_url"]
-            .as_str()
-            .unwrap_or("unknown")
-            .to_string();
-        Ok(clone_url)
-    } else {
-        let msg = resp_body["message"]
-            .as_str()
-            .unwrap_or("Unknown error")
-            .to_string();
-        anyhow::bail!("{}", msg)
+fn origin_url() -> Option<String> {
+    super::runner::run_git(&["remote", "get-url", "origin"])
+        .ok()
+        .map(|url| url.trim().to_string())
+}
+
+/// The host and `(owner, repo)` that `origin` points at. The repository is
+/// an error when there is no origin or it isn't on a known GitHub host; the
+/// host then falls back to the default.
+fn resolve(
+    config: &GithubConfig,
+    origin: Option<&str>,
+) -> (GithubHost, Result<(String, String), String>) {
+    let Some(url) = origin else {
+        return (
+            GithubHost::default_for(config),
+            Err("No 'origin' remote found".to_string()),
+        );
+    };
+    if let Some(remote) = parse_remote_url(url)
+        && let Some(host) = GithubHost::lookup(config, &remote.host)
+    {
+        return (host, Ok((remote.owner, remote.repo)));
     }
+    (
+        GithubHost::default_for(config),
+        Err(format!(
+            "Remote 'origin' is not a GitHub URL: {} (add GitHub Enterprise hosts under [[github.hosts]])",
+            url
+        )),
+    )
 }
 
-/// A GitHub collaborator entry.
+// ─── Client ──────────────────────────────────────────────────────
+
+/// REST API client for one GitHub host, scoped to the `origin` repository.
 #[derive(Debug, Clone)]
-pub struct Collaborator {
-    pub login: String,
-    pub role: String,
+pub struct GithubClient {
+    host: GithubHost,
+    token: String,
+    /// `origin` as `(owner, repo)`, or why it can't be used.
+    repo: Result<(String, String), String>,
 }
 
-/// Parse owner/repo from a GitHub remote URL.
-/// Supports both HTTPS (https://github.com/owner/repo.git) and SSH (git@github.com:owner/repo.git).
-pub fn parse_repo_from_remote() -> Result<(String, String)> {
-    let output = super::runner::run_git(&["remote", "get-url", "origin"])
-        .context("No 'origin' remote found")?;
-    let url = output.trim();
-
-    // SSH: git@github.com:owner/repo.git
-    if let Some(rest) = url.strip_prefix("git@github.com:") {
-        let path = rest.trim_end_matches(".git");
-        let parts: Vec<&str> = path.splitn(2, '/').collect();
-        if parts.len() == 2 {
-            return Ok((parts[0].to_string(), parts[1].to_string()));
+impl GithubClient {
+    /// Client for `host` that isn't tied to a repository.
+    pub fn new(host: GithubHost, token: String) -> Self {
+        GithubClient {
+            host,
+            token,
+            repo: Err("No GitHub repository selected".to_string()),
         }
     }
 
-    // HTTPS: https://github.com/owner/repo.git
-    if url.contains("github.com") {
-        let path = url
-            .split("github.com/")
-            .nth(1)
-            .context("Cannot parse GitHub URL")?;
-        let path = path.trim_end_matches(".git");
-        let parts: Vec<&str> = path.splitn(2, '/').collect();
-        if parts.len() == 2 {
-            return Ok((parts[0].to_string(), parts[1].to_string()));
+    /// Client for the host and repository `origin` points at, with the
+    /// token that host issued. Fails when not logged in to it.
+    pub fn for_origin(config: &GithubConfig) -> Result<Self> {
+        Self::for_remote(config, origin_url().as_deref())
+    }
+
+    fn for_remote(config: &GithubConfig, origin: Option<&str>) -> Result<Self> {
+        let (host, repo) = resolve(config, origin);
+        // Never fall back to another host's token
+        let token = config
+            .token_for(&host.name)
+            .with_context(|| format!("Not logged in to {}", host.name))?;
+        Ok(GithubClient { host, token, repo })
+    }
+
+    /// `<api>/repos/<owner>/<repo><path>`.
+    fn repo_url(&self, path: &str) -> Result<String> {
+        match &self.repo {
+            Ok((owner, repo)) => Ok(format!(
+                "{}/repos/{}/{}{}",
+                self.host.api_url, owner, repo, path
+            )),
+            Err(e) => anyhow::bail!("{}", e),
         }
     }
 
-    anyhow::bail!("Remote 'origin' is not a GitHub URL: {}", url)
-}
+    fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        reqwest::blocking::Client::new()
+            .request(method, url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("User-Agent", "zit-cli")
+            .header("Accept", "application/vnd.github+json")
+    }
 
-/// List collaborators for the current repository.
-pub fn list_collaborators(token: &str) -> Result<Vec<Collaborator>> {
-    let (owner, repo) = parse_repo_from_remote()?;
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .get(format!(
-            "https://api.github.com/repos/{}/{}/collaborators",
-            owner, repo
-        ))
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .send()
-        .context("Failed to fetch collaborators")?;
+    fn get(&self, url: &str) -> Result<Response> {
+        self.request(Method::GET, url)
+            .send()
+            .context("GitHub API request failed")
+    }
 
-    let status = resp.status();
-    let body: serde_json::Value = resp
-        .json()
-        .context("Failed to parse collaborators response")?;
+    fn send_json(&self, method: Method, url: &str, body: &serde_json::Value) -> Result<Response> {
+        self.request(method, url)
+            .json(body)
+            .send()
+            .context("GitHub API request failed")
+    }
 
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
+    /// Fetch the authenticated user's username.
+    pub fn username(&self) -> Result<String> {
+        let resp = self
+            .get(&format!("{}/user", self.host.api_url))
+            .context("Failed to fetch user info")?;
+
+        let body: serde_json::Value = resp.json().context("Failed to parse user response")?;
+        let login = body["login"]
+            .as_str()
+            .context("Missing login field")?
+            .to_string();
+
+        Ok(login)
     }
 
-    let collabs = body
-        .as_array()
-        .context("Expected array")?
-        .iter()
-        .filter_map(|c| {
-            let login = c["login"].as_str()?.to_string();
-            let role = c["role_name"]
+    /// Create a repository for the authenticated user.
+    pub fn create_repo(&self, name: &str, description: &str, private: bool) -> Result<String> {
+        let body = serde_json::json!({
+            "name": name,
+            "description": description,
+            "private": private,
+            "auto_init": false,
+        });
+
+        let resp = self
+            .send_json(
+                Method::POST,
+                &format!("{}/user/repos", self.host.api_url),
+                &body,
+            )
+            .context("Failed to create repository")?;
+
+        let status = resp.status();
+        let resp_body: serde_json::Value = resp.json().context("Failed to parse response")?;
+
+        if status.is_success() {
+            let clone_url = resp_body["clone_url"]
                 .as_str()
-                .unwrap_or("collaborator")
+                .unwrap_or("unknown")
                 .to_string();
-            Some(Collaborator { login, role })
-        })
-        .collect();
-
-    Ok(collabs)
-}
-
-/// Add a collaborator to the current repository.
-pub fn add_collaborator(token: &str, username: &str) -> Result<String> {
-    let (owner, repo) = parse_repo_from_remote()?;
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .put(format!(
-            "https://api.github.com/repos/{}/{}/collaborators/{}",
-            owner, repo, username
-        ))
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .json(&serde_json::json!({"permission": "push"}))
-        .send()
-        .context("Failed to add collaborator")?;
+            Ok(clone_url)
+        } else {
+            let msg = resp_body["message"]
+                .as_str()
+                .unwrap_or("Unknown error")
+                .to_string();
+            anyhow::bail!("{}", msg)
+        }
+    }
 
-    let status = resp.status();
-    if status.is_success() || status.as_u16() == 201 || status.as_u16() == 204 {
-        Ok(format!("Invited '{}' as collaborator", username))
-    } else {
-        let body: serde_json::Value = resp.json().unwrap_or_default();
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg)
+    /// List collaborators for the current repository.
+    pub fn list_collaborators(&self) -> Result<Vec<Collaborator>> {
+        let resp = self
+            .get(&self.repo_url("/collaborators")?)
+            .context("Failed to fetch collaborators")?;
+
+        let status = resp.status();
+        let body: serde_json::Value = resp
+            .json()
+            .context("Failed to parse collaborators response")?;
+
+        if !status.is_success() {
+            let msg = body["message"].as_str().unwrap_or("Unknown error");
+            anyhow::bail!("{}", msg);
+        }
+
+        let collabs = body
+            .as_array()
+            .context("Expected array")?
+            .iter()
+            .filter_map(|c| {
+                let login = c["login"].as_str()?.to_string();
+                let role = c["role_name"]
+                    .as_str()
+                    .unwrap_or("collaborator")
+                    .to_string();
+                Some(Collaborator { login, role })
+            })
+            .collect();
+
+        Ok(collabs)
     }
-}
 
-/// Remove a collaborator from the current repository.
-pub fn remove_collaborator(token: &str, username: &str) -> Result<()> {
-    let (owner, repo) = parse_repo_from_remote()?;
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .delete(format!(
-            "https://api.github.com/repos/{}/{}/collaborators/{}",
-            owner, repo, username
-        ))
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .send()
-        .context("Failed to remove collaborator")?;
+    /// Add a collaborator to the current repository.
+    pub fn add_collaborator(&self, username: &str) -> Result<String> {
+        let resp = self
+            .send_json(
+                Method::PUT,
+                &self.repo_url(&format!("/collaborators/{}", username))?,
+                &serde_json::json!({"permission": "push"}),
+            )
+            .context("Failed to add collaborator")?;
+
+        let status = resp.status();
+        if status.is_success() || status.as_u16() == 201 || status.as_u16() == 204 {
+            Ok(format!("Invited '{}' as collaborator", username))
+        } else {
+            let body: serde_json::Value = resp.json().unwrap_or_default();
+            let msg = body["message"].as_str().unwrap_or("Unknown error");
+            anyhow::bail!("{}", msg)
+        }
+    }
 
-    let status = resp.status();
-    if status.is_success() || status.as_u16() == 204 {
-        Ok(())
-    } else {
-        let body: serde_json::Value = resp.json().unwrap_or_default();
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg)
+    /// Remove a collaborator from the current repository.
+    pub fn remove_collaborator(&self, username: &str) -> Result<()> {
+        let resp = self
+            .request(
+                Method::DELETE,
+                &self.repo_url(&format!("/collaborators/{}", username))?,
+            )
+            .send()
+            .context("Failed to remove collaborator")?;
+
+        let status = resp.status();
+        if status.is_success() || status.as_u16() == 204 {
+            Ok(())
+        } else {
+            let body: serde_json::Value = resp.json().unwrap_or_default();
+            let msg = body["message"].as_str().unwrap_or("Unknown error");
+            anyhow::bail!("{}", msg)
+        }
     }
 }
 
+/// A GitHub collaborator entry.
+#[derive(Debug, Clone)]
+pub struct Collaborator {
+    pub login: String,
+    pub role: String,
+}
+
 // ─── Pull Request Types ────────────────────────────────────────
 
 #[derive(Debug, Clone, Deserialize)]
@@ -409,180 +596,108 @@ pub struct MergeResponse {
 
 // ─── Pull Request API Functions ────────────────────────────────
 
-fn gh_get(token: &str, url: &str) -> Result<reqwest::blocking::Response> {
-    let client = reqwest::blocking::Client::new();
-    client
-        .get(url)
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .send()
-        .context("GitHub API request failed")
-}
-
-fn gh_put_json(
-    token: &str,
-    url: &str,
-    body: &serde_json::Value,
-) -> Result<reqwest::blocking::Response> {
-    let client = reqwest::blocking::Client::new();
-    client
-        .put(url)
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .json(body)
-        .send()
-        .context("GitHub API request failed")
-}
-
-fn gh_patch_json(
-    token: &str,
-    url: &str,
-    body: &serde_json::Value,
-) -> Result<reqwest::blocking::Response> {
-    let client = reqwest::blocking::Client::new();
-    client
-        .patch(url)
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .json(body)
-        .send()
-        .context("GitHub API request failed")
-}
-
-/// List pull requests. `state` is "open", "closed", or "all".
-pub fn list_pull_requests(token: &str, state: &str) -> Result<Vec<PullRequest>> {
-    let (owner, repo) = parse_repo_from_remote()?;
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/pulls?state={}&per_page=50&sort=updated&direction=desc",
-        owner, repo, state
-    );
-    let resp = gh_get(token, &url)?;
+/// Deserialize a JSON API response, or fail with GitHub's error message.
+fn parse_response<T: DeserializeOwned>(resp: Response, what: &str) -> Result<T> {
     let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse PR list")?;
+    let body: serde_json::Value = resp
+        .json()
+        .with_context(|| format!("Failed to parse {}", what))?;
     if !status.is_success() {
         let msg = body["message"].as_str().unwrap_or("Unknown error");
         anyhow::bail!("{}", msg);
     }
-    let prs: Vec<PullRequest> =
-        serde_json::from_value(body).context("Failed to deserialize PR list")?;
-    Ok(prs)
+    serde_json::from_value(body).with_context(|| format!("Failed to deserialize {}", what))
 }
 
-/// Get a single pull request with full detail (includes mergeable, additions/deletions).
-pub fn get_pull_request(token: &str, number: u64) -> Result<PullRequest> {
-    let (owner, repo) = parse_repo_from_remote()?;
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/pulls/{}",
-        owner, repo, number
-    );
-    let resp = gh_get(token, &url)?;
-    let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse PR detail")?;
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
+impl GithubClient {
+    /// List pull requests. `state` is "open", "closed", or "all".
+    pub fn list_pull_requests(&self, state: &str) -> Result<Vec<PullRequest>> {
+        let url = self.repo_url(&format!(
+            "/pulls?state={}&per_page=50&sort=updated&direction=desc",
+            state
+        ))?;
+        parse_response(self.get(&url)?, "PR list")
     }
-    let pr: PullRequest = serde_json::from_value(body).context("Failed to deserialize PR")?;
-    Ok(pr)
-}
 
-/// Get CI check runs for a commit SHA.
-pub fn get_check_runs(token: &str, sha: &str) -> Result<CheckRunsResponse> {
-    let (owner, repo) = parse_repo_from_remote()?;
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/commits/{}/check-runs",
-        owner, repo, sha
-    );
-    let resp = gh_get(token, &url)?;
-    let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse check runs")?;
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
+    /// Get a single pull request with full detail (includes mergeable, additions/deletions).
+    pub fn get_pull_request(&self, number: u64) -> Result<PullRequest> {
+        let url = self.repo_url(&format!("/pulls/{}", number))?;
+        parse_response(self.get(&url)?, "PR detail")
     }
-    let runs: CheckRunsResponse =
-        serde_json::from_value(body).context("Failed to deserialize check runs")?;
-    Ok(runs)
-}
 
-/// Get files changed in a PR.
-pub fn get_pr_files(token: &str, number: u64) -> Result<Vec<PrFile>> {
-    let (owner, repo) = parse_repo_from_remote()?;
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/pulls/{}/files?per_page=100",
-        owner, repo, number
-    );
-    let resp = gh_get(token, &url)?;
-    let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse PR files")?;
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
+    /// Get CI check runs for a commit SHA.
+    pub fn get_check_runs(&self, sha: &str) -> Result<CheckRunsResponse> {
+        let url = self.repo_url(&format!("/commits/{}/check-runs", sha))?;
+        parse_response(self.get(&url)?, "check runs")
     }
-    let files: Vec<PrFile> =
-        serde_json::from_value(body).context("Failed to deserialize PR files")?;
-    Ok(files)
-}
 
-/// Get reviews on a PR.
-pub fn get_pr_reviews(token: &str, number: u64) -> Result<Vec<PrReview>> {
-    let (owner, repo) = parse_repo_from_remote()?;
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/pulls/{}/reviews",
-        owner, repo, number
-    );
-    let resp = gh_get(token, &url)?;
-    let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse PR reviews")?;
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
+    /// Get files changed in a PR.
+    pub fn get_pr_files(&self, number: u64) -> Result<Vec<PrFile>> {
+        let url = self.repo_url(&format!("/pulls/{}/files?per_page=100", number))?;
+        parse_response(self.get(&url)?, "PR files")
     }
-    let reviews: Vec<PrReview> =
-        serde_json::from_value(body).context("Failed to deserialize PR reviews")?;
-    Ok(reviews)
-}
 
-/// Merge a pull request. `merge_method` is "merge", "squash", or "rebase".
-pub fn merge_pull_request(token: &str, number: u64, merge_method: &str) -> Result<MergeResponse> {
-    let (owner, repo) = parse_repo_from_remote()?;
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/pulls/{}/merge",
-        owner, repo, number
-    );
-    let body = serde_json::json!({ "merge_method": merge_method });
-    let resp = gh_put_json(token, &url, &body)?;
-    let status = resp.status();
-    let resp_body: serde_json::Value = resp.json().context("Failed to parse merge response")?;
-    if !status.is_success() {
-        let msg = resp_body["message"].as_str().unwrap_or("Merge failed");
-        anyhow::bail!("{}", msg);
+    /// Get reviews on a PR.
+    pub fn get_pr_reviews(&self, number: u64) -> Result<Vec<PrReview>> {
+        let url = self.repo_url(&format!("/pulls/{}/reviews", number))?;
+        parse_response(self.get(&url)?, "PR reviews")
+    }
+
+    /// Merge a pull request. `merge_method` is "merge", "squash", or "rebase".
+    pub fn merge_pull_request(&self, number: u64, merge_method: &str) -> Result<MergeResponse> {
+        let url = self.repo_url(&format!("/pulls/{}/merge", number))?;
+        let body = serde_json::json!({ "merge_method": merge_method });
+        parse_response(self.send_json(Method::PUT, &url, &body)?, "merge response")
+    }
+
+    /// Close a pull request.
+    pub fn close_pull_request(&self, number: u64) -> Result<PullRequest> {
+        let url = self.repo_url(&format!("/pulls/{}", number))?;
+        let body = serde_json::json!({ "state": "closed" });
+        parse_response(
+            self.send_json(Method::PATCH, &url, &body)?,
+            "close response",
+        )
     }
-    let merge: MergeResponse =
-        serde_json::from_value(resp_body).context("Failed to deserialize merge response")?;
-    Ok(merge)
 }
 
-/// Close a pull request.
-pub fn close_pull_request(token: &str, number: u64) -> Result<PullRequest> {
-    let (owner, repo) = parse_repo_from_remote()?;
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/pulls/{}",
-        owner, repo, number
-    );
-    let body = serde_json::json!({ "state": "closed" });
-    let resp = gh_patch_json(token, &url, &body)?;
-    let status = resp.status();
-    let resp_body: serde_json::Value = resp.json().context("Failed to parse close response")?;
-    if !status.is_success() {
-        let msg = resp_body["message"].as_str().unwrap_or("Close failed");
-        anyhow::bail!("{}", msg);
+// ─── Releases ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
+pub struct Release {
+    pub id: u64,
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub html_url: String,
+    pub draft: bool,
+    pub prerelease: bool,
+}
+
+impl GithubClient {
+    /// Create a GitHub Release for an existing tag.
+    /// The tag should already be pushed; otherwise GitHub creates it on the default branch.
+    pub fn create_release(
+        &self,
+        tag: &str,
+        name: &str,
+        body: &str,
+        draft: bool,
+        prerelease: bool,
+    ) -> Result<Release> {
+        let url = self.repo_url("/releases")?;
+        let body = serde_json::json!({
+            "tag_name": tag,
+            "name": name,
+            "body": body,
+            "draft": draft,
+            "prerelease": prerelease,
+        });
+        parse_response(
+            self.send_json(Method::POST, &url, &body)?,
+            "release response",
+        )
     }
-    let pr: PullRequest = serde_json::from_value(resp_body).context("Failed to deserialize PR")?;
-    Ok(pr)
 }
 
 // ─── GitHub Actions Types ────────────────────────────────────────
@@ -641,75 +756,287 @@ pub struct WorkflowJobsResponse {
 
 // ─── GitHub Actions API Functions ────────────────────────────────
 
-/// List workflow runs for the repository, sorted newest first.
-pub fn list_workflow_runs(token: &str) -> Result<WorkflowRunsResponse> {
-    let (owner, repo) = parse_repo_from_remote()?;
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/actions/runs?per_page=30&sort=created&direction=desc",
-        owner, repo
-    );
-    let resp = gh_get(token, &url)?;
-    let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse workflow runs")?;
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
+impl GithubClient {
+    /// List workflow runs for the repository, sorted newest first.
+    pub fn list_workflow_runs(&self) -> Result<WorkflowRunsResponse> {
+        let url = self.repo_url("/actions/runs?per_page=30&sort=created&direction=desc")?;
+        parse_response(self.get(&url)?, "workflow runs")
+    }
+
+    /// List jobs for a specific workflow run.
+    pub fn list_run_jobs(&self, run_id: u64) -> Result<WorkflowJobsResponse> {
+        let url = self.repo_url(&format!("/actions/runs/{}/jobs", run_id))?;
+        parse_response(self.get(&url)?, "workflow jobs")
+    }
+
+    /// Download logs for a specific job. Returns the log text.
+    pub fn get_job_logs(&self, job_id: u64) -> Result<String> {
+        let url = self.repo_url(&format!("/actions/jobs/{}/logs", job_id))?;
+        let resp = self.get(&url).context("Failed to fetch job logs")?;
+
+        let status = resp.status();
+        if status.is_redirection() {
+            // GitHub redirects to a temporary URL for log downloads
+            if let Some(location) = resp.headers().get("location") {
+                let redirect_url = location.to_str().unwrap_or("");
+                let log_resp = reqwest::blocking::get(redirect_url)
+                    .context("Failed to follow log redirect")?;
+                return log_resp.text().context("Failed to read log text");
+            }
+        }
+
+        if !status.is_success() {
+            let body = resp.text().unwrap_or_default();
+            anyhow::bail!("Failed to fetch logs ({}): {}", status, body);
+        }
+
+        resp.text().context("Failed to read log text")
     }
-    let runs: WorkflowRunsResponse =
-        serde_json::from_value(body).context("Failed to deserialize workflow runs")?;
-    Ok(runs)
 }
 
-/// List jobs for a specific workflow run.
-pub fn list_run_jobs(token: &str, run_id: u64) -> Result<WorkflowJobsResponse> {
-    let (owner, repo) = parse_repo_from_remote()?;
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/actions/runs/{}/jobs",
-        owner, repo, run_id
-    );
-    let resp = gh_get(token, &url)?;
-    let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse workflow jobs")?;
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    fn enterprise_config() -> GithubConfig {
+        GithubConfig {
+            hosts: vec![GithubHostConfig {
+                host: "GitHub.Example.com".to_string(),
+                client_id: Some("Iv1.enterprise".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
     }
-    let jobs: WorkflowJobsResponse =
-        serde_json::from_value(body).context("Failed to deserialize workflow jobs")?;
-    Ok(jobs)
-}
-
-/// Download logs for a specific job. Returns the log text.
-pub fn get_job_logs(token: &str, job_id: u64) -> Result<String> {
-    let (owner, repo) = parse_repo_from_remote()?;
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/actions/jobs/{}/logs",
-        owner, repo, job_id
-    );
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .get(&url)
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .send()
-        .context("Failed to fetch job logs")?;
 
-    let status = resp.status();
-    if status.is_redirection() {
-        // GitHub redirects to a temporary URL for log downloads
-        if let Some(location) = resp.headers().get("location") {
-            let redirect_url = location.to_str().unwrap_or("");
-            let log_resp =
-                reqwest::blocking::get(redirect_url).context("Failed to follow log redirect")?;
-            return log_resp.text().context("Failed to read log text");
+    /// Answer one request per entry of `responses` on a local port. The
+    /// handle yields each request's head and body as text.
+    fn mock_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                }
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8_lossy(&content));
+                requests.push(request);
+
+                let reply = format!(
+                    "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(reply.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (base, handle)
+    }
+
+    fn mock_client(base: &str) -> GithubClient {
+        GithubClient {
+            host: GithubHost::enterprise(&GithubHostConfig {
+                host: "127.0.0.1".to_string(),
+                web_url: Some(base.to_string()),
+                api_url: Some(format!("{}/api/v3/", base)),
+                client_id: Some("Iv1.mock".to_string()),
+                ..Default::default()
+            }),
+            token: "test-token".to_string(),
+            repo: Ok(("acme".to_string(), "widgets".to_string())),
         }
     }
 
-    if !status.is_success() {
-        let body = resp.text().unwrap_or_default();
-        anyhow::bail!("Failed to fetch logs ({}): {}", status, body);
+    #[test]
+    fn test_parse_remote_url_forms() {
+        let expected = |host: &str| RemoteRepo {
+            host: host.to_string(),
+            owner: "acme".to_string(),
+            repo: "widgets".to_string(),
+        };
+        assert_eq!(
+            parse_remote_url("https://github.com/acme/widgets.git"),
+            Some(expected("github.com"))
+        );
+        assert_eq!(
+            parse_remote_url("git@GitHub.example.com:acme/widgets.git\n"),
+            Some(expected("github.example.com"))
+        );
+        assert_eq!(
+            parse_remote_url("ssh://git@github.example.com:2222/acme/widgets"),
+            Some(expected("github.example.com"))
+        );
+        assert_eq!(
+            parse_remote_url("https://user:pw@github.example.com:8443/acme/widgets/"),
+            Some(expected("github.example.com"))
+        );
+        assert_eq!(parse_remote_url("/srv/git/widgets.git"), None);
+        assert_eq!(parse_remote_url("https://gitlab.com/group/sub/repo"), None);
+    }
+
+    #[test]
+    fn test_enterprise_host_defaults() {
+        let host = GithubHost::lookup(&enterprise_config(), "github.example.com").unwrap();
+        assert_eq!(host.name, "github.example.com");
+        assert_eq!(host.web_url, "https://GitHub.Example.com");
+        assert_eq!(host.api_url, "https://GitHub.Example.com/api/v3");
+        assert_eq!(host.client_id.as_deref(), Some("Iv1.enterprise"));
+
+        let host = GithubHost::enterprise(&GithubHostConfig {
+            host: "ghe.local".to_string(),
+            web_url: Some("https://ghe.local:8443/".to_string()),
+            api_url: None,
+            client_id: None,
+            token: None,
+            username: None,
+        });
+        assert_eq!(host.api_url, "https://ghe.local:8443/api/v3");
+        assert!(host.request_device_code().is_err());
+    }
+
+    #[test]
+    fn test_resolve_picks_host_from_origin() {
+        let config = enterprise_config();
+        let (host, repo) = resolve(&config, Some("git@github.example.com:acme/widgets.git"));
+        assert_eq!(host.api_url, "https://GitHub.Example.com/api/v3");
+        assert_eq!(repo, Ok(("acme".to_string(), "widgets".to_string())));
+
+        let (host, repo) = resolve(&config, Some("https://github.com/acme/widgets"));
+        assert_eq!(host, GithubHost::github_com());
+        assert!(repo.is_ok());
+
+        let (host, repo) = resolve(&config, Some("https://gitlab.com/acme/widgets.git"));
+        assert_eq!(host, GithubHost::github_com());
+        assert!(repo.unwrap_err().contains("not a GitHub URL"));
+
+        let (_, repo) = resolve(&config, None);
+        assert_eq!(repo, Err("No 'origin' remote found".to_string()));
     }
 
-    resp.text().context("Failed to read log text")
+    #[test]
+    fn test_client_only_uses_token_of_origin_host() {
+        let mut config = enterprise_config();
+        config.pat = Some("dotcom-token".to_string());
+        config.hosts[0].token = Some("ghe-token".to_string());
+
+        let client =
+            GithubClient::for_remote(&config, Some("git@github.example.com:a/b.git")).unwrap();
+        assert_eq!(client.token, "ghe-token");
+        let client = GithubClient::for_remote(&config, Some("https://github.com/a/b.git")).unwrap();
+        assert_eq!(client.token, "dotcom-token");
+
+        // A github.com token is never sent to an Enterprise host
+        config.hosts[0].token = None;
+        let err =
+            GithubClient::for_remote(&config, Some("git@github.example.com:a/b.git")).unwrap_err();
+        assert_eq!(err.to_string(), "Not logged in to github.example.com");
+    }
+
+    #[test]
+    fn test_default_host_setting() {
+        let config = GithubConfig {
+            host: Some("git.corp.internal".to_string()),
+            ..Default::default()
+        };
+        let host = GithubHost::default_for(&config);
+        assert_eq!(host.api_url, "https://git.corp.internal/api/v3");
+        assert_eq!(host.client_id, None);
+        // An instance named only by `github.host` still matches remotes
+        let (host, repo) = resolve(&config, Some("git@git.corp.internal:acme/widgets.git"));
+        assert_eq!(host.name, "git.corp.internal");
+        assert!(repo.is_ok());
+
+        assert_eq!(
+            GithubHost::default_for(&GithubConfig::default()),
+            GithubHost::github_com()
+        );
+    }
+
+    #[test]
+    fn test_client_calls_configured_api_base() {
+        let (base, server) = mock_server(vec![(
+            200,
+            r#"[{"number": 7, "title": "Add widgets", "state": "open", "body": null,
+                "html_url": "https://ghe/acme/widgets/pull/7",
+                "created_at": "2024-05-01T00:00:00Z", "updated_at": "2024-05-02T00:00:00Z",
+                "merged_at": null, "mergeable": null, "mergeable_state": null,
+                "head": {"ref": "feature", "sha": "abc"}, "base": {"ref": "main", "sha": "def"},
+                "user": {"login": "octo"}, "additions": null, "deletions": null,
+                "changed_files": null, "comments": null, "review_comments": null}]"#,
+        )]);
+        let prs = mock_client(&base).list_pull_requests("open").unwrap();
+        assert_eq!(prs.len(), 1);
+        assert_eq!(prs[0].number, 7);
+        assert_eq!(prs[0].head.ref_name, "feature");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with(
+            "GET /api/v3/repos/acme/widgets/pulls?state=open&per_page=50&sort=updated&direction=desc HTTP/1.1"
+        ));
+        assert!(
+            requests[0]
+                .to_lowercase()
+                .contains("authorization: bearer test-token")
+        );
+    }
+
+    #[test]
+    fn test_client_reports_api_error_message() {
+        let (base, server) = mock_server(vec![(404, r#"{"message": "Not Found"}"#)]);
+        let err = mock_client(&base).get_pull_request(3).unwrap_err();
+        assert_eq!(err.to_string(), "Not Found");
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /api/v3/repos/acme/widgets/pulls/3 "));
+    }
+
+    #[test]
+    fn test_device_flow_uses_host_web_url_and_client_id() {
+        let (base, server) = mock_server(vec![
+            (
+                200,
+                r#"{"device_code": "dev-1", "user_code": "ABCD-1234",
+                    "verification_uri": "https://ghe/login/device",
+                    "expires_in": 900, "interval": 5}"#,
+            ),
+            (200, r#"{"error": "authorization_pending"}"#),
+            (
+                200,
+                r#"{"access_token": "gho_abc", "token_type": "bearer", "scope": "repo"}"#,
+            ),
+        ]);
+        let host = mock_client(&base).host;
+        let code = host.request_device_code().unwrap();
+        assert_eq!(code.user_code, "ABCD-1234");
+        assert!(matches!(host.poll_for_token("dev-1"), PollResult::Pending));
+        assert!(matches!(
+            host.poll_for_token("dev-1"),
+            PollResult::Success(token) if token.access_token == "gho_abc"
+        ));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /login/device/code "));
+        assert!(requests[0].contains("client_id=Iv1.mock"));
+        assert!(requests[1].starts_with("POST /login/oauth/access_token "));
+        assert!(requests[1].contains("device_code=dev-1"));
+    }
 }
@@ -0,0 +1,398 @@
+//! Import of gitleaks-format rule packs (`.gitleaks.toml`), so a team's
+//! existing gitleaks config can drive zit's scanner without being copied
+//! into `custom_patterns`.
+//!
+//! Supported per rule: `id`, `regex`, `secretGroup`, `entropy`, `keywords`,
+//! `path` and `[rules.allowlist]`/`[[rules.allowlists]]`. Top-level
+//! `[allowlist]`/`[[allowlists]]` apply to every rule in the pack.
+//! Path-only rules (no `regex`) and allowlisted `commits` are not supported.
+
+use anyhow::{Context, Result, bail};
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+use super::secrets::{SecretRule, shannon_entropy};
+
+/// Conditions of an imported rule beyond its regex.
+#[derive(Debug, Clone)]
+pub struct RuleConditions {
+    /// Capture group holding the secret; see [`RuleConditions::secret`].
+    secret_group: Option<usize>,
+    /// Minimum Shannon entropy of the secret.
+    min_entropy: Option<f64>,
+    /// Only files whose path matches are scanned.
+    path: Option<Regex>,
+    allowlists: Vec<Allowlist>,
+}
+
+impl RuleConditions {
+    /// First match of `pattern` in `line` of `file` that passes every
+    /// condition, as the secret it captured. Without a file the path
+    /// condition is skipped.
+    pub fn check<'l>(&self, pattern: &Regex, file: Option<&str>, line: &'l str) -> Option<&'l str> {
+        if let Some(path) = &self.path
+            && let Some(file) = file
+            && !path.is_match(file)
+        {
+            return None;
+        }
+        let file = file.unwrap_or("");
+        pattern.captures_iter(line).find_map(|caps| {
+            let matched = caps.get(0)?.as_str();
+            let secret = self.secret(pattern, &caps);
+            if let Some(min) = self.min_entropy
+                && shannon_entropy(secret) < min
+            {
+                return None;
+            }
+            let allowed = self
+                .allowlists
+                .iter()
+                .any(|a| a.allows(file, line, matched, secret));
+            (!allowed).then_some(secret)
+        })
+    }
+
+    /// The secret in a match, as gitleaks picks it: `secretGroup` when set,
+    /// otherwise the only capture group, otherwise the whole match.
+    fn secret<'l>(&self, pattern: &Regex, caps: &Captures<'l>) -> &'l str {
+        let group = match self.secret_group {
+            Some(group) => group,
+            None if pattern.captures_len() == 2 => 1,
+            None => 0,
+        };
+        caps.get(group)
+            .or_else(|| caps.get(0))
+            .map_or("", |m| m.as_str())
+    }
+}
+
+/// What an allowlist's `regexes` are matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RegexTarget {
+    Secret,
+    Match,
+    Line,
+}
+
+#[derive(Debug, Clone)]
+struct Allowlist {
+    /// `condition = "AND"`: every listed check must pass, not just one.
+    match_all: bool,
+    paths: Vec<Regex>,
+    regexes: Vec<Regex>,
+    regex_target: RegexTarget,
+    /// Lowercase words that mark the secret as a placeholder.
+    stopwords: Vec<String>,
+    /// `commits` were listed; zit can't check them, so AND never passes.
+    has_commits: bool,
+}
+
+impl Allowlist {
+    fn allows(&self, file: &str, line: &str, matched: &str, secret: &str) -> bool {
+        let target = match self.regex_target {
+            RegexTarget::Secret => secret,
+            RegexTarget::Match => matched,
+            RegexTarget::Line => line,
+        };
+        let secret_lower = secret.to_lowercase();
+        let checks = [
+            (!self.paths.is_empty()).then(|| self.paths.iter().any(|p| p.is_match(file))),
+            (!self.regexes.is_empty()).then(|| self.regexes.iter().any(|r| r.is_match(target))),
+            (!self.stopwords.is_empty())
+                .then(|| self.stopwords.iter().any(|w| secret_lower.contains(w))),
+            self.has_commits.then_some(false),
+        ];
+        let mut checks = checks.into_iter().flatten().peekable();
+        if checks.peek().is_none() {
+            return false;
+        }
+        if self.match_all {
+            checks.all(|passed| passed)
+        } else {
+            checks.any(|passed| passed)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PackFile {
+    #[serde(default)]
+    rules: Vec<PackRule>,
+    allowlist: Option<PackAllowlist>,
+    #[serde(default)]
+    allowlists: Vec<PackAllowlist>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackRule {
+    id: String,
+    regex: Option<String>,
+    secret_group: Option<usize>,
+    entropy: Option<f64>,
+    #[serde(default)]
+    keywords: Vec<String>,
+    path: Option<String>,
+    allowlist: Option<PackAllowlist>,
+    #[serde(default)]
+    allowlists: Vec<PackAllowlist>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackAllowlist {
+    condition: Option<String>,
+    #[serde(default)]
+    paths: Vec<String>,
+    #[serde(default)]
+    regexes: Vec<String>,
+    regex_target: Option<String>,
+    #[serde(default)]
+    stopwords: Vec<String>,
+    #[serde(default)]
+    commits: Vec<String>,
+}
+
+impl PackAllowlist {
+    fn compile(&self) -> Result<Allowlist> {
+        let match_all = match self.condition.as_deref().map(str::to_uppercase).as_deref() {
+            None | Some("OR") => false,
+            Some("AND") => true,
+            Some(other) => bail!("invalid allowlist condition '{}'", other),
+        };
+        let regex_target = match self.regex_target.as_deref() {
+            None | Some("secret") => RegexTarget::Secret,
+            Some("match") => RegexTarget::Match,
+            Some("line") => RegexTarget::Line,
+            Some(other) => bail!("invalid allowlist regexTarget '{}'", other),
+        };
+        Ok(Allowlist {
+            match_all,
+            paths: compile_all(&self.paths)?,
+            regexes: compile_all(&self.regexes)?,
+            regex_target,
+            stopwords: self.stopwords.iter().map(|w| w.to_lowercase()).collect(),
+            has_commits: !self.commits.is_empty(),
+        })
+    }
+}
+
+fn compile_all(patterns: &[String]) -> Result<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|p| Regex::new(p).with_context(|| format!("invalid regex '{}'", p)))
+        .collect()
+}
+
+/// Rules of a gitleaks TOML document. Rules that can't be used (no regex,
+/// or one that doesn't compile) are logged and skipped.
+pub fn parse_rules(content: &str) -> Result<Vec<SecretRule>> {
+    let pack: PackFile = toml::from_str(content)?;
+    let global = pack
+        .allowlist
+        .iter()
+        .chain(&pack.allowlists)
+        .map(PackAllowlist::compile)
+        .collect::<Result<Vec<_>>>()
+        .context("invalid global allowlist")?;
+
+    let mut rules = Vec::new();
+    for rule in pack.rules {
+        let id = rule.id.clone();
+        match compile_rule(rule, &global) {
+            Ok(Some(rule)) => rules.push(rule),
+            Ok(None) => log::debug!("Skipping gitleaks rule '{}': no regex", id),
+            Err(e) => log::warn!("Skipping gitleaks rule '{}': {:#}", id, e),
+        }
+    }
+    Ok(rules)
+}
+
+fn compile_rule(rule: PackRule, global: &[Allowlist]) -> Result<Option<SecretRule>> {
+    let Some(regex) = rule.regex else {
+        return Ok(None);
+    };
+    let pattern = Regex::new(&regex).context("invalid regex")?;
+    let path = rule
+        .path
+        .as_deref()
+        .map(Regex::new)
+        .transpose()
+        .context("invalid path")?;
+    let mut allowlists = global.to_vec();
+    for allowlist in rule.allowlist.iter().chain(&rule.allowlists) {
+        allowlists.push(allowlist.compile()?);
+    }
+    Ok(Some(SecretRule {
+        name: rule.id,
+        pattern,
+        entropy: None,
+        keywords: rule.keywords.iter().map(|k| k.to_lowercase()).collect(),
+        conditions: Some(Box::new(RuleConditions {
+            secret_group: rule.secret_group.filter(|&g| g > 0),
+            min_entropy: rule.entropy,
+            path,
+            allowlists,
+        })),
+    }))
+}
+
+/// Compiled packs by path, with the modification time they were read at.
+type PackCache = HashMap<PathBuf, (SystemTime, Vec<SecretRule>)>;
+
+/// Load the rules of the pack at `path` (relative to the repository root,
+/// or `~/`-prefixed). Compiled packs are cached until the file changes.
+pub fn load_pack(path: &str) -> Result<Vec<SecretRule>> {
+    static CACHE: OnceLock<Mutex<PackCache>> = OnceLock::new();
+
+    let path = resolve(path);
+    let modified = std::fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some((cached_at, rules)) = cache.lock().unwrap().get(&path)
+        && *cached_at == modified
+    {
+        return Ok(rules.clone());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let rules = parse_rules(&content).with_context(|| format!("Invalid {}", path.display()))?;
+    cache
+        .lock()
+        .unwrap()
+        .insert(path, (modified, rules.clone()));
+    Ok(rules)
+}
+
+fn resolve(path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/")
+        && let Some(home) = std::env::var_os("HOME")
+    {
+        return Path::new(&home).join(rest);
+    }
+    let path = Path::new(path);
+    match super::repo::current() {
+        Ok(repo) if path.is_relative() => repo.root().join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::secrets::scan_content;
+
+    const PACK: &str = r#"
+title = "security team rules"
+
+[extend]
+useDefault = true
+
+[allowlist]
+paths = ['''(^|/)testdata/''']
+
+[[rules]]
+id = "acme-api-token"
+description = "ACME API token"
+regex = '''(?i)acme[_-]?token\s*=\s*["']?(acme_[a-z0-9]{16})'''
+keywords = ["ACME"]
+tags = ["api"]
+
+[rules.allowlist]
+stopwords = ["example"]
+
+[[rules]]
+id = "internal-signing-key"
+regex = '''signing_key:\s*([A-Za-z0-9+/]{24,})'''
+secretGroup = 1
+entropy = 4.0
+path = '''\.ya?ml$'''
+
+[[rules.allowlists]]
+condition = "AND"
+regexTarget = "line"
+regexes = ['''#\s*fixture''']
+paths = ['''^deploy/''']
+
+[[rules]]
+id = "pkcs12-file"
+path = '''\.p12$'''
+"#;
+
+    fn rules() -> Vec<SecretRule> {
+        parse_rules(PACK).unwrap()
+    }
+
+    #[test]
+    fn test_parse_skips_path_only_rules() {
+        let rules = rules();
+        let ids: Vec<&str> = rules.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(ids, ["acme-api-token", "internal-signing-key"]);
+        assert_eq!(rules[0].keywords, ["acme"]);
+    }
+
+    #[test]
+    fn test_secret_group_and_keywords() {
+        let line = "ACME_TOKEN = 'acme_0123456789abcdef'";
+        let findings = scan_content("app.env", line, &rules());
+        assert_eq!(findings.len(), 1, "{:?}", findings);
+        assert_eq!(findings[0].rule_name, "acme-api-token");
+        assert_eq!(
+            findings[0].secret_hash,
+            crate::git::secrets::secret_hash("acme_0123456789abcdef")
+        );
+
+        // The keyword prefilter rejects the line before the regex runs
+        assert!(!rules()[0].may_match("token = 'other_0123456789abcdef'"));
+    }
+
+    #[test]
+    fn test_rule_and_global_allowlists() {
+        let rules = rules();
+        let placeholder = "acme_token=acme_example123456789";
+        assert!(scan_content("app.env", placeholder, &rules).is_empty());
+        let real = "acme_token=acme_0123456789abcdef";
+        assert!(scan_content("pkg/testdata/app.env", real, &rules).is_empty());
+        assert_eq!(scan_content("pkg/app.env", real, &rules).len(), 1);
+    }
+
+    #[test]
+    fn test_path_entropy_and_and_condition() {
+        let rules = rules();
+        let key = "signing_key: q8Zr3LmX9vTb2NwKp5YhGd7Fs4Jc";
+        assert_eq!(scan_content("config.yml", key, &rules).len(), 1);
+        // Not a YAML file
+        assert!(scan_content("config.json", key, &rules).is_empty());
+        // Too regular to be a key
+        let low = "signing_key: aaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        assert!(scan_content("config.yml", low, &rules).is_empty());
+        // AND: the fixture comment only counts under deploy/
+        let fixture = format!("{}  # fixture", key);
+        assert!(scan_content("deploy/app.yaml", &fixture, &rules).is_empty());
+        assert_eq!(scan_content("app.yaml", &fixture, &rules).len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_rule_is_skipped() {
+        let pack = r#"
+[[rules]]
+id = "broken"
+regex = '''(unclosed'''
+
+[[rules]]
+id = "fine"
+regex = '''fine_[0-9]{8}'''
+"#;
+        let rules = parse_rules(pack).unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].name, "fine");
+        assert!(parse_rules("[allowlist]\ncondition = \"XOR\"\npaths = ['x']").is_err());
+    }
+}
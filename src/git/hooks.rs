@@ -0,0 +1,285 @@
+//! Native git hooks that run zit's secret scanner, so commits and pushes
+//! made outside the TUI (IDEs, plain `git`) are checked too.
+//!
+//! An existing hook is moved aside to `<hook>.zit-chained` and run first;
+//! uninstalling puts it back.
+
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+
+/// Marker identifying hooks written by `zit hooks install`.
+const MARKER: &str = "# zit-managed-hook";
+
+/// Suffix of a pre-existing hook that zit's hook chains to.
+const CHAINED_SUFFIX: &str = ".zit-chained";
+
+/// The hooks zit installs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    PreCommit,
+    PrePush,
+}
+
+impl Hook {
+    pub const ALL: [Hook; 2] = [Hook::PreCommit, Hook::PrePush];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Hook::PreCommit => "pre-commit",
+            Hook::PrePush => "pre-push",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|h| h.name() == name)
+    }
+}
+
+/// What `install`/`uninstall` did to one hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookChange {
+    Installed,
+    /// Installed, with the previous hook kept and run first.
+    Chained,
+    /// Our hook was already there; rewritten in place.
+    Updated,
+    Removed,
+    /// Removed, and the previous hook put back.
+    Restored,
+    /// No zit hook to remove.
+    NotInstalled,
+}
+
+impl std::fmt::Display for HookChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            HookChange::Installed => "installed",
+            HookChange::Chained => "installed (existing hook chained)",
+            HookChange::Updated => "updated",
+            HookChange::Removed => "removed",
+            HookChange::Restored => "removed (previous hook restored)",
+            HookChange::NotInstalled => "not installed",
+        };
+        f.write_str(text)
+    }
+}
+
+/// Hooks directory of the current repository, honouring `core.hooksPath`.
+pub fn hooks_dir() -> Result<PathBuf> {
+    let repo = super::repo::current()?;
+    let path = super::run_git(&["rev-parse", "--git-path", "hooks"])?;
+    Ok(repo.root().join(path.trim()))
+}
+
+/// Install zit's pre-commit and pre-push hooks, invoking `zit_exe`.
+pub fn install(zit_exe: &Path) -> Result<Vec<(Hook, HookChange)>> {
+    let dir = hooks_dir()?;
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    Hook::ALL
+        .into_iter()
+        .map(|hook| Ok((hook, install_hook(&dir, hook, zit_exe)?)))
+        .collect()
+}
+
+/// Remove zit's hooks, restoring any hook they were chained to.
+pub fn uninstall() -> Result<Vec<(Hook, HookChange)>> {
+    let dir = hooks_dir()?;
+    Hook::ALL
+        .into_iter()
+        .map(|hook| Ok((hook, uninstall_hook(&dir, hook)?)))
+        .collect()
+}
+
+fn install_hook(dir: &Path, hook: Hook, zit_exe: &Path) -> Result<HookChange> {
+    let path = dir.join(hook.name());
+    let chained = chained_path(dir, hook);
+    let change = if is_managed(&path) {
+        HookChange::Updated
+    } else if path.exists() {
+        if chained.exists() {
+            bail!(
+                "{} exists and so does {}; move one of them first",
+                path.display(),
+                chained.display()
+            );
+        }
+        std::fs::rename(&path, &chained)
+            .with_context(|| format!("Failed to move {} aside", path.display()))?;
+        HookChange::Chained
+    } else {
+        HookChange::Installed
+    };
+
+    std::fs::write(&path, hook_script(hook, zit_exe))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    make_executable(&path)?;
+    Ok(change)
+}
+
+fn uninstall_hook(dir: &Path, hook: Hook) -> Result<HookChange> {
+    let path = dir.join(hook.name());
+    if !is_managed(&path) {
+        return Ok(HookChange::NotInstalled);
+    }
+    std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    let chained = chained_path(dir, hook);
+    if chained.exists() {
+        std::fs::rename(&chained, &path)
+            .with_context(|| format!("Failed to restore {}", chained.display()))?;
+        return Ok(HookChange::Restored);
+    }
+    Ok(HookChange::Removed)
+}
+
+fn chained_path(dir: &Path, hook: Hook) -> PathBuf {
+    dir.join(format!("{}{}", hook.name(), CHAINED_SUFFIX))
+}
+
+fn is_managed(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .map(|content| content.lines().any(|l| l == MARKER))
+        .unwrap_or(false)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("Failed to make {} executable", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// Shell script for `hook`. Falls back to `zit` on `PATH` if the binary
+/// that installed it has moved. pre-push gets its ref list on stdin, so it
+/// is buffered and replayed to both the chained hook and zit.
+fn hook_script(hook: Hook, zit_exe: &Path) -> String {
+    let name = hook.name();
+    let exe = shell_quote(&zit_exe.to_string_lossy());
+    let (chained, run) = match hook {
+        Hook::PreCommit => (
+            format!(r#""$hook_dir/{name}{CHAINED_SUFFIX}" "$@" || exit $?"#),
+            format!(r#"exec "$zit" hooks run {name} "$@""#),
+        ),
+        Hook::PrePush => (
+            format!(r#"printf '%s' "$input" | "$hook_dir/{name}{CHAINED_SUFFIX}" "$@" || exit $?"#),
+            format!(r#"printf '%s' "$input" | "$zit" hooks run {name} "$@""#),
+        ),
+    };
+    let read_input = match hook {
+        Hook::PreCommit => "",
+        Hook::PrePush => "input=$(cat; echo x)\ninput=${input%x}\n",
+    };
+    format!(
+        "#!/bin/sh\n\
+         {MARKER}\n\
+         # Scans for secrets with zit. Remove with `zit hooks uninstall`;\n\
+         # bypass once with --no-verify.\n\
+         zit={exe}\n\
+         [ -x \"$zit\" ] || zit=zit\n\
+         hook_dir=$(dirname \"$0\")\n\
+         {read_input}\
+         if [ -x \"$hook_dir/{name}{CHAINED_SUFFIX}\" ]; then\n    \
+             {chained}\n\
+         fi\n\
+         {run}\n"
+    )
+}
+
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// One line of pre-push input: `<local ref> <local sha> <remote ref> <remote sha>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushedRef {
+    pub local_ref: String,
+    pub local_sha: String,
+    pub remote_sha: String,
+}
+
+impl PushedRef {
+    /// Revision range of commits this push sends, `None` for a deletion.
+    /// New branches are compared against every remote-tracking ref.
+    pub fn range(&self) -> Option<String> {
+        if is_zero_oid(&self.local_sha) {
+            return None;
+        }
+        if is_zero_oid(&self.remote_sha) {
+            Some(format!("{} --not --remotes", self.local_sha))
+        } else {
+            Some(format!("{} ^{}", self.local_sha, self.remote_sha))
+        }
+    }
+}
+
+/// Parse the ref list git feeds to pre-push on stdin.
+pub fn parse_push_refs(input: &str) -> Vec<PushedRef> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let local_ref = parts.next()?;
+            let local_sha = parts.next()?;
+            let _remote_ref = parts.next()?;
+            let remote_sha = parts.next()?;
+            Some(PushedRef {
+                local_ref: local_ref.to_string(),
+                local_sha: local_sha.to_string(),
+                remote_sha: remote_sha.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn is_zero_oid(oid: &str) -> bool {
+    !oid.is_empty() && oid.bytes().all(|b| b == b'0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_names_round_trip() {
+        for hook in Hook::ALL {
+            assert_eq!(Hook::from_name(hook.name()), Some(hook));
+        }
+        assert_eq!(Hook::from_name("post-commit"), None);
+    }
+
+    #[test]
+    fn test_hook_script_chains_and_quotes() {
+        let script = hook_script(Hook::PreCommit, Path::new("/opt/it's/zit"));
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.lines().any(|l| l == MARKER));
+        assert!(script.contains(r"zit='/opt/it'\''s/zit'"));
+        assert!(script.contains("pre-commit.zit-chained\" \"$@\" || exit $?"));
+        assert!(script.ends_with("exec \"$zit\" hooks run pre-commit \"$@\"\n"));
+
+        let script = hook_script(Hook::PrePush, Path::new("zit"));
+        assert!(script.contains("input=$(cat; echo x)"));
+        assert!(script.contains("| \"$zit\" hooks run pre-push \"$@\""));
+    }
+
+    #[test]
+    fn test_parse_push_refs_ranges() {
+        let zero = "0".repeat(40);
+        let a = "a".repeat(40);
+        let b = "b".repeat(40);
+        let input = format!(
+            "refs/heads/main {a} refs/heads/main {b}\n\
+             refs/heads/new {a} refs/heads/new {zero}\n\
+             (delete) {zero} refs/heads/old {b}\n"
+        );
+        let refs = parse_push_refs(&input);
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0].local_ref, "refs/heads/main");
+        assert_eq!(refs[0].range(), Some(format!("{a} ^{b}")));
+        assert_eq!(refs[1].range(), Some(format!("{a} --not --remotes")));
+        assert_eq!(refs[2].range(), None);
+    }
+}
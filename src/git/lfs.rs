@@ -0,0 +1,607 @@
+//! Git LFS — tracked patterns from `.gitattributes`, pointer files, locks,
+//! and which objects are present in the local LFS store.
+//!
+//! Everything except locks and fetching works without the `git-lfs` binary:
+//! tracking is an attribute, pointers are small text blobs and the store is
+//! a directory under `.git/lfs`.
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use super::diff::{DiffLine, DiffLineType, FileDiff, Hunk};
+use super::policy::format_size;
+
+/// First line of every pointer file.
+const POINTER_VERSION: &str = "version https://git-lfs.github.com/spec/v1";
+/// Version line written by pre-1.0 clients.
+const LEGACY_POINTER_VERSION: &str = "version https://hawser.github.com/spec/v1";
+
+/// Pointer files are always smaller than this.
+const MAX_POINTER_SIZE: u64 = 1024;
+
+/// Pathspec matching every path with the LFS filter attribute.
+const LFS_PATHSPEC: &str = ":(attr:filter=lfs)";
+
+/// Attributes `git lfs track` writes for a pattern.
+const TRACK_ATTRIBUTES: &str = "filter=lfs diff=lfs merge=lfs -text";
+
+/// Hunk header shown in place of a pointer file's text.
+const POINTER_HUNK_HEADER: &str = "@@ Git LFS object @@";
+
+/// Contents of an LFS pointer file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LfsPointer {
+    /// Hex SHA-256 of the object, without the `sha256:` prefix.
+    pub oid: String,
+    pub size: u64,
+}
+
+impl LfsPointer {
+    /// Parse pointer file text; `None` for anything that isn't a pointer.
+    pub fn parse(text: &str) -> Option<Self> {
+        if text.len() as u64 >= MAX_POINTER_SIZE {
+            return None;
+        }
+        let mut lines = text.lines();
+        let version = lines.next()?;
+        if version != POINTER_VERSION && version != LEGACY_POINTER_VERSION {
+            return None;
+        }
+        let mut oid = None;
+        let mut size = None;
+        for line in lines {
+            let (key, value) = line.split_once(' ')?;
+            match key {
+                "oid" => oid = value.strip_prefix("sha256:").map(str::to_string),
+                "size" => size = value.parse().ok(),
+                _ => {}
+            }
+        }
+        let oid = oid.filter(|o| o.len() == 64 && o.bytes().all(|b| b.is_ascii_hexdigit()))?;
+        Some(Self { oid, size: size? })
+    }
+
+    pub fn short_oid(&self) -> &str {
+        &self.oid[..12]
+    }
+
+    /// `LFS object (1.2 MiB) sha256:1a2b3c4d5e6f`
+    pub fn describe(&self) -> String {
+        format!(
+            "LFS object ({}) sha256:{}",
+            format_size(self.size),
+            self.short_oid()
+        )
+    }
+}
+
+/// A pattern tracked by LFS in a `.gitattributes` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedPattern {
+    pub pattern: String,
+    /// The `.gitattributes` file it comes from, relative to the root.
+    pub source: String,
+    /// Files matching it are checked out read-only until locked.
+    pub lockable: bool,
+}
+
+/// A file stored through LFS, as recorded in the index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LfsObject {
+    pub path: String,
+    pub pointer: LfsPointer,
+    /// The object is in the local LFS store, so the file can be checked out.
+    pub present: bool,
+}
+
+/// A file lock held on the LFS server.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct LfsLock {
+    pub id: String,
+    pub path: String,
+    #[serde(default)]
+    pub owner: LockOwner,
+    #[serde(default)]
+    pub locked_at: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct LockOwner {
+    #[serde(default)]
+    pub name: String,
+}
+
+/// Whether the `git-lfs` extension is installed.
+pub fn is_installed() -> bool {
+    super::run_git(&["lfs", "version"]).is_ok()
+}
+
+/// Paths with the LFS filter attribute, tracked or untracked.
+pub fn lfs_paths() -> Result<Vec<String>> {
+    let output = super::run_git(&[
+        "ls-files",
+        "-z",
+        "--cached",
+        "--others",
+        "--exclude-standard",
+        "--",
+        LFS_PATHSPEC,
+    ])?;
+    let mut paths: Vec<String> = output
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+        .collect();
+    // Unmerged paths are listed once per stage
+    paths.dedup();
+    Ok(paths)
+}
+
+/// LFS patterns from every `.gitattributes` file in the working tree.
+pub fn tracked_patterns() -> Result<Vec<TrackedPattern>> {
+    let root = super::repo::current()?.root().to_path_buf();
+    let output = super::run_git(&[
+        "ls-files",
+        "-z",
+        "--cached",
+        "--others",
+        "--exclude-standard",
+        "--",
+        ".gitattributes",
+        "*/.gitattributes",
+    ])?;
+    let mut sources: Vec<&str> = output.split('\0').filter(|p| !p.is_empty()).collect();
+    sources.dedup();
+
+    let mut patterns = Vec::new();
+    for source in sources {
+        if let Ok(content) = std::fs::read_to_string(root.join(source)) {
+            patterns.extend(parse_attributes(&content, source));
+        }
+    }
+    Ok(patterns)
+}
+
+/// LFS patterns of one `.gitattributes` file.
+fn parse_attributes(content: &str, source: &str) -> Vec<TrackedPattern> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (pattern, attrs) = split_attribute_line(line)?;
+            if !attrs.contains(&"filter=lfs") {
+                return None;
+            }
+            Some(TrackedPattern {
+                pattern: pattern.to_string(),
+                source: source.to_string(),
+                lockable: attrs.contains(&"lockable"),
+            })
+        })
+        .collect()
+}
+
+/// `(pattern, attributes)` of a `.gitattributes` line; `None` for blanks,
+/// comments and macro definitions.
+fn split_attribute_line(line: &str) -> Option<(&str, Vec<&str>)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') || line.starts_with("[attr]") {
+        return None;
+    }
+    let mut fields = line.split_whitespace();
+    let pattern = fields.next()?;
+    Some((pattern, fields.collect()))
+}
+
+/// Track `pattern` with LFS in the root `.gitattributes`. Spaces are
+/// escaped the way `git lfs track` does. Returns `false` if it was already
+/// tracked there.
+pub fn track(pattern: &str) -> Result<bool> {
+    let pattern = escape_pattern(pattern.trim());
+    if pattern.is_empty() {
+        bail!("Pattern cannot be empty");
+    }
+    let path = attributes_path(".gitattributes")?;
+    let content = std::fs::read_to_string(&path).unwrap_or_default();
+    match add_pattern(&content, &pattern) {
+        Some(updated) => {
+            std::fs::write(&path, updated)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Stop tracking `pattern` with LFS, removing it from the file it was
+/// found in. Files already stored as pointers stay that way until re-added.
+pub fn untrack(pattern: &TrackedPattern) -> Result<()> {
+    let path = attributes_path(&pattern.source)?;
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let Some(updated) = remove_pattern(&content, &pattern.pattern) else {
+        bail!("'{}' is not tracked in {}", pattern.pattern, pattern.source);
+    };
+    std::fs::write(&path, updated).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn attributes_path(source: &str) -> Result<PathBuf> {
+    Ok(super::repo::current()?.root().join(source))
+}
+
+fn escape_pattern(pattern: &str) -> String {
+    pattern.replace(' ', "[[:space:]]")
+}
+
+/// `content` with a tracking line for `pattern` appended, or `None` if the
+/// pattern is already tracked.
+fn add_pattern(content: &str, pattern: &str) -> Option<String> {
+    let tracked = content.lines().any(|line| {
+        split_attribute_line(line)
+            .is_some_and(|(p, attrs)| p == pattern && attrs.contains(&"filter=lfs"))
+    });
+    if tracked {
+        return None;
+    }
+    let mut updated = content.to_string();
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push_str(&format!("{} {}\n", pattern, TRACK_ATTRIBUTES));
+    Some(updated)
+}
+
+/// `content` without the LFS lines for `pattern`, or `None` if there were none.
+fn remove_pattern(content: &str, pattern: &str) -> Option<String> {
+    let mut removed = false;
+    let mut updated = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        let is_lfs = split_attribute_line(line)
+            .is_some_and(|(p, attrs)| p == pattern && attrs.contains(&"filter=lfs"));
+        if is_lfs {
+            removed = true;
+        } else {
+            updated.push_str(line);
+        }
+    }
+    removed.then_some(updated)
+}
+
+/// Pointer of every LFS file in the index, and whether its object is in
+/// the local store.
+pub fn objects() -> Result<Vec<LfsObject>> {
+    let repo = super::repo::current()?;
+    let output = super::run_git(&["ls-files", "-z", "--stage", "--", LFS_PATHSPEC])?;
+    let store = object_store()?;
+
+    let mut objects = Vec::new();
+    for (path, blob) in parse_stage(&output) {
+        let Some(info) = repo.object_info(&blob)? else {
+            continue;
+        };
+        if info.size >= MAX_POINTER_SIZE {
+            // Committed without the LFS filter: the blob is the file itself
+            continue;
+        }
+        let Some((_, content)) = repo.read_object(&blob)? else {
+            continue;
+        };
+        let Some(pointer) = LfsPointer::parse(&String::from_utf8_lossy(&content)) else {
+            continue;
+        };
+        let present = is_present(&store, &pointer);
+        objects.push(LfsObject {
+            path,
+            pointer,
+            present,
+        });
+    }
+    Ok(objects)
+}
+
+/// `(path, blob)` pairs from `ls-files --stage -z`. Only stage 0 and the
+/// "ours" side of a conflict are kept.
+fn parse_stage(output: &str) -> Vec<(String, String)> {
+    output
+        .split('\0')
+        .filter_map(|record| {
+            let (meta, path) = record.split_once('\t')?;
+            let mut fields = meta.split(' ');
+            let _mode = fields.next()?;
+            let blob = fields.next()?;
+            let stage = fields.next()?;
+            matches!(stage, "0" | "2").then(|| (path.to_string(), blob.to_string()))
+        })
+        .collect()
+}
+
+/// Directory of the local object store, honouring `lfs.storage`.
+fn object_store() -> Result<PathBuf> {
+    let repo = super::repo::current()?;
+    let common = repo.common_dir();
+    let storage = super::run_git(&["config", "--get", "lfs.storage"])
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default();
+    Ok(if storage.is_empty() {
+        common.join("lfs")
+    } else {
+        common.join(storage)
+    }
+    .join("objects"))
+}
+
+/// Stored objects live at `objects/ab/cd/abcd…`.
+fn object_path(store: &Path, pointer: &LfsPointer) -> PathBuf {
+    store
+        .join(&pointer.oid[..2])
+        .join(&pointer.oid[2..4])
+        .join(&pointer.oid)
+}
+
+fn is_present(store: &Path, pointer: &LfsPointer) -> bool {
+    std::fs::metadata(object_path(store, pointer)).is_ok_and(|m| m.len() == pointer.size)
+}
+
+/// Locks on the LFS server. Needs `git-lfs` and network access.
+pub fn locks() -> Result<Vec<LfsLock>> {
+    if !is_installed() {
+        bail!("git-lfs is not installed");
+    }
+    let output = super::run_git(&["lfs", "locks", "--json"])?;
+    parse_locks(&output)
+}
+
+fn parse_locks(output: &str) -> Result<Vec<LfsLock>> {
+    if output.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(output).context("Unexpected `git lfs locks` output")
+}
+
+/// Download missing objects and check them out; `None` fetches all of them.
+pub fn pull(path: Option<&str>) -> Result<String> {
+    if !is_installed() {
+        bail!("git-lfs is not installed");
+    }
+    match path {
+        Some(path) => super::run_git(&["lfs", "pull", "--include", path, "--exclude", ""]),
+        None => super::run_git(&["lfs", "pull"]),
+    }
+}
+
+/// Replace a diff between LFS pointer files with one line per side naming
+/// the object and its size. Leaves `file` alone unless both sides (where
+/// present) are pointers.
+pub fn summarize_pointer_diff(file: &mut FileDiff) {
+    if file.hunks.is_empty() {
+        return;
+    }
+    let mut old = String::new();
+    let mut new = String::new();
+    for line in file.hunks.iter().flat_map(|h| &h.lines) {
+        // `\ No newline at end of file`
+        if line.content.starts_with('\\') {
+            continue;
+        }
+        let text = line.content.get(1..).unwrap_or("");
+        match line.line_type {
+            DiffLineType::Header => continue,
+            DiffLineType::Context => {
+                old.push_str(text);
+                old.push('\n');
+                new.push_str(text);
+                new.push('\n');
+            }
+            DiffLineType::Removed => {
+                old.push_str(text);
+                old.push('\n');
+            }
+            DiffLineType::Added => {
+                new.push_str(text);
+                new.push('\n');
+            }
+        }
+    }
+    let side = |text: &str| {
+        if text.is_empty() {
+            Ok(None)
+        } else {
+            LfsPointer::parse(text).map(Some).ok_or(())
+        }
+    };
+    let (Ok(old), Ok(new)) = (side(&old), side(&new)) else {
+        return;
+    };
+    if old.is_none() && new.is_none() {
+        return;
+    }
+
+    let mut lines = vec![DiffLine {
+        line_type: DiffLineType::Header,
+        content: POINTER_HUNK_HEADER.to_string(),
+    }];
+    match (&old, &new) {
+        (Some(o), Some(n)) if o == n => lines.push(DiffLine {
+            line_type: DiffLineType::Context,
+            content: format!(" {} (unchanged)", o.describe()),
+        }),
+        _ => {
+            if let Some(o) = &old {
+                lines.push(DiffLine {
+                    line_type: DiffLineType::Removed,
+                    content: format!("-{}", o.describe()),
+                });
+            }
+            if let Some(n) = &new {
+                lines.push(DiffLine {
+                    line_type: DiffLineType::Added,
+                    content: format!("+{}", n.describe()),
+                });
+            }
+        }
+    }
+    file.hunks = vec![Hunk {
+        header: POINTER_HUNK_HEADER.to_string(),
+        old_start: 0,
+        old_count: 0,
+        new_start: 0,
+        new_count: 0,
+        lines,
+    }];
+    file.lfs = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::diff::parse_diff_output;
+
+    fn pointer_text(c: char, size: u64) -> String {
+        format!(
+            "{}\noid sha256:{}\nsize {}\n",
+            POINTER_VERSION,
+            c.to_string().repeat(64),
+            size
+        )
+    }
+
+    #[test]
+    fn test_parse_pointer() {
+        let pointer = LfsPointer::parse(&pointer_text('a', 12_582_912)).unwrap();
+        assert_eq!(pointer.oid, "a".repeat(64));
+        assert_eq!(pointer.size, 12_582_912);
+        assert_eq!(
+            pointer.describe(),
+            "LFS object (12.0 MiB) sha256:aaaaaaaaaaaa"
+        );
+
+        assert!(LfsPointer::parse("hello world\n").is_none());
+        assert!(
+            LfsPointer::parse(&format!("{}\noid sha256:abc\nsize 1\n", POINTER_VERSION)).is_none()
+        );
+        assert!(LfsPointer::parse(&format!("{}\nsize 1\n", POINTER_VERSION)).is_none());
+    }
+
+    #[test]
+    fn test_parse_attributes() {
+        let content = "# assets\n\
+                       *.psd filter=lfs diff=lfs merge=lfs -text lockable\n\
+                       *.txt text eol=lf\n\
+                       [attr]binary -diff -merge -text\n\
+                       video/** filter=lfs diff=lfs merge=lfs -text\n";
+        let patterns = parse_attributes(content, "assets/.gitattributes");
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(patterns[0].pattern, "*.psd");
+        assert!(patterns[0].lockable);
+        assert_eq!(patterns[1].pattern, "video/**");
+        assert!(!patterns[1].lockable);
+        assert_eq!(patterns[1].source, "assets/.gitattributes");
+    }
+
+    #[test]
+    fn test_add_and_remove_pattern() {
+        let content = "*.txt text";
+        let added = add_pattern(content, "*.bin").unwrap();
+        assert_eq!(
+            added,
+            "*.txt text\n*.bin filter=lfs diff=lfs merge=lfs -text\n"
+        );
+        assert!(add_pattern(&added, "*.bin").is_none());
+        assert_eq!(escape_pattern("my file.bin"), "my[[:space:]]file.bin");
+
+        assert_eq!(remove_pattern(&added, "*.bin").unwrap(), "*.txt text\n");
+        assert!(remove_pattern(&added, "*.txt").is_none());
+    }
+
+    #[test]
+    fn test_parse_stage_keeps_ours_on_conflict() {
+        let output = "100644 1111111111111111111111111111111111111111 0\ta.bin\0\
+                      100644 2222222222222222222222222222222222222222 1\tb.bin\0\
+                      100644 3333333333333333333333333333333333333333 2\tb.bin\0\
+                      100644 4444444444444444444444444444444444444444 3\tb.bin\0";
+        let entries = parse_stage(output);
+        assert_eq!(
+            entries,
+            vec![
+                ("a.bin".to_string(), "1".repeat(40)),
+                ("b.bin".to_string(), "3".repeat(40)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_object_path_layout() {
+        let pointer = LfsPointer {
+            oid: format!("abcd{}", "0".repeat(60)),
+            size: 1,
+        };
+        let path = object_path(Path::new("/repo/.git/lfs/objects"), &pointer);
+        assert_eq!(
+            path,
+            Path::new("/repo/.git/lfs/objects/ab/cd").join(&pointer.oid)
+        );
+    }
+
+    #[test]
+    fn test_parse_locks() {
+        let output = r#"[{"id":"3","path":"art/hero.psd","owner":{"name":"ada"},"locked_at":"2024-03-01T10:00:00Z"}]"#;
+        let locks = parse_locks(output).unwrap();
+        assert_eq!(locks.len(), 1);
+        assert_eq!(locks[0].path, "art/hero.psd");
+        assert_eq!(locks[0].owner.name, "ada");
+        assert!(parse_locks("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_summarize_modified_pointer() {
+        let diff = format!(
+            "diff --git a/a.bin b/a.bin\n\
+             index 1111111..2222222 100644\n\
+             --- a/a.bin\n\
+             +++ b/a.bin\n\
+             @@ -1,3 +1,3 @@\n \
+             {}\n\
+             -oid sha256:{}\n\
+             -size 2048\n\
+             +oid sha256:{}\n\
+             +size 1048576\n",
+            POINTER_VERSION,
+            "a".repeat(64),
+            "b".repeat(64)
+        );
+        let files = parse_diff_output(&diff);
+        assert!(files[0].lfs);
+        let lines: Vec<&str> = files[0].hunks[0]
+            .lines
+            .iter()
+            .map(|l| l.content.as_str())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                POINTER_HUNK_HEADER,
+                "-LFS object (2 KiB) sha256:aaaaaaaaaaaa",
+                "+LFS object (1.0 MiB) sha256:bbbbbbbbbbbb",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summarize_added_pointer_and_plain_text() {
+        let pointer: String = pointer_text('c', 10)
+            .lines()
+            .map(|l| format!("+{}\n", l))
+            .collect();
+        let diff = format!(
+            "diff --git a/c.bin b/c.bin\nnew file mode 100644\n@@ -0,0 +1,3 @@\n{}\
+             diff --git a/notes.txt b/notes.txt\n@@ -1 +1 @@\n-old\n+new\n",
+            pointer
+        );
+        let files = parse_diff_output(&diff);
+        assert!(files[0].lfs);
+        assert_eq!(files[0].hunks[0].lines.len(), 2);
+        assert_eq!(files[0].hunks[0].lines[1].line_type, DiffLineType::Added);
+        assert!(!files[1].lfs);
+        assert_eq!(files[1].hunks[0].lines.len(), 3);
+    }
+}
@@ -17,34 +17,44 @@ pub struct CommitEntry {
     pub date: String, // relative date like "2 hours ago"
     #[allow(dead_code)]
     pub date_iso: String, // ISO format for sorting
-    #[allow(dead_code)]
     pub parents: Vec<String>,
-    pub refs: String,  // decorated refs (HEAD -> main, origin/main, tag: v1.0)
-    pub graph: String, // graph characters for this line
+    pub refs: String, // decorated refs (HEAD -> main, origin/main, tag: v1.0)
 }
 
 const LOG_FORMAT: &str = "%H\x1f%h\x1f%s\x1f%an\x1f%ar\x1f%aI\x1f%P\x1f%D";
 const SEPARATOR: char = '\x1f';
 
-/// Fetch commit log entries with optional pagination.
-pub fn get_log(count: usize, skip: usize, branch: Option<&str>) -> Result<Vec<CommitEntry>> {
+/// Fetch commit log entries with optional pagination, filtered by `query`.
+/// Topological order keeps every commit above its parents, which the
+/// Timeline's lane graph relies on.
+pub fn get_log(
+    count: usize,
+    skip: usize,
+    branch: Option<&str>,
+    query: &LogQuery,
+) -> Result<Vec<CommitEntry>> {
     let count_str = format!("-{}", count);
     let skip_str = format!("--skip={}", skip);
     let format_str = format!("--format={}", LOG_FORMAT);
 
-    // Force color=never to allow reliable regex parsing of graph structure vs content
     let mut args = vec![
         "log",
         &count_str,
         &skip_str,
         &format_str,
-        "--graph",
+        "--topo-order",
         "--color=never",
     ];
 
+    let (filters, paths) = query.args();
+    args.extend(filters.iter().map(String::as_str));
     if let Some(b) = branch {
         args.push(b);
     }
+    if !paths.is_empty() {
+        args.push("--");
+        args.extend(paths.iter().map(String::as_str));
+    }
 
     let output = run_git(&args)?;
     let entries = parse_log_output(&output);
@@ -53,7 +63,195 @@ pub fn get_log(count: usize, skip: usize, branch: Option<&str>) -> Result<Vec<Co
 
 /// Get the last N commits (shorthand for dashboard use).
 pub fn get_recent_commits(count: usize) -> Result<Vec<CommitEntry>> {
-    get_log(count, 0, None)
+    get_log(count, 0, None, &LogQuery::default())
+}
+
+/// Which commits a [`LogQuery`] keeps by parent count.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum MergeFilter {
+    #[default]
+    All,
+    Only,
+    Exclude,
+}
+
+/// Structured commit search, parsed from the Timeline's `/` query syntax:
+///
+/// `author:alice committer:bob since:2w until:2024-06-01 path:src/ui/*.rs
+///  -S:token -G:regex merges:only|none free text`
+///
+/// Repeated `author:`/`committer:`/`path:` terms match any of the values;
+/// everything else must match. Free text searches commit messages. Values
+/// with spaces can be quoted: `author:"Ada Lovelace"`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogQuery {
+    pub text: Option<String>,
+    pub authors: Vec<String>,
+    pub committers: Vec<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub paths: Vec<String>,
+    /// `-S`: commits that change the number of occurrences of a string.
+    pub pickaxe: Option<String>,
+    /// `-G`: commits whose diff has added/removed lines matching a regex.
+    pub pickaxe_regex: Option<String>,
+    pub merges: MergeFilter,
+}
+
+impl LogQuery {
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut query = LogQuery::default();
+        let mut text: Vec<String> = Vec::new();
+
+        for token in split_query(input)? {
+            let Some((key, value)) = token.split_once(':') else {
+                text.push(token);
+                continue;
+            };
+            let value = value.to_string();
+            let needs_value = |v: &str| {
+                if v.is_empty() {
+                    anyhow::bail!("'{}:' needs a value", key);
+                }
+                Ok(())
+            };
+            match key {
+                "author" => {
+                    needs_value(&value)?;
+                    query.authors.push(value);
+                }
+                "committer" => {
+                    needs_value(&value)?;
+                    query.committers.push(value);
+                }
+                "since" | "after" => {
+                    needs_value(&value)?;
+                    query.since = Some(expand_date(&value));
+                }
+                "until" | "before" => {
+                    needs_value(&value)?;
+                    query.until = Some(expand_date(&value));
+                }
+                "path" => {
+                    needs_value(&value)?;
+                    query.paths.push(value);
+                }
+                "-S" => {
+                    needs_value(&value)?;
+                    query.pickaxe = Some(value);
+                }
+                "-G" => {
+                    needs_value(&value)?;
+                    regex::Regex::new(&value)
+                        .map_err(|e| anyhow::anyhow!("invalid -G pattern: {}", e))?;
+                    query.pickaxe_regex = Some(value);
+                }
+                "merges" => {
+                    query.merges = match value.as_str() {
+                        "only" => MergeFilter::Only,
+                        "none" | "no" => MergeFilter::Exclude,
+                        "all" => MergeFilter::All,
+                        _ => anyhow::bail!("merges: expects only, none or all"),
+                    }
+                }
+                // Not a search key, e.g. "fix: typo"
+                _ => text.push(token),
+            }
+        }
+
+        if !text.is_empty() {
+            query.text = Some(text.join(" "));
+        }
+        Ok(query)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == LogQuery::default()
+    }
+
+    /// `git log` options for this query, and the pathspecs that go after `--`.
+    pub fn args(&self) -> (Vec<String>, Vec<String>) {
+        let mut args = Vec::new();
+        if let Some(text) = &self.text {
+            args.push(format!("--grep={}", text));
+            args.push("-i".to_string());
+        }
+        args.extend(self.authors.iter().map(|a| format!("--author={}", a)));
+        args.extend(self.committers.iter().map(|c| format!("--committer={}", c)));
+        if let Some(since) = &self.since {
+            args.push(format!("--since={}", since));
+        }
+        if let Some(until) = &self.until {
+            args.push(format!("--until={}", until));
+        }
+        if let Some(s) = &self.pickaxe {
+            args.push(format!("-S{}", s));
+        }
+        if let Some(g) = &self.pickaxe_regex {
+            args.push(format!("-G{}", g));
+        }
+        match self.merges {
+            MergeFilter::All => {}
+            MergeFilter::Only => args.push("--merges".to_string()),
+            MergeFilter::Exclude => args.push("--no-merges".to_string()),
+        }
+
+        let paths = self
+            .paths
+            .iter()
+            .map(|p| {
+                if p.contains(['*', '?', '[']) {
+                    format!(":(glob){}", p)
+                } else {
+                    p.clone()
+                }
+            })
+            .collect();
+        (args, paths)
+    }
+}
+
+/// Split a query on whitespace, keeping double-quoted runs together.
+fn split_query(input: &str) -> Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in input.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if quoted {
+        anyhow::bail!("unterminated quote in search");
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+/// Expand shorthand ages such as `2w` or `3mo` into git's `2.weeks.ago`;
+/// anything else (ISO dates, `yesterday`) is passed through.
+fn expand_date(value: &str) -> String {
+    let digits = value.bytes().take_while(u8::is_ascii_digit).count();
+    if digits == 0 {
+        return value.to_string();
+    }
+    let unit = match &value[digits..] {
+        "h" => "hours",
+        "d" => "days",
+        "w" => "weeks",
+        "mo" => "months",
+        "y" => "years",
+        _ => return value.to_string(),
+    };
+    format!("{}.{}.ago", &value[..digits], unit)
 }
 
 fn parse_log_output(output: &str) -> Vec<CommitEntry> {
@@ -61,50 +259,94 @@ fn parse_log_output(output: &str) -> Vec<CommitEntry> {
     let re = commit_regex();
 
     for line in output.lines() {
-        // Find where the Commit Hash starts
-        if let Some(mat) = re.find(line) {
-            let start_idx = mat.start();
-            let graph = &line[..start_idx];
-            let data = &line[start_idx..];
-
-            let parts: Vec<&str> = data.split(SEPARATOR).collect();
-            if parts.len() < 8 {
-                continue;
-            }
+        // Lines without a commit hash (blank lines, --name-status output) are skipped
+        let Some(mat) = re.find(line) else {
+            continue;
+        };
+        let parts: Vec<&str> = line[mat.start()..].split(SEPARATOR).collect();
+        if parts.len() < 8 {
+            continue;
+        }
+
+        let parents: Vec<String> = parts[6].split_whitespace().map(|s| s.to_string()).collect();
+
+        entries.push(CommitEntry {
+            hash: parts[0].to_string(),
+            short_hash: parts[1].to_string(),
+            message: parts[2].to_string(),
+            author: parts[3].to_string(),
+            date: parts[4].to_string(),
+            date_iso: parts[5].to_string(),
+            parents,
+            refs: parts[7].to_string(),
+        });
+    }
+
+    entries
+}
+
+/// Commits in a revision range such as `v1.0.0..HEAD`, newest first.
+pub fn get_range(range: &str, count: usize) -> Result<Vec<CommitEntry>> {
+    let count_str = format!("-{}", count);
+    let format_str = format!("--format={}", LOG_FORMAT);
+    let output = run_git(&["log", &count_str, &format_str, range])?;
+    Ok(parse_log_output(&output))
+}
+
+/// A commit in a file's history, with the path the file had at that commit.
+#[derive(Debug, Clone)]
+pub struct FileCommit {
+    pub commit: CommitEntry,
+    pub path: String,
+    /// Previous path when this commit renamed or copied the file.
+    pub old_path: Option<String>,
+}
 
-            let parents: Vec<String> = parts[6].split_whitespace().map(|s| s.to_string()).collect();
-
-            entries.push(CommitEntry {
-                hash: parts[0].to_string(),
-                short_hash: parts[1].to_string(),
-                message: parts[2].to_string(),
-                author: parts[3].to_string(),
-                date: parts[4].to_string(),
-                date_iso: parts[5].to_string(),
-                parents,
-                refs: parts[7].to_string(),
-                graph: graph.to_string(),
-            });
-        } else {
-            // No commit hash found -> This is a graph-only line (e.g. "| \")
-            // We still want to render it to maintain the graph continuity
-            if !line.trim().is_empty() {
-                entries.push(CommitEntry {
-                    hash: String::new(),
-                    short_hash: String::new(),
-                    message: String::new(),
-                    author: String::new(),
-                    date: String::new(),
-                    date_iso: String::new(),
-                    parents: Vec::new(),
-                    refs: String::new(),
-                    graph: line.to_string(),
+/// Commits that touched `path`, newest first, following renames (`git log --follow`).
+pub fn file_history(path: &str, count: usize) -> Result<Vec<FileCommit>> {
+    let count_str = format!("-{}", count);
+    let format_str = format!("--format={}", LOG_FORMAT);
+    let output = run_git(&[
+        "log",
+        &count_str,
+        &format_str,
+        "--follow",
+        "--name-status",
+        "--",
+        path,
+    ])?;
+    Ok(parse_file_history(&output, path))
+}
+
+/// Parse `log --name-status` output: each commit line is followed by the
+/// status line for the followed file (`M\tpath` or `R087\told\tnew`).
+fn parse_file_history(output: &str, path: &str) -> Vec<FileCommit> {
+    let mut history: Vec<FileCommit> = Vec::new();
+    for line in output.lines() {
+        if commit_regex().is_match(line) {
+            if let Some(commit) = parse_log_output(line).into_iter().next() {
+                history.push(FileCommit {
+                    commit,
+                    path: path.to_string(),
+                    old_path: None,
                 });
             }
+            continue;
+        }
+        let Some(entry) = history.last_mut() else {
+            continue;
+        };
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields.as_slice() {
+            [status, old, new] if status.starts_with(['R', 'C']) => {
+                entry.path = new.to_string();
+                entry.old_path = Some(old.to_string());
+            }
+            [_, file] => entry.path = file.to_string(),
+            _ => {}
         }
     }
-
-    entries
+    history
 }
 
 /// Get the total number of commits in the current branch.
@@ -113,16 +355,13 @@ pub fn commit_count() -> Result<usize> {
     Ok(output.trim().parse().unwrap_or(0))
 }
 
-/// Search commits by message text.
-pub fn search_commits(query: &str, count: usize) -> Result<Vec<CommitEntry>> {
-    let count_str = format!("-{}", count);
-    let format_str = format!("--format={}", LOG_FORMAT);
-    let grep_str = format!("--grep={}", query);
-
-    // Note: search doesn't use --graph usually, but if we want consistent return type,
-    // we can parse it. Without --graph, the regex matches at index 0, so graph is empty string.
-    let output = run_git(&["log", &count_str, &format_str, &grep_str, "-i"])?;
-    Ok(parse_log_output(&output))
+/// Index of `hash` in the Timeline's log order (`--topo-order` from HEAD),
+/// or `None` when HEAD does not contain it.
+pub fn commit_position(hash: &str) -> Result<Option<usize>> {
+    let full = run_git(&["rev-parse", "--verify", &format!("{}^{{commit}}", hash)])?;
+    let full = full.trim();
+    let output = run_git(&["rev-list", "--topo-order", "HEAD"])?;
+    Ok(output.lines().position(|line| line == full))
 }
 
 #[cfg(test)]
@@ -139,16 +378,13 @@ mod tests {
         assert_eq!(entries[0].message, "feat: add login");
         assert_eq!(entries[0].author, "John");
         assert_eq!(entries[0].refs, "HEAD -> main");
-        assert_eq!(entries[0].graph, "* ");
     }
 
     #[test]
-    fn test_parse_graph_only_line() {
-        let sample = "| \\ \n";
+    fn test_parse_skips_lines_without_commit() {
+        let sample = "| \\ \n\nM\tsrc/main.rs\n";
         let entries = parse_log_output(sample);
-        assert_eq!(entries.len(), 1);
-        assert_eq!(entries[0].hash, "");
-        assert_eq!(entries[0].graph, "| \\ ");
+        assert!(entries.is_empty());
     }
 
     #[test]
@@ -187,4 +423,84 @@ mod tests {
         let entries = parse_log_output(sample);
         assert!(entries.is_empty());
     }
+
+    #[test]
+    fn test_log_query_parse() {
+        let q = LogQuery::parse(
+            r#"author:alice author:"Bob Smith" path:src/ since:2w -S:token fix crash merges:none"#,
+        )
+        .unwrap();
+        assert_eq!(q.authors, vec!["alice", "Bob Smith"]);
+        assert_eq!(q.paths, vec!["src/"]);
+        assert_eq!(q.since.as_deref(), Some("2.weeks.ago"));
+        assert_eq!(q.pickaxe.as_deref(), Some("token"));
+        assert_eq!(q.text.as_deref(), Some("fix crash"));
+        assert_eq!(q.merges, MergeFilter::Exclude);
+    }
+
+    #[test]
+    fn test_log_query_plain_text_and_unknown_keys() {
+        let q = LogQuery::parse("fix: typo").unwrap();
+        assert_eq!(q.text.as_deref(), Some("fix: typo"));
+        assert!(LogQuery::parse("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_log_query_errors() {
+        assert!(LogQuery::parse("author:").is_err());
+        assert!(LogQuery::parse(r#"author:"open"#).is_err());
+        assert!(LogQuery::parse("merges:sometimes").is_err());
+        assert!(LogQuery::parse("-G:(").is_err());
+    }
+
+    #[test]
+    fn test_log_query_args() {
+        let q = LogQuery::parse(
+            r"committer:ci until:2024-06-01 -G:fn\s+main path:*.rs path:docs merges:only bug",
+        )
+        .unwrap();
+        let (args, paths) = q.args();
+        assert_eq!(
+            args,
+            vec![
+                "--grep=bug",
+                "-i",
+                "--committer=ci",
+                "--until=2024-06-01",
+                "-Gfn\\s+main",
+                "--merges",
+            ]
+        );
+        assert_eq!(paths, vec![":(glob)*.rs", "docs"]);
+    }
+
+    #[test]
+    fn test_expand_date() {
+        assert_eq!(expand_date("3d"), "3.days.ago");
+        assert_eq!(expand_date("6mo"), "6.months.ago");
+        assert_eq!(expand_date("2024-01-01"), "2024-01-01");
+        assert_eq!(expand_date("yesterday"), "yesterday");
+    }
+
+    #[test]
+    fn test_parse_file_history_follows_renames() {
+        let sample = "abc123def456abc123def456abc123def456abc1\x1fabc123d\x1fedit\x1fAlice\x1f1 hour ago\x1f2026-01-02T00:00:00+00:00\x1fdef456abc123def456abc123def456abc123def4\x1f\n\
+                      \n\
+                      M\tsrc/new.rs\n\
+                      def456abc123def456abc123def456abc123def4\x1fdef456a\x1frename\x1fBob\x1f2 hours ago\x1f2026-01-01T00:00:00+00:00\x1f\x1f\n\
+                      \n\
+                      R095\tsrc/old.rs\tsrc/new.rs\n";
+        let history = parse_file_history(sample, "src/new.rs");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].commit.message, "edit");
+        assert_eq!(history[0].path, "src/new.rs");
+        assert!(history[0].old_path.is_none());
+        assert_eq!(history[1].path, "src/new.rs");
+        assert_eq!(history[1].old_path.as_deref(), Some("src/old.rs"));
+    }
+
+    #[test]
+    fn test_parse_file_history_empty() {
+        assert!(parse_file_history("", "a.rs").is_empty());
+    }
 }
@@ -1,7 +1,7 @@
 //! Merge conflict detection, parsing, and resolution helpers.
 
 use super::runner::run_git;
-use anyhow::{bail, Result};
+use anyhow::{Result, bail};
 use std::fs;
 use std::path::Path;
 
@@ -73,8 +73,8 @@ pub struct ConflictFile {
 /// Returns None if no merge operation is active.
 pub fn get_merge_state() -> Option<MergeState> {
     // Find the .git directory (handles worktrees and submodules)
-    let git_dir = run_git(&["rev-parse", "--git-dir"]).ok()?;
-    let git_dir = git_dir.trim();
+    let repo = super::repo::current().ok()?;
+    let git_dir = repo.git_dir();
 
     let head_name = run_git(&["rev-parse", "--abbrev-ref", "HEAD"])
         .unwrap_or_else(|_| "HEAD".to_string())
@@ -314,7 +314,8 @@ pub fn continue_merge() -> Result<()> {
                 Ok(())
             }
             MergeType::Rebase => {
-                run_git(&["rebase", "--continue"])?;
+                // Keeps the commit message instead of waiting on an editor
+                super::rebase::continue_rebase()?;
                 Ok(())
             }
             MergeType::CherryPick => {
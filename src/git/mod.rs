@@ -1,16 +1,29 @@
 pub mod bisect;
+pub mod blame;
 pub mod branch;
 pub mod cherry_pick;
 pub mod diff;
 pub mod github_auth;
+pub mod gitleaks;
+pub mod hooks;
+pub mod lfs;
 pub mod log;
 pub mod merge;
+pub mod policy;
+pub mod rebase;
 pub mod reflog;
 pub mod remote;
+pub mod repo;
 pub mod runner;
+pub mod secret_audit;
+pub mod secret_baseline;
+pub mod secret_report;
 pub mod secrets;
 pub mod stash;
 pub mod status;
+pub mod submodule;
+pub mod tag;
+pub mod worktree;
 
 pub use branch::{BranchEntry, BranchOps};
 pub use diff::{DiffLine, DiffLineType};
@@ -0,0 +1,294 @@
+//! Commit policy: staged files that shouldn't be committed as they are —
+//! over the size limit, binaries outside Git LFS, or forbidden paths such
+//! as `.env` and private keys.
+
+use anyhow::{Result, bail};
+
+use crate::config::{CommitConfig, wildcard_match};
+
+/// Why a staged file was flagged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyIssue {
+    /// Matches a `forbidden_paths` pattern.
+    Forbidden { pattern: String },
+    /// Staged blob is larger than `max_file_size_kb`.
+    TooLarge { size: u64 },
+    /// Binary content not stored through Git LFS.
+    Binary,
+}
+
+/// Fix offered for a finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyAction {
+    Unstage,
+    /// Add to `.gitignore` and stop tracking.
+    Gitignore,
+    TrackWithLfs,
+}
+
+impl PolicyAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            PolicyAction::Unstage => "unstage",
+            PolicyAction::Gitignore => "add to .gitignore",
+            PolicyAction::TrackWithLfs => "track with LFS",
+        }
+    }
+}
+
+/// A staged file that breaks the commit policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyFinding {
+    pub path: String,
+    pub issue: PolicyIssue,
+}
+
+impl PolicyFinding {
+    pub fn suggested_action(&self) -> PolicyAction {
+        match self.issue {
+            PolicyIssue::Forbidden { .. } => PolicyAction::Gitignore,
+            PolicyIssue::TooLarge { .. } | PolicyIssue::Binary => PolicyAction::TrackWithLfs,
+        }
+    }
+
+    /// Short description of the problem, without the path.
+    pub fn describe(&self) -> String {
+        match &self.issue {
+            PolicyIssue::Forbidden { pattern } => format!("forbidden path ({})", pattern),
+            PolicyIssue::TooLarge { size } => format!("large file ({})", format_size(*size)),
+            PolicyIssue::Binary => "binary file not tracked by LFS".to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for PolicyFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} — {}; suggested: {}",
+            self.path,
+            self.describe(),
+            self.suggested_action().label()
+        )
+    }
+}
+
+/// Check the files a commit would add or modify against `config`. One
+/// finding per file: a forbidden path wins over size, size over binary.
+pub fn check_staged(config: &CommitConfig) -> Result<Vec<PolicyFinding>> {
+    // git's own binary detection, honouring .gitattributes
+    let numstat = super::run_git(&[
+        "diff",
+        "--cached",
+        "--numstat",
+        "-z",
+        "--no-renames",
+        "--diff-filter=d",
+    ])?;
+    let changed = parse_numstat(&numstat);
+    if changed.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let limit = config.max_file_size_kb.saturating_mul(1024);
+    let sizes = if limit > 0 {
+        staged_sizes(changed.iter().map(|(path, _)| path.as_str()))?
+    } else {
+        Vec::new()
+    };
+
+    let mut findings = Vec::new();
+    for (path, binary) in changed {
+        let forbidden = config
+            .forbidden_paths
+            .iter()
+            .find(|p| forbidden_match(p, &path));
+        let size = sizes
+            .iter()
+            .find(|(p, _)| *p == path)
+            .map_or(0, |(_, s)| *s);
+        let issue = if let Some(pattern) = forbidden {
+            PolicyIssue::Forbidden {
+                pattern: pattern.clone(),
+            }
+        } else if limit > 0 && size > limit {
+            PolicyIssue::TooLarge { size }
+        } else if binary && config.flag_binaries {
+            PolicyIssue::Binary
+        } else {
+            continue;
+        };
+        findings.push(PolicyFinding { path, issue });
+    }
+    Ok(findings)
+}
+
+/// Parse `diff --numstat -z` records into (path, is binary). Binary files
+/// show `-` for both counts.
+fn parse_numstat(output: &str) -> Vec<(String, bool)> {
+    output
+        .split('\0')
+        .filter_map(|record| {
+            let mut fields = record.splitn(3, '\t');
+            let added = fields.next()?;
+            let _deleted = fields.next()?;
+            let path = fields.next()?;
+            Some((path.to_string(), added == "-"))
+        })
+        .collect()
+}
+
+/// Size of each path's staged blob.
+fn staged_sizes<'a>(paths: impl Iterator<Item = &'a str>) -> Result<Vec<(String, u64)>> {
+    let repo = super::repo::current()?;
+    let mut sizes = Vec::new();
+    for (path, oid) in super::secrets::staged_blobs(&paths.collect::<Vec<_>>())? {
+        if let Some(info) = repo.object_info(&oid)? {
+            sizes.push((path, info.size));
+        }
+    }
+    Ok(sizes)
+}
+
+/// gitignore-style match: `dir/` matches a directory anywhere in the path,
+/// a pattern with a `/` is anchored at the root, anything else matches the
+/// file name. `*` is a wildcard.
+pub fn forbidden_match(pattern: &str, path: &str) -> bool {
+    if let Some(dir) = pattern.strip_suffix('/') {
+        let dirs = path.rsplit_once('/').map_or("", |(dirs, _)| dirs);
+        if dir.contains('/') {
+            let dir = dir.trim_start_matches('/');
+            return dirs == dir || wildcard_match(&format!("{}/*", dir), dirs);
+        }
+        return dirs.split('/').any(|d| wildcard_match(dir, d));
+    }
+    if pattern.contains('/') {
+        return wildcard_match(pattern.trim_start_matches('/'), path);
+    }
+    let name = path.rsplit('/').next().unwrap_or(path);
+    wildcard_match(pattern, name)
+}
+
+/// Apply `action` to `finding`'s file.
+pub fn apply(finding: &PolicyFinding, action: PolicyAction) -> Result<()> {
+    let path = finding.path.as_str();
+    match action {
+        PolicyAction::Unstage => unstage(path),
+        PolicyAction::Gitignore => {
+            let entry = match &finding.issue {
+                PolicyIssue::Forbidden { pattern } => pattern.clone(),
+                _ => format!("/{}", path),
+            };
+            add_to_gitignore(&entry)?;
+            // Stops tracking a file already in HEAD; the copy on disk stays.
+            // Paths are literal so a name like `[draft].env` isn't a glob.
+            super::run_git(&["--literal-pathspecs", "rm", "--cached", "-q", "--", path])?;
+            super::run_git(&["--literal-pathspecs", "add", "--", ".gitignore"])?;
+            Ok(())
+        }
+        PolicyAction::TrackWithLfs => {
+            if !super::lfs::is_installed() {
+                bail!("git-lfs is not installed");
+            }
+            super::run_git(&["lfs", "track", "--filename", path])?;
+            // Re-add so the LFS filter stores a pointer instead of the blob
+            super::run_git(&["--literal-pathspecs", "add", "--", ".gitattributes", path])?;
+            Ok(())
+        }
+    }
+}
+
+fn unstage(path: &str) -> Result<()> {
+    let has_head = super::run_git(&["rev-parse", "--verify", "--quiet", "HEAD"]).is_ok();
+    if has_head {
+        super::run_git(&["--literal-pathspecs", "restore", "--staged", "--", path])?;
+    } else {
+        super::run_git(&["--literal-pathspecs", "rm", "--cached", "-q", "--", path])?;
+    }
+    Ok(())
+}
+
+/// Append `entry` to the root `.gitignore` unless it is already listed.
+fn add_to_gitignore(entry: &str) -> Result<()> {
+    let path = super::repo::current()?.root().join(".gitignore");
+    let mut content = std::fs::read_to_string(&path).unwrap_or_default();
+    if content.lines().any(|l| l.trim() == entry) {
+        return Ok(());
+    }
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(entry);
+    content.push('\n');
+    std::fs::write(&path, content)?;
+    Ok(())
+}
+
+/// `12.0 MiB`, or whole KiB below that.
+pub(crate) fn format_size(bytes: u64) -> String {
+    const MIB: f64 = 1024.0 * 1024.0;
+    if bytes as f64 >= MIB {
+        format!("{:.1} MiB", bytes as f64 / MIB)
+    } else {
+        format!("{} KiB", bytes.div_ceil(1024))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forbidden_match_file_names() {
+        assert!(forbidden_match(".env", ".env"));
+        assert!(forbidden_match(".env", "app/.env"));
+        assert!(!forbidden_match(".env", ".env.example"));
+        assert!(forbidden_match("*.pem", "certs/server.pem"));
+        assert!(!forbidden_match("*.pem", "docs/pem.md"));
+    }
+
+    #[test]
+    fn test_forbidden_match_directories_and_anchors() {
+        assert!(forbidden_match("node_modules/", "node_modules/x/index.js"));
+        assert!(forbidden_match("node_modules/", "web/node_modules/a.js"));
+        assert!(!forbidden_match("node_modules/", "node_modules.md"));
+        assert!(forbidden_match("build/out/", "build/out/app.bin"));
+        assert!(!forbidden_match("build/out/", "src/build/out/app.bin"));
+        assert!(forbidden_match("/secrets/*.json", "secrets/prod.json"));
+        assert!(!forbidden_match("/secrets/*.json", "app/secrets/prod.json"));
+    }
+
+    #[test]
+    fn test_parse_numstat_flags_binaries() {
+        let out = "3\t1\tsrc/main.rs\0-\t-\tlogo.png\0";
+        assert_eq!(
+            parse_numstat(out),
+            vec![
+                ("src/main.rs".to_string(), false),
+                ("logo.png".to_string(), true)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_finding_display_and_action() {
+        let finding = PolicyFinding {
+            path: "data/dump.sql".to_string(),
+            issue: PolicyIssue::TooLarge {
+                size: 12 * 1024 * 1024,
+            },
+        };
+        assert_eq!(finding.suggested_action(), PolicyAction::TrackWithLfs);
+        assert_eq!(
+            finding.to_string(),
+            "data/dump.sql — large file (12.0 MiB); suggested: track with LFS"
+        );
+        let finding = PolicyFinding {
+            path: ".env".to_string(),
+            issue: PolicyIssue::Forbidden {
+                pattern: ".env".to_string(),
+            },
+        };
+        assert_eq!(finding.suggested_action(), PolicyAction::Gitignore);
+    }
+}
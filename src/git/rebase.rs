@@ -0,0 +1,388 @@
+//! Interactive rebase — build a todo list, preview its result and run it
+//! through `GIT_SEQUENCE_EDITOR` without opening an editor.
+
+use super::runner::{run_git, run_git_with_env};
+use anyhow::{Result, bail};
+use std::path::Path;
+use std::time::Duration;
+
+/// What to do with a commit in the rebase todo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RebaseAction {
+    Pick,
+    Reword,
+    Edit,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub const ALL: [RebaseAction; 6] = [
+        RebaseAction::Pick,
+        RebaseAction::Reword,
+        RebaseAction::Edit,
+        RebaseAction::Squash,
+        RebaseAction::Fixup,
+        RebaseAction::Drop,
+    ];
+
+    /// Todo-file command name.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Edit => "edit",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+
+    /// The next action, for cycling with a single key.
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|a| *a == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    /// Whether the commit is folded into the one before it.
+    pub fn melds(self) -> bool {
+        matches!(self, RebaseAction::Squash | RebaseAction::Fixup)
+    }
+}
+
+/// One line of the rebase todo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TodoEntry {
+    pub action: RebaseAction,
+    pub hash: String,
+    pub short_hash: String,
+    pub summary: String,
+    /// Replacement message for `reword`; `None` keeps the original.
+    pub new_message: Option<String>,
+}
+
+/// A commit in the rebased history, as predicted from the todo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewCommit {
+    pub summary: String,
+    /// Short hashes of the original commits that make up this one.
+    pub sources: Vec<String>,
+    /// Rebase stops here for amending (`edit`).
+    pub stops: bool,
+}
+
+/// How a rebase run ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RebaseOutcome {
+    /// All steps applied.
+    Done,
+    /// A step conflicted — resolve, then continue.
+    Conflict,
+    /// Stopped at an `edit` step for amending.
+    Stopped,
+}
+
+/// Commits that an interactive rebase onto `base` would replay, oldest first.
+/// `None` rebases the whole history (`--root`).
+pub fn todo_for(base: Option<&str>) -> Result<Vec<TodoEntry>> {
+    let range = match base {
+        Some(base) => format!("{}..HEAD", base),
+        None => "HEAD".to_string(),
+    };
+    let output = run_git(&[
+        "log",
+        "--reverse",
+        "--no-merges",
+        "--format=%H%x1f%h%x1f%s",
+        &range,
+    ])?;
+    Ok(parse_todo_log(&output))
+}
+
+fn parse_todo_log(output: &str) -> Vec<TodoEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\x1f');
+            let hash = parts.next()?;
+            let short_hash = parts.next()?;
+            let summary = parts.next().unwrap_or("");
+            Some(TodoEntry {
+                action: RebaseAction::Pick,
+                hash: hash.to_string(),
+                short_hash: short_hash.to_string(),
+                summary: summary.to_string(),
+                new_message: None,
+            })
+        })
+        .collect()
+}
+
+/// Check that the todo can run: something must be kept and the first kept
+/// commit cannot be squashed into nothing.
+pub fn validate(entries: &[TodoEntry]) -> Result<()> {
+    let Some(first) = entries.iter().find(|e| e.action != RebaseAction::Drop) else {
+        bail!("Every commit is dropped — nothing would be left to rebase");
+    };
+    if first.action.melds() {
+        bail!(
+            "Cannot {} {} — there is no earlier commit to fold it into",
+            first.action.as_str(),
+            first.short_hash
+        );
+    }
+    Ok(())
+}
+
+/// Predict the history the todo produces, oldest first.
+pub fn preview(entries: &[TodoEntry]) -> Vec<PreviewCommit> {
+    let mut result: Vec<PreviewCommit> = Vec::new();
+    for entry in entries {
+        match entry.action {
+            RebaseAction::Drop => {}
+            RebaseAction::Squash | RebaseAction::Fixup if !result.is_empty() => {
+                if let Some(last) = result.last_mut() {
+                    last.sources.push(entry.short_hash.clone());
+                }
+            }
+            _ => {
+                let summary = match (entry.action, &entry.new_message) {
+                    (RebaseAction::Reword, Some(msg)) => {
+                        msg.lines().next().unwrap_or("").to_string()
+                    }
+                    _ => entry.summary.clone(),
+                };
+                result.push(PreviewCommit {
+                    summary,
+                    sources: vec![entry.short_hash.clone()],
+                    stops: entry.action == RebaseAction::Edit,
+                });
+            }
+        }
+    }
+    result
+}
+
+/// Render the todo file. A reword with a new message becomes a `pick`
+/// followed by an `exec` that amends from `message_file(i)`, so git never
+/// needs an editor.
+pub fn render_todo(entries: &[TodoEntry], message_file: impl Fn(usize) -> String) -> String {
+    let mut todo = String::new();
+    for (i, entry) in entries.iter().enumerate() {
+        match (entry.action, &entry.new_message) {
+            (RebaseAction::Reword, Some(_)) => {
+                todo.push_str(&format!("pick {} {}\n", entry.hash, entry.summary));
+                todo.push_str(&format!(
+                    "exec git commit --amend --only --no-verify --quiet -F {}\n",
+                    shell_quote(&message_file(i))
+                ));
+            }
+            (action, _) => {
+                todo.push_str(&format!(
+                    "{} {} {}\n",
+                    action.as_str(),
+                    entry.hash,
+                    entry.summary
+                ));
+            }
+        }
+    }
+    todo
+}
+
+/// Quote a path for `sh`.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Environment that makes git accept default messages instead of opening an editor.
+const NO_EDITOR: (&str, &str) = ("GIT_EDITOR", "true");
+
+/// Replaying a long todo (hooks, `exec` steps) can take far longer than the
+/// default git timeout, and killing git midway leaves the rebase half-applied.
+const REBASE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Run an interactive rebase onto `base` (or `--root`) with the given todo.
+pub fn start(base: Option<&str>, entries: &[TodoEntry]) -> Result<RebaseOutcome> {
+    validate(entries)?;
+    let repo = super::repo::current()?;
+    let dir = repo.git_dir().join("zit-rebase");
+    std::fs::create_dir_all(&dir)?;
+
+    let message_path = |i: usize| dir.join(format!("message-{}", i));
+    for (i, entry) in entries.iter().enumerate() {
+        if let Some(ref msg) = entry.new_message {
+            std::fs::write(message_path(i), msg)?;
+        }
+    }
+    let todo_path = dir.join("git-rebase-todo");
+    let todo = render_todo(entries, |i| message_path(i).to_string_lossy().to_string());
+    std::fs::write(&todo_path, todo)?;
+
+    // git runs the sequence editor as `sh -c '<editor> "$@"' <todo file>`
+    let editor = format!("cp {}", shell_quote(&todo_path.to_string_lossy()));
+    let mut args = vec!["rebase", "-i"];
+    match base {
+        Some(base) => args.push(base),
+        None => args.push("--root"),
+    }
+    let result = run_git_with_env(
+        &args,
+        &[("GIT_SEQUENCE_EDITOR", &editor), NO_EDITOR],
+        REBASE_TIMEOUT,
+    );
+    finish(repo.git_dir(), result)
+}
+
+/// Continue after resolving a conflict or amending an `edit` stop.
+pub fn continue_rebase() -> Result<RebaseOutcome> {
+    let repo = super::repo::current()?;
+    let result = run_git_with_env(&["rebase", "--continue"], &[NO_EDITOR], REBASE_TIMEOUT);
+    finish(repo.git_dir(), result)
+}
+
+/// Skip the current step.
+pub fn skip() -> Result<RebaseOutcome> {
+    let repo = super::repo::current()?;
+    let result = run_git_with_env(&["rebase", "--skip"], &[NO_EDITOR], REBASE_TIMEOUT);
+    finish(repo.git_dir(), result)
+}
+
+/// Whether an interactive rebase is in progress.
+pub fn in_progress() -> bool {
+    super::repo::current()
+        .map(|repo| repo.git_dir().join("rebase-merge").exists())
+        .unwrap_or(false)
+}
+
+/// Steps already applied and total steps of the running rebase.
+pub fn progress() -> Option<(usize, usize)> {
+    let repo = super::repo::current().ok()?;
+    let dir = repo.git_dir().join("rebase-merge");
+    let read = |name: &str| {
+        std::fs::read_to_string(dir.join(name))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    };
+    Some((read("msgnum")?, read("end")?))
+}
+
+/// Classify the result of a rebase command by the state it left behind.
+fn finish(git_dir: &Path, result: Result<String>) -> Result<RebaseOutcome> {
+    let rebasing = git_dir.join("rebase-merge").exists() || git_dir.join("rebase-apply").exists();
+    if !rebasing {
+        let _ = std::fs::remove_dir_all(git_dir.join("zit-rebase"));
+        return result.map(|_| RebaseOutcome::Done);
+    }
+    let conflicted = run_git(&["diff", "--name-only", "--diff-filter=U"])
+        .map(|out| !out.trim().is_empty())
+        .unwrap_or(false);
+    if conflicted {
+        return Ok(RebaseOutcome::Conflict);
+    }
+    // Stopped cleanly at an `edit` step; a failed `exec` also leaves the
+    // rebase open but is reported as an error
+    result.map(|_| RebaseOutcome::Stopped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(short: &str, action: RebaseAction) -> TodoEntry {
+        TodoEntry {
+            action,
+            hash: format!("{}000000", short),
+            short_hash: short.to_string(),
+            summary: format!("commit {}", short),
+            new_message: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_todo_log() {
+        let output = "aaaa\x1fa1\x1fFirst\nbbbb\x1fb2\x1fSecond: with colon\n";
+        let entries = parse_todo_log(output);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].hash, "aaaa");
+        assert_eq!(entries[1].summary, "Second: with colon");
+        assert!(entries.iter().all(|e| e.action == RebaseAction::Pick));
+    }
+
+    #[test]
+    fn test_action_cycle() {
+        let mut action = RebaseAction::Pick;
+        for _ in 0..RebaseAction::ALL.len() {
+            action = action.next();
+        }
+        assert_eq!(action, RebaseAction::Pick);
+        assert_eq!(RebaseAction::Pick.next(), RebaseAction::Reword);
+    }
+
+    #[test]
+    fn test_validate() {
+        let ok = vec![
+            entry("a", RebaseAction::Pick),
+            entry("b", RebaseAction::Fixup),
+        ];
+        assert!(validate(&ok).is_ok());
+
+        let leading_squash = vec![
+            entry("a", RebaseAction::Drop),
+            entry("b", RebaseAction::Squash),
+        ];
+        assert!(validate(&leading_squash).is_err());
+
+        let all_dropped = vec![entry("a", RebaseAction::Drop)];
+        assert!(validate(&all_dropped).is_err());
+    }
+
+    #[test]
+    fn test_preview() {
+        let mut reword = entry("c", RebaseAction::Reword);
+        reword.new_message = Some("Better title\n\nBody".to_string());
+        let entries = vec![
+            entry("a", RebaseAction::Pick),
+            entry("b", RebaseAction::Squash),
+            reword,
+            entry("d", RebaseAction::Drop),
+            entry("e", RebaseAction::Edit),
+        ];
+        let result = preview(&entries);
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].summary, "commit a");
+        assert_eq!(result[0].sources, ["a", "b"]);
+        assert_eq!(result[1].summary, "Better title");
+        assert!(result[2].stops);
+    }
+
+    #[test]
+    fn test_render_todo() {
+        let mut reword = entry("b", RebaseAction::Reword);
+        reword.new_message = Some("New".to_string());
+        let entries = vec![
+            entry("a", RebaseAction::Pick),
+            reword,
+            entry("c", RebaseAction::Fixup),
+        ];
+        let todo = render_todo(&entries, |i| format!("/tmp/it's/msg-{}", i));
+        let lines: Vec<&str> = todo.lines().collect();
+        assert_eq!(lines[0], "pick a000000 commit a");
+        assert_eq!(lines[1], "pick b000000 commit b");
+        assert_eq!(
+            lines[2],
+            r"exec git commit --amend --only --no-verify --quiet -F '/tmp/it'\''s/msg-1'"
+        );
+        assert_eq!(lines[3], "fixup c000000 commit c");
+    }
+
+    #[test]
+    fn test_reword_without_message_keeps_command() {
+        let entries = vec![entry("a", RebaseAction::Reword)];
+        let todo = render_todo(&entries, |_| unreachable!());
+        assert_eq!(todo, "reword a000000 commit a\n");
+    }
+}
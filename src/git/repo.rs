@@ -0,0 +1,355 @@
+//! Repository session — caches the repo root and keeps long-lived
+//! `git cat-file --batch` / `--batch-check` pipes for object lookups.
+//!
+//! `run_git` goes through the current session, so callers no longer pay for
+//! a `git rev-parse --show-toplevel` spawn on every command.
+
+use super::runner::spawn_git;
+use anyhow::{Context, Result, bail};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Session for the repository containing the current working directory.
+/// Re-discovered automatically when the working directory changes.
+static SESSION: Mutex<Option<Arc<Repository>>> = Mutex::new(None);
+
+/// Header information for a git object (`<oid> <type> <size>`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectInfo {
+    pub oid: String,
+    pub kind: String,
+    pub size: u64,
+}
+
+/// A long-lived `git cat-file` process speaking the batch protocol.
+struct CatFilePipe {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl CatFilePipe {
+    fn spawn(root: &Path, mode: &str) -> Result<Self> {
+        let mut child = Command::new("git")
+            .args(["cat-file", mode])
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to start git cat-file {}", mode))?;
+        let stdin = child.stdin.take().context("cat-file stdin unavailable")?;
+        let stdout = child.stdout.take().context("cat-file stdout unavailable")?;
+        Ok(Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        })
+    }
+
+    /// Send one object name and read back its header line.
+    fn request(&mut self, rev: &str) -> Result<Option<ObjectInfo>> {
+        writeln!(self.stdin, "{}", rev)?;
+        self.stdin.flush()?;
+
+        let mut header = String::new();
+        if self.stdout.read_line(&mut header)? == 0 {
+            bail!("git cat-file exited unexpectedly");
+        }
+        Ok(parse_batch_header(&header))
+    }
+
+    /// Read an object's content (plus the trailing newline) after its header.
+    fn read_content(&mut self, size: u64) -> Result<Vec<u8>> {
+        let mut content = vec![0u8; size as usize];
+        self.stdout.read_exact(&mut content)?;
+        let mut newline = [0u8; 1];
+        self.stdout.read_exact(&mut newline)?;
+        Ok(content)
+    }
+}
+
+impl Drop for CatFilePipe {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// A repository session: resolved paths plus reusable object pipes.
+pub struct Repository {
+    /// Working directory the session was discovered from.
+    cwd: PathBuf,
+    root: PathBuf,
+    git_dir: PathBuf,
+    common_dir: PathBuf,
+    batch: Mutex<Option<CatFilePipe>>,
+    batch_check: Mutex<Option<CatFilePipe>>,
+}
+
+impl std::fmt::Debug for Repository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Repository")
+            .field("root", &self.root)
+            .field("git_dir", &self.git_dir)
+            .finish()
+    }
+}
+
+/// Get the session for the current working directory, discovering it if needed.
+pub fn current() -> Result<Arc<Repository>> {
+    let cwd = std::env::current_dir().context("Cannot determine current directory")?;
+    let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+
+    if let Some(repo) = session.as_ref()
+        && repo.cwd == cwd
+    {
+        return Ok(Arc::clone(repo));
+    }
+
+    let repo = Arc::new(Repository::discover(&cwd)?);
+    *session = Some(Arc::clone(&repo));
+    Ok(repo)
+}
+
+impl Repository {
+    /// Locate the repository containing `dir` (one `git rev-parse` spawn).
+    pub fn discover(dir: &Path) -> Result<Self> {
+        let output = spawn_git(
+            &[
+                "rev-parse",
+                "--show-toplevel",
+                "--absolute-git-dir",
+                "--git-common-dir",
+            ],
+            Some(dir),
+            Duration::from_secs(10),
+        )?;
+        let mut lines = output.lines();
+        let (Some(root), Some(git_dir), Some(common_dir)) =
+            (lines.next(), lines.next(), lines.next())
+        else {
+            bail!("Unexpected rev-parse output: {}", output.trim());
+        };
+
+        // --git-common-dir may be relative to the directory rev-parse ran in
+        let common_dir = Path::new(common_dir);
+        let common_dir = if common_dir.is_absolute() {
+            common_dir.to_path_buf()
+        } else {
+            dir.join(common_dir)
+        };
+
+        log::debug!("Repository session at {}", root);
+        Ok(Self {
+            cwd: dir.to_path_buf(),
+            root: PathBuf::from(root),
+            git_dir: PathBuf::from(git_dir),
+            common_dir,
+            batch: Mutex::new(None),
+            batch_check: Mutex::new(None),
+        })
+    }
+
+    /// Top-level directory of the working tree.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The `.git` directory for this worktree.
+    pub fn git_dir(&self) -> &Path {
+        &self.git_dir
+    }
+
+    /// The shared `.git` directory (differs from `git_dir` in linked worktrees).
+    pub fn common_dir(&self) -> &Path {
+        &self.common_dir
+    }
+
+    /// Run a git command from the repo root with a custom timeout.
+    pub fn run_with_timeout(&self, args: &[&str], timeout: Duration) -> Result<String> {
+        spawn_git(args, Some(&self.root), timeout)
+    }
+
+    /// Look up an object's type and size via the `--batch-check` pipe.
+    /// Returns `Ok(None)` if the object does not exist.
+    ///
+    /// The cat-file process is long-lived, so prefer full object ids: symbolic
+    /// names such as `HEAD` or `:path` may be resolved against cached state.
+    pub fn object_info(&self, rev: &str) -> Result<Option<ObjectInfo>> {
+        Self::with_pipe(
+            &self.batch_check,
+            &self.root,
+            "--batch-check",
+            rev,
+            |pipe| pipe.request(rev),
+        )
+    }
+
+    /// Read an object's raw content via the `--batch` pipe.
+    /// Returns `Ok(None)` if the object does not exist.
+    pub fn read_object(&self, rev: &str) -> Result<Option<(ObjectInfo, Vec<u8>)>> {
+        Self::with_pipe(&self.batch, &self.root, "--batch", rev, |pipe| {
+            match pipe.request(rev)? {
+                Some(info) => {
+                    let content = pipe.read_content(info.size)?;
+                    Ok(Some((info, content)))
+                }
+                None => Ok(None),
+            }
+        })
+    }
+
+    /// Parent commit ids of a commit, read from the commit object itself.
+    pub fn commit_parents(&self, oid: &str) -> Result<Vec<String>> {
+        let Some((info, content)) = self.read_object(oid)? else {
+            bail!("commit {} not found", oid);
+        };
+        if info.kind != "commit" {
+            bail!("{} is a {}, not a commit", oid, info.kind);
+        }
+        Ok(parse_commit_parents(&String::from_utf8_lossy(&content)))
+    }
+
+    /// Number of stash entries, read from the stash reflog without spawning git.
+    pub fn stash_count(&self) -> u32 {
+        std::fs::read_to_string(self.common_dir.join("logs/refs/stash"))
+            .map(|log| log.lines().filter(|l| !l.trim().is_empty()).count() as u32)
+            .unwrap_or(0)
+    }
+
+    /// Run `f` against a pipe, (re)spawning it if needed. A broken pipe is
+    /// restarted once before giving up.
+    fn with_pipe<T>(
+        slot: &Mutex<Option<CatFilePipe>>,
+        root: &Path,
+        mode: &str,
+        rev: &str,
+        mut f: impl FnMut(&mut CatFilePipe) -> Result<T>,
+    ) -> Result<T> {
+        if rev.is_empty() || rev.contains('\n') {
+            bail!("invalid object name: {:?}", rev);
+        }
+
+        let mut slot = slot.lock().unwrap_or_else(|e| e.into_inner());
+        for attempt in 0..2 {
+            if slot.is_none() {
+                *slot = Some(CatFilePipe::spawn(root, mode)?);
+            }
+            let pipe = slot.as_mut().expect("pipe was just spawned");
+            match f(pipe) {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    // The stream is now in an unknown state — drop it
+                    *slot = None;
+                    if attempt == 1 {
+                        return Err(e);
+                    }
+                    log::debug!("git cat-file {} failed, restarting: {}", mode, e);
+                }
+            }
+        }
+        unreachable!("loop always returns")
+    }
+}
+
+/// Parse a `cat-file --batch(-check)` header line.
+/// Returns `None` for `<name> missing` / `<name> ambiguous` responses.
+fn parse_batch_header(line: &str) -> Option<ObjectInfo> {
+    let mut parts = line.trim_end().split(' ');
+    let oid = parts.next()?;
+    let kind = parts.next()?;
+    let size = parts.next()?.parse().ok()?;
+    Some(ObjectInfo {
+        oid: oid.to_string(),
+        kind: kind.to_string(),
+        size,
+    })
+}
+
+/// Extract `parent` ids from a raw commit object.
+fn parse_commit_parents(commit: &str) -> Vec<String> {
+    commit
+        .lines()
+        .take_while(|l| !l.is_empty())
+        .filter_map(|l| l.strip_prefix("parent "))
+        .map(|p| p.trim().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_batch_header() {
+        let info = parse_batch_header("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 blob 0\n").unwrap();
+        assert_eq!(info.oid, "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391");
+        assert_eq!(info.kind, "blob");
+        assert_eq!(info.size, 0);
+    }
+
+    #[test]
+    fn test_parse_batch_header_missing() {
+        assert!(parse_batch_header("deadbeef missing\n").is_none());
+        assert!(parse_batch_header("abc ambiguous\n").is_none());
+    }
+
+    #[test]
+    fn test_parse_commit_parents() {
+        let commit = "tree 4b825dc642cb6eb9a060e54bf8d69288fbee4904\n\
+                      parent 1111111111111111111111111111111111111111\n\
+                      parent 2222222222222222222222222222222222222222\n\
+                      author A <a@example.com> 0 +0000\n\
+                      \n\
+                      parent in message body\n";
+        assert_eq!(
+            parse_commit_parents(commit),
+            vec![
+                "1111111111111111111111111111111111111111".to_string(),
+                "2222222222222222222222222222222222222222".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_parents_root() {
+        let commit = "tree 4b825dc642cb6eb9a060e54bf8d69288fbee4904\nauthor A <a@example.com> 0 +0000\n\nroot\n";
+        assert!(parse_commit_parents(commit).is_empty());
+    }
+
+    #[test]
+    fn test_session_reads_objects_through_pipes() {
+        let repo = current().expect("tests run inside the zit repository");
+        assert!(repo.root().join("Cargo.toml").exists());
+
+        let head = repo
+            .run_with_timeout(&["rev-parse", "HEAD"], Duration::from_secs(10))
+            .unwrap();
+        let head = head.trim();
+
+        let info = repo.object_info(head).unwrap().expect("HEAD exists");
+        assert_eq!(info.kind, "commit");
+
+        // Second lookup reuses the same pipe
+        let (_, content) = repo.read_object(head).unwrap().expect("HEAD exists");
+        assert!(content.starts_with(b"tree "));
+        let (_, again) = repo.read_object(head).unwrap().expect("HEAD exists");
+        assert_eq!(content, again);
+
+        assert!(
+            repo.object_info("0000000000000000000000000000000000000001")
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_session_rejects_newline_in_name() {
+        let repo = current().unwrap();
+        assert!(repo.object_info("HEAD\nHEAD").is_err());
+    }
+}
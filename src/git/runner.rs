@@ -1,9 +1,11 @@
-use anyhow::{bail, Context, Result};
-use std::process::Command;
+use anyhow::{Context, Result, bail};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
 use std::time::{Duration, Instant};
 
 /// Default timeout for git commands (30 seconds).
-const GIT_TIMEOUT: Duration = Duration::from_secs(30);
+pub(super) const GIT_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Execute a git command with the given arguments and return stdout.
 /// Fails with a descriptive error if the command exits non-zero or times out after 30s.
@@ -11,60 +13,57 @@ pub fn run_git(args: &[&str]) -> Result<String> {
     run_git_with_timeout(args, GIT_TIMEOUT)
 }
 
-/// Raw git execution without repo-root detection (used internally to find repo root).
-fn run_git_raw(args: &[&str]) -> Result<String> {
-    let child = Command::new("git")
-        .args(args)
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .spawn()
-        .context("Failed to execute git command")?;
-
-    let output = child
-        .wait_with_output()
-        .context("Failed to read git output")?;
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("git {} failed: {}", args.join(" "), stderr.trim());
+/// Execute a git command with a custom timeout.
+/// Runs from the repo root of the cached repository session when inside a repo.
+pub fn run_git_with_timeout(args: &[&str], timeout: Duration) -> Result<String> {
+    match super::repo::current() {
+        Ok(repo) => repo.run_with_timeout(args, timeout),
+        Err(_) => spawn_git(args, None, timeout),
     }
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    Ok(stdout.trim().to_string())
 }
 
-/// Execute a git command with a custom timeout.
-pub fn run_git_with_timeout(args: &[&str], timeout: Duration) -> Result<String> {
-    log::debug!("git {}", args.join(" "));
+/// Execute a git command with extra environment variables (e.g. the editors
+/// used by `rebase -i`) and a custom timeout, from the repo root when inside a repo.
+pub fn run_git_with_env(args: &[&str], env: &[(&str, &str)], timeout: Duration) -> Result<String> {
+    let root = super::repo::current().ok();
+    spawn_git_env(args, root.as_ref().map(|r| r.root()), env, timeout)
+}
 
-    // Find the repo root once and set it as working directory
-    let repo_root = run_git_raw(&["rev-parse", "--show-toplevel"]).ok();
+/// Spawn git in `dir` (or the current directory) and collect stdout.
+pub(super) fn spawn_git(args: &[&str], dir: Option<&Path>, timeout: Duration) -> Result<String> {
+    spawn_git_env(args, dir, &[], timeout)
+}
+
+/// Spawn git with extra environment variables and collect stdout.
+/// Output is drained on background threads so large diffs can't fill the pipe
+/// and stall the child while we wait on it.
+fn spawn_git_env(
+    args: &[&str],
+    dir: Option<&Path>,
+    env: &[(&str, &str)],
+    timeout: Duration,
+) -> Result<String> {
+    log::debug!("git {}", args.join(" "));
 
     let mut cmd = Command::new("git");
     cmd.args(args)
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped());
+        .envs(env.iter().copied())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
 
-    if let Some(ref root) = repo_root {
-        cmd.current_dir(root);
+    if let Some(dir) = dir {
+        cmd.current_dir(dir);
     }
 
     let mut child = cmd.spawn().context("Failed to execute git command")?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
 
     let start = Instant::now();
-    loop {
+    let status = loop {
         match child.try_wait() {
-            Ok(Some(status)) => {
-                // Process finished
-                let output = child
-                    .wait_with_output()
-                    .context("Failed to read git output")?;
-                if !status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    log::warn!("git {} failed: {}", args.join(" "), stderr.trim());
-                    bail!("git {} failed: {}", args.join(" "), stderr.trim());
-                }
-                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                return Ok(stdout);
-            }
+            Ok(Some(status)) => break status,
             Ok(None) => {
                 // Still running — check timeout
                 if start.elapsed() > timeout {
@@ -76,13 +75,33 @@ pub fn run_git_with_timeout(args: &[&str], timeout: Duration) -> Result<String>
                         timeout.as_secs()
                     );
                 }
-                std::thread::sleep(Duration::from_millis(50));
+                std::thread::sleep(Duration::from_millis(5));
             }
             Err(e) => {
                 bail!("Failed waiting for git {}: {}", args.join(" "), e);
             }
         }
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr);
+        log::warn!("git {} failed: {}", args.join(" "), stderr.trim());
+        bail!("git {} failed: {}", args.join(" "), stderr.trim());
     }
+    Ok(String::from_utf8_lossy(&stdout).to_string())
+}
+
+/// Read a child pipe to completion on a background thread.
+fn drain(pipe: Option<impl Read + Send + 'static>) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
 }
 
 /// Check if the current directory is inside a git repository.
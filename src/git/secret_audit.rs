@@ -0,0 +1,372 @@
+//! Full-history secret audit: walk every commit in a range, scan the lines
+//! each one added, and report where every secret was introduced and
+//! whether it is still present at HEAD.
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+
+use super::secret_baseline::Baseline;
+use super::secrets::{self, SecretFinding, SecretRule};
+
+/// Separates commits in the streamed log; fields are split by `FIELD_SEP`.
+const COMMIT_MARK: char = '\x1e';
+const FIELD_SEP: char = '\x1f';
+
+/// Options accepted in a range besides plain revisions.
+const RANGE_OPTIONS: &[&str] = &["--all", "--branches", "--tags", "--remotes", "--not"];
+
+/// A secret and the commit that introduced it.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryFinding {
+    pub commit: String,
+    pub author: String,
+    /// Author date, `YYYY-MM-DD`.
+    pub date: String,
+    pub summary: String,
+    #[serde(flatten)]
+    pub finding: SecretFinding,
+    /// The line is still in the file at HEAD.
+    pub at_head: bool,
+}
+
+impl HistoryFinding {
+    pub fn short_hash(&self) -> &str {
+        &self.commit[..7.min(self.commit.len())]
+    }
+}
+
+impl std::fmt::Display for HistoryFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} ({}) {}{}",
+            self.short_hash(),
+            self.date,
+            self.author,
+            self.finding,
+            if self.at_head { " [still at HEAD]" } else { "" }
+        )
+    }
+}
+
+/// Result of a history audit.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryAudit {
+    /// Oldest introduction first.
+    pub findings: Vec<HistoryFinding>,
+    pub commits_scanned: usize,
+}
+
+impl HistoryAudit {
+    pub fn at_head(&self) -> usize {
+        self.findings.iter().filter(|f| f.at_head).count()
+    }
+}
+
+/// `git log` arguments for a user-supplied range: revisions such as
+/// `main`, `v1.0..HEAD` or `^old`, plus `--all`/`--branches`/`--tags`/
+/// `--remotes`, `--not`, `--since=` and `--until=`. Empty means every ref.
+pub fn range_args(range: Option<&str>) -> Result<Vec<String>> {
+    let args: Vec<String> = range
+        .unwrap_or("")
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+    if args.is_empty() {
+        return Ok(vec!["--all".to_string()]);
+    }
+    for arg in &args {
+        let allowed = !arg.starts_with('-')
+            || RANGE_OPTIONS.contains(&arg.as_str())
+            || arg.starts_with("--since=")
+            || arg.starts_with("--until=");
+        if !allowed {
+            bail!("unsupported option in range: {}", arg);
+        }
+    }
+    Ok(args)
+}
+
+/// Scan the patch of every commit in `range` (all refs when `None`),
+/// skipping baselined findings. `progress` is called after each commit
+/// with (scanned, total); return `false` from it to cancel.
+pub fn audit_history(
+    range: Option<&str>,
+    rules: &[SecretRule],
+    allowlist: &[String],
+    mut progress: impl FnMut(usize, usize) -> bool,
+) -> Result<HistoryAudit> {
+    let revs = range_args(range)?;
+    let rev_refs: Vec<&str> = revs.iter().map(String::as_str).collect();
+
+    let mut count_args = vec!["rev-list", "--count"];
+    count_args.extend(&rev_refs);
+    let total: usize = super::run_git(&count_args)?.trim().parse().unwrap_or(0);
+
+    let repo = super::repo::current()?;
+    let format = format!(
+        "--format={}%H{}%an{}%ad{}%s",
+        COMMIT_MARK, FIELD_SEP, FIELD_SEP, FIELD_SEP
+    );
+    // Oldest first, so the first sighting of a secret is its introduction.
+    // Merges are diffed against their first parent, so lines added while
+    // resolving one are scanned; lines merged in from the other side were
+    // already reported at their own commit and are deduplicated.
+    let mut args = vec![
+        "-c",
+        "core.quotePath=false",
+        "log",
+        "--reverse",
+        "--topo-order",
+        "--patch",
+        "--diff-merges=first-parent",
+        "--no-color",
+        "--no-ext-diff",
+        "--no-textconv",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+        "--date=short",
+        &format,
+    ];
+    args.extend(&rev_refs);
+    args.push("--");
+    log::debug!("git {}", args.join(" "));
+
+    let mut child = Command::new("git")
+        .args(&args)
+        .current_dir(repo.root())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to execute git log")?;
+    let stderr = child.stderr.take().map(|mut pipe| {
+        std::thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = pipe.read_to_string(&mut buf);
+            buf
+        })
+    });
+    let stdout = child.stdout.take().context("git log has no stdout")?;
+
+    let baseline = Baseline::load().unwrap_or_else(|e| {
+        log::warn!("Ignoring secrets baseline: {:#}", e);
+        Baseline::default()
+    });
+    let mut walker = Walker::new(rules, allowlist, baseline);
+    let mut reader = BufReader::new(stdout);
+    let mut raw = Vec::new();
+    let mut cancelled = false;
+    loop {
+        raw.clear();
+        if reader.read_until(b'\n', &mut raw)? == 0 {
+            break;
+        }
+        let line = String::from_utf8_lossy(&raw);
+        if let Some(header) = line.strip_prefix(COMMIT_MARK) {
+            if walker.finish_commit() && !progress(walker.scanned, total) {
+                cancelled = true;
+                break;
+            }
+            walker.start_commit(header.trim_end_matches(['\n', '\r']));
+        } else {
+            walker.patch.push_str(&line);
+        }
+    }
+    if cancelled {
+        let _ = child.kill();
+        let _ = child.wait();
+        bail!("Scan cancelled");
+    }
+    walker.finish_commit();
+    progress(walker.scanned, total);
+
+    let status = child.wait()?;
+    if !status.success() {
+        let stderr = stderr.and_then(|h| h.join().ok()).unwrap_or_default();
+        bail!("git log failed: {}", stderr.trim());
+    }
+
+    let mut findings = walker.findings;
+    mark_at_head(&mut findings, &walker.lines);
+    Ok(HistoryAudit {
+        findings,
+        commits_scanned: walker.scanned,
+    })
+}
+
+/// Per-commit state while streaming the log.
+struct Walker<'a> {
+    rules: &'a [SecretRule],
+    allowlist: &'a [String],
+    baseline: Baseline,
+    header: Option<Header>,
+    patch: String,
+    scanned: usize,
+    findings: Vec<HistoryFinding>,
+    /// Text of the line behind each finding, parallel to `findings`.
+    lines: Vec<String>,
+    /// (file, rule, line text) already reported by an earlier commit.
+    seen: HashSet<(String, String, String)>,
+}
+
+struct Header {
+    commit: String,
+    author: String,
+    date: String,
+    summary: String,
+}
+
+impl<'a> Walker<'a> {
+    fn new(rules: &'a [SecretRule], allowlist: &'a [String], baseline: Baseline) -> Self {
+        Self {
+            rules,
+            allowlist,
+            baseline,
+            header: None,
+            patch: String::new(),
+            scanned: 0,
+            findings: Vec::new(),
+            lines: Vec::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    fn start_commit(&mut self, header: &str) {
+        let mut fields = header.split(FIELD_SEP);
+        let mut next = || fields.next().unwrap_or("").to_string();
+        self.header = Some(Header {
+            commit: next(),
+            author: next(),
+            date: next(),
+            summary: next(),
+        });
+        self.patch.clear();
+    }
+
+    /// Scan the buffered commit, if any. Returns whether there was one.
+    fn finish_commit(&mut self) -> bool {
+        let Some(header) = self.header.take() else {
+            return false;
+        };
+        self.scanned += 1;
+
+        let found = secrets::scan_diff_content(&self.patch, self.rules, self.allowlist);
+        let mut blobs: HashMap<String, Vec<String>> = HashMap::new();
+        for finding in found.into_iter().filter(|f| !self.baseline.contains(f)) {
+            // The same secret re-added later (cherry-picks, reverts of a
+            // removal) was already reported where it first appeared
+            let lines = blobs
+                .entry(finding.file.clone())
+                .or_insert_with(|| file_lines(&header.commit, &finding.file));
+            let text = lines
+                .get(finding.line.saturating_sub(1))
+                .map(|l| l.trim().to_string())
+                .unwrap_or_default();
+            let key = (
+                finding.file.clone(),
+                finding.rule_name.clone(),
+                text.clone(),
+            );
+            if !self.seen.insert(key) {
+                continue;
+            }
+            self.findings.push(HistoryFinding {
+                commit: header.commit.clone(),
+                author: header.author.clone(),
+                date: header.date.clone(),
+                summary: header.summary.clone(),
+                finding,
+                at_head: false,
+            });
+            self.lines.push(text);
+        }
+        self.patch.clear();
+        true
+    }
+}
+
+/// Lines of `path` as of `rev`, empty if it can't be read.
+fn file_lines(rev: &str, path: &str) -> Vec<String> {
+    let Ok(repo) = super::repo::current() else {
+        return Vec::new();
+    };
+    match repo.read_object(&format!("{}:{}", rev, path)) {
+        Ok(Some((_, content))) => String::from_utf8_lossy(&content)
+            .lines()
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Set `at_head` on findings whose line is still in the file at HEAD.
+fn mark_at_head(findings: &mut [HistoryFinding], lines: &[String]) {
+    let Ok(head) = super::run_git(&["rev-parse", "--verify", "--quiet", "HEAD^{commit}"]) else {
+        return;
+    };
+    let head = head.trim();
+    let mut files: HashMap<String, HashSet<String>> = HashMap::new();
+    for (finding, text) in findings.iter_mut().zip(lines) {
+        if text.is_empty() {
+            continue;
+        }
+        let at_head = files
+            .entry(finding.finding.file.clone())
+            .or_insert_with(|| {
+                file_lines(head, &finding.finding.file)
+                    .iter()
+                    .map(|l| l.trim().to_string())
+                    .collect()
+            });
+        finding.at_head = at_head.contains(text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_args_default_to_all_refs() {
+        assert_eq!(range_args(None).unwrap(), ["--all"]);
+        assert_eq!(range_args(Some("  ")).unwrap(), ["--all"]);
+    }
+
+    #[test]
+    fn test_range_args_accepts_revisions_and_dates() {
+        assert_eq!(
+            range_args(Some("v1.0..main --since=2024-01-01 ^old")).unwrap(),
+            ["v1.0..main", "--since=2024-01-01", "^old"]
+        );
+        assert_eq!(range_args(Some("--branches")).unwrap(), ["--branches"]);
+        assert_eq!(
+            range_args(Some("abc --not --remotes")).unwrap(),
+            ["abc", "--not", "--remotes"]
+        );
+    }
+
+    #[test]
+    fn test_range_args_rejects_other_options() {
+        let err = range_args(Some("main --output=/tmp/x")).unwrap_err();
+        assert!(err.to_string().contains("--output=/tmp/x"));
+    }
+
+    #[test]
+    fn test_walker_splits_header_fields() {
+        let rules = secrets::default_rules();
+        let mut walker = Walker::new(&rules, &[], Baseline::default());
+        assert!(!walker.finish_commit());
+        walker.start_commit("abc123\x1fAda Lovelace\x1f2024-03-01\x1fAdd config");
+        let header = walker.header.as_ref().unwrap();
+        assert_eq!(header.commit, "abc123");
+        assert_eq!(header.author, "Ada Lovelace");
+        assert_eq!(header.date, "2024-03-01");
+        assert_eq!(header.summary, "Add config");
+        assert!(walker.finish_commit());
+        assert_eq!(walker.scanned, 1);
+    }
+}
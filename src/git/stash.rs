@@ -47,10 +47,10 @@ fn extract_branch(message: &str) -> String {
     // Patterns: "WIP on <branch>: ...", "On <branch>: ...", "index on <branch>: ..."
     let prefixes = ["WIP on ", "On ", "index on "];
     for prefix in &prefixes {
-        if let Some(rest) = message.strip_prefix(prefix) {
-            if let Some(colon_pos) = rest.find(':') {
-                return rest[..colon_pos].to_string();
-            }
+        if let Some(rest) = message.strip_prefix(prefix)
+            && let Some(colon_pos) = rest.find(':')
+        {
+            return rest[..colon_pos].to_string();
         }
     }
     String::new()
@@ -1,7 +1,10 @@
+use super::diff::unquote_path;
 use super::runner::run_git;
 use anyhow::Result;
+use serde::Serialize;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 #[allow(dead_code)]
 pub enum FileStatus {
     Modified,
@@ -14,15 +17,63 @@ pub enum FileStatus {
     Conflicted,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FileEntry {
     pub status: FileStatus,
     pub path: String,
     #[allow(dead_code)]
     pub original_path: Option<String>, // For renames
+    /// Set when the entry is a submodule (gitlink).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submodule: Option<SubmoduleState>,
 }
 
-#[derive(Debug, Clone)]
+/// Submodule state from the `sub` field of a porcelain v2 entry (`S<c><m><u>`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct SubmoduleState {
+    /// The submodule's checked-out commit differs from the recorded one.
+    pub new_commits: bool,
+    /// Tracked files inside the submodule are modified.
+    pub modified_content: bool,
+    /// The submodule contains untracked files.
+    pub untracked_content: bool,
+}
+
+impl SubmoduleState {
+    /// Parse the `sub` field. Returns `None` for regular files (`N...`).
+    pub fn parse(field: &str) -> Option<Self> {
+        let flags: Vec<char> = field.strip_prefix('S')?.chars().collect();
+        if flags.len() != 3 {
+            return None;
+        }
+        Some(Self {
+            new_commits: flags[0] == 'C',
+            modified_content: flags[1] == 'M',
+            untracked_content: flags[2] == 'U',
+        })
+    }
+
+    /// Human-readable summary, e.g. "new commits, modified content".
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.new_commits {
+            parts.push("new commits");
+        }
+        if self.modified_content {
+            parts.push("modified content");
+        }
+        if self.untracked_content {
+            parts.push("untracked content");
+        }
+        if parts.is_empty() {
+            "pointer changed".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct RepoStatus {
     pub branch: String,
     pub upstream: Option<String>,
@@ -58,11 +109,41 @@ impl RepoStatus {
             && self.untracked.is_empty()
             && self.conflicts.is_empty()
     }
+
+    /// Changed submodules with their combined state (staged and unstaged
+    /// entries for the same path are merged).
+    pub fn submodule_changes(&self) -> Vec<(String, SubmoduleState)> {
+        let mut changes: Vec<(String, SubmoduleState)> = Vec::new();
+        for entry in self.staged.iter().chain(&self.unstaged) {
+            let Some(state) = entry.submodule else {
+                continue;
+            };
+            match changes.iter_mut().find(|(path, _)| *path == entry.path) {
+                Some((_, merged)) => {
+                    merged.new_commits |= state.new_commits;
+                    merged.modified_content |= state.modified_content;
+                    merged.untracked_content |= state.untracked_content;
+                }
+                None => changes.push((entry.path.clone(), state)),
+            }
+        }
+        changes
+    }
 }
 
 /// Fetch the full repository status by parsing `git status --porcelain=v2 --branch`.
 pub fn get_status() -> Result<RepoStatus> {
-    let output = run_git(&["status", "--porcelain=v2", "--branch"])?;
+    // No optional locks: status must not rewrite .git/index, or the file
+    // watcher would see its own refresh as a repository change
+    // Raw UTF-8 names; any other quoting is undone per path
+    let output = run_git(&[
+        "--no-optional-locks",
+        "-c",
+        "core.quotePath=false",
+        "status",
+        "--porcelain=v2",
+        "--branch",
+    ])?;
 
     let mut branch = String::from("(unknown)");
     let mut upstream = None;
@@ -104,24 +185,26 @@ pub fn get_status() -> Result<RepoStatus> {
             if let Some(path) = parts.last() {
                 conflicts.push(FileEntry {
                     status: FileStatus::Conflicted,
-                    path: path.to_string(),
+                    path: unquote_path(path),
                     original_path: None,
+                    submodule: None,
                 });
             }
         } else if line.starts_with("? ") {
             // Untracked
-            let path = line.strip_prefix("? ").unwrap_or("").to_string();
+            let path = unquote_path(line.strip_prefix("? ").unwrap_or(""));
             untracked.push(FileEntry {
                 status: FileStatus::Untracked,
                 path,
                 original_path: None,
+                submodule: None,
             });
         }
     }
 
-    // Get stash count
-    let stash_count = run_git(&["stash", "list"])
-        .map(|s| s.lines().count() as u32)
+    // Stash count comes straight from the stash reflog (no extra git spawn)
+    let stash_count = super::repo::current()
+        .map(|repo| repo.stash_count())
         .unwrap_or(0);
 
     Ok(RepoStatus {
@@ -149,7 +232,8 @@ fn parse_ordinary_entry(
         return;
     }
     let xy = parts[1];
-    let path = parts[8].to_string();
+    let submodule = SubmoduleState::parse(parts[2]);
+    let path = unquote_path(parts[8]);
     let x = xy.chars().next().unwrap_or('.');
     let y = xy.chars().nth(1).unwrap_or('.');
 
@@ -160,12 +244,14 @@ fn parse_ordinary_entry(
                 status,
                 path: path.clone(),
                 original_path: None,
+                submodule,
             });
         } else {
             staged.push(FileEntry {
                 status,
                 path: path.clone(),
                 original_path: None,
+                submodule,
             });
         }
     }
@@ -176,6 +262,7 @@ fn parse_ordinary_entry(
             status,
             path,
             original_path: None,
+            submodule,
         });
     }
 }
@@ -187,10 +274,11 @@ fn parse_rename_entry(line: &str, staged: &mut Vec<FileEntry>, unstaged: &mut Ve
         return;
     }
     let xy = parts[1];
+    let submodule = SubmoduleState::parse(parts[2]);
     let path_part = parts[9];
-    let paths: Vec<&str> = path_part.split('\t').collect();
-    let path = paths.first().unwrap_or(&"").to_string();
-    let orig = paths.get(1).map(|s| s.to_string());
+    let paths: Vec<String> = path_part.split('\t').map(unquote_path).collect();
+    let path = paths.first().cloned().unwrap_or_default();
+    let orig = paths.get(1).cloned();
 
     let x = xy.chars().next().unwrap_or('.');
 
@@ -204,6 +292,7 @@ fn parse_rename_entry(line: &str, staged: &mut Vec<FileEntry>, unstaged: &mut Ve
             status,
             path,
             original_path: orig,
+            submodule,
         });
     }
 
@@ -211,8 +300,9 @@ fn parse_rename_entry(line: &str, staged: &mut Vec<FileEntry>, unstaged: &mut Ve
     if let Some(status) = char_to_status(y) {
         unstaged.push(FileEntry {
             status,
-            path: paths.first().unwrap_or(&"").to_string(),
+            path: paths.first().cloned().unwrap_or_default(),
             original_path: None,
+            submodule,
         });
     }
 }
@@ -270,6 +360,7 @@ mod tests {
                     status: FileStatus::Untracked,
                     path,
                     original_path: None,
+                    submodule: None,
                 });
             }
         }
@@ -286,6 +377,25 @@ mod tests {
         assert_eq!(untracked[0].path, "untracked_file.txt");
     }
 
+    #[test]
+    fn test_parse_entries_unquote_paths() {
+        let (mut staged, mut unstaged, mut conflicts) = (Vec::new(), Vec::new(), Vec::new());
+        parse_ordinary_entry(
+            "1 A. N... 000000 100644 100644 0000 abc1 \"tab\\there.env\"",
+            &mut staged,
+            &mut unstaged,
+            &mut conflicts,
+        );
+        parse_rename_entry(
+            "2 R. N... 100644 100644 100644 abc1 abc1 R100 new name.env\t\"old\\\"q.env\"",
+            &mut staged,
+            &mut unstaged,
+        );
+        assert_eq!(staged[0].path, "tab\there.env");
+        assert_eq!(staged[1].path, "new name.env");
+        assert_eq!(staged[1].original_path.as_deref(), Some("old\"q.env"));
+    }
+
     // ── char_to_status tests ────────────────────────────────────────
     #[test]
     fn test_char_to_status_modified() {
@@ -346,6 +456,7 @@ mod tests {
             status: FileStatus::Modified,
             path: "test.rs".to_string(),
             original_path: None,
+            submodule: None,
         });
         assert!(!s.is_clean());
     }
@@ -357,6 +468,7 @@ mod tests {
             status: FileStatus::Untracked,
             path: "new.rs".to_string(),
             original_path: None,
+            submodule: None,
         });
         assert!(!s.is_clean());
     }
@@ -368,6 +480,7 @@ mod tests {
             status: FileStatus::Conflicted,
             path: "merge.rs".to_string(),
             original_path: None,
+            submodule: None,
         });
         assert!(!s.is_clean());
     }
@@ -387,6 +500,66 @@ mod tests {
         assert_eq!(unstaged[0].path, "both.rs");
     }
 
+    // ── Submodules ──────────────────────────────────────────────────
+    #[test]
+    fn test_submodule_state_parse() {
+        assert_eq!(SubmoduleState::parse("N..."), None);
+        assert_eq!(
+            SubmoduleState::parse("SC.U"),
+            Some(SubmoduleState {
+                new_commits: true,
+                modified_content: false,
+                untracked_content: true,
+            })
+        );
+        assert_eq!(
+            SubmoduleState::parse("S...").map(|s| s.describe()),
+            Some("pointer changed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_ordinary_entry_submodule() {
+        let line = "1 MM SCM. 160000 160000 160000 abc123 def456 vendor/lib";
+        let mut staged = Vec::new();
+        let mut unstaged = Vec::new();
+        let mut conflicts = Vec::new();
+        parse_ordinary_entry(line, &mut staged, &mut unstaged, &mut conflicts);
+        let sub = unstaged[0].submodule.unwrap();
+        assert!(sub.new_commits && sub.modified_content && !sub.untracked_content);
+        assert!(staged[0].submodule.is_some());
+
+        let regular = "1 .M N... 100644 100644 100644 abc123 def456 src/lib.rs";
+        parse_ordinary_entry(regular, &mut staged, &mut unstaged, &mut conflicts);
+        assert!(unstaged[1].submodule.is_none());
+    }
+
+    #[test]
+    fn test_submodule_changes_merges_entries() {
+        let entry = |sub: &str| FileEntry {
+            status: FileStatus::Modified,
+            path: "vendor/lib".to_string(),
+            original_path: None,
+            submodule: SubmoduleState::parse(sub),
+        };
+        let mut s = RepoStatus::default();
+        s.staged.push(entry("S..."));
+        s.unstaged.push(entry("S.MU"));
+        s.unstaged.push(FileEntry {
+            submodule: None,
+            path: "README.md".to_string(),
+            ..entry("N...")
+        });
+
+        let changes = s.submodule_changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].0, "vendor/lib");
+        assert_eq!(
+            changes[0].1.describe(),
+            "modified content, untracked content"
+        );
+    }
+
     #[test]
     fn test_parse_ordinary_entry_conflicted() {
         // XY=U. means conflicted in index
@@ -0,0 +1,275 @@
+//! Submodule operations — status, init, update, sync and pointer commit ranges.
+
+use super::diff::{DiffLine, DiffLineType};
+use super::runner::run_git;
+use anyhow::Result;
+
+/// How a submodule's checkout relates to the commit recorded in the superproject.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubmoduleSync {
+    /// Checked out at the recorded commit.
+    InSync,
+    /// Checked out at a different commit (`+`).
+    OutOfSync,
+    /// Not initialized / not cloned yet (`-`).
+    Uninitialized,
+    /// Merge conflict on the gitlink (`U`).
+    Conflict,
+}
+
+/// A submodule as reported by `git submodule status`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Submodule {
+    pub path: String,
+    /// Checked-out commit, or the recorded one when uninitialized.
+    pub commit: String,
+    pub sync: SubmoduleSync,
+    /// `git describe` output for the commit, when available.
+    pub describe: Option<String>,
+}
+
+/// A commit inside a submodule pointer range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeCommit {
+    pub hash: String,
+    pub summary: String,
+    /// `true` if the commit is reachable only from the new pointer,
+    /// `false` if only from the old one (the pointer moved backwards).
+    pub added: bool,
+}
+
+/// List submodules of the current repository.
+pub fn list() -> Result<Vec<Submodule>> {
+    let output = run_git(&["submodule", "status"])?;
+    Ok(parse_status(&output))
+}
+
+/// Parse `git submodule status` output.
+/// Format: `<flag><sha> <path>[ (<describe>)]` where flag is ` `, `+`, `-` or `U`.
+pub fn parse_status(output: &str) -> Vec<Submodule> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut chars = line.chars();
+            let sync = match chars.next()? {
+                ' ' => SubmoduleSync::InSync,
+                '+' => SubmoduleSync::OutOfSync,
+                '-' => SubmoduleSync::Uninitialized,
+                'U' => SubmoduleSync::Conflict,
+                _ => return None,
+            };
+            let rest = chars.as_str();
+            let (commit, rest) = rest.split_once(' ')?;
+            let (path, describe) = match rest.rsplit_once(" (") {
+                Some((path, desc)) if desc.ends_with(')') => {
+                    (path, Some(desc.trim_end_matches(')').to_string()))
+                }
+                _ => (rest, None),
+            };
+            Some(Submodule {
+                path: path.to_string(),
+                commit: commit.to_string(),
+                sync,
+                describe,
+            })
+        })
+        .collect()
+}
+
+/// Register a submodule's URL in `.git/config`.
+pub fn init(path: &str) -> Result<String> {
+    run_git(&["submodule", "init", "--", path])
+}
+
+/// Check out the recorded commit, cloning first if needed.
+/// `None` updates every submodule recursively.
+pub fn update(path: Option<&str>) -> Result<String> {
+    match path {
+        Some(path) => run_git(&["submodule", "update", "--init", "--", path]),
+        None => run_git(&["submodule", "update", "--init", "--recursive"]),
+    }
+}
+
+/// Copy the URL from `.gitmodules` into the submodule's config.
+pub fn sync(path: &str) -> Result<String> {
+    run_git(&["submodule", "sync", "--", path])
+}
+
+/// Commit recorded for `path` in the index (`:path`) or at `HEAD`.
+pub fn recorded_commit(path: &str, at_head: bool) -> Result<String> {
+    let spec = if at_head {
+        format!("HEAD:{}", path)
+    } else {
+        format!(":{}", path)
+    };
+    Ok(run_git(&["rev-parse", &spec])?.trim().to_string())
+}
+
+/// Commits between two pointers of the submodule at `path`.
+/// Requires the submodule to be checked out.
+pub fn commit_range(path: &str, old: &str, new: &str) -> Result<Vec<RangeCommit>> {
+    let range = format!("{}...{}", old, new);
+    let output = run_git(&[
+        "-C",
+        path,
+        "log",
+        "--left-right",
+        "--format=%m%x1f%h%x1f%s",
+        &range,
+    ])?;
+    Ok(parse_range(&output))
+}
+
+/// Parse `log --left-right --format=%m%x1f%h%x1f%s` output.
+fn parse_range(output: &str) -> Vec<RangeCommit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\x1f');
+            let mark = parts.next()?;
+            let hash = parts.next()?;
+            let summary = parts.next().unwrap_or("");
+            Some(RangeCommit {
+                hash: hash.to_string(),
+                summary: summary.to_string(),
+                added: mark == ">",
+            })
+        })
+        .collect()
+}
+
+/// Extract the old and new pointer from a gitlink diff
+/// (`-Subproject commit <old>` / `+Subproject commit <new>[-dirty]`).
+/// Either side is `None` when the submodule was added or removed.
+pub fn parse_pointer_diff(diff: &str) -> (Option<String>, Option<String>) {
+    let mut old = None;
+    let mut new = None;
+    for line in diff.lines() {
+        if let Some(hash) = line.strip_prefix("-Subproject commit ") {
+            old = Some(hash.trim().trim_end_matches("-dirty").to_string());
+        } else if let Some(hash) = line.strip_prefix("+Subproject commit ") {
+            new = Some(hash.trim().trim_end_matches("-dirty").to_string());
+        }
+    }
+    (old, new)
+}
+
+/// Render a submodule pointer change as diff lines: an `old..new` header
+/// followed by one line per commit in the range.
+pub fn pointer_diff_lines(path: &str, staged: bool) -> Result<Vec<DiffLine>> {
+    let diff = if staged {
+        run_git(&["diff", "--cached", "--submodule=short", "--", path])?
+    } else {
+        run_git(&["diff", "--submodule=short", "--", path])?
+    };
+    let (old, new) = parse_pointer_diff(&diff);
+    Ok(range_lines(path, old.as_deref(), new.as_deref()))
+}
+
+/// Build the diff lines for a pointer move from `old` to `new`.
+pub fn range_lines(path: &str, old: Option<&str>, new: Option<&str>) -> Vec<DiffLine> {
+    let short = |h: &str| h[..7.min(h.len())].to_string();
+    let line = |line_type, content: String| DiffLine { line_type, content };
+
+    let header = match (old, new) {
+        (Some(o), Some(n)) if o == n => {
+            format!("Submodule {} {} (pointer unchanged)", path, short(o))
+        }
+        (Some(o), Some(n)) => format!("Submodule {} {}..{}", path, short(o), short(n)),
+        (None, Some(n)) => format!("Submodule {} added at {}", path, short(n)),
+        (Some(o), None) => format!("Submodule {} removed (was {})", path, short(o)),
+        (None, None) => format!("Submodule {}", path),
+    };
+    let mut lines = vec![line(DiffLineType::Header, header)];
+
+    let (Some(old), Some(new)) = (old, new) else {
+        return lines;
+    };
+    if old == new {
+        return lines;
+    }
+    match commit_range(path, old, new) {
+        Ok(commits) if commits.is_empty() => {
+            lines.push(line(
+                DiffLineType::Context,
+                "  (no commits in range)".into(),
+            ));
+        }
+        Ok(commits) => {
+            for c in commits {
+                let (kind, sign) = if c.added {
+                    (DiffLineType::Added, '+')
+                } else {
+                    (DiffLineType::Removed, '-')
+                };
+                lines.push(line(kind, format!("{} {} {}", sign, c.hash, c.summary)));
+            }
+        }
+        Err(_) => lines.push(line(
+            DiffLineType::Context,
+            "  (commit range unavailable — submodule not checked out or commits not fetched)"
+                .into(),
+        )),
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status() {
+        let output = " 1111111111111111111111111111111111111111 libs/core (v1.2.0)\n\
+                      +2222222222222222222222222222222222222222 libs/ui (v0.3-4-g2222222)\n\
+                      -3333333333333333333333333333333333333333 vendor/docs\n\
+                      U4444444444444444444444444444444444444444 libs/conflicted\n";
+        let subs = parse_status(output);
+        assert_eq!(subs.len(), 4);
+        assert_eq!(subs[0].path, "libs/core");
+        assert_eq!(subs[0].sync, SubmoduleSync::InSync);
+        assert_eq!(subs[0].describe.as_deref(), Some("v1.2.0"));
+        assert_eq!(subs[1].sync, SubmoduleSync::OutOfSync);
+        assert_eq!(subs[2].sync, SubmoduleSync::Uninitialized);
+        assert_eq!(subs[2].path, "vendor/docs");
+        assert!(subs[2].describe.is_none());
+        assert_eq!(subs[3].sync, SubmoduleSync::Conflict);
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let output = ">\x1fabc1234\x1fAdd parser\n<\x1fdef5678\x1fOld fix\n";
+        let commits = parse_range(output);
+        assert_eq!(commits.len(), 2);
+        assert!(commits[0].added);
+        assert_eq!(commits[0].summary, "Add parser");
+        assert!(!commits[1].added);
+    }
+
+    #[test]
+    fn test_parse_pointer_diff() {
+        let diff = "diff --git a/lib b/lib\n\
+                    index 1111111..2222222 160000\n\
+                    --- a/lib\n\
+                    +++ b/lib\n\
+                    @@ -1 +1 @@\n\
+                    -Subproject commit 1111111111111111111111111111111111111111\n\
+                    +Subproject commit 2222222222222222222222222222222222222222-dirty\n";
+        let (old, new) = parse_pointer_diff(diff);
+        assert_eq!(old.unwrap(), "1111111111111111111111111111111111111111");
+        assert_eq!(new.unwrap(), "2222222222222222222222222222222222222222");
+
+        let (old, new) = parse_pointer_diff("+Subproject commit abc\n");
+        assert!(old.is_none());
+        assert_eq!(new.as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn test_range_lines_headers() {
+        let lines = range_lines("lib", None, Some("2222222222"));
+        assert_eq!(lines[0].content, "Submodule lib added at 2222222");
+        let lines = range_lines("lib", Some("1111111111"), Some("1111111111"));
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].content.contains("unchanged"));
+    }
+}
@@ -0,0 +1,207 @@
+//! Tag operations — list, create, delete and push tags, and find the commit
+//! range a release covers.
+
+use super::runner::run_git;
+use anyhow::{Result, bail};
+
+/// Kind of tag to create.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TagKind {
+    /// Plain ref to a commit (`git tag <name>`).
+    Lightweight,
+    /// Tag object with tagger and message (`git tag -a`).
+    Annotated,
+    /// Annotated tag signed with the configured GPG/SSH key (`git tag -s`).
+    Signed,
+}
+
+impl TagKind {
+    /// Whether this kind of tag carries a message.
+    pub fn needs_message(self) -> bool {
+        !matches!(self, TagKind::Lightweight)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TagKind::Lightweight => "lightweight",
+            TagKind::Annotated => "annotated",
+            TagKind::Signed => "signed",
+        }
+    }
+}
+
+/// A tag as reported by `git for-each-ref refs/tags`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tag {
+    pub name: String,
+    /// Short hash of the tagged commit.
+    pub target: String,
+    pub kind: TagKind,
+    /// Tag message subject for annotated tags, commit subject otherwise.
+    pub subject: String,
+    /// Relative creation date ("3 days ago").
+    pub date: String,
+}
+
+const TAG_FORMAT: &str = "%(refname:short)%1f%(objecttype)%1f%(objectname:short)%1f\
+                          %(*objectname:short)%1f%(contents:subject)%1f\
+                          %(creatordate:relative)%1f%(contents:signature)%1e";
+
+/// List tags, newest first.
+pub fn list() -> Result<Vec<Tag>> {
+    let format = format!("--format={}", TAG_FORMAT);
+    let output = run_git(&["for-each-ref", "--sort=-creatordate", &format, "refs/tags"])?;
+    Ok(parse_tags(&output))
+}
+
+/// Parse `for-each-ref` output produced with `TAG_FORMAT`.
+/// Records end with `\x1e` because signatures span several lines.
+fn parse_tags(output: &str) -> Vec<Tag> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let record = record.trim_start_matches('\n');
+            if record.is_empty() {
+                return None;
+            }
+            let parts: Vec<&str> = record.splitn(7, '\x1f').collect();
+            if parts.len() < 6 {
+                return None;
+            }
+            let annotated = parts[1] == "tag";
+            let signed = parts.get(6).is_some_and(|sig| !sig.trim().is_empty());
+            let kind = match (annotated, signed) {
+                (false, _) => TagKind::Lightweight,
+                (true, false) => TagKind::Annotated,
+                (true, true) => TagKind::Signed,
+            };
+            // Annotated tags point at a tag object; the commit is the peeled ref
+            let target = if annotated { parts[3] } else { parts[2] };
+            Some(Tag {
+                name: parts[0].to_string(),
+                target: target.to_string(),
+                kind,
+                subject: parts[4].to_string(),
+                date: parts[5].to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Create a tag on `target` (defaults to `HEAD`).
+/// Annotated and signed tags require a non-empty `message`.
+pub fn create(name: &str, kind: TagKind, message: &str, target: Option<&str>) -> Result<()> {
+    if kind.needs_message() && message.trim().is_empty() {
+        bail!("A {} tag needs a message", kind.label());
+    }
+    let mut args = vec!["tag"];
+    match kind {
+        TagKind::Lightweight => {}
+        TagKind::Annotated => args.extend(["-a", "-m", message]),
+        TagKind::Signed => args.extend(["-s", "-m", message]),
+    }
+    args.push(name);
+    if let Some(target) = target {
+        args.push(target);
+    }
+    run_git(&args)?;
+    Ok(())
+}
+
+/// Delete a local tag.
+pub fn delete(name: &str) -> Result<()> {
+    run_git(&["tag", "-d", name])?;
+    Ok(())
+}
+
+/// Push a single tag to `remote`.
+pub fn push(remote: &str, name: &str) -> Result<String> {
+    let refspec = format!("refs/tags/{}", name);
+    run_git(&["push", remote, &refspec])
+}
+
+/// Push every local tag to `remote`.
+pub fn push_all(remote: &str) -> Result<String> {
+    run_git(&["push", remote, "--tags"])
+}
+
+/// Most recent tag reachable from `rev`, if any.
+pub fn describe(rev: &str) -> Option<String> {
+    run_git(&["describe", "--tags", "--abbrev=0", rev])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Most recent tag reachable from `HEAD`.
+pub fn latest() -> Option<String> {
+    describe("HEAD")
+}
+
+/// Tag preceding `name` in its history — the start of the release range.
+pub fn previous(name: &str) -> Option<String> {
+    describe(&format!("{}^", name))
+}
+
+/// Range spec for the commits in `to` that are not in `from`.
+/// Without `from` the range covers the whole history up to `to`.
+pub fn range_spec(from: Option<&str>, to: &str) -> String {
+    match from {
+        Some(from) => format!("{}..{}", from, to),
+        None => to.to_string(),
+    }
+}
+
+/// Whether a tag name looks like a semver pre-release (`v1.2.0-rc.1`).
+pub fn is_prerelease(name: &str) -> bool {
+    let version = name.trim_start_matches(['v', 'V']);
+    version.starts_with(|c: char| c.is_ascii_digit()) && version.contains('-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tags() {
+        let output = "v1.1.0\x1ftag\x1f1111111\x1faaaaaaa\x1fRelease 1.1\x1f2 days ago\x1f\
+                      -----BEGIN PGP SIGNATURE-----\nabc\n-----END PGP SIGNATURE-----\n\x1e\n\
+                      v1.0.0\x1ftag\x1f2222222\x1fbbbbbbb\x1fRelease 1.0\x1f3 weeks ago\x1f\x1e\n\
+                      nightly\x1fcommit\x1fccccccc\x1f\x1fFix build\x1f5 weeks ago\x1f\x1e\n";
+        let tags = parse_tags(output);
+        assert_eq!(tags.len(), 3);
+        assert_eq!(tags[0].name, "v1.1.0");
+        assert_eq!(tags[0].kind, TagKind::Signed);
+        assert_eq!(tags[0].target, "aaaaaaa");
+        assert_eq!(tags[1].kind, TagKind::Annotated);
+        assert_eq!(tags[1].subject, "Release 1.0");
+        assert_eq!(tags[2].kind, TagKind::Lightweight);
+        assert_eq!(tags[2].target, "ccccccc");
+        assert_eq!(tags[2].date, "5 weeks ago");
+    }
+
+    #[test]
+    fn test_parse_tags_empty() {
+        assert!(parse_tags("").is_empty());
+        assert!(parse_tags("\n").is_empty());
+    }
+
+    #[test]
+    fn test_range_spec() {
+        assert_eq!(range_spec(Some("v1.0.0"), "HEAD"), "v1.0.0..HEAD");
+        assert_eq!(range_spec(None, "v1.0.0"), "v1.0.0");
+    }
+
+    #[test]
+    fn test_is_prerelease() {
+        assert!(is_prerelease("v1.2.0-rc.1"));
+        assert!(is_prerelease("2.0.0-beta"));
+        assert!(!is_prerelease("v1.2.0"));
+        assert!(!is_prerelease("release-2024"));
+    }
+
+    #[test]
+    fn test_create_requires_message() {
+        assert!(create("v1.0.0", TagKind::Annotated, "  ", None).is_err());
+    }
+}
@@ -0,0 +1,255 @@
+//! Worktree management — list, add, remove, prune, lock and unlock linked worktrees.
+
+use super::runner::run_git;
+use anyhow::{Result, bail};
+use std::path::{Path, PathBuf};
+
+/// A single worktree as reported by `git worktree list --porcelain`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Worktree {
+    pub path: PathBuf,
+    /// Checked-out commit (empty for a bare repository).
+    pub head: String,
+    /// Short branch name, `None` when detached or bare.
+    pub branch: Option<String>,
+    pub is_bare: bool,
+    pub is_detached: bool,
+    /// `Some(reason)` when locked; the reason may be empty.
+    pub locked: Option<String>,
+    /// `Some(reason)` when git considers the worktree prunable.
+    pub prunable: Option<String>,
+    /// The main worktree (always listed first).
+    pub is_main: bool,
+}
+
+impl Worktree {
+    /// Branch name, or the short HEAD for a detached worktree.
+    pub fn label(&self) -> String {
+        if let Some(ref branch) = self.branch {
+            branch.clone()
+        } else if self.is_bare {
+            "(bare)".to_string()
+        } else {
+            format!("({})", &self.head[..7.min(self.head.len())])
+        }
+    }
+}
+
+/// List all worktrees of the current repository, main worktree first.
+pub fn list() -> Result<Vec<Worktree>> {
+    let output = run_git(&["worktree", "list", "--porcelain"])?;
+    Ok(parse_porcelain(&output))
+}
+
+/// Parse `git worktree list --porcelain` output.
+///
+/// Records are separated by blank lines; each starts with `worktree <path>`
+/// followed by attribute lines (`HEAD`, `branch`, `bare`, `detached`,
+/// `locked [reason]`, `prunable [reason]`).
+pub fn parse_porcelain(output: &str) -> Vec<Worktree> {
+    let mut worktrees: Vec<Worktree> = Vec::new();
+
+    for line in output.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            worktrees.push(Worktree {
+                path: PathBuf::from(path),
+                is_main: worktrees.is_empty(),
+                ..Worktree::default()
+            });
+            continue;
+        }
+        let Some(current) = worktrees.last_mut() else {
+            continue;
+        };
+
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        match key {
+            "HEAD" => current.head = value.to_string(),
+            "branch" => {
+                let short = value.strip_prefix("refs/heads/").unwrap_or(value);
+                current.branch = Some(short.to_string());
+            }
+            "bare" => current.is_bare = true,
+            "detached" => current.is_detached = true,
+            "locked" => current.locked = Some(value.to_string()),
+            "prunable" => current.prunable = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    worktrees
+}
+
+/// Create a worktree at `path` checking out `branch`.
+/// With `new_branch`, the branch is created from HEAD first (`-b`).
+pub fn add(path: &str, branch: &str, new_branch: bool) -> Result<()> {
+    if path.trim().is_empty() {
+        bail!("Worktree path cannot be empty");
+    }
+    if new_branch {
+        run_git(&["worktree", "add", "-b", branch, path])?;
+    } else {
+        run_git(&["worktree", "add", path, branch])?;
+    }
+    Ok(())
+}
+
+/// Remove a linked worktree. `force` discards uncommitted changes.
+pub fn remove(path: &Path, force: bool) -> Result<()> {
+    let path = path.to_string_lossy();
+    if force {
+        run_git(&["worktree", "remove", "--force", &path])?;
+    } else {
+        run_git(&["worktree", "remove", &path])?;
+    }
+    Ok(())
+}
+
+/// Clean up administrative data for worktrees whose directories are gone.
+/// Returns the paths that were pruned. `--verbose` reports on stderr, which
+/// `run_git` drops, so the list is compared before and after instead.
+pub fn prune() -> Result<Vec<PathBuf>> {
+    let before = list()?;
+    run_git(&["worktree", "prune"])?;
+    Ok(pruned_paths(&before, &list()?))
+}
+
+/// Paths of the worktrees in `before` that are gone from `after`.
+pub fn pruned_paths(before: &[Worktree], after: &[Worktree]) -> Vec<PathBuf> {
+    before
+        .iter()
+        .filter(|wt| !after.iter().any(|a| a.path == wt.path))
+        .map(|wt| wt.path.clone())
+        .collect()
+}
+
+/// Lock a worktree so it is not pruned or removed.
+pub fn lock(path: &Path, reason: Option<&str>) -> Result<()> {
+    let path = path.to_string_lossy();
+    match reason.filter(|r| !r.trim().is_empty()) {
+        Some(reason) => run_git(&["worktree", "lock", "--reason", reason, &path])?,
+        None => run_git(&["worktree", "lock", &path])?,
+    };
+    Ok(())
+}
+
+/// Unlock a previously locked worktree.
+pub fn unlock(path: &Path) -> Result<()> {
+    run_git(&["worktree", "unlock", &path.to_string_lossy()])?;
+    Ok(())
+}
+
+/// Number of changed or untracked files in the worktree at `path`.
+pub fn dirty_count(path: &Path) -> Result<usize> {
+    let output = run_git(&[
+        "-C",
+        &path.to_string_lossy(),
+        "--no-optional-locks",
+        "status",
+        "--porcelain",
+    ])?;
+    Ok(output.lines().filter(|l| !l.trim().is_empty()).count())
+}
+
+/// Default location for a new worktree: a sibling of the main worktree
+/// named `<repo>-<branch>`, with `/` in the branch replaced by `-`.
+pub fn default_path(main_root: &Path, branch: &str) -> PathBuf {
+    let repo_name = main_root
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "repo".to_string());
+    let dir = format!("{}-{}", repo_name, branch.replace('/', "-"));
+    main_root
+        .parent()
+        .map(|p| p.join(&dir))
+        .unwrap_or_else(|| PathBuf::from(dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain() {
+        let output = "worktree /src/zit\n\
+                      HEAD 1111111111111111111111111111111111111111\n\
+                      branch refs/heads/main\n\
+                      \n\
+                      worktree /src/zit-hotfix\n\
+                      HEAD 2222222222222222222222222222222222222222\n\
+                      branch refs/heads/hotfix/login\n\
+                      locked on usb drive\n\
+                      \n\
+                      worktree /tmp/gone\n\
+                      HEAD 3333333333333333333333333333333333333333\n\
+                      detached\n\
+                      prunable gitdir file points to non-existent location\n";
+        let wts = parse_porcelain(output);
+        assert_eq!(wts.len(), 3);
+
+        assert!(wts[0].is_main);
+        assert_eq!(wts[0].path, PathBuf::from("/src/zit"));
+        assert_eq!(wts[0].branch.as_deref(), Some("main"));
+        assert!(wts[0].locked.is_none());
+
+        assert!(!wts[1].is_main);
+        assert_eq!(wts[1].branch.as_deref(), Some("hotfix/login"));
+        assert_eq!(wts[1].locked.as_deref(), Some("on usb drive"));
+
+        assert!(wts[2].is_detached);
+        assert!(wts[2].branch.is_none());
+        assert!(wts[2].prunable.is_some());
+        assert_eq!(wts[2].label(), "(3333333)");
+    }
+
+    #[test]
+    fn test_parse_porcelain_bare_and_bare_lock() {
+        let output = "worktree /srv/repo.git\nbare\n\nworktree /srv/wt\nHEAD abc\nbranch refs/heads/dev\nlocked\n";
+        let wts = parse_porcelain(output);
+        assert!(wts[0].is_bare);
+        assert_eq!(wts[0].label(), "(bare)");
+        assert_eq!(wts[1].locked.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_pruned_paths() {
+        let wt = |path: &str| Worktree {
+            path: PathBuf::from(path),
+            ..Worktree::default()
+        };
+        let before = [wt("/repo"), wt("/repo-feature"), wt("/repo-fix")];
+        let after = [wt("/repo"), wt("/repo-fix")];
+        assert_eq!(
+            pruned_paths(&before, &after),
+            vec![PathBuf::from("/repo-feature")]
+        );
+        assert!(pruned_paths(&after, &after).is_empty());
+    }
+
+    #[test]
+    fn test_parse_porcelain_empty() {
+        assert!(parse_porcelain("").is_empty());
+    }
+
+    #[test]
+    fn test_dirty_count() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let status = std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+        assert_eq!(dirty_count(dir.path()).unwrap(), 0);
+
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "b").unwrap();
+        assert_eq!(dirty_count(dir.path()).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_default_path() {
+        let path = default_path(Path::new("/src/zit"), "feature/login");
+        assert_eq!(path, PathBuf::from("/src/zit-feature-login"));
+    }
+}
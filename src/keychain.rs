@@ -63,6 +63,28 @@ pub fn delete_github_token() {
     delete_secret(KEY_GITHUB_TOKEN);
 }
 
+// ── GitHub Enterprise Tokens ────────────────────────────────────────
+
+/// Keychain key of the token for a GitHub Enterprise host.
+fn github_host_key(host: &str) -> String {
+    format!("{}:{}", KEY_GITHUB_TOKEN, host.to_lowercase())
+}
+
+/// Store the token issued by the GitHub Enterprise instance at `host`.
+pub fn store_github_host_token(host: &str, token: &str) -> Result<()> {
+    set_secret(&github_host_key(host), token)
+}
+
+/// Retrieve the token for the GitHub Enterprise instance at `host`.
+pub fn get_github_host_token(host: &str) -> Option<String> {
+    get_secret(&github_host_key(host))
+}
+
+/// Delete the stored token for the GitHub Enterprise instance at `host`.
+pub fn delete_github_host_token(host: &str) {
+    delete_secret(&github_host_key(host));
+}
+
 // ── GitHub PAT ──────────────────────────────────────────────────────
 
 /// Store a GitHub Personal Access Token securely.
@@ -99,43 +121,47 @@ pub fn delete_ai_api_key() {
 
 // ── Bulk Operations ─────────────────────────────────────────────────
 
-/// Delete all zit secrets from the keychain (used on logout).
-pub fn clear_all() {
-    delete_github_token();
-    delete_github_pat();
-    delete_ai_api_key();
-}
-
 /// Migrate plaintext tokens from config to keychain.
 /// Returns the number of secrets migrated.
 pub fn migrate_from_config(config: &mut crate::config::Config) -> u32 {
     let mut count = 0;
 
     // Migrate GitHub OAuth token
-    if let Some(ref token) = config.github.oauth_token {
-        if store_github_token(token).is_ok() {
-            config.github.oauth_token = None;
-            count += 1;
-            log::info!("Migrated GitHub OAuth token to keychain");
-        }
+    if let Some(ref token) = config.github.oauth_token
+        && store_github_token(token).is_ok()
+    {
+        config.github.oauth_token = None;
+        count += 1;
+        log::info!("Migrated GitHub OAuth token to keychain");
     }
 
     // Migrate GitHub PAT
-    if let Some(ref pat) = config.github.pat {
-        if store_github_pat(pat).is_ok() {
-            config.github.pat = None;
+    if let Some(ref pat) = config.github.pat
+        && store_github_pat(pat).is_ok()
+    {
+        config.github.pat = None;
+        count += 1;
+        log::info!("Migrated GitHub PAT to keychain");
+    }
+
+    // Migrate GitHub Enterprise tokens
+    for entry in &mut config.github.hosts {
+        if let Some(ref token) = entry.token
+            && store_github_host_token(&entry.host, token).is_ok()
+        {
+            entry.token = None;
             count += 1;
-            log::info!("Migrated GitHub PAT to keychain");
+            log::info!("Migrated {} token to keychain", entry.host);
         }
     }
 
     // Migrate AI API key
-    if let Some(ref key) = config.ai.api_key {
-        if store_ai_api_key(key).is_ok() {
-            config.ai.api_key = None;
-            count += 1;
-            log::info!("Migrated AI API key to keychain");
-        }
+    if let Some(ref key) = config.ai.api_key
+        && store_ai_api_key(key).is_ok()
+    {
+        config.ai.api_key = None;
+        count += 1;
+        log::info!("Migrated AI API key to keychain");
     }
 
     count
@@ -151,5 +177,9 @@ mod tests {
         assert_eq!(KEY_GITHUB_TOKEN, "github-oauth-token");
         assert_eq!(KEY_GITHUB_PAT, "github-pat");
         assert_eq!(KEY_AI_API_KEY, "ai-api-key");
+        assert_eq!(
+            github_host_key("GitHub.Example.com"),
+            "github-oauth-token:github.example.com"
+        );
     }
 }
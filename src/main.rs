@@ -1,23 +1,25 @@
 mod ai;
 mod app;
+mod cli;
 mod config;
 mod event;
 mod git;
 mod keychain;
 mod ui;
+mod watcher;
 
 use anyhow::{Context, Result};
 use crossterm::{
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{
+    Frame, Terminal,
     backend::CrosstermBackend,
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
-    Frame, Terminal,
 };
 use std::io;
 
@@ -34,6 +36,7 @@ fn print_help() {
     println!();
     println!("USAGE:");
     println!("    zit [OPTIONS]");
+    println!("    zit [OPTIONS] <SUBCOMMAND>");
     println!();
     println!("OPTIONS:");
     println!("    -h, --help       Print this help message");
@@ -41,6 +44,23 @@ fn print_help() {
     println!("    --verbose        Enable verbose logging (ZIT_LOG=debug)");
     println!("    --no-ai          Disable AI features for this session");
     println!();
+    println!("SUBCOMMANDS:");
+    println!("    status [--json]              Print repository status");
+    println!("    commit -m <msg> | --ai       Commit staged changes (--force skips secret scan)");
+    println!("    scan                         Scan staged files for secrets (exit 1 on findings)");
+    println!(
+        "    scan --format json|sarif     Print findings as JSON or SARIF 2.1.0 (code scanning)"
+    );
+    println!(
+        "    scan --history [<range>]     Find secrets introduced anywhere in history (all refs by default)"
+    );
+    println!(
+        "    hooks install | uninstall    Add or remove git pre-commit/pre-push hooks that scan for secrets"
+    );
+    println!("    pr list [--state <s>]        List pull requests (open, closed, all)");
+    println!("    agent [--yes] \"<task>\"       Run the AI agent non-interactively");
+    println!("    config                       Show effective settings and where each came from");
+    println!();
     println!("ENVIRONMENT:");
     println!("    ZIT_LOG          Set log level (error, warn, info, debug, trace)");
     println!("    ZIT_AI_ENDPOINT  AI mentor API endpoint URL");
@@ -50,6 +70,8 @@ fn print_help() {
     println!("    s  Staging     c  Commit      b  Branches");
     println!("    l  Timeline    t  Time Travel  r  Reflog");
     println!("    g  GitHub      a  AI Mentor    x  Stash");
+    println!("    W  Worktrees   S  Submodules   T  Tags");
+    println!("    R  Rebase      H  Secret Audit L  Git LFS");
     println!("    ?  Help");
 }
 
@@ -57,7 +79,9 @@ fn main() -> Result<()> {
     // Parse CLI flags
     let args: Vec<String> = std::env::args().skip(1).collect();
     let mut no_ai = false;
-    for arg in &args {
+    let mut subcommand_args = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
         match arg.as_str() {
             "-h" | "--help" => {
                 print_help();
@@ -73,6 +97,12 @@ fn main() -> Result<()> {
             "--no-ai" => {
                 no_ai = true;
             }
+            other if !other.starts_with('-') => {
+                // Everything from the first positional argument belongs to the subcommand
+                subcommand_args.push(arg.clone());
+                subcommand_args.extend(iter.by_ref().cloned());
+                break;
+            }
             other => {
                 eprintln!("Unknown option: {}", other);
                 eprintln!("Run 'zit --help' for usage.");
@@ -81,6 +111,19 @@ fn main() -> Result<()> {
         }
     }
 
+    let subcommand = if subcommand_args.is_empty() {
+        None
+    } else {
+        match cli::parse(&subcommand_args) {
+            Ok(cmd) => Some(cmd),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                eprintln!("Run 'zit --help' for usage.");
+                std::process::exit(2);
+            }
+        }
+    };
+
     // Initialize logging
     env_logger::Builder::from_env(env_logger::Env::new().filter("ZIT_LOG"))
         .format_timestamp(None)
@@ -107,6 +150,13 @@ fn main() -> Result<()> {
     let mut config = config::Config::load().unwrap_or_default();
     log::debug!("Config loaded from {:?}", config::Config::path());
 
+    // Merge repo-local overrides (.zit.toml, .git/zit.toml)
+    if let Ok(repo) = git::repo::current()
+        && let Err(e) = config.apply_repo_layers(repo.root(), repo.common_dir())
+    {
+        eprintln!("Warning: {:#}", e);
+    }
+
     // Apply --no-ai flag
     if no_ai {
         config.ai.enabled = false;
@@ -123,6 +173,17 @@ fn main() -> Result<()> {
         }
     }
 
+    // Non-interactive subcommand: run it and exit without starting the TUI
+    if let Some(cmd) = subcommand {
+        match cli::run(cmd, &config) {
+            Ok(code) => std::process::exit(code),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
     let tick_rate = config.general.tick_rate_ms;
 
     // Install panic hook that restores the terminal before printing the panic
@@ -151,10 +212,11 @@ fn main() -> Result<()> {
 
     // Create app and event handler
     let mut app = App::new(config);
-    let events = EventHandler::new(tick_rate);
+    let mut events = EventHandler::new(tick_rate);
+    events.watch_repo();
 
     // Main loop
-    let res = run_app(&mut terminal, &mut app, &events);
+    let res = run_app(&mut terminal, &mut app, &mut events);
 
     // Restore terminal
     disable_raw_mode()?;
@@ -179,7 +241,7 @@ fn main() -> Result<()> {
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
-    events: &EventHandler,
+    events: &mut EventHandler,
 ) -> Result<()> {
     loop {
         // Draw
@@ -196,8 +258,11 @@ fn run_app(
                 app.poll_ai_result();
                 app.poll_agent_command();
                 app.tick_animations();
-                // Auto-refresh on tick for the current view
-                app.refresh();
+                ui::secret_audit::tick(app);
+                // Without a file watcher, fall back to refreshing on every tick
+                if !events.is_watching() {
+                    app.refresh();
+                }
                 // Poll GitHub Device Flow if active
                 if app.view == View::GitHub {
                     ui::github::tick_device_auth(app);
@@ -205,6 +270,9 @@ fn run_app(
                     ui::github::tick_actions_state(app);
                 }
             }
+            AppEvent::RepoChanged => {
+                app.refresh();
+            }
             AppEvent::Mouse(mouse) => {
                 app.poll_ai_result();
                 app.poll_agent_command();
@@ -218,6 +286,11 @@ fn run_app(
         if !app.running {
             return Ok(());
         }
+
+        // Jumped into another worktree — watch that one instead
+        if std::mem::take(&mut app.repo_switched) {
+            events.watch_repo();
+        }
     }
 }
 
@@ -286,6 +359,30 @@ fn draw(f: &mut Frame, app: &mut App) {
         View::CherryPick => {
             ui::cherry_pick::render(f, area, &mut app.cherry_pick_state);
         }
+        View::Worktrees => {
+            ui::worktrees::render(f, area, &mut app.worktrees_state);
+        }
+        View::Submodules => {
+            ui::submodules::render(f, area, &mut app.submodules_state);
+        }
+        View::Tags => {
+            ui::tags::render(f, area, &mut app.tags_state);
+        }
+        View::Rebase => {
+            ui::rebase::render(f, area, &mut app.rebase_state);
+        }
+        View::FileHistory => {
+            ui::file_history::render(f, area, &mut app.file_history_state);
+        }
+        View::Blame => {
+            ui::blame::render(f, area, &mut app.blame_state);
+        }
+        View::SecretAudit => {
+            ui::secret_audit::render(f, area, &mut app.secret_audit_state);
+        }
+        View::Lfs => {
+            ui::lfs::render(f, area, &mut app.lfs_state);
+        }
         View::Agent => {
             let ai_available = app.ai_client.is_some();
             let loading = app.ai_loading;
@@ -423,10 +520,17 @@ fn draw(f: &mut Frame, app: &mut App) {
                     Style::default().fg(Color::Gray)
                 };
 
-                lines.push(Line::from(vec![
+                let mut spans = vec![
                     Span::styled(prefix, Style::default().fg(Color::Red)),
                     Span::styled(&finding.rule_name, style),
-                ]));
+                ];
+                if finding.confidence < git::secrets::Confidence::High {
+                    spans.push(Span::styled(
+                        format!("  {} confidence", finding.confidence),
+                        Style::default().fg(Color::Yellow),
+                    ));
+                }
+                lines.push(Line::from(spans));
                 lines.push(Line::from(Span::styled(
                     format!(
                         "       {}:{} — {}",
@@ -444,6 +548,8 @@ fn draw(f: &mut Frame, app: &mut App) {
                 Span::raw(" Force  "),
                 Span::styled("a", Style::default().fg(Color::Yellow)),
                 Span::raw(" Allowlist  "),
+                Span::styled("b", Style::default().fg(Color::Yellow)),
+                Span::raw(" Baseline  "),
                 Span::styled("j/k", Style::default().fg(Color::Cyan)),
                 Span::raw(" Navigate"),
             ]));
@@ -462,6 +568,79 @@ fn draw(f: &mut Frame, app: &mut App) {
 
             f.render_widget(popup, popup_area);
         }
+        Popup::PolicyWarning { findings, selected } => {
+            let popup_area = ui::utils::centered_rect(70, 60, area);
+            f.render_widget(Clear, popup_area);
+
+            let mut lines = vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    "  ⚠ These files shouldn't be committed as they are:",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Line::from(""),
+            ];
+
+            for (i, finding) in findings.iter().enumerate() {
+                let is_sel = i == *selected;
+                let prefix = if is_sel { "  ▶ " } else { "    " };
+                let style = if is_sel {
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Gray)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(prefix, Style::default().fg(Color::Yellow)),
+                    Span::styled(&finding.path, style),
+                ]));
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "       {} — suggested: {}",
+                        finding.describe(),
+                        finding.suggested_action().label()
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled(" Esc", Style::default().fg(Color::Green)),
+                Span::raw(" Abort  "),
+                Span::styled("Enter", Style::default().fg(Color::Cyan)),
+                Span::raw(" Suggested  "),
+                Span::styled("u", Style::default().fg(Color::Yellow)),
+                Span::raw(" Unstage  "),
+                Span::styled("i", Style::default().fg(Color::Yellow)),
+                Span::raw(" Ignore  "),
+                Span::styled("l", Style::default().fg(Color::Yellow)),
+                Span::raw(" LFS  "),
+                Span::styled("f", Style::default().fg(Color::Red)),
+                Span::raw(" Force  "),
+                Span::styled("j/k", Style::default().fg(Color::Cyan)),
+                Span::raw(" Navigate"),
+            ]));
+
+            let popup = Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(Span::styled(
+                            " ⚠ Commit Policy ",
+                            Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD),
+                        ))
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Yellow)),
+                )
+                .wrap(Wrap { trim: false });
+
+            f.render_widget(popup, popup_area);
+        }
         Popup::None => {}
     }
 }
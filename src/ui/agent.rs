@@ -1,10 +1,10 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
-    Frame,
 };
 use std::sync::mpsc;
 use std::time::SystemTime;
@@ -206,6 +206,39 @@ pub fn is_destructive_command(args: &[String]) -> bool {
         || matches!(sub, "filter-branch" | "filter-repo")
 }
 
+// ─── Tool execution ────────────────────────────────────────────
+
+/// Run an agent tool-use command (`git ...` or a file-reading command).
+/// Returns the combined output and whether the command succeeded.
+pub fn run_tool_command(args: &[String]) -> (String, bool) {
+    let Some((cmd, cmd_args)) = args.split_first() else {
+        return ("empty command".to_string(), false);
+    };
+
+    if cmd == "git" {
+        let args_refs: Vec<&str> = cmd_args.iter().map(|s| s.as_str()).collect();
+        return match crate::git::run_git(&args_refs) {
+            Ok(out) => (out, true),
+            Err(e) => (e.to_string(), false),
+        };
+    }
+
+    // File-reading commands: cat, head, tail, grep, find, ls, wc
+    match std::process::Command::new(cmd).args(cmd_args).output() {
+        Ok(out) => {
+            let stdout = String::from_utf8_lossy(&out.stdout).to_string();
+            let stderr = String::from_utf8_lossy(&out.stderr).to_string();
+            let combined = if !stderr.is_empty() {
+                format!("{}\n{}", stdout, stderr)
+            } else {
+                stdout
+            };
+            (combined, out.status.success())
+        }
+        Err(e) => (e.to_string(), false),
+    }
+}
+
 // ─── Rendering ─────────────────────────────────────────────────
 
 pub fn render(
@@ -871,15 +904,14 @@ fn handle_input_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<
                 app.agent_state.input_active = false;
             }
         }
-        KeyCode::Up => {
-            if app.agent_state.input.is_empty() && !app.agent_state.input_history.is_empty() {
-                if app.agent_state.history_index < app.agent_state.input_history.len() {
-                    let idx =
-                        app.agent_state.input_history.len() - 1 - app.agent_state.history_index;
-                    app.agent_state.input = app.agent_state.input_history[idx].clone();
-                    app.agent_state.history_index += 1;
-                }
-            }
+        KeyCode::Up
+            if app.agent_state.input.is_empty()
+                && !app.agent_state.input_history.is_empty()
+                && app.agent_state.history_index < app.agent_state.input_history.len() =>
+        {
+            let idx = app.agent_state.input_history.len() - 1 - app.agent_state.history_index;
+            app.agent_state.input = app.agent_state.input_history[idx].clone();
+            app.agent_state.history_index += 1;
         }
         KeyCode::Down => {
             if app.agent_state.history_index > 0 {
@@ -915,10 +947,8 @@ fn handle_input_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<
 
             app.start_agent_chat();
         }
-        KeyCode::Char(c) => {
-            if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                app.agent_state.input.push(c);
-            }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.agent_state.input.push(c);
         }
         KeyCode::Backspace => {
             app.agent_state.input.pop();
@@ -1,10 +1,10 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
-    Frame,
 };
 use serde::{Deserialize, Serialize};
 
@@ -503,15 +503,13 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
 fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
     match key.code {
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.ai_mentor_state.selected > 0 {
-                app.ai_mentor_state.selected -= 1;
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.ai_mentor_state.selected > 0 => {
+            app.ai_mentor_state.selected -= 1;
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.ai_mentor_state.selected + 1 < MENU_ITEMS.len() {
-                app.ai_mentor_state.selected += 1;
-            }
+        KeyCode::Down | KeyCode::Char('j')
+            if app.ai_mentor_state.selected + 1 < MENU_ITEMS.len() =>
+        {
+            app.ai_mentor_state.selected += 1;
         }
         KeyCode::Enter => {
             if app.ai_client.is_none()
@@ -598,10 +596,8 @@ fn handle_input_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<
                 app.start_ai_ask(query);
             }
         }
-        KeyCode::Char(c) => {
-            if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                app.ai_mentor_state.input.push(c);
-            }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.ai_mentor_state.input.push(c);
         }
         KeyCode::Backspace => {
             app.ai_mentor_state.input.pop();
@@ -641,29 +637,25 @@ fn handle_history_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Resul
             app.ai_mentor_state.mode = AiMode::Menu;
             app.ai_mentor_state.history_scroll = 0;
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.ai_mentor_state.history_selected > 0 {
-                app.ai_mentor_state.history_selected -= 1;
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.ai_mentor_state.history_selected > 0 => {
+            app.ai_mentor_state.history_selected -= 1;
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if history_len > 0 && app.ai_mentor_state.history_selected + 1 < history_len {
-                app.ai_mentor_state.history_selected += 1;
-            }
+        KeyCode::Down | KeyCode::Char('j')
+            if history_len > 0 && app.ai_mentor_state.history_selected + 1 < history_len =>
+        {
+            app.ai_mentor_state.history_selected += 1;
         }
-        KeyCode::Enter => {
-            // View selected history entry in the result view
-            if history_len > 0 {
-                let idx = history_len.saturating_sub(1) - app.ai_mentor_state.history_selected;
-                if let Some(entry) = app.ai_mentor_state.history.get(idx) {
-                    app.ai_mentor_state.result_text = format!(
-                        "── {} ──\n[{}]\n\n{}",
-                        entry.query, entry.timestamp, entry.response
-                    );
-                    app.ai_mentor_state.result_scroll = 0;
-                    app.ai_mentor_state.last_action = Some("History".to_string());
-                    app.ai_mentor_state.mode = AiMode::Result;
-                }
+        // View selected history entry in the result view
+        KeyCode::Enter if history_len > 0 => {
+            let idx = history_len.saturating_sub(1) - app.ai_mentor_state.history_selected;
+            if let Some(entry) = app.ai_mentor_state.history.get(idx) {
+                app.ai_mentor_state.result_text = format!(
+                    "── {} ──\n[{}]\n\n{}",
+                    entry.query, entry.timestamp, entry.response
+                );
+                app.ai_mentor_state.result_scroll = 0;
+                app.ai_mentor_state.last_action = Some("History".to_string());
+                app.ai_mentor_state.mode = AiMode::Result;
             }
         }
         KeyCode::PageDown => {
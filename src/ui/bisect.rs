@@ -2,11 +2,11 @@
 
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
-    Frame,
 };
 
 use crate::git;
@@ -412,17 +412,15 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
         match state.mode {
             BisectMode::PickBad | BisectMode::PickGood => {
                 match key.code {
-                    KeyCode::Up | KeyCode::Char('k') => {
-                        if state.selected > 0 {
-                            state.selected -= 1;
-                            state.list_state.select(Some(state.selected));
-                        }
+                    KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+                        state.selected -= 1;
+                        state.list_state.select(Some(state.selected));
                     }
-                    KeyCode::Down | KeyCode::Char('j') => {
-                        if state.selected + 1 < state.commits.len() {
-                            state.selected += 1;
-                            state.list_state.select(Some(state.selected));
-                        }
+                    KeyCode::Down | KeyCode::Char('j')
+                        if state.selected + 1 < state.commits.len() =>
+                    {
+                        state.selected += 1;
+                        state.list_state.select(Some(state.selected));
                     }
                     KeyCode::Enter => {
                         if let Some((hash, _)) = state.commits.get(state.selected) {
@@ -465,14 +463,12 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                             }
                         }
                     }
-                    KeyCode::Esc => {
-                        if state.mode == BisectMode::PickGood {
-                            // Go back to picking bad commit
-                            state.mode = BisectMode::PickBad;
-                            state.bad_commit = None;
-                            state.selected = 0;
-                            state.list_state.select(Some(0));
-                        }
+                    KeyCode::Esc if state.mode == BisectMode::PickGood => {
+                        // Go back to picking bad commit
+                        state.mode = BisectMode::PickBad;
+                        state.bad_commit = None;
+                        state.selected = 0;
+                        state.list_state.select(Some(0));
                     }
                     _ => {}
                 }
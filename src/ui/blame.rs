@@ -0,0 +1,561 @@
+//! Blame view — each line of a file with the commit that last changed it,
+//! grouped by commit and coloured by author and age.
+
+use anyhow::{Result, bail};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use std::ops::Range;
+
+use crate::app::View;
+use crate::git;
+use crate::git::blame::{Blame, BlameCommit};
+use crate::ui::syntax;
+
+/// Width of the hash/author/age gutter, in columns.
+const GUTTER_WIDTH: usize = 30;
+
+/// Colours for `git::blame::age_bucket`, newest first.
+const AGE_COLORS: [Color; 5] = [
+    Color::LightGreen,
+    Color::Green,
+    Color::Yellow,
+    Color::LightRed,
+    Color::DarkGray,
+];
+
+const AUTHOR_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Blue,
+    Color::LightYellow,
+    Color::LightCyan,
+    Color::LightMagenta,
+];
+
+/// A blamed revision the view can step back to.
+struct Revision {
+    path: String,
+    rev: Option<String>,
+    selected: usize,
+}
+
+#[derive(Default)]
+pub struct BlameState {
+    pub blame: Blame,
+    groups: Vec<Range<usize>>,
+    /// Selected line (0-based index into `blame.lines`).
+    pub selected: usize,
+    scroll: usize,
+    /// Revisions visited with `p`, popped with Backspace.
+    history: Vec<Revision>,
+    /// AI explanation as (commit hash, text).
+    pub explanation: Option<(String, String)>,
+    pub explain_loading: bool,
+    /// View to return to on Esc.
+    pub origin: Option<View>,
+}
+
+impl BlameState {
+    /// Blame `path` at `rev` (working tree when `None`), starting fresh.
+    pub fn open(&mut self, path: &str, rev: Option<&str>) -> Result<()> {
+        self.load(path, rev)?;
+        self.history.clear();
+        self.selected = 0;
+        self.scroll = 0;
+        Ok(())
+    }
+
+    fn load(&mut self, path: &str, rev: Option<&str>) -> Result<()> {
+        let blame = git::blame::blame(path, rev)?;
+        if blame.lines.is_empty() {
+            bail!("'{}' is empty", path);
+        }
+        self.groups = blame.groups();
+        self.blame = blame;
+        self.explanation = None;
+        Ok(())
+    }
+
+    pub fn refresh(&mut self) {
+        if self.blame.path.is_empty() {
+            return;
+        }
+        let (path, rev) = (self.blame.path.clone(), self.blame.rev.clone());
+        if self.load(&path, rev.as_deref()).is_ok() {
+            self.selected = self.selected.min(self.blame.lines.len() - 1);
+        }
+    }
+
+    pub fn selected_commit(&self) -> Option<&BlameCommit> {
+        self.blame.commit_for(self.selected)
+    }
+
+    fn current_group(&self) -> usize {
+        self.groups
+            .iter()
+            .position(|g| g.contains(&self.selected))
+            .unwrap_or(0)
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        let last = self.blame.lines.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    /// Move to the first line of the next (`forward`) or previous commit group.
+    fn jump_group(&mut self, forward: bool) {
+        let current = self.current_group();
+        let target = if forward {
+            current + 1
+        } else {
+            current.saturating_sub(1)
+        };
+        if let Some(group) = self.groups.get(target) {
+            self.selected = group.start;
+        }
+    }
+
+    /// Re-blame the file at the parent of the selected line's commit, to see
+    /// what the line looked like before that change.
+    fn blame_parent(&mut self) -> Result<()> {
+        let Some(commit) = self.selected_commit() else {
+            return Ok(());
+        };
+        if commit.is_uncommitted() {
+            bail!("line is not committed yet");
+        }
+        let Some((parent, parent_path)) = commit.previous.clone() else {
+            bail!(
+                "{} introduced this file — nothing earlier",
+                commit.short_hash()
+            );
+        };
+        let orig_line = self.blame.lines[self.selected].orig_line as usize;
+
+        let revision = Revision {
+            path: self.blame.path.clone(),
+            rev: self.blame.rev.clone(),
+            selected: self.selected,
+        };
+        self.load(&parent_path, Some(&parent))?;
+        self.history.push(revision);
+        // The line's position in the commit is the closest match in its parent
+        self.selected = orig_line.saturating_sub(1).min(self.blame.lines.len() - 1);
+        Ok(())
+    }
+
+    /// Return to the revision blamed before the last `blame_parent`.
+    fn back(&mut self) -> Result<bool> {
+        let Some(revision) = self.history.pop() else {
+            return Ok(false);
+        };
+        self.load(&revision.path, revision.rev.as_deref())?;
+        self.selected = revision.selected.min(self.blame.lines.len() - 1);
+        Ok(true)
+    }
+
+    /// Keep the selected line inside a viewport of `height` rows.
+    fn scroll_into_view(&mut self, height: usize) {
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if height > 0 && self.selected >= self.scroll + height {
+            self.scroll = self.selected + 1 - height;
+        }
+    }
+}
+
+fn now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// Compact age such as `5m`, `3d`, `4mo` or `2y`.
+fn short_age(time: i64, now: i64) -> String {
+    let secs = now.saturating_sub(time).max(0);
+    match secs {
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h", s / 3600),
+        s if s < 30 * 86_400 => format!("{}d", s / 86_400),
+        s if s < 365 * 86_400 => format!("{}mo", s / (30 * 86_400)),
+        s => format!("{}y", s / (365 * 86_400)),
+    }
+}
+
+/// Stable colour for an author name.
+fn author_color(author: &str) -> Color {
+    let hash = author.bytes().fold(0usize, |acc, b| {
+        acc.wrapping_mul(31).wrapping_add(b as usize)
+    });
+    AUTHOR_COLORS[hash % AUTHOR_COLORS.len()]
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        format!("{:<width$}", text)
+    } else {
+        let cut: String = text.chars().take(width - 1).collect();
+        format!("{}…", cut)
+    }
+}
+
+fn gutter_spans(commit: Option<&BlameCommit>, now: i64) -> Vec<Span<'static>> {
+    let Some(commit) = commit else {
+        return vec![Span::raw(" ".repeat(GUTTER_WIDTH))];
+    };
+    if commit.is_uncommitted() {
+        return vec![Span::styled(
+            truncate("(not committed)", GUTTER_WIDTH),
+            Style::default().fg(Color::DarkGray),
+        )];
+    }
+    let age_color = AGE_COLORS[git::blame::age_bucket(commit.author_time, now)];
+    vec![
+        Span::styled(
+            format!("{} ", commit.short_hash()),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::styled(
+            truncate(&commit.author, 16),
+            Style::default().fg(author_color(&commit.author)),
+        ),
+        Span::styled(
+            format!(" {:>5}", short_age(commit.author_time, now)),
+            Style::default().fg(age_color),
+        ),
+    ]
+}
+
+fn content_spans(lang: Option<&syntax::Language>, text: &str) -> Vec<Span<'static>> {
+    let text = text.replace('\t', "    ");
+    match lang {
+        Some(lang) => syntax::tokenize(lang, &text)
+            .into_iter()
+            .map(|(range, kind)| Span::styled(text[range].to_string(), kind.style()))
+            .collect(),
+        None => vec![Span::raw(text)],
+    }
+}
+
+pub fn render(f: &mut Frame, area: Rect, state: &mut BlameState) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+        .split(area);
+
+    render_lines(f, chunks[0], state);
+    render_commit(f, chunks[1], state);
+}
+
+fn render_lines(f: &mut Frame, area: Rect, state: &mut BlameState) {
+    let height = area.height.saturating_sub(2) as usize;
+    state.scroll_into_view(height);
+
+    let now = now();
+    let lang = syntax::language_for(&state.blame.path);
+    let number_width = state.blame.lines.len().to_string().len();
+    let current = state.groups.get(state.current_group()).cloned();
+
+    let lines: Vec<Line> = state
+        .blame
+        .lines
+        .iter()
+        .enumerate()
+        .skip(state.scroll)
+        .take(height)
+        .map(|(i, line)| {
+            let first_in_group = state.groups.iter().any(|g| g.start == i);
+            let in_current = current.as_ref().is_some_and(|g| g.contains(&i));
+            let mut spans = if first_in_group {
+                gutter_spans(state.blame.commits.get(&line.hash), now)
+            } else {
+                gutter_spans(None, now)
+            };
+            spans.push(Span::styled(
+                if in_current { " ┃" } else { " │" },
+                Style::default().fg(if in_current {
+                    Color::Magenta
+                } else {
+                    Color::DarkGray
+                }),
+            ));
+            spans.push(Span::styled(
+                format!("{:>width$} ", line.final_line, width = number_width),
+                Style::default().fg(Color::DarkGray),
+            ));
+            spans.extend(content_spans(lang, &line.content));
+            let mut row = Line::from(spans);
+            if i == state.selected {
+                row = row.style(Style::default().bg(Color::DarkGray));
+            }
+            row
+        })
+        .collect();
+
+    let rev = state.blame.rev.as_deref().map_or_else(
+        || "working tree".to_string(),
+        |r| r[..r.len().min(7)].to_string(),
+    );
+    let depth = if state.history.is_empty() {
+        String::new()
+    } else {
+        format!(" ↶{}", state.history.len())
+    };
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" Blame: {} @ {}{} ", state.blame.path, rev, depth),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_commit(f: &mut Frame, area: Rect, state: &BlameState) {
+    let label = Style::default().fg(Color::DarkGray);
+    let mut lines: Vec<Line> = Vec::new();
+
+    match state.selected_commit() {
+        Some(commit) if commit.is_uncommitted() => {
+            lines.push(Line::from(Span::styled(
+                "Not committed yet",
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+        Some(commit) => {
+            let now = now();
+            lines.push(Line::from(vec![
+                Span::styled("Commit  ", label),
+                Span::styled(commit.hash.clone(), Style::default().fg(Color::Yellow)),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("Author  ", label),
+                Span::styled(
+                    format!("{} {}", commit.author, commit.author_mail),
+                    Style::default().fg(author_color(&commit.author)),
+                ),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("Age     ", label),
+                Span::styled(
+                    format!("{} ago", short_age(commit.author_time, now)),
+                    Style::default()
+                        .fg(AGE_COLORS[git::blame::age_bucket(commit.author_time, now)]),
+                ),
+            ]));
+            if commit.filename != state.blame.path && !commit.filename.is_empty() {
+                lines.push(Line::from(vec![
+                    Span::styled("Path    ", label),
+                    Span::raw(commit.filename.clone()),
+                ]));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                commit.summary.clone(),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            lines.push(Line::from(""));
+            let parent = match &commit.previous {
+                Some((hash, _)) => format!("p: blame parent {}", &hash[..hash.len().min(7)]),
+                None => "root of this file's history".to_string(),
+            };
+            lines.push(Line::from(Span::styled(parent, label)));
+            lines.push(Line::from(""));
+
+            match &state.explanation {
+                _ if state.explain_loading => lines.push(Line::from(Span::styled(
+                    "⏳ Asking AI why this changed...",
+                    Style::default().fg(Color::Yellow),
+                ))),
+                Some((hash, text)) if *hash == commit.hash => {
+                    lines.push(Line::from(Span::styled(
+                        "Why it changed",
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    )));
+                    lines.extend(text.lines().map(|l| Line::from(l.to_string())));
+                }
+                _ => lines.push(Line::from(Span::styled(
+                    "w: ask AI why this changed",
+                    label,
+                ))),
+            }
+        }
+        None => {}
+    }
+
+    let block = Block::default()
+        .title(Span::styled(" Commit ", Style::default().fg(Color::Cyan)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    let state = &mut app.blame_state;
+    let mut status_msg: Option<String> = None;
+
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => state.move_by(1),
+        KeyCode::Up | KeyCode::Char('k') => state.move_by(-1),
+        KeyCode::PageDown => state.move_by(20),
+        KeyCode::PageUp => state.move_by(-20),
+        KeyCode::Home => state.selected = 0,
+        KeyCode::End => state.move_by(isize::MAX),
+        KeyCode::Char('J') => state.jump_group(true),
+        KeyCode::Char('K') => state.jump_group(false),
+        KeyCode::Char('p') => {
+            if let Err(e) = state.blame_parent() {
+                status_msg = Some(format!("Error: {}", e));
+            }
+        }
+        KeyCode::Backspace => match state.back() {
+            Ok(true) => {}
+            Ok(false) => status_msg = Some("Already at the newest revision".to_string()),
+            Err(e) => status_msg = Some(format!("Error: {}", e)),
+        },
+        KeyCode::Enter | KeyCode::Char('t') => {
+            if let Some(commit) = state.selected_commit()
+                && !commit.is_uncommitted()
+            {
+                let hash = commit.hash.clone();
+                match app.timeline_state.jump_to(&hash) {
+                    Ok(()) => app.view = View::Timeline,
+                    Err(e) => status_msg = Some(format!("Error: {}", e)),
+                }
+            }
+        }
+        KeyCode::Char('w') => {
+            app.start_ai_explain_change();
+        }
+        KeyCode::Char('y') => {
+            if let Some(commit) = state.selected_commit()
+                && !commit.is_uncommitted()
+            {
+                let hash = commit.short_hash().to_string();
+                match cli_clipboard::set_contents(hash.clone()) {
+                    Ok(()) => status_msg = Some(format!("✓ Copied to clipboard: {}", hash)),
+                    Err(_) => status_msg = Some(format!("Copied (display only): {}", hash)),
+                }
+            }
+        }
+        KeyCode::Esc => {
+            let origin = state.origin.take().unwrap_or(View::Dashboard);
+            app.view = origin;
+            app.refresh();
+        }
+        _ => {}
+    }
+
+    if let Some(msg) = status_msg {
+        app.set_status(msg);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::blame::BlameLine;
+    use std::collections::HashMap;
+
+    fn state(hashes: &[&str]) -> BlameState {
+        let lines: Vec<BlameLine> = hashes
+            .iter()
+            .enumerate()
+            .map(|(i, h)| BlameLine {
+                hash: h.to_string(),
+                orig_line: i as u32 + 1,
+                final_line: i as u32 + 1,
+                content: String::new(),
+            })
+            .collect();
+        let commits: HashMap<String, BlameCommit> = hashes
+            .iter()
+            .map(|h| {
+                (
+                    h.to_string(),
+                    BlameCommit {
+                        hash: h.to_string(),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+        let blame = Blame {
+            path: "a.rs".to_string(),
+            rev: None,
+            commits,
+            lines,
+        };
+        BlameState {
+            groups: blame.groups(),
+            blame,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_jump_group() {
+        let mut s = state(&["a", "a", "b", "c", "c"]);
+        s.jump_group(true);
+        assert_eq!(s.selected, 2);
+        s.jump_group(true);
+        assert_eq!(s.selected, 3);
+        s.jump_group(true);
+        assert_eq!(s.selected, 3);
+        s.selected = 4;
+        s.jump_group(false);
+        assert_eq!(s.selected, 2);
+    }
+
+    #[test]
+    fn test_move_by_clamps() {
+        let mut s = state(&["a", "a", "b"]);
+        s.move_by(-1);
+        assert_eq!(s.selected, 0);
+        s.move_by(isize::MAX);
+        assert_eq!(s.selected, 2);
+    }
+
+    #[test]
+    fn test_scroll_into_view() {
+        let mut s = state(&["a"; 50]);
+        s.selected = 30;
+        s.scroll_into_view(10);
+        assert_eq!(s.scroll, 21);
+        s.selected = 5;
+        s.scroll_into_view(10);
+        assert_eq!(s.scroll, 5);
+    }
+
+    #[test]
+    fn test_short_age() {
+        let now = 1_700_000_000;
+        assert_eq!(short_age(now - 120, now), "2m");
+        assert_eq!(short_age(now - 3 * 86_400, now), "3d");
+        assert_eq!(short_age(now - 90 * 86_400, now), "3mo");
+        assert_eq!(short_age(now - 800 * 86_400, now), "2y");
+    }
+
+    #[test]
+    fn test_author_color_is_stable() {
+        assert_eq!(author_color("Alice"), author_color("Alice"));
+    }
+}
@@ -1,10 +1,10 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Rect},
     style::{Color, Modifier, Style},
     text::Span,
     widgets::{Block, Borders, Cell, Row, Table, TableState},
-    Frame,
 };
 
 use crate::git;
@@ -118,19 +118,17 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut BranchesState) {
 
 pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
     match key.code {
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.branches_state.selected > 0 {
-                app.branches_state.selected -= 1;
-                let sel = app.branches_state.selected;
-                app.branches_state.table_state.select(Some(sel));
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.branches_state.selected > 0 => {
+            app.branches_state.selected -= 1;
+            let sel = app.branches_state.selected;
+            app.branches_state.table_state.select(Some(sel));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.branches_state.selected + 1 < app.branches_state.branches.len() {
-                app.branches_state.selected += 1;
-                let sel = app.branches_state.selected;
-                app.branches_state.table_state.select(Some(sel));
-            }
+        KeyCode::Down | KeyCode::Char('j')
+            if app.branches_state.selected + 1 < app.branches_state.branches.len() =>
+        {
+            app.branches_state.selected += 1;
+            let sel = app.branches_state.selected;
+            app.branches_state.table_state.select(Some(sel));
         }
         KeyCode::Enter => {
             // Switch to selected branch
@@ -179,6 +177,10 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                     app.set_status("Cannot delete the current branch");
                     return Ok(());
                 }
+                if app.config.branches.is_protected(&branch.name) {
+                    app.set_status(format!("'{}' is a protected branch", branch.name));
+                    return Ok(());
+                }
                 let name = branch.name.clone();
                 app.popup = crate::app::Popup::Confirm {
                     title: "Delete Branch".to_string(),
@@ -191,6 +193,12 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
             }
         }
         KeyCode::Char('R') => {
+            if let Ok(current) = git::BranchOps::current()
+                && app.config.branches.is_protected(&current)
+            {
+                app.set_status(format!("'{}' is a protected branch", current));
+                return Ok(());
+            }
             app.popup = crate::app::Popup::Input {
                 title: "Rename Branch".to_string(),
                 prompt: "New name: ".to_string(),
@@ -198,6 +206,23 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 on_submit: crate::app::InputAction::RenameBranch,
             };
         }
+        KeyCode::Char('W') => {
+            let selected = app.branches_state.selected;
+            if let Some(branch) = app.branches_state.branches.get(selected) {
+                if branch.is_current {
+                    app.set_status("Branch is already checked out here");
+                    return Ok(());
+                }
+                // For a remote branch, let git create the local tracking branch
+                let name = if branch.is_remote {
+                    let short = branch.name.trim_start_matches("remotes/");
+                    short.split_once('/').map_or(short, |(_, b)| b).to_string()
+                } else {
+                    branch.name.clone()
+                };
+                app.add_worktree(&name, false);
+            }
+        }
         KeyCode::Tab => {
             app.branches_state.show_remote = !app.branches_state.show_remote;
             app.branches_state.refresh();
@@ -2,15 +2,16 @@
 
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
-    Frame,
 };
 
 use crate::git;
 use crate::git::log::CommitEntry;
+use crate::ui::diff_view;
 
 /// Sub-view within the Cherry Pick screen.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -43,8 +44,9 @@ pub struct CherryPickState {
     pub marked: Vec<String>,
 
     /// Diff preview of the currently highlighted commit.
-    pub diff_text: String,
+    pub diff: Vec<git::diff::FileDiff>,
     pub diff_scroll: u16,
+    pub diff_mode: diff_view::DiffMode,
 
     /// Whether a cherry-pick conflict is active.
     pub conflict_active: bool,
@@ -63,8 +65,9 @@ impl Default for CherryPickState {
             commit_selected: 0,
             commit_list_state: ListState::default(),
             marked: Vec::new(),
-            diff_text: String::new(),
+            diff: Vec::new(),
             diff_scroll: 0,
+            diff_mode: diff_view::DiffMode::default(),
             conflict_active: false,
         }
     }
@@ -109,12 +112,10 @@ impl CherryPickState {
     }
 
     fn update_diff(&mut self) {
-        self.diff_text.clear();
+        self.diff.clear();
         self.diff_scroll = 0;
         if let Some(commit) = self.commits.get(self.commit_selected) {
-            if let Ok(diff) = git::cherry_pick::commit_diff(&commit.hash) {
-                self.diff_text = diff;
-            }
+            self.diff = git::diff::get_commit_diff(&commit.hash).unwrap_or_default();
         }
     }
 
@@ -357,31 +358,24 @@ fn render_commit_select(f: &mut Frame, area: Rect, state: &mut CherryPickState)
     f.render_stateful_widget(list, content_chunks[0], &mut state.commit_list_state);
 
     // Diff preview
-    let diff_lines: Vec<Line> = state
-        .diff_text
-        .lines()
-        .map(|line| {
-            let color = crate::ui::utils::diff_line_color(line);
-            Line::from(Span::styled(line, Style::default().fg(color)))
-        })
-        .collect();
-
     let diff_title = if let Some(c) = state.commits.get(state.commit_selected) {
         format!(" {} — {} ", c.short_hash, c.message)
     } else {
         " Diff Preview ".to_string()
     };
 
-    let diff = Paragraph::new(diff_lines)
-        .block(
-            Block::default()
-                .title(Span::styled(diff_title, Style::default().fg(Color::White)))
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
-        )
-        .scroll((state.diff_scroll, 0))
-        .wrap(Wrap { trim: false });
-    f.render_widget(diff, content_chunks[1]);
+    let block = Block::default()
+        .title(Span::styled(diff_title, Style::default().fg(Color::White)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    diff_view::render_files(
+        f,
+        content_chunks[1],
+        block,
+        &state.diff,
+        state.diff_mode,
+        state.diff_scroll,
+    );
 
     // Keybindings
     let keys = Paragraph::new(Line::from(vec![
@@ -393,6 +387,8 @@ fn render_commit_select(f: &mut Frame, area: Rect, state: &mut CherryPickState)
         Span::raw(" Apply "),
         Span::styled("[PgDn/Up]", Style::default().fg(Color::Cyan)),
         Span::raw(" Scroll diff "),
+        Span::styled("[v]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Side-by-side "),
         Span::styled("[Esc]", Style::default().fg(Color::Cyan)),
         Span::raw(" Back "),
         Span::styled("[q]", Style::default().fg(Color::Red)),
@@ -442,9 +438,7 @@ fn render_in_progress(f: &mut Frame, area: Rect, state: &mut CherryPickState) {
                 Span::styled("  Status: ", Style::default().fg(Color::DarkGray)),
                 Span::styled(
                     "CONFLICT",
-                    Style::default()
-                        .fg(Color::Red)
-                        .add_modifier(Modifier::BOLD),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(Span::styled(
@@ -543,17 +537,15 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
         match state.mode {
             CherryPickMode::BranchSelect => match key.code {
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if state.branch_selected > 0 {
-                        state.branch_selected -= 1;
-                        state.branch_list_state.select(Some(state.branch_selected));
-                    }
+                KeyCode::Up | KeyCode::Char('k') if state.branch_selected > 0 => {
+                    state.branch_selected -= 1;
+                    state.branch_list_state.select(Some(state.branch_selected));
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if state.branch_selected + 1 < state.branches.len() {
-                        state.branch_selected += 1;
-                        state.branch_list_state.select(Some(state.branch_selected));
-                    }
+                KeyCode::Down | KeyCode::Char('j')
+                    if state.branch_selected + 1 < state.branches.len() =>
+                {
+                    state.branch_selected += 1;
+                    state.branch_list_state.select(Some(state.branch_selected));
                 }
                 KeyCode::Enter => {
                     if let Some(branch) = state.branches.get(state.branch_selected) {
@@ -566,19 +558,17 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
             },
 
             CherryPickMode::CommitSelect => match key.code {
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if state.commit_selected > 0 {
-                        state.commit_selected -= 1;
-                        state.commit_list_state.select(Some(state.commit_selected));
-                        state.update_diff();
-                    }
+                KeyCode::Up | KeyCode::Char('k') if state.commit_selected > 0 => {
+                    state.commit_selected -= 1;
+                    state.commit_list_state.select(Some(state.commit_selected));
+                    state.update_diff();
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if state.commit_selected + 1 < state.commits.len() {
-                        state.commit_selected += 1;
-                        state.commit_list_state.select(Some(state.commit_selected));
-                        state.update_diff();
-                    }
+                KeyCode::Down | KeyCode::Char('j')
+                    if state.commit_selected + 1 < state.commits.len() =>
+                {
+                    state.commit_selected += 1;
+                    state.commit_list_state.select(Some(state.commit_selected));
+                    state.update_diff();
                 }
                 KeyCode::Char(' ') => {
                     // Toggle mark on current commit
@@ -654,7 +644,7 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                     state.source_branch = None;
                     state.commits.clear();
                     state.marked.clear();
-                    state.diff_text.clear();
+                    state.diff.clear();
                     state.refresh();
                 }
                 KeyCode::PageDown => {
@@ -663,6 +653,10 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 KeyCode::PageUp => {
                     state.diff_scroll = state.diff_scroll.saturating_sub(10);
                 }
+                KeyCode::Char('v') => {
+                    state.diff_mode = state.diff_mode.toggle();
+                    state.diff_scroll = 0;
+                }
                 _ => {}
             },
 
@@ -1,20 +1,31 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
-    Frame,
 };
 
+use crate::config::{BranchesConfig, CommitConfig};
 use crate::git;
 
+/// Commit types accepted when the Conventional Commits convention is on.
+const CONVENTIONAL_TYPES: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+
 pub struct CommitState {
     pub message: String,
     pub staged_files: Vec<git::FileEntry>,
     pub stat_output: String,
     pub editing: bool,
     pub validation_warnings: Vec<String>,
+    /// Current branch, checked against the protected branches.
+    pub branch: String,
+    /// Message conventions from the (layered) config.
+    pub conventions: CommitConfig,
+    pub protected: BranchesConfig,
 }
 
 impl Default for CommitState {
@@ -25,6 +36,9 @@ impl Default for CommitState {
             stat_output: String::new(),
             editing: true,
             validation_warnings: Vec::new(),
+            branch: String::new(),
+            conventions: CommitConfig::default(),
+            protected: BranchesConfig::default(),
         }
     }
 }
@@ -33,6 +47,7 @@ impl CommitState {
     pub fn refresh(&mut self) {
         if let Ok(status) = git::status::get_status() {
             self.staged_files = status.staged;
+            self.branch = status.branch;
         }
         if let Ok(stat) = git::diff::get_staged_stat() {
             self.stat_output = stat;
@@ -43,6 +58,13 @@ impl CommitState {
     pub fn validate(&mut self) {
         self.validation_warnings.clear();
 
+        if self.protected.is_protected(&self.branch) {
+            self.validation_warnings.push(format!(
+                "'{}' is a protected branch — consider committing on a feature branch",
+                self.branch
+            ));
+        }
+
         if self.message.is_empty() {
             return;
         }
@@ -51,16 +73,23 @@ impl CommitState {
 
         // Subject line checks
         if let Some(subject) = lines.first() {
-            if subject.len() > 72 {
+            let max = self.conventions.max_subject_length;
+            if subject.len() > max {
                 self.validation_warnings.push(format!(
-                    "Subject line is {} chars (recommended: ≤72)",
-                    subject.len()
+                    "Subject line is {} chars (recommended: ≤{})",
+                    subject.len(),
+                    max
                 ));
             }
             if subject.ends_with('.') {
                 self.validation_warnings
                     .push("Subject should not end with a period".to_string());
             }
+            if self.conventions.is_conventional()
+                && let Some(warning) = check_conventional(subject)
+            {
+                self.validation_warnings.push(warning);
+            }
         }
 
         // Body line checks
@@ -82,6 +111,30 @@ impl CommitState {
     }
 }
 
+/// Check a subject line against Conventional Commits (`type(scope)!: description`).
+fn check_conventional(subject: &str) -> Option<String> {
+    let Some((prefix, description)) = subject.split_once(": ") else {
+        return Some("Subject should follow Conventional Commits: type(scope): description".into());
+    };
+    let prefix = prefix.strip_suffix('!').unwrap_or(prefix);
+    let kind = match prefix.split_once('(') {
+        Some((kind, scope)) if scope.ends_with(')') && scope.len() > 1 => kind,
+        Some(_) => return Some(format!("Malformed scope in '{}'", prefix)),
+        None => prefix,
+    };
+    if !CONVENTIONAL_TYPES.contains(&kind) {
+        return Some(format!(
+            "Unknown commit type '{}' (use {})",
+            kind,
+            CONVENTIONAL_TYPES.join(", ")
+        ));
+    }
+    if description.trim().is_empty() {
+        return Some("Commit description is empty".to_string());
+    }
+    None
+}
+
 pub fn render(
     f: &mut Frame,
     area: Rect,
@@ -220,8 +273,8 @@ pub fn render(
         } else {
             last_line.len()
         };
-        if editor_inner_width > 0 {
-            visual_y += (last_line.len() / editor_inner_width) as u16;
+        if let Some(wrapped_rows) = last_line.len().checked_div(editor_inner_width) {
+            visual_y += wrapped_rows as u16;
         }
 
         f.set_cursor_position((
@@ -318,11 +371,9 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 state.editing = false;
             }
         }
-        KeyCode::Enter => {
-            // Enter commits if message is non-empty
-            if !state.message.trim().is_empty() {
-                do_commit(app)?;
-            }
+        // Enter commits if message is non-empty
+        KeyCode::Enter if !state.message.trim().is_empty() => {
+            do_commit(app)?;
         }
         KeyCode::Tab => {
             // Tab adds a newline for multi-line commit messages
@@ -365,14 +416,35 @@ fn do_commit(app: &mut crate::app::App) -> anyhow::Result<()> {
         return Ok(());
     }
 
+    // ── Commit policy: large files, binaries, forbidden paths ───────
+    match git::policy::check_staged(&app.config.commit) {
+        Ok(findings) if !findings.is_empty() => {
+            app.popup = crate::app::Popup::PolicyWarning {
+                findings,
+                selected: 0,
+            };
+            return Ok(());
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("Commit policy check failed: {:#}", e),
+    }
+
+    scan_and_commit(app)
+}
+
+/// Scan the staged changes for secrets, then commit. Runs once the commit
+/// policy has passed or been overridden.
+pub fn scan_and_commit(app: &mut crate::app::App) -> anyhow::Result<()> {
     // ── Secret scanning before commit ───────────────────────────────
     if app.config.secrets.enabled {
-        let rules = git::secrets::default_rules();
-        let findings = git::secrets::scan_staged_files(
-            &app.commit_state.staged_files,
-            &rules,
-            &app.config.secrets.allowlist,
-        );
+        // Only lines this commit adds — secrets already in HEAD shouldn't
+        // block every commit. Fall back to the full index scan.
+        let rules = git::secrets::rules_for(&app.config.secrets);
+        let allowlist = &app.config.secrets.allowlist;
+        let findings =
+            git::secrets::scan_staged_additions(&rules, allowlist).unwrap_or_else(|_| {
+                git::secrets::scan_staged_files(&app.commit_state.staged_files, &rules, allowlist)
+            });
 
         if !findings.is_empty() {
             app.popup = crate::app::Popup::SecretWarning {
@@ -470,4 +542,56 @@ mod tests {
         let warnings = validate_msg(&msg);
         assert_eq!(warnings.len(), 2);
     }
+
+    fn validate_with(msg: &str, conventions: CommitConfig) -> Vec<String> {
+        let mut state = CommitState {
+            message: msg.to_string(),
+            conventions,
+            ..CommitState::default()
+        };
+        state.validate();
+        state.validation_warnings
+    }
+
+    fn conventional() -> CommitConfig {
+        CommitConfig {
+            convention: Some("conventional".to_string()),
+            ..CommitConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_configured_subject_length() {
+        let config = CommitConfig {
+            max_subject_length: 50,
+            ..CommitConfig::default()
+        };
+        let warnings = validate_with(&"a".repeat(51), config);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("≤50"));
+    }
+
+    #[test]
+    fn test_validate_conventional_commits() {
+        assert!(validate_with("feat(ui): add tag view", conventional()).is_empty());
+        assert!(validate_with("fix!: drop old flag", conventional()).is_empty());
+        assert_eq!(validate_with("Add tag view", conventional()).len(), 1);
+        assert!(validate_with("feature: add tag view", conventional())[0].contains("Unknown"));
+        assert!(validate_with("feat(: oops", conventional())[0].contains("scope"));
+    }
+
+    #[test]
+    fn test_validate_protected_branch() {
+        let mut state = CommitState {
+            message: "Fix bug".to_string(),
+            branch: "main".to_string(),
+            protected: BranchesConfig {
+                protected: vec!["main".to_string()],
+            },
+            ..CommitState::default()
+        };
+        state.validate();
+        assert_eq!(state.validation_warnings.len(), 1);
+        assert!(state.validation_warnings[0].contains("protected"));
+    }
 }
@@ -1,13 +1,14 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
-    Frame,
 };
 
 use crate::git;
+use crate::ui::graph::GraphLayout;
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum DashboardFocus {
@@ -29,6 +30,7 @@ pub struct DashboardState {
     pub commit_count: usize,
     pub is_clean: bool,
     pub recent_commits: Vec<git::CommitEntry>,
+    pub recent_graph: GraphLayout,
     pub error: Option<String>,
     pub focus: DashboardFocus,
     pub display_staged: usize,
@@ -56,6 +58,7 @@ impl Default for DashboardState {
             commit_count: 0,
             is_clean: true,
             recent_commits: Vec::new(),
+            recent_graph: GraphLayout::default(),
             error: None,
             focus: DashboardFocus::default(),
             display_staged: 0,
@@ -94,8 +97,14 @@ impl DashboardState {
         }
 
         match git::log::get_recent_commits(5) {
-            Ok(commits) => self.recent_commits = commits,
-            Err(_) => self.recent_commits = Vec::new(),
+            Ok(commits) => {
+                self.recent_graph = GraphLayout::new(&commits);
+                self.recent_commits = commits;
+            }
+            Err(_) => {
+                self.recent_graph = GraphLayout::default();
+                self.recent_commits = Vec::new();
+            }
         }
 
         self.commit_count = git::log::commit_count().unwrap_or(0);
@@ -141,6 +150,7 @@ impl DashboardState {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn render(
     f: &mut Frame,
     area: Rect,
@@ -376,19 +386,10 @@ pub fn render(
     let commit_items: Vec<ListItem> = state
         .recent_commits
         .iter()
-        .map(|c| {
-            let graph_span = if c.graph.is_empty() {
-                Span::raw("  ")
-            } else {
-                Span::styled(format!("{} ", c.graph), Style::default().fg(Color::Magenta))
-            };
-
-            if c.hash.is_empty() {
-                return ListItem::new(Line::from(vec![graph_span]));
-            }
-
-            ListItem::new(Line::from(vec![
-                graph_span,
+        .zip(&state.recent_graph.rows)
+        .map(|(c, graph)| {
+            let mut spans = graph.spans();
+            spans.extend([
                 Span::styled(
                     format!("{} ", c.short_hash),
                     Style::default().fg(Color::Yellow),
@@ -398,7 +399,8 @@ pub fn render(
                     format!(" ({})", c.date),
                     Style::default().fg(Color::DarkGray),
                 ),
-            ]))
+            ]);
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
@@ -862,13 +864,12 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
     }
 
     match state.focus {
-        DashboardFocus::Left => match key.code {
-            KeyCode::Char('a') => {
+        DashboardFocus::Left => {
+            if let KeyCode::Char('a') = key.code {
                 state.focus = DashboardFocus::Right;
                 return Ok(());
             }
-            _ => {}
-        },
+        }
         DashboardFocus::Right => {
             if let crate::ui::ai_mentor::AiMode::Menu = app.ai_mentor_state.mode {
                 match key.code {
@@ -0,0 +1,653 @@
+//! Shared diff widget used by every view that shows a `FileDiff`.
+//!
+//! Renders unified or side-by-side, colours code by language (see `syntax`)
+//! and highlights the words that changed between paired removed/added lines.
+//! Only the rows that can be on screen are styled, so large diffs stay cheap.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use std::ops::Range;
+
+use crate::git::diff::FileDiff;
+use crate::git::{self, DiffLine, DiffLineType};
+use crate::ui::syntax::{self, Language, TokenKind};
+
+const ADDED_BG: Color = Color::Indexed(22);
+const ADDED_EMPHASIS_BG: Color = Color::Indexed(28);
+const REMOVED_BG: Color = Color::Indexed(52);
+const REMOVED_EMPHASIS_BG: Color = Color::Indexed(88);
+
+/// Byte ranges within a line.
+type Ranges = Vec<Range<usize>>;
+
+/// Skip word highlighting when a line pair would need a larger LCS table.
+const WORD_DIFF_LIMIT: usize = 250_000;
+
+/// How a diff is laid out.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DiffMode {
+    #[default]
+    Unified,
+    SideBySide,
+}
+
+impl DiffMode {
+    pub fn toggle(self) -> Self {
+        match self {
+            DiffMode::Unified => DiffMode::SideBySide,
+            DiffMode::SideBySide => DiffMode::Unified,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DiffMode::Unified => "unified",
+            DiffMode::SideBySide => "side-by-side",
+        }
+    }
+}
+
+/// A row of the flattened diff: a file banner or one line of a hunk.
+#[derive(Clone, Copy)]
+enum Item<'a> {
+    File(&'a FileDiff),
+    Line(&'a str, &'a DiffLine),
+}
+
+/// Render a list of file diffs with a banner per file.
+pub fn render_files(
+    f: &mut Frame,
+    area: Rect,
+    block: Block,
+    files: &[FileDiff],
+    mode: DiffMode,
+    scroll: u16,
+) {
+    let mut items = Vec::new();
+    for file in files {
+        items.push(Item::File(file));
+        for hunk in &file.hunks {
+            items.extend(hunk.lines.iter().map(|l| Item::Line(&file.path, l)));
+        }
+    }
+    render_items(f, area, block, &items, mode, scroll);
+}
+
+/// Render the diff lines of a single file.
+pub fn render_lines(
+    f: &mut Frame,
+    area: Rect,
+    block: Block,
+    path: &str,
+    lines: &[DiffLine],
+    mode: DiffMode,
+    scroll: u16,
+) {
+    let items: Vec<Item> = lines.iter().map(|l| Item::Line(path, l)).collect();
+    render_items(f, area, block, &items, mode, scroll);
+}
+
+/// Styled unified lines for `lines[range]`, one output line per input line.
+/// Word highlights still take lines outside the range into account.
+pub fn unified_lines(path: &str, lines: &[DiffLine], range: Range<usize>) -> Vec<Line<'static>> {
+    let items: Vec<Item> = lines.iter().map(|l| Item::Line(path, l)).collect();
+    let partners = pair_changes(&items);
+    let end = range.end.min(items.len());
+    (range.start.min(end)..end)
+        .map(|i| unified_line(&items, &partners, i))
+        .collect()
+}
+
+fn render_items(
+    f: &mut Frame,
+    area: Rect,
+    block: Block,
+    items: &[Item],
+    mode: DiffMode,
+    scroll: u16,
+) {
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let partners = pair_changes(items);
+    let height = inner.height as usize;
+    let scroll = scroll as usize;
+
+    match mode {
+        DiffMode::Unified => {
+            // Wrapped lines only take more rows, so this many lines always fill the view
+            let end = (scroll + height).min(items.len());
+            let lines: Vec<Line> = (0..end)
+                .map(|i| unified_line(items, &partners, i))
+                .collect();
+            let paragraph = Paragraph::new(lines)
+                .scroll((scroll as u16, 0))
+                .wrap(Wrap { trim: false });
+            f.render_widget(paragraph, inner);
+        }
+        DiffMode::SideBySide => {
+            let rows = side_by_side_rows(items);
+            let visible = rows.iter().skip(scroll).take(height);
+            let mut left = Vec::new();
+            let mut right = Vec::new();
+            for row in visible {
+                let (l, r) = side_by_side_line(items, &partners, row);
+                left.push(l);
+                right.push(r);
+            }
+            let halves = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(inner);
+            let divider = Block::default()
+                .borders(Borders::RIGHT)
+                .border_style(Style::default().fg(Color::DarkGray));
+            f.render_widget(Paragraph::new(left).block(divider), halves[0]);
+            f.render_widget(Paragraph::new(right), halves[1]);
+        }
+    }
+}
+
+// ── Pairing & word diff ──────────────────────────────────────────────
+
+/// For each item, the index of the line it is paired with for word highlights:
+/// the n-th removed line of a change block pairs with its n-th added line.
+fn pair_changes(items: &[Item]) -> Vec<Option<usize>> {
+    let mut partners = vec![None; items.len()];
+    let kind = |i: usize| match items.get(i) {
+        Some(Item::Line(_, l)) => Some(&l.line_type),
+        _ => None,
+    };
+    let mut i = 0;
+    while i < items.len() {
+        if kind(i) != Some(&DiffLineType::Removed) {
+            i += 1;
+            continue;
+        }
+        let removed_start = i;
+        while kind(i) == Some(&DiffLineType::Removed) || is_no_newline(items, i) {
+            i += 1;
+        }
+        let added_start = i;
+        while kind(i) == Some(&DiffLineType::Added) || is_no_newline(items, i) {
+            i += 1;
+        }
+        let removed: Vec<usize> = (removed_start..added_start)
+            .filter(|&j| kind(j) == Some(&DiffLineType::Removed))
+            .collect();
+        let added: Vec<usize> = (added_start..i)
+            .filter(|&j| kind(j) == Some(&DiffLineType::Added))
+            .collect();
+        for (&r, &a) in removed.iter().zip(&added) {
+            partners[r] = Some(a);
+            partners[a] = Some(r);
+        }
+    }
+    partners
+}
+
+fn is_no_newline(items: &[Item], i: usize) -> bool {
+    matches!(items.get(i), Some(Item::Line(_, l)) if l.content.starts_with('\\'))
+}
+
+/// Line text without its `+`/`-`/space prefix.
+fn body(line: &DiffLine) -> &str {
+    let mut chars = line.content.chars();
+    chars.next();
+    chars.as_str()
+}
+
+/// Split a line into words, whitespace runs and single punctuation characters.
+fn word_tokens(text: &str) -> Vec<Range<usize>> {
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            0
+        } else if c.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    };
+    let mut tokens: Vec<Range<usize>> = Vec::new();
+    let mut last_class = None;
+    for (i, c) in text.char_indices() {
+        let cls = class(c);
+        match tokens.last_mut() {
+            Some(range) if last_class == Some(cls) && cls != 2 => range.end = i + c.len_utf8(),
+            _ => tokens.push(i..i + c.len_utf8()),
+        }
+        last_class = Some(cls);
+    }
+    tokens
+}
+
+/// Byte ranges of `old` and `new` that differ, found with an LCS over word tokens.
+/// Returns `None` when the lines share no words — highlighting everything is just noise.
+fn word_diff(old: &str, new: &str) -> Option<(Ranges, Ranges)> {
+    let a = word_tokens(old);
+    let b = word_tokens(new);
+    if a.is_empty() || b.is_empty() || a.len() * b.len() > WORD_DIFF_LIMIT {
+        return None;
+    }
+    let word = |text: &str, r: &Range<usize>| text[r.clone()].to_string();
+    let a_words: Vec<String> = a.iter().map(|r| word(old, r)).collect();
+    let b_words: Vec<String> = b.iter().map(|r| word(new, r)).collect();
+
+    // lcs[i][j] = LCS length of a_words[i..] and b_words[j..]
+    let (n, m) = (a.len(), b.len());
+    let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+    let at = |i: usize, j: usize| i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[at(i, j)] = if a_words[i] == b_words[j] {
+                lcs[at(i + 1, j + 1)] + 1
+            } else {
+                lcs[at(i + 1, j)].max(lcs[at(i, j + 1)])
+            };
+        }
+    }
+
+    let mut a_kept = vec![false; n];
+    let mut b_kept = vec![false; m];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a_words[i] == b_words[j] {
+            a_kept[i] = true;
+            b_kept[j] = true;
+            i += 1;
+            j += 1;
+        } else if lcs[at(i + 1, j)] >= lcs[at(i, j + 1)] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    let shares_a_word = a_kept
+        .iter()
+        .zip(&a_words)
+        .any(|(&kept, w)| kept && !w.trim().is_empty());
+    if !shares_a_word {
+        return None;
+    }
+    Some((changed_ranges(&a, &a_kept), changed_ranges(&b, &b_kept)))
+}
+
+/// Merge the ranges of unmatched tokens into contiguous spans.
+fn changed_ranges(tokens: &[Range<usize>], kept: &[bool]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for (token, &kept) in tokens.iter().zip(kept) {
+        if kept {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if last.end == token.start => last.end = token.end,
+            _ => ranges.push(token.clone()),
+        }
+    }
+    ranges
+}
+
+/// Word highlights for item `i`, relative to its body.
+fn emphasis_for(items: &[Item], partners: &[Option<usize>], i: usize) -> Vec<Range<usize>> {
+    let (Some(Item::Line(_, line)), Some(Some(p))) = (items.get(i), partners.get(i)) else {
+        return Vec::new();
+    };
+    let Some(Item::Line(_, other)) = items.get(*p) else {
+        return Vec::new();
+    };
+    let result = if line.line_type == DiffLineType::Removed {
+        word_diff(body(line), body(other)).map(|(old, _)| old)
+    } else {
+        word_diff(body(other), body(line)).map(|(_, new)| new)
+    };
+    result.unwrap_or_default()
+}
+
+// ── Styling ──────────────────────────────────────────────────────────
+
+fn file_banner(file: &FileDiff) -> Line<'static> {
+    let (added, removed) =
+        file.hunks
+            .iter()
+            .flat_map(|h| &h.lines)
+            .fold((0, 0), |(a, r), l| match l.line_type {
+                DiffLineType::Added => (a + 1, r),
+                DiffLineType::Removed => (a, r + 1),
+                _ => (a, r),
+            });
+    let name = match &file.old_path {
+        Some(old) => format!("{} → {}", old, file.path),
+        None => file.path.clone(),
+    };
+    let language =
+        syntax::language_for(&file.path).map_or(String::new(), |l| format!("  {}", l.name));
+    Line::from(vec![
+        Span::styled(
+            format!("━━ {} ", name),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("+{}", added), Style::default().fg(Color::Green)),
+        Span::raw(" "),
+        Span::styled(format!("-{}", removed), Style::default().fg(Color::Red)),
+        Span::styled(language, Style::default().fg(Color::DarkGray)),
+    ])
+}
+
+/// Style a diff line's body: syntax colours over the line's base style, with
+/// changed words on a stronger background.
+fn body_spans(
+    lang: Option<&Language>,
+    text: &str,
+    base: Style,
+    emphasis: &[Range<usize>],
+    emphasis_bg: Color,
+) -> Vec<Span<'static>> {
+    let tokens = match lang {
+        Some(lang) => syntax::tokenize(lang, text),
+        None => vec![(0..text.len(), TokenKind::Plain)],
+    };
+    let mut cuts: Vec<usize> = tokens.iter().flat_map(|(r, _)| [r.start, r.end]).collect();
+    cuts.extend(emphasis.iter().flat_map(|r| [r.start, r.end]));
+    cuts.retain(|&c| c <= text.len());
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    let mut spans = Vec::new();
+    let mut token = 0;
+    for pair in cuts.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        while token + 1 < tokens.len() && tokens[token].0.end <= start {
+            token += 1;
+        }
+        let kind = tokens.get(token).map_or(TokenKind::Plain, |(_, k)| *k);
+        let mut style = base.patch(kind.style());
+        if emphasis.iter().any(|r| r.start <= start && end <= r.end) {
+            style = style.bg(emphasis_bg).add_modifier(Modifier::BOLD);
+        }
+        spans.push(Span::styled(text[start..end].to_string(), style));
+    }
+    spans
+}
+
+/// Spans for one diff line, including its `+`/`-` marker.
+fn line_spans(path: &str, line: &DiffLine, emphasis: &[Range<usize>]) -> Vec<Span<'static>> {
+    let lang = syntax::language_for(path);
+    match line.line_type {
+        DiffLineType::Header => vec![Span::styled(
+            line.content.clone(),
+            Style::default().fg(Color::Cyan),
+        )],
+        _ if line.content.starts_with('\\') => vec![Span::styled(
+            line.content.clone(),
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        )],
+        DiffLineType::Context => {
+            let mut spans = vec![Span::raw(" ")];
+            spans.extend(body_spans(
+                lang,
+                body(line),
+                Style::default().fg(Color::Gray),
+                &[],
+                Color::Reset,
+            ));
+            spans
+        }
+        DiffLineType::Added | DiffLineType::Removed => {
+            let added = line.line_type == DiffLineType::Added;
+            let (marker, fg, bg, emphasis_bg) = if added {
+                ("+", Color::Green, ADDED_BG, ADDED_EMPHASIS_BG)
+            } else {
+                ("-", Color::Red, REMOVED_BG, REMOVED_EMPHASIS_BG)
+            };
+            let mut spans = vec![Span::styled(
+                marker,
+                Style::default().fg(fg).bg(bg).add_modifier(Modifier::BOLD),
+            )];
+            spans.extend(body_spans(
+                lang,
+                body(line),
+                Style::default().fg(Color::White).bg(bg),
+                emphasis,
+                emphasis_bg,
+            ));
+            spans
+        }
+    }
+}
+
+fn unified_line(items: &[Item], partners: &[Option<usize>], i: usize) -> Line<'static> {
+    match items[i] {
+        Item::File(file) => file_banner(file),
+        Item::Line(path, line) => {
+            Line::from(line_spans(path, line, &emphasis_for(items, partners, i)))
+        }
+    }
+}
+
+// ── Side-by-side ─────────────────────────────────────────────────────
+
+/// A side-by-side row: a full-width item, or old/new lines with their numbers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Row {
+    Full(usize),
+    Split {
+        left: Option<(usize, u32)>,
+        right: Option<(usize, u32)>,
+    },
+}
+
+/// Lay items out in rows: context on both sides, removals on the left aligned
+/// with the additions that replace them on the right.
+fn side_by_side_rows(items: &[Item]) -> Vec<Row> {
+    let mut rows = Vec::new();
+    let (mut old_no, mut new_no) = (0u32, 0u32);
+    let mut removed: Vec<(usize, u32)> = Vec::new();
+    let mut added: Vec<(usize, u32)> = Vec::new();
+
+    fn flush(rows: &mut Vec<Row>, removed: &mut Vec<(usize, u32)>, added: &mut Vec<(usize, u32)>) {
+        for k in 0..removed.len().max(added.len()) {
+            rows.push(Row::Split {
+                left: removed.get(k).copied(),
+                right: added.get(k).copied(),
+            });
+        }
+        removed.clear();
+        added.clear();
+    }
+
+    for (i, item) in items.iter().enumerate() {
+        let line = match item {
+            Item::File(_) => {
+                flush(&mut rows, &mut removed, &mut added);
+                rows.push(Row::Full(i));
+                continue;
+            }
+            Item::Line(_, line) => line,
+        };
+        match line.line_type {
+            DiffLineType::Header => {
+                flush(&mut rows, &mut removed, &mut added);
+                let (old_start, _, new_start, _) = git::diff::parse_hunk_header(&line.content);
+                old_no = old_start;
+                new_no = new_start;
+                rows.push(Row::Full(i));
+            }
+            // "\ No newline" markers sit under the line they annotate
+            _ if line.content.starts_with('\\') => {
+                if !added.is_empty() {
+                    added.push((i, 0));
+                } else if !removed.is_empty() {
+                    removed.push((i, 0));
+                } else {
+                    rows.push(Row::Full(i));
+                }
+            }
+            DiffLineType::Removed => {
+                if !added.is_empty() {
+                    flush(&mut rows, &mut removed, &mut added);
+                }
+                removed.push((i, old_no));
+                old_no += 1;
+            }
+            DiffLineType::Added => {
+                added.push((i, new_no));
+                new_no += 1;
+            }
+            DiffLineType::Context => {
+                flush(&mut rows, &mut removed, &mut added);
+                rows.push(Row::Split {
+                    left: Some((i, old_no)),
+                    right: Some((i, new_no)),
+                });
+                old_no += 1;
+                new_no += 1;
+            }
+        }
+    }
+    flush(&mut rows, &mut removed, &mut added);
+    rows
+}
+
+fn side_by_side_line(
+    items: &[Item],
+    partners: &[Option<usize>],
+    row: &Row,
+) -> (Line<'static>, Line<'static>) {
+    let half = |cell: Option<(usize, u32)>| -> Line<'static> {
+        let Some((i, number)) = cell else {
+            return Line::from(Span::styled("", Style::default().fg(Color::DarkGray)));
+        };
+        let Item::Line(path, line) = items[i] else {
+            return Line::default();
+        };
+        let gutter = if number == 0 {
+            "     ".to_string()
+        } else {
+            format!("{:>4} ", number)
+        };
+        let mut spans = vec![Span::styled(gutter, Style::default().fg(Color::DarkGray))];
+        spans.extend(line_spans(path, line, &emphasis_for(items, partners, i)));
+        Line::from(spans)
+    };
+    match *row {
+        Row::Full(i) => (unified_line(items, partners, i), Line::default()),
+        Row::Split { left, right } => (half(left), half(right)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(line_type: DiffLineType, content: &str) -> DiffLine {
+        DiffLine {
+            line_type,
+            content: content.to_string(),
+        }
+    }
+
+    fn sample() -> Vec<DiffLine> {
+        vec![
+            line(DiffLineType::Header, "@@ -10,4 +10,5 @@"),
+            line(DiffLineType::Context, " let a = 1;"),
+            line(DiffLineType::Removed, "-let b = 2;"),
+            line(DiffLineType::Added, "+let b = 3;"),
+            line(DiffLineType::Added, "+let c = 4;"),
+            line(DiffLineType::Context, " let d = 5;"),
+        ]
+    }
+
+    #[test]
+    fn test_word_diff_marks_changed_words() {
+        let (old, new) = word_diff("let b = 2;", "let b = 3;").unwrap();
+        assert_eq!(old, vec![8..9]);
+        assert_eq!(new, vec![8..9]);
+    }
+
+    #[test]
+    fn test_word_diff_skips_unrelated_lines() {
+        assert!(word_diff("foo", "bar").is_none());
+        assert!(word_diff("", "bar").is_none());
+    }
+
+    #[test]
+    fn test_pair_changes_pairs_in_order() {
+        let lines = sample();
+        let items: Vec<Item> = lines.iter().map(|l| Item::Line("a.rs", l)).collect();
+        let partners = pair_changes(&items);
+        assert_eq!(partners[2], Some(3));
+        assert_eq!(partners[3], Some(2));
+        assert_eq!(partners[4], None);
+        assert_eq!(partners[1], None);
+    }
+
+    #[test]
+    fn test_side_by_side_rows_align_changes() {
+        let lines = sample();
+        let items: Vec<Item> = lines.iter().map(|l| Item::Line("a.rs", l)).collect();
+        let rows = side_by_side_rows(&items);
+        assert_eq!(
+            rows,
+            vec![
+                Row::Full(0),
+                Row::Split {
+                    left: Some((1, 10)),
+                    right: Some((1, 10))
+                },
+                Row::Split {
+                    left: Some((2, 11)),
+                    right: Some((3, 11))
+                },
+                Row::Split {
+                    left: None,
+                    right: Some((4, 12))
+                },
+                Row::Split {
+                    left: Some((5, 12)),
+                    right: Some((5, 13))
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unified_lines_keeps_one_line_per_input() {
+        let lines = sample();
+        assert_eq!(
+            unified_lines("a.rs", &lines, 0..lines.len()).len(),
+            lines.len()
+        );
+        assert_eq!(unified_lines("a.rs", &lines, 2..4).len(), 2);
+        assert!(unified_lines("a.rs", &lines, 4..99).len() == 2);
+    }
+
+    #[test]
+    fn test_body_spans_cover_text() {
+        let lang = syntax::language_for("a.rs");
+        let spans = body_spans(
+            lang,
+            "let b = 3;",
+            Style::default(),
+            std::slice::from_ref(&(8..9)),
+            ADDED_EMPHASIS_BG,
+        );
+        let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "let b = 3;");
+        let emphasized: Vec<&str> = spans
+            .iter()
+            .filter(|s| s.style.bg == Some(ADDED_EMPHASIS_BG))
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(emphasized, vec!["3"]);
+    }
+}
@@ -0,0 +1,204 @@
+//! File history — every commit that touched one path, following renames,
+//! with each commit's diff for just that file.
+
+use anyhow::{Result, bail};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState},
+};
+
+use crate::app::View;
+use crate::git;
+use crate::git::log::FileCommit;
+use crate::ui::diff_view;
+
+/// Most commits loaded for one file.
+pub const HISTORY_LIMIT: usize = 500;
+
+#[derive(Default)]
+pub struct FileHistoryState {
+    pub path: String,
+    pub commits: Vec<FileCommit>,
+    pub selected: usize,
+    pub list_state: ListState,
+    pub diff: Vec<git::diff::FileDiff>,
+    pub diff_scroll: u16,
+    pub diff_mode: diff_view::DiffMode,
+    /// View to return to on Esc.
+    pub origin: Option<View>,
+}
+
+impl FileHistoryState {
+    /// Load the history of `path`, starting at its newest commit.
+    pub fn open(&mut self, path: &str) -> Result<()> {
+        let commits = git::log::file_history(path, HISTORY_LIMIT)?;
+        if commits.is_empty() {
+            bail!("'{}' has no committed history", path);
+        }
+        self.path = path.to_string();
+        self.commits = commits;
+        self.selected = 0;
+        self.list_state.select(Some(0));
+        self.update_diff();
+        Ok(())
+    }
+
+    pub fn refresh(&mut self) {
+        if self.path.is_empty() {
+            return;
+        }
+        let Ok(commits) = git::log::file_history(&self.path, HISTORY_LIMIT) else {
+            return;
+        };
+        // Keep the selected commit if it is still in the history
+        let current = self.selected_commit().map(|c| c.commit.hash.clone());
+        self.commits = commits;
+        self.selected = current
+            .and_then(|hash| self.commits.iter().position(|c| c.commit.hash == hash))
+            .unwrap_or(0);
+        self.list_state.select(if self.commits.is_empty() {
+            None
+        } else {
+            Some(self.selected)
+        });
+    }
+
+    pub fn selected_commit(&self) -> Option<&FileCommit> {
+        self.commits.get(self.selected)
+    }
+
+    fn update_diff(&mut self) {
+        self.diff.clear();
+        self.diff_scroll = 0;
+        if let Some(entry) = self.commits.get(self.selected) {
+            let mut paths = vec![entry.path.as_str()];
+            if let Some(old) = &entry.old_path {
+                paths.push(old);
+            }
+            self.diff =
+                git::diff::get_commit_file_diff(&entry.commit.hash, &paths).unwrap_or_default();
+        }
+    }
+}
+
+pub fn render(f: &mut Frame, area: Rect, state: &mut FileHistoryState) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+
+    let items: Vec<ListItem> = state
+        .commits
+        .iter()
+        .map(|entry| {
+            let mut spans = vec![
+                Span::styled(
+                    format!("{} ", entry.commit.short_hash),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(&entry.commit.message, Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("  {} · {}", entry.commit.author, entry.commit.date),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ];
+            if let Some(old) = &entry.old_path {
+                spans.push(Span::styled(
+                    format!("  ← {}", old),
+                    Style::default().fg(Color::Magenta),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" History: {} ({}) ", state.path, state.commits.len()),
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Magenta)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(list, chunks[0], &mut state.list_state);
+
+    let title = match state.selected_commit() {
+        Some(entry) => format!(
+            " {} — {} ({}) ",
+            entry.commit.short_hash,
+            entry.path,
+            state.diff_mode.label()
+        ),
+        None => " Diff ".to_string(),
+    };
+    let block = Block::default()
+        .title(Span::styled(title, Style::default().fg(Color::White)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    diff_view::render_files(
+        f,
+        chunks[1],
+        block,
+        &state.diff,
+        state.diff_mode,
+        state.diff_scroll,
+    );
+}
+
+pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    let state = &mut app.file_history_state;
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+            state.selected -= 1;
+            state.list_state.select(Some(state.selected));
+            state.update_diff();
+        }
+        KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < state.commits.len() => {
+            state.selected += 1;
+            state.list_state.select(Some(state.selected));
+            state.update_diff();
+        }
+        KeyCode::PageDown => state.diff_scroll = state.diff_scroll.saturating_add(10),
+        KeyCode::PageUp => state.diff_scroll = state.diff_scroll.saturating_sub(10),
+        KeyCode::Char('v') => {
+            state.diff_mode = state.diff_mode.toggle();
+            state.diff_scroll = 0;
+        }
+        KeyCode::Char('b') => {
+            if let Some(entry) = state.selected_commit() {
+                let (path, hash) = (entry.path.clone(), entry.commit.hash.clone());
+                app.open_blame(&path, Some(&hash));
+            }
+        }
+        KeyCode::Char('y') => {
+            if let Some(entry) = state.selected_commit() {
+                let hash = entry.commit.short_hash.clone();
+                match cli_clipboard::set_contents(hash.clone()) {
+                    Ok(()) => app.set_status(format!("✓ Copied to clipboard: {}", hash)),
+                    Err(_) => app.set_status(format!("Copied (display only): {}", hash)),
+                }
+            }
+        }
+        KeyCode::Esc => {
+            let origin = state.origin.take().unwrap_or(View::Dashboard);
+            app.view = origin;
+            app.refresh();
+        }
+        _ => {}
+    }
+    Ok(())
+}
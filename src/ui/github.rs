@@ -1,10 +1,10 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs},
-    Frame,
 };
 use std::sync::{Arc, Mutex};
 
@@ -213,6 +213,8 @@ impl PullRequestsState {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct DeviceAuthState {
+    /// Host the code was issued by; polling must go to the same one.
+    pub host: git::github_auth::GithubHost,
     pub user_code: String,
     pub device_code: String,
     pub verification_uri: String,
@@ -271,8 +273,14 @@ impl GitHubState {
     }
 }
 
+/// Whether zit holds a token for the GitHub host `origin` points at.
+pub fn logged_in(config: &crate::config::GithubConfig) -> bool {
+    let host = git::github_auth::GithubHost::current(config);
+    config.token_for(&host.name).is_some()
+}
+
 pub fn render(f: &mut Frame, area: Rect, state: &mut GitHubState, config: &crate::config::Config) {
-    let has_token = config.github.get_token().is_some();
+    let has_token = logged_in(&config.github);
 
     match &state.view {
         GitHubView::Menu => render_menu(f, area, state, has_token, config),
@@ -321,7 +329,8 @@ fn render_menu(
 
     // Auth status
     let auth_status = if has_token {
-        let user_info = if let Some(ref username) = config.github.username {
+        let host = git::github_auth::GithubHost::current(&config.github);
+        let user_info = if let Some(username) = config.github.username_for(&host.name) {
             format!("Authenticated as @{}", username)
         } else {
             "Authenticated".to_string()
@@ -763,19 +772,15 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
 fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
     match key.code {
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.github_state.menu_selected > 0 {
-                app.github_state.menu_selected -= 1;
-                let sel = app.github_state.menu_selected;
-                app.github_state.menu_state.select(Some(sel));
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.github_state.menu_selected > 0 => {
+            app.github_state.menu_selected -= 1;
+            let sel = app.github_state.menu_selected;
+            app.github_state.menu_state.select(Some(sel));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.github_state.menu_selected < 8 {
-                app.github_state.menu_selected += 1;
-                let sel = app.github_state.menu_selected;
-                app.github_state.menu_state.select(Some(sel));
-            }
+        KeyCode::Down | KeyCode::Char('j') if app.github_state.menu_selected < 8 => {
+            app.github_state.menu_selected += 1;
+            let sel = app.github_state.menu_selected;
+            app.github_state.menu_state.select(Some(sel));
         }
         KeyCode::Enter => {
             match app.github_state.menu_selected {
@@ -785,7 +790,7 @@ fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<(
                 }
                 1 => {
                     // Create repo
-                    if app.config.github.get_token().is_none() {
+                    if !logged_in(&app.config.github) {
                         app.github_state.status =
                             Some("Login first to create a repository".to_string());
                         return Ok(());
@@ -854,7 +859,7 @@ fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<(
                 }
                 5 => {
                     // Collaborators — load and switch view
-                    if app.config.github.get_token().is_none() {
+                    if !logged_in(&app.config.github) {
                         app.github_state.status =
                             Some("Login first to manage collaborators".to_string());
                         return Ok(());
@@ -864,7 +869,7 @@ fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<(
                 }
                 6 => {
                     // Pull Requests
-                    if app.config.github.get_token().is_none() {
+                    if !logged_in(&app.config.github) {
                         app.github_state.status =
                             Some("Login first to view pull requests".to_string());
                         return Ok(());
@@ -874,7 +879,7 @@ fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<(
                 }
                 7 => {
                     // Actions
-                    if app.config.github.get_token().is_none() {
+                    if !logged_in(&app.config.github) {
                         app.github_state.status = Some("Login first to view Actions".to_string());
                         return Ok(());
                     }
@@ -882,14 +887,14 @@ fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<(
                     app.github_state.view = GitHubView::Actions;
                 }
                 8 => {
-                    // Logout — clear keychain and config
-                    if app.config.github.get_token().is_some() {
-                        crate::keychain::clear_all();
-                        app.config.github.oauth_token = None;
-                        app.config.github.pat = None;
-                        app.config.github.username = None;
+                    // Logout — clear this host's token and the AI key from keychain and config
+                    let host = git::github_auth::GithubHost::current(&app.config.github);
+                    if app.config.github.token_for(&host.name).is_some() {
+                        app.config.github.clear_token(&host.name);
+                        app.config.github.set_username(&host.name, None);
+                        crate::keychain::delete_ai_api_key();
                         let _ = app.config.save();
-                        app.github_state.status = Some("Logged out".to_string());
+                        app.github_state.status = Some(format!("Logged out of {}", host.name));
                     } else {
                         app.github_state.status = Some("Not logged in".to_string());
                     }
@@ -908,9 +913,11 @@ fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<(
 }
 
 fn start_device_flow(app: &mut crate::app::App) {
-    match git::github_auth::request_device_code() {
+    let host = git::github_auth::GithubHost::current(&app.config.github);
+    match host.request_device_code() {
         Ok(response) => {
             app.github_state.view = GitHubView::DeviceAuth(DeviceAuthState {
+                host,
                 user_code: response.user_code,
                 device_code: response.device_code,
                 verification_uri: response.verification_uri,
@@ -936,13 +943,13 @@ fn handle_device_auth_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::R
 /// Called on every tick event to poll GitHub for authorization status.
 pub fn tick_device_auth(app: &mut crate::app::App) {
     // Check for background operation results (push/pull/sync)
-    if let Ok(mut result) = app.github_state.bg_result.try_lock() {
-        if let Some(msg) = result.take() {
-            app.github_state.status = Some(msg);
-        }
+    if let Ok(mut result) = app.github_state.bg_result.try_lock()
+        && let Some(msg) = result.take()
+    {
+        app.github_state.status = Some(msg);
     }
 
-    let (device_code, _interval) = {
+    let (device_code, host) = {
         if let GitHubView::DeviceAuth(ref mut auth) = app.github_state.view {
             auth.ticks_since_poll += 1;
             // interval is in seconds, tick_rate is in ms (typically 2000ms = 2s)
@@ -953,13 +960,13 @@ pub fn tick_device_auth(app: &mut crate::app::App) {
                 return; // Not time to poll yet
             }
             auth.ticks_since_poll = 0;
-            (auth.device_code.clone(), auth.interval)
+            (auth.device_code.clone(), auth.host.clone())
         } else {
             return; // Not in device auth view
         }
     };
 
-    match git::github_auth::poll_for_token(&device_code) {
+    match host.poll_for_token(&device_code) {
         git::github_auth::PollResult::Pending => {
             if let GitHubView::DeviceAuth(ref mut auth) = app.github_state.view {
                 auth.status = "Waiting for you to authorize in browser...".to_string();
@@ -967,14 +974,14 @@ pub fn tick_device_auth(app: &mut crate::app::App) {
         }
         git::github_auth::PollResult::Success(token) => {
             // Fetch username
-            let username = git::github_auth::get_username(&token.access_token).ok();
-
-            // Store token in OS keychain (fall back to config if keychain fails)
-            if crate::keychain::store_github_token(&token.access_token).is_err() {
-                log::warn!("Keychain unavailable, storing token in config file");
-                app.config.github.oauth_token = Some(token.access_token);
-            }
-            app.config.github.username = username.clone();
+            let name = host.name.clone();
+            let username = git::github_auth::GithubClient::new(host, token.access_token.clone())
+                .username()
+                .ok();
+
+            // Store token for the host that issued it (keychain, else config)
+            app.config.github.set_token(&name, token.access_token);
+            app.config.github.set_username(&name, username.clone());
             let _ = app.config.save();
 
             let msg = if let Some(user) = username {
@@ -1016,10 +1023,8 @@ fn handle_create_repo_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::R
         KeyCode::Tab | KeyCode::Down => {
             app.github_state.create_field = (app.github_state.create_field + 1).min(3);
         }
-        KeyCode::BackTab | KeyCode::Up => {
-            if app.github_state.create_field > 0 {
-                app.github_state.create_field -= 1;
-            }
+        KeyCode::BackTab | KeyCode::Up if app.github_state.create_field > 0 => {
+            app.github_state.create_field -= 1;
         }
         KeyCode::Char(' ') if app.github_state.create_field == 2 => {
             app.github_state.repo_private = !app.github_state.repo_private;
@@ -1031,10 +1036,12 @@ fn handle_create_repo_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::R
                 return Ok(());
             }
 
-            if let Some(token) = app.config.github.get_token() {
+            let host = git::github_auth::GithubHost::current(&app.config.github);
+            if let Some(token) = app.config.github.token_for(&host.name) {
                 let desc = app.github_state.repo_desc.clone();
                 let private = app.github_state.repo_private;
-                match git::github_auth::create_repo(&token, &name, &desc, private) {
+                let client = git::github_auth::GithubClient::new(host, token);
+                match client.create_repo(&name, &desc, private) {
                     Ok(clone_url) => {
                         // Add remote origin if not already set
                         let _ = git::RemoteOps::add("origin", &clone_url);
@@ -1071,24 +1078,24 @@ fn handle_create_repo_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::R
 }
 
 fn load_collaborators(app: &mut crate::app::App) {
-    if let Some(token) = app.config.github.get_token() {
-        match git::github_auth::list_collaborators(&token) {
-            Ok(collabs) => {
-                app.github_state.collaborators = collabs;
-                app.github_state.collab_selected = 0;
-                app.github_state.collab_list_state.select(
-                    if app.github_state.collaborators.is_empty() {
-                        None
-                    } else {
-                        Some(0)
-                    },
-                );
-                app.github_state.collab_error = None;
-            }
-            Err(e) => {
-                app.github_state.collab_error = Some(format!("Error: {}", e));
-                app.github_state.collaborators.clear();
-            }
+    match git::github_auth::GithubClient::for_origin(&app.config.github)
+        .and_then(|client| client.list_collaborators())
+    {
+        Ok(collabs) => {
+            app.github_state.collaborators = collabs;
+            app.github_state.collab_selected = 0;
+            app.github_state.collab_list_state.select(
+                if app.github_state.collaborators.is_empty() {
+                    None
+                } else {
+                    Some(0)
+                },
+            );
+            app.github_state.collab_error = None;
+        }
+        Err(e) => {
+            app.github_state.collab_error = Some(format!("Error: {}", e));
+            app.github_state.collaborators.clear();
         }
     }
 }
@@ -1099,21 +1106,18 @@ fn handle_collaborators_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow:
             app.github_state.view = GitHubView::Menu;
             app.github_state.collab_error = None;
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.github_state.collab_selected > 0 {
-                app.github_state.collab_selected -= 1;
-                let sel = app.github_state.collab_selected;
-                app.github_state.collab_list_state.select(Some(sel));
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.github_state.collab_selected > 0 => {
+            app.github_state.collab_selected -= 1;
+            let sel = app.github_state.collab_selected;
+            app.github_state.collab_list_state.select(Some(sel));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        KeyCode::Down | KeyCode::Char('j')
             if !app.github_state.collaborators.is_empty()
-                && app.github_state.collab_selected + 1 < app.github_state.collaborators.len()
-            {
-                app.github_state.collab_selected += 1;
-                let sel = app.github_state.collab_selected;
-                app.github_state.collab_list_state.select(Some(sel));
-            }
+                && app.github_state.collab_selected + 1 < app.github_state.collaborators.len() =>
+        {
+            app.github_state.collab_selected += 1;
+            let sel = app.github_state.collab_selected;
+            app.github_state.collab_list_state.select(Some(sel));
         }
         KeyCode::Char('a') => {
             // Add collaborator via input popup
@@ -1151,12 +1155,20 @@ fn handle_collaborators_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow:
 fn start_load_prs(app: &mut crate::app::App) {
     app.github_state.pr_state.loading = true;
     app.github_state.pr_state.error = None;
-    let token = app.config.github.get_token().unwrap_or_default();
+    let client = match git::github_auth::GithubClient::for_origin(&app.config.github) {
+        Ok(client) => client,
+        Err(e) => {
+            app.github_state.pr_state.loading = false;
+            app.github_state.pr_state.error = Some(e.to_string());
+            return;
+        }
+    };
     let filter = app.github_state.pr_state.filter.api_state().to_string();
     let bg = app.github_state.pr_state.bg_result.clone();
     std::thread::spawn(move || {
-        let result =
-            git::github_auth::list_pull_requests(&token, &filter).map_err(|e| e.to_string());
+        let result = client
+            .list_pull_requests(&filter)
+            .map_err(|e| e.to_string());
         if let Ok(mut r) = bg.lock() {
             *r = Some(PrBgResult::PrList(result));
         }
@@ -1169,14 +1181,21 @@ fn start_load_pr_detail(app: &mut crate::app::App, number: u64) {
     app.github_state.pr_state.detail_tab = PrDetailTab::Overview;
     app.github_state.pr_state.detail_scroll = 0;
     app.github_state.pr_state.files_selected = 0;
-    let token = app.config.github.get_token().unwrap_or_default();
+    let client = match git::github_auth::GithubClient::for_origin(&app.config.github) {
+        Ok(client) => client,
+        Err(e) => {
+            app.github_state.pr_state.loading = false;
+            app.github_state.pr_state.error = Some(e.to_string());
+            return;
+        }
+    };
     let bg = app.github_state.pr_state.bg_result.clone();
     std::thread::spawn(move || {
-        let pr = git::github_auth::get_pull_request(&token, number).map_err(|e| e.to_string());
+        let pr = client.get_pull_request(number).map_err(|e| e.to_string());
         let sha = pr.as_ref().map(|p| p.head.sha.clone()).unwrap_or_default();
-        let checks = git::github_auth::get_check_runs(&token, &sha).map_err(|e| e.to_string());
-        let files = git::github_auth::get_pr_files(&token, number).map_err(|e| e.to_string());
-        let reviews = git::github_auth::get_pr_reviews(&token, number).map_err(|e| e.to_string());
+        let checks = client.get_check_runs(&sha).map_err(|e| e.to_string());
+        let files = client.get_pr_files(number).map_err(|e| e.to_string());
+        let reviews = client.get_pr_reviews(number).map_err(|e| e.to_string());
         if let Ok(mut r) = bg.lock() {
             *r = Some(PrBgResult::PrDetail {
                 pr,
@@ -1828,14 +1847,14 @@ fn render_pr_reviews(f: &mut Frame, area: Rect, state: &GitHubState) {
         );
         lines.push(Line::from(vec![icon, user, state_text]));
 
-        if let Some(ref body) = review.body {
-            if !body.is_empty() {
-                for body_line in body.lines().take(3) {
-                    lines.push(Line::from(Span::styled(
-                        format!("      {}", body_line),
-                        Style::default().fg(Color::DarkGray),
-                    )));
-                }
+        if let Some(ref body) = review.body
+            && !body.is_empty()
+        {
+            for body_line in body.lines().take(3) {
+                lines.push(Line::from(Span::styled(
+                    format!("      {}", body_line),
+                    Style::default().fg(Color::DarkGray),
+                )));
             }
         }
         lines.push(Line::from(""));
@@ -1863,21 +1882,18 @@ fn handle_pull_requests_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow:
             app.github_state.view = GitHubView::Menu;
             app.github_state.pr_state.error = None;
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.github_state.pr_state.selected > 0 {
-                app.github_state.pr_state.selected -= 1;
-                let sel = app.github_state.pr_state.selected;
-                app.github_state.pr_state.list_state.select(Some(sel));
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.github_state.pr_state.selected > 0 => {
+            app.github_state.pr_state.selected -= 1;
+            let sel = app.github_state.pr_state.selected;
+            app.github_state.pr_state.list_state.select(Some(sel));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        KeyCode::Down | KeyCode::Char('j')
             if !app.github_state.pr_state.prs.is_empty()
-                && app.github_state.pr_state.selected + 1 < app.github_state.pr_state.prs.len()
-            {
-                app.github_state.pr_state.selected += 1;
-                let sel = app.github_state.pr_state.selected;
-                app.github_state.pr_state.list_state.select(Some(sel));
-            }
+                && app.github_state.pr_state.selected + 1 < app.github_state.pr_state.prs.len() =>
+        {
+            app.github_state.pr_state.selected += 1;
+            let sel = app.github_state.pr_state.selected;
+            app.github_state.pr_state.list_state.select(Some(sel));
         }
         KeyCode::Enter => {
             if let Some(pr) = app
@@ -1946,20 +1962,20 @@ fn handle_pr_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Res
         },
         KeyCode::Char('m') => {
             // Merge PR
-            if let Some(pr) = app.github_state.pr_state.detail_pr.as_ref() {
-                if pr.state == "open" {
-                    let number = pr.number;
-                    let method = app.github_state.pr_state.merge_method.label().to_string();
-                    app.popup = crate::app::Popup::Confirm {
-                        title: "Merge Pull Request".to_string(),
-                        message: format!(
-                            "Merge PR #{} using {}?\n\n[y] Yes  [n] No",
-                            number,
-                            app.github_state.pr_state.merge_method.display()
-                        ),
-                        on_confirm: crate::app::ConfirmAction::MergePullRequest { number, method },
-                    };
-                }
+            if let Some(pr) = app.github_state.pr_state.detail_pr.as_ref()
+                && pr.state == "open"
+            {
+                let number = pr.number;
+                let method = app.github_state.pr_state.merge_method.label().to_string();
+                app.popup = crate::app::Popup::Confirm {
+                    title: "Merge Pull Request".to_string(),
+                    message: format!(
+                        "Merge PR #{} using {}?\n\n[y] Yes  [n] No",
+                        number,
+                        app.github_state.pr_state.merge_method.display()
+                    ),
+                    on_confirm: crate::app::ConfirmAction::MergePullRequest { number, method },
+                };
             }
         }
         KeyCode::Char('M') => {
@@ -1968,18 +1984,15 @@ fn handle_pr_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Res
         }
         KeyCode::Char('c') => {
             // Close PR
-            if let Some(pr) = app.github_state.pr_state.detail_pr.as_ref() {
-                if pr.state == "open" {
-                    let number = pr.number;
-                    app.popup = crate::app::Popup::Confirm {
-                        title: "Close Pull Request".to_string(),
-                        message: format!(
-                            "Close PR #{} without merging?\n\n[y] Yes  [n] No",
-                            number,
-                        ),
-                        on_confirm: crate::app::ConfirmAction::ClosePullRequest(number),
-                    };
-                }
+            if let Some(pr) = app.github_state.pr_state.detail_pr.as_ref()
+                && pr.state == "open"
+            {
+                let number = pr.number;
+                app.popup = crate::app::Popup::Confirm {
+                    title: "Close Pull Request".to_string(),
+                    message: format!("Close PR #{} without merging?\n\n[y] Yes  [n] No", number,),
+                    on_confirm: crate::app::ConfirmAction::ClosePullRequest(number),
+                };
             }
         }
         KeyCode::Char('o') => {
@@ -2392,22 +2405,19 @@ fn handle_actions_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Resul
         KeyCode::Esc => {
             app.github_state.view = GitHubView::Menu;
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.github_state.actions_state.selected > 0 {
-                app.github_state.actions_state.selected -= 1;
-                let sel = app.github_state.actions_state.selected;
-                app.github_state.actions_state.list_state.select(Some(sel));
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.github_state.actions_state.selected > 0 => {
+            app.github_state.actions_state.selected -= 1;
+            let sel = app.github_state.actions_state.selected;
+            app.github_state.actions_state.list_state.select(Some(sel));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        KeyCode::Down | KeyCode::Char('j')
             if !app.github_state.actions_state.runs.is_empty()
                 && app.github_state.actions_state.selected + 1
-                    < app.github_state.actions_state.runs.len()
-            {
-                app.github_state.actions_state.selected += 1;
-                let sel = app.github_state.actions_state.selected;
-                app.github_state.actions_state.list_state.select(Some(sel));
-            }
+                    < app.github_state.actions_state.runs.len() =>
+        {
+            app.github_state.actions_state.selected += 1;
+            let sel = app.github_state.actions_state.selected;
+            app.github_state.actions_state.list_state.select(Some(sel));
         }
         KeyCode::Enter => {
             if let Some(run) = app
@@ -2512,10 +2522,18 @@ fn handle_action_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow:
 fn start_load_actions(app: &mut crate::app::App) {
     app.github_state.actions_state.loading = true;
     app.github_state.actions_state.error = None;
-    let token = app.config.github.get_token().unwrap_or_default();
+    let client = match git::github_auth::GithubClient::for_origin(&app.config.github) {
+        Ok(client) => client,
+        Err(e) => {
+            app.github_state.actions_state.loading = false;
+            app.github_state.actions_state.error = Some(e.to_string());
+            return;
+        }
+    };
     let bg = app.github_state.actions_state.bg_result.clone();
     std::thread::spawn(move || {
-        let result = git::github_auth::list_workflow_runs(&token)
+        let result = client
+            .list_workflow_runs()
             .map(|r| r.workflow_runs)
             .map_err(|e| e.to_string());
         if let Ok(mut r) = bg.lock() {
@@ -2527,10 +2545,18 @@ fn start_load_actions(app: &mut crate::app::App) {
 fn start_load_action_detail(app: &mut crate::app::App, run_id: u64) {
     app.github_state.actions_state.loading = true;
     app.github_state.actions_state.error = None;
-    let token = app.config.github.get_token().unwrap_or_default();
+    let client = match git::github_auth::GithubClient::for_origin(&app.config.github) {
+        Ok(client) => client,
+        Err(e) => {
+            app.github_state.actions_state.loading = false;
+            app.github_state.actions_state.error = Some(e.to_string());
+            return;
+        }
+    };
     let bg = app.github_state.actions_state.bg_result.clone();
     std::thread::spawn(move || {
-        let result = git::github_auth::list_run_jobs(&token, run_id)
+        let result = client
+            .list_run_jobs(run_id)
             .map(|r| r.jobs)
             .map_err(|e| e.to_string());
         if let Ok(mut r) = bg.lock() {
@@ -2541,10 +2567,17 @@ fn start_load_action_detail(app: &mut crate::app::App, run_id: u64) {
 
 fn start_load_job_logs(app: &mut crate::app::App, job_id: u64) {
     app.github_state.actions_state.loading = true;
-    let token = app.config.github.get_token().unwrap_or_default();
+    let client = match git::github_auth::GithubClient::for_origin(&app.config.github) {
+        Ok(client) => client,
+        Err(e) => {
+            app.github_state.actions_state.loading = false;
+            app.github_state.actions_state.error = Some(e.to_string());
+            return;
+        }
+    };
     let bg = app.github_state.actions_state.bg_result.clone();
     std::thread::spawn(move || {
-        let result = git::github_auth::get_job_logs(&token, job_id).map_err(|e| e.to_string());
+        let result = client.get_job_logs(job_id).map_err(|e| e.to_string());
         if let Ok(mut r) = bg.lock() {
             *r = Some(ActionsBgResult::JobLogs(job_id, result));
         }
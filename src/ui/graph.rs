@@ -0,0 +1,452 @@
+//! Lane-based commit graph computed from `CommitEntry::parents`.
+//!
+//! Commits are laid out one row each, newest first. Every lane waits for a
+//! specific commit; a commit takes the leftmost lane waiting for it and hands
+//! that lane (and its colour) to its first parent, so a branch keeps one
+//! colour down the whole graph. Extra parents open new lanes. The layout is
+//! incremental: pushing the next page of commits continues the same lanes.
+
+use ratatui::{
+    style::{Color, Style},
+    text::Span,
+};
+
+use crate::git::CommitEntry;
+
+/// Branch colours, assigned in order as lanes open.
+const LANE_COLORS: [Color; 8] = [
+    Color::Magenta,
+    Color::Cyan,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightMagenta,
+];
+
+/// One character of a graph row and the lane colour it is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub glyph: char,
+    pub color: Option<usize>,
+}
+
+impl Cell {
+    const EMPTY: Cell = Cell {
+        glyph: ' ',
+        color: None,
+    };
+}
+
+/// The graph drawn beside one commit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphRow {
+    /// Two cells per lane: the lane itself and the gap to its right.
+    pub cells: Vec<Cell>,
+    /// Lane colours still open below this row, for drawing filler rows.
+    pub lanes_below: Vec<Option<usize>>,
+    /// The commit only continues its own lane: it was expected, has one
+    /// parent and opens or closes no other lane. Runs of these can fold.
+    pub linear: bool,
+}
+
+impl GraphRow {
+    pub fn spans(&self) -> Vec<Span<'static>> {
+        cell_spans(&self.cells)
+    }
+
+    /// Filler drawn in place of folded commits below this row.
+    pub fn filler_spans(&self) -> Vec<Span<'static>> {
+        let cells: Vec<Cell> = self
+            .lanes_below
+            .iter()
+            .flat_map(|lane| {
+                let glyph = if lane.is_some() { '┆' } else { ' ' };
+                [
+                    Cell {
+                        glyph,
+                        color: *lane,
+                    },
+                    Cell::EMPTY,
+                ]
+            })
+            .collect();
+        cell_spans(&cells)
+    }
+}
+
+fn cell_spans(cells: &[Cell]) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut run_color: Option<usize> = None;
+    for cell in cells {
+        if cell.color != run_color && !run.is_empty() {
+            spans.push(styled(std::mem::take(&mut run), run_color));
+        }
+        run_color = cell.color;
+        run.push(cell.glyph);
+    }
+    if !run.is_empty() {
+        spans.push(styled(run, run_color));
+    }
+    spans
+}
+
+fn styled(text: String, color: Option<usize>) -> Span<'static> {
+    match color {
+        Some(c) => Span::styled(
+            text,
+            Style::default().fg(LANE_COLORS[c % LANE_COLORS.len()]),
+        ),
+        None => Span::raw(text),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Lane {
+    /// Commit this lane continues to.
+    waiting_for: String,
+    color: usize,
+}
+
+/// How a lane other than the commit's own is touched on the commit's row.
+#[derive(Debug, Clone, Copy)]
+enum Link {
+    /// A branch from above merges into this commit and ends.
+    End,
+    /// A new lane starts for one of the commit's extra parents.
+    Start,
+    /// An extra parent is already awaited by this lane.
+    Join,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct GraphLayout {
+    lanes: Vec<Option<Lane>>,
+    next_color: usize,
+    pub rows: Vec<GraphRow>,
+}
+
+impl GraphLayout {
+    pub fn new(commits: &[CommitEntry]) -> Self {
+        let mut layout = Self::default();
+        layout.extend(commits);
+        layout
+    }
+
+    /// Lay out further commits that follow the ones already pushed.
+    pub fn extend(&mut self, commits: &[CommitEntry]) {
+        for commit in commits {
+            self.push(commit);
+        }
+    }
+
+    fn open_color(&mut self) -> usize {
+        let color = self.next_color;
+        self.next_color += 1;
+        color
+    }
+
+    /// First free lane, ignoring `reserved` lanes that are busy on this row.
+    fn free_lane(&mut self, reserved: &[usize]) -> usize {
+        let slot =
+            (0..self.lanes.len()).find(|i| self.lanes[*i].is_none() && !reserved.contains(i));
+        slot.unwrap_or_else(|| {
+            self.lanes.push(None);
+            self.lanes.len() - 1
+        })
+    }
+
+    fn push(&mut self, commit: &CommitEntry) {
+        let above: Vec<Option<usize>> = self
+            .lanes
+            .iter()
+            .map(|l| l.as_ref().map(|l| l.color))
+            .collect();
+        let waiting: Vec<usize> = (0..self.lanes.len())
+            .filter(|&i| {
+                self.lanes[i]
+                    .as_ref()
+                    .is_some_and(|l| l.waiting_for == commit.hash)
+            })
+            .collect();
+
+        // A commit nobody waits for is a branch tip and opens a lane
+        let (col, color) = match waiting.first() {
+            Some(&col) => (col, self.lanes[col].as_ref().map_or(0, |l| l.color)),
+            None => {
+                let col = self.free_lane(&[]);
+                (col, self.open_color())
+            }
+        };
+
+        let mut links: Vec<(usize, Link, usize)> = Vec::new();
+        let mut reserved = waiting.clone();
+        reserved.push(col);
+        for &lane in &waiting[waiting.len().min(1)..] {
+            let lane_color = self.lanes[lane].as_ref().map_or(color, |l| l.color);
+            links.push((lane, Link::End, lane_color));
+        }
+
+        match commit.parents.split_first() {
+            None => self.lanes[col] = None,
+            Some((first, rest)) => {
+                self.lanes[col] = Some(Lane {
+                    waiting_for: first.clone(),
+                    color,
+                });
+                for parent in rest {
+                    let existing = (0..self.lanes.len()).find(|&i| {
+                        i != col
+                            && !waiting.contains(&i)
+                            && self.lanes[i]
+                                .as_ref()
+                                .is_some_and(|l| &l.waiting_for == parent)
+                    });
+                    match existing {
+                        Some(lane) => {
+                            let lane_color = self.lanes[lane].as_ref().map_or(color, |l| l.color);
+                            links.push((lane, Link::Join, lane_color));
+                        }
+                        None => {
+                            let lane = self.free_lane(&reserved);
+                            let lane_color = self.open_color();
+                            self.lanes[lane] = Some(Lane {
+                                waiting_for: parent.clone(),
+                                color: lane_color,
+                            });
+                            reserved.push(lane);
+                            links.push((lane, Link::Start, lane_color));
+                        }
+                    }
+                }
+            }
+        }
+        for &lane in &waiting[waiting.len().min(1)..] {
+            self.lanes[lane] = None;
+        }
+        while self.lanes.last().is_some_and(Option::is_none) {
+            self.lanes.pop();
+        }
+
+        let linear = !waiting.is_empty() && commit.parents.len() == 1 && links.is_empty();
+        let cells = draw_row(&above, col, color, commit.parents.len() > 1, &links);
+        let lanes_below = self
+            .lanes
+            .iter()
+            .map(|l| l.as_ref().map(|l| l.color))
+            .collect();
+        self.rows.push(GraphRow {
+            cells,
+            lanes_below,
+            linear,
+        });
+    }
+}
+
+fn draw_row(
+    above: &[Option<usize>],
+    col: usize,
+    color: usize,
+    merge: bool,
+    links: &[(usize, Link, usize)],
+) -> Vec<Cell> {
+    let width = links
+        .iter()
+        .map(|(lane, _, _)| *lane)
+        .chain([col, above.len().saturating_sub(1)])
+        .max()
+        .unwrap_or(0)
+        + 1;
+
+    // Colour of the horizontal line passing position `x` (in half-lanes), if any
+    let horizontal = |x: usize| {
+        links.iter().find_map(|&(lane, _, c)| {
+            let (lo, hi) = (col.min(lane) * 2, col.max(lane) * 2);
+            (lo < x && x < hi).then_some(c)
+        })
+    };
+
+    let mut cells = Vec::with_capacity(width * 2);
+    for lane in 0..width {
+        let passing = above.get(lane).copied().flatten();
+        let cell = if lane == col {
+            Cell {
+                glyph: if merge { '○' } else { '●' },
+                color: Some(color),
+            }
+        } else if let Some(&(_, link, c)) = links.iter().find(|(l, _, _)| *l == lane) {
+            let right = lane > col;
+            let glyph = match link {
+                Link::End if right => '╯',
+                Link::End => '╰',
+                Link::Start if right => '╮',
+                Link::Start => '╭',
+                Link::Join if right => '┤',
+                Link::Join => '├',
+            };
+            Cell {
+                glyph,
+                color: Some(c),
+            }
+        } else {
+            match (passing, horizontal(lane * 2)) {
+                (Some(p), Some(_)) => Cell {
+                    glyph: '┼',
+                    color: Some(p),
+                },
+                (Some(p), None) => Cell {
+                    glyph: '│',
+                    color: Some(p),
+                },
+                (None, Some(h)) => Cell {
+                    glyph: '─',
+                    color: Some(h),
+                },
+                (None, None) => Cell::EMPTY,
+            }
+        };
+        cells.push(cell);
+        cells.push(match horizontal(lane * 2 + 1) {
+            Some(h) => Cell {
+                glyph: '─',
+                color: Some(h),
+            },
+            None => Cell::EMPTY,
+        });
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(hash: &str, parents: &[&str]) -> CommitEntry {
+        CommitEntry {
+            hash: hash.to_string(),
+            short_hash: hash.to_string(),
+            message: String::new(),
+            author: String::new(),
+            date: String::new(),
+            date_iso: String::new(),
+            parents: parents.iter().map(|p| p.to_string()).collect(),
+            refs: String::new(),
+        }
+    }
+
+    fn text(row: &GraphRow) -> String {
+        row.cells
+            .iter()
+            .map(|c| c.glyph)
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    }
+
+    /// m merges feature branch f2-f1 into main b-a.
+    fn merge_history() -> Vec<CommitEntry> {
+        vec![
+            commit("m", &["b", "f2"]),
+            commit("f2", &["f1"]),
+            commit("b", &["a"]),
+            commit("f1", &["a"]),
+            commit("a", &[]),
+        ]
+    }
+
+    #[test]
+    fn test_linear_history_uses_one_lane() {
+        let layout =
+            GraphLayout::new(&[commit("c", &["b"]), commit("b", &["a"]), commit("a", &[])]);
+        let rows: Vec<String> = layout.rows.iter().map(text).collect();
+        assert_eq!(rows, ["●", "●", "●"]);
+        assert!(!layout.rows[0].linear); // branch tip
+        assert!(layout.rows[1].linear);
+        assert!(!layout.rows[2].linear); // root
+    }
+
+    #[test]
+    fn test_merge_and_fork_connectors() {
+        let layout = GraphLayout::new(&merge_history());
+        let rows: Vec<String> = layout.rows.iter().map(text).collect();
+        assert_eq!(rows, ["○─╮", "│ ●", "● │", "│ ●", "●─╯"]);
+    }
+
+    #[test]
+    fn test_branch_keeps_its_colour() {
+        let layout = GraphLayout::new(&merge_history());
+        let color_of = |row: usize, cell: usize| layout.rows[row].cells[cell].color;
+        // main line
+        assert_eq!(color_of(0, 0), color_of(2, 0));
+        assert_eq!(color_of(2, 0), color_of(4, 0));
+        // feature lane opened by the merge
+        assert_eq!(color_of(0, 2), color_of(1, 2));
+        assert_eq!(color_of(1, 2), color_of(3, 2));
+        assert_ne!(color_of(0, 0), color_of(0, 2));
+    }
+
+    #[test]
+    fn test_extending_matches_full_layout() {
+        // 30 stacked feature merges, loaded in chunks that split branches
+        let history: Vec<CommitEntry> = (0..30)
+            .flat_map(|k| {
+                let id = |name: &str| format!("{}{}", name, k);
+                let next = format!("m{}", k + 1);
+                [
+                    commit(&id("m"), &[&id("b"), &id("f2")]),
+                    commit(&id("f2"), &[&id("f1")]),
+                    commit(&id("b"), &[&id("a")]),
+                    commit(&id("f1"), &[&id("a")]),
+                    commit(&id("a"), &[&next]),
+                ]
+            })
+            .collect();
+        let full = GraphLayout::new(&history);
+        let mut paged = GraphLayout::default();
+        for chunk in history.chunks(7) {
+            paged.extend(chunk);
+        }
+        assert_eq!(full.rows, paged.rows);
+        assert_eq!(text(&full.rows[100]), "○─╮");
+    }
+
+    #[test]
+    fn test_connector_crosses_passing_lane() {
+        // Lanes 0 and 2 end at a while lane 1 passes through to c
+        let layout = GraphLayout::new(&[
+            commit("t1", &["a"]),
+            commit("t2", &["c"]),
+            commit("t3", &["a"]),
+            commit("a", &["c"]),
+            commit("c", &[]),
+        ]);
+        let rows: Vec<String> = layout.rows.iter().map(text).collect();
+        assert_eq!(rows, ["●", "│ ●", "│ │ ●", "●─┼─╯", "●─╯"]);
+    }
+
+    #[test]
+    fn test_merge_joins_awaited_parent() {
+        // m's second parent is already awaited by the lane of tip t
+        let layout = GraphLayout::new(&[
+            commit("t", &["p"]),
+            commit("m", &["a", "p"]),
+            commit("p", &["a"]),
+            commit("a", &[]),
+        ]);
+        let rows: Vec<String> = layout.rows.iter().map(text).collect();
+        assert_eq!(rows[1], "├─○");
+    }
+
+    #[test]
+    fn test_filler_spans_follow_open_lanes() {
+        let layout = GraphLayout::new(&merge_history());
+        let filler: String = layout.rows[1]
+            .filler_spans()
+            .iter()
+            .map(|s| s.content.to_string())
+            .collect();
+        assert_eq!(filler.trim_end(), "┆ ┆");
+    }
+}
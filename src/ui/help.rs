@@ -1,9 +1,9 @@
 use ratatui::{
+    Frame,
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
-    Frame,
 };
 
 use super::utils::centered_rect;
@@ -32,6 +32,12 @@ pub fn render(f: &mut Frame, area: Rect, current_view: View) {
             ("B", "Open Bisect view"),
             ("p", "Open Cherry Pick view"),
             ("A", "Open Agent Mode"),
+            ("W", "Open Worktrees view"),
+            ("S", "Open Submodules view"),
+            ("T", "Open Tags & Releases view"),
+            ("R", "Open Interactive Rebase"),
+            ("H", "Open Secret Audit of history"),
+            ("L", "Open Git LFS view"),
             ("Tab", "Switch panel focus"),
             ("?", "Toggle this help"),
             ("q", "Quit / Unfocus AI"),
@@ -41,11 +47,21 @@ pub fn render(f: &mut Frame, area: Rect, current_view: View) {
             ("↑/↓ or j/k", "Navigate files"),
             ("Space", "Toggle stage/unstage"),
             ("h", "Toggle hunk mode"),
+            ("s (hunk mode)", "Split hunk at context gaps"),
+            ("Enter/l (hunk mode)", "Select individual lines"),
+            ("d (hunk mode)", "Discard hunk or selected lines"),
+            ("Space/v (line mode)", "Toggle line selection"),
+            ("a (line mode)", "Select all lines in hunk"),
+            ("Enter (line mode)", "Stage/unstage selected lines"),
             ("A or Ctrl+A", "Stage all files"),
             ("u", "Unstage all files"),
             ("R or Ctrl+R", "AI diff review"),
             ("/", "Search files"),
             ("c", "Open Commit view"),
+            ("L", "History of selected file"),
+            ("b", "Blame selected file"),
+            ("t", "Track file type with Git LFS"),
+            ("v", "Toggle side-by-side diff"),
             ("PgDn/PgUp", "Scroll diff"),
             ("q", "Back to Dashboard"),
         ],
@@ -63,15 +79,23 @@ pub fn render(f: &mut Frame, area: Rect, current_view: View) {
             ("n", "Create new branch"),
             ("d", "Delete branch"),
             ("R", "Rename current branch"),
+            ("W", "Create worktree for branch"),
             ("Tab", "Toggle local/remote"),
             ("q", "Back to Dashboard"),
         ],
         View::Timeline => vec![
-            ("↑/↓ or j/k", "Navigate commits"),
-            ("Enter", "View commit details & diff"),
-            ("/", "Search commits by message"),
+            ("↑/↓ or j/k", "Navigate commits (loads more on demand)"),
+            ("Enter", "View commit details & diff / expand ⋯ run"),
+            ("z", "Fold / show all linear runs"),
+            ("v (detail)", "Toggle side-by-side diff"),
+            (
+                "/",
+                "Search commits (author: committer: since: until: path: -S: -G: merges:)",
+            ),
+            ("F", "History of a file (follows renames)"),
             ("y", "Copy commit hash"),
-            ("PgDn/PgUp", "Next/prev page"),
+            ("R", "Interactive rebase onto this commit"),
+            ("PgDn/PgUp", "Scroll by 20 commits"),
             ("q", "Back to Dashboard"),
         ],
         View::TimeTravel => vec![
@@ -85,6 +109,7 @@ pub fn render(f: &mut Frame, area: Rect, current_view: View) {
         View::Reflog => vec![
             ("↑/↓ or j/k", "Navigate entries"),
             ("Enter", "View diff"),
+            ("v (diff)", "Toggle side-by-side diff"),
             ("b", "Create branch from entry"),
             ("f", "Cycle operation filter"),
             ("c", "Clear filter"),
@@ -111,6 +136,7 @@ pub fn render(f: &mut Frame, area: Rect, current_view: View) {
             ("d", "Drop stash entry"),
             ("n", "New stash (push)"),
             ("D", "Clear all stashes"),
+            ("v", "Toggle side-by-side diff"),
             ("PgDn/PgUp", "Scroll diff"),
             ("q", "Back to Dashboard"),
         ],
@@ -161,6 +187,7 @@ pub fn render(f: &mut Frame, area: Rect, current_view: View) {
             ("Space", "Toggle mark commit for multi-pick"),
             ("c", "Continue after conflict"),
             ("A", "Abort cherry-pick"),
+            ("v", "Toggle side-by-side diff"),
             ("PgDn/PgUp", "Scroll diff"),
             ("Esc", "Back to branch select"),
             ("q", "Back to Dashboard"),
@@ -177,6 +204,93 @@ pub fn render(f: &mut Frame, area: Rect, current_view: View) {
             ("Esc", "Exit input / Back"),
             ("q", "Back to Dashboard"),
         ],
+        View::Worktrees => vec![
+            ("↑/↓ or j/k", "Navigate worktrees"),
+            ("Enter", "Jump into worktree"),
+            ("n", "New worktree with a new branch"),
+            ("d", "Remove worktree"),
+            ("l", "Lock / unlock worktree"),
+            ("p", "Prune stale worktrees"),
+            ("q", "Back to Dashboard"),
+        ],
+        View::Submodules => vec![
+            ("↑/↓ or j/k", "Navigate submodules"),
+            ("i", "Init submodule"),
+            ("u", "Update (clone / check out recorded commit)"),
+            ("U", "Update all submodules recursively"),
+            ("s", "Sync URL from .gitmodules"),
+            ("PgDn/PgUp", "Scroll detail"),
+            ("q", "Back to Dashboard"),
+        ],
+        View::Rebase => vec![
+            ("↑/↓ or j/k", "Navigate"),
+            ("Enter", "Choose base / start rebase"),
+            ("p/r/e/s/f/d", "Pick, reword, edit, squash, fixup, drop"),
+            ("Space", "Cycle action"),
+            ("J/K", "Move commit later / earlier"),
+            ("c", "Continue (while rebasing)"),
+            ("s", "Skip step (while rebasing)"),
+            ("A", "Abort rebase"),
+            ("m", "Resolve conflicts"),
+            ("Esc", "Back to base selection"),
+            ("q", "Back to Dashboard"),
+        ],
+        View::FileHistory => vec![
+            ("↑/↓ or j/k", "Navigate commits"),
+            ("v", "Toggle side-by-side diff"),
+            ("b", "Blame file at this commit"),
+            ("y", "Copy commit hash"),
+            ("PgDn/PgUp", "Scroll diff"),
+            ("Esc", "Back to previous view"),
+            ("q", "Back to Dashboard"),
+        ],
+        View::Blame => vec![
+            ("↑/↓ or j/k", "Navigate lines"),
+            ("J/K", "Next / previous commit group"),
+            ("PgDn/PgUp", "Page through the file"),
+            ("Home/End", "First / last line"),
+            ("p", "Blame the parent revision of this line's commit"),
+            ("Backspace", "Back to the newer revision"),
+            ("Enter/t", "Show commit in Timeline"),
+            ("w", "Ask AI why this changed"),
+            ("y", "Copy commit hash"),
+            ("Esc", "Back to previous view"),
+            ("q", "Back to Dashboard"),
+        ],
+        View::SecretAudit => vec![
+            ("↑/↓ or j/k", "Navigate findings"),
+            ("s", "Rescan history"),
+            ("r", "Set revision range (empty for all refs)"),
+            ("x", "Cancel running scan"),
+            ("Enter", "Blame the file at the introducing commit"),
+            ("t", "Show commit in Timeline"),
+            ("y", "Copy commit hash"),
+            ("Esc/q", "Back to Dashboard"),
+        ],
+        View::Lfs => vec![
+            ("Tab", "Switch between patterns, objects and locks"),
+            ("↑/↓ or j/k", "Navigate"),
+            ("a", "Track a new pattern"),
+            ("d", "Untrack selected pattern"),
+            ("f", "Fetch selected missing object"),
+            ("F", "Fetch all missing objects"),
+            ("Enter", "Open file in Staging"),
+            ("r", "Reload (including locks)"),
+            ("q", "Back to Dashboard"),
+        ],
+        View::Tags => vec![
+            ("↑/↓ or j/k", "Navigate tags"),
+            ("n", "New lightweight tag on HEAD"),
+            ("a", "New annotated tag on HEAD"),
+            ("s", "New signed tag on HEAD"),
+            ("d", "Delete local tag"),
+            ("p", "Push tag to origin"),
+            ("P", "Push all tags to origin"),
+            ("i", "Draft release notes with AI"),
+            ("R", "Create GitHub Release"),
+            ("PgDn/PgUp", "Scroll detail"),
+            ("q", "Back to Dashboard"),
+        ],
     };
 
     let view_name = match current_view {
@@ -194,6 +308,14 @@ pub fn render(f: &mut Frame, area: Rect, current_view: View) {
         View::Bisect => "Bisect",
         View::CherryPick => "Cherry Pick",
         View::Agent => "Agent",
+        View::Worktrees => "Worktrees",
+        View::Submodules => "Submodules",
+        View::Tags => "Tags",
+        View::Rebase => "Rebase",
+        View::FileHistory => "File History",
+        View::Blame => "Blame",
+        View::SecretAudit => "Secret Audit",
+        View::Lfs => "Git LFS",
     };
 
     let mut lines = vec![
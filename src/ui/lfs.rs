@@ -0,0 +1,364 @@
+//! Git LFS panel — tracked patterns, objects in the index (and whether
+//! they're downloaded), and locks held on the server.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::git;
+use crate::git::lfs::{LfsLock, LfsObject, TrackedPattern};
+use crate::git::policy::format_size;
+
+/// Which list has the cursor.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum LfsFocus {
+    #[default]
+    Patterns,
+    Objects,
+    Locks,
+}
+
+impl LfsFocus {
+    fn next(self) -> Self {
+        match self {
+            LfsFocus::Patterns => LfsFocus::Objects,
+            LfsFocus::Objects => LfsFocus::Locks,
+            LfsFocus::Locks => LfsFocus::Patterns,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct LfsState {
+    /// `git-lfs` is on the PATH; locks and fetching need it.
+    pub installed: bool,
+    pub patterns: Vec<TrackedPattern>,
+    pub objects: Vec<LfsObject>,
+    pub locks: Vec<LfsLock>,
+    /// Why locks couldn't be listed.
+    pub locks_error: Option<String>,
+    pub focus: LfsFocus,
+    pub patterns_state: ListState,
+    pub objects_state: ListState,
+    pub locks_state: ListState,
+}
+
+impl LfsState {
+    /// Reload patterns and objects. Locks need the server, so they are
+    /// only reloaded by `refresh_locks`.
+    pub fn refresh(&mut self) {
+        self.installed = git::lfs::is_installed();
+        self.patterns = git::lfs::tracked_patterns().unwrap_or_default();
+        self.objects = git::lfs::objects().unwrap_or_default();
+        clamp(&mut self.patterns_state, self.patterns.len());
+        clamp(&mut self.objects_state, self.objects.len());
+    }
+
+    pub fn refresh_locks(&mut self) {
+        match git::lfs::locks() {
+            Ok(locks) => {
+                self.locks = locks;
+                self.locks_error = None;
+            }
+            Err(e) => {
+                self.locks.clear();
+                self.locks_error = Some(e.to_string());
+            }
+        }
+        clamp(&mut self.locks_state, self.locks.len());
+    }
+
+    pub fn missing_count(&self) -> usize {
+        self.objects.iter().filter(|o| !o.present).count()
+    }
+
+    pub fn selected_pattern(&self) -> Option<&TrackedPattern> {
+        self.patterns.get(self.patterns_state.selected()?)
+    }
+
+    pub fn selected_object(&self) -> Option<&LfsObject> {
+        self.objects.get(self.objects_state.selected()?)
+    }
+
+    /// Path of the object or lock under the cursor.
+    fn selected_path(&self) -> Option<String> {
+        match self.focus {
+            LfsFocus::Patterns => None,
+            LfsFocus::Objects => self.selected_object().map(|o| o.path.clone()),
+            LfsFocus::Locks => self
+                .locks
+                .get(self.locks_state.selected()?)
+                .map(|l| l.path.clone()),
+        }
+    }
+
+    fn move_cursor(&mut self, down: bool) {
+        let (state, len) = match self.focus {
+            LfsFocus::Patterns => (&mut self.patterns_state, self.patterns.len()),
+            LfsFocus::Objects => (&mut self.objects_state, self.objects.len()),
+            LfsFocus::Locks => (&mut self.locks_state, self.locks.len()),
+        };
+        let Some(current) = state.selected() else {
+            return;
+        };
+        if down && current + 1 < len {
+            state.select(Some(current + 1));
+        } else if !down && current > 0 {
+            state.select(Some(current - 1));
+        }
+    }
+}
+
+/// Keep a list's selection inside `len` items.
+fn clamp(state: &mut ListState, len: usize) {
+    state.select(match (state.selected(), len) {
+        (_, 0) => None,
+        (Some(i), _) => Some(i.min(len - 1)),
+        (None, _) => Some(0),
+    });
+}
+
+fn list_block(title: String, focused: bool) -> Block<'static> {
+    Block::default()
+        .title(Span::styled(title, Style::default().fg(Color::White)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if focused {
+            Color::Cyan
+        } else {
+            Color::DarkGray
+        }))
+}
+
+fn highlight() -> Style {
+    Style::default()
+        .bg(Color::DarkGray)
+        .add_modifier(Modifier::BOLD)
+}
+
+/// One-line hint drawn inside an empty list.
+fn render_hint(f: &mut Frame, area: Rect, text: &str) {
+    if area.height < 3 {
+        return;
+    }
+    let hint = Paragraph::new(Line::from(Span::styled(
+        format!(" {}", text),
+        Style::default().fg(Color::DarkGray),
+    )));
+    let hint_area = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: 1,
+    };
+    f.render_widget(hint, hint_area);
+}
+
+pub fn render(f: &mut Frame, area: Rect, state: &mut LfsState) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(40), // Patterns + locks
+            Constraint::Percentage(60), // Objects
+        ])
+        .split(area);
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(columns[0]);
+
+    // ── Tracked patterns ──
+    let items: Vec<ListItem> = state
+        .patterns
+        .iter()
+        .map(|p| {
+            let mut spans = vec![Span::styled(
+                format!(" {}", p.pattern),
+                Style::default().fg(Color::White),
+            )];
+            if p.lockable {
+                spans.push(Span::styled(
+                    "  lockable",
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            if p.source != ".gitattributes" {
+                spans.push(Span::styled(
+                    format!("  {}", p.source),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(list_block(
+            format!(" Tracked Patterns ({}) ", state.patterns.len()),
+            state.focus == LfsFocus::Patterns,
+        ))
+        .highlight_style(highlight())
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(list, left[0], &mut state.patterns_state);
+    if state.patterns.is_empty() {
+        render_hint(f, left[0], "Nothing tracked — press a to add a pattern.");
+    }
+
+    // ── Locks ──
+    let items: Vec<ListItem> = state
+        .locks
+        .iter()
+        .map(|lock| {
+            ListItem::new(Line::from(vec![
+                Span::styled(" 🔒 ", Style::default().fg(Color::Yellow)),
+                Span::styled(&lock.path, Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("  {}", lock.owner.name),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    format!("  {}", lock.locked_at.get(..10).unwrap_or(&lock.locked_at)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(list_block(
+            format!(" Locks ({}) ", state.locks.len()),
+            state.focus == LfsFocus::Locks,
+        ))
+        .highlight_style(highlight())
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(list, left[1], &mut state.locks_state);
+    if let Some(err) = &state.locks_error {
+        render_hint(f, left[1], err);
+    } else if state.locks.is_empty() {
+        render_hint(f, left[1], "No locks.");
+    }
+
+    // ── Objects ──
+    let items: Vec<ListItem> = state
+        .objects
+        .iter()
+        .map(|obj| {
+            let (icon, color, note) = if obj.present {
+                ("✓", Color::Green, "")
+            } else {
+                ("✗", Color::Red, "  missing")
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {} ", icon),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(&obj.path, Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("  {}", format_size(obj.pointer.size)),
+                    Style::default().fg(Color::Magenta),
+                ),
+                Span::styled(note, Style::default().fg(Color::Red)),
+            ]))
+        })
+        .collect();
+    let mut title = format!(
+        " Objects ({}, {} missing locally) ",
+        state.objects.len(),
+        state.missing_count()
+    );
+    if !state.installed {
+        title.push_str("— git-lfs not installed ");
+    }
+    let list = List::new(items)
+        .block(list_block(title, state.focus == LfsFocus::Objects))
+        .highlight_style(highlight())
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(list, columns[1], &mut state.objects_state);
+    if state.objects.is_empty() {
+        render_hint(f, columns[1], "No LFS files in the index.");
+    }
+}
+
+pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    let mut status_msg: Option<String> = None;
+    let mut ai_error: Option<String> = None;
+    let mut open_path: Option<String> = None;
+
+    {
+        let state = &mut app.lfs_state;
+
+        let mut run = |label: String, result: anyhow::Result<String>| match result {
+            Ok(_) => status_msg = Some(label),
+            Err(e) => {
+                let err_str = e.to_string();
+                status_msg = Some(format!("Error: {}", err_str));
+                ai_error = Some(err_str);
+            }
+        };
+
+        match key.code {
+            KeyCode::Tab => state.focus = state.focus.next(),
+            KeyCode::Up | KeyCode::Char('k') => state.move_cursor(false),
+            KeyCode::Down | KeyCode::Char('j') => state.move_cursor(true),
+            KeyCode::Char('d') if state.focus == LfsFocus::Patterns => {
+                if let Some(pattern) = state.selected_pattern().cloned() {
+                    run(
+                        format!("Stopped tracking '{}' with LFS", pattern.pattern),
+                        git::lfs::untrack(&pattern).map(|()| String::new()),
+                    );
+                    state.refresh();
+                }
+            }
+            KeyCode::Char('f') => match state.selected_object().cloned() {
+                Some(obj) if state.focus == LfsFocus::Objects && !obj.present => {
+                    run(
+                        format!("Fetched {}", obj.path),
+                        git::lfs::pull(Some(&obj.path)),
+                    );
+                    state.refresh();
+                }
+                _ => status_msg = Some("Select a missing object to fetch".to_string()),
+            },
+            KeyCode::Char('F') => {
+                run(
+                    "Fetched missing LFS objects".to_string(),
+                    git::lfs::pull(None),
+                );
+                state.refresh();
+            }
+            KeyCode::Char('r') => {
+                state.refresh();
+                state.refresh_locks();
+                status_msg = Some("LFS state reloaded".to_string());
+            }
+            KeyCode::Enter => open_path = state.selected_path(),
+            _ => {}
+        }
+    }
+
+    if key.code == KeyCode::Char('a') {
+        app.popup = crate::app::Popup::Input {
+            title: "Track with Git LFS".to_string(),
+            prompt: "Pattern: ".to_string(),
+            value: String::new(),
+            on_submit: crate::app::InputAction::LfsTrack,
+        };
+    }
+
+    if let Some(path) = open_path {
+        app.open_in_staging(&path);
+    }
+
+    if let Some(msg) = status_msg {
+        app.set_status(&msg);
+    }
+
+    if let Some(err) = ai_error {
+        app.start_ai_error_explain(err);
+    }
+
+    Ok(())
+}
@@ -2,11 +2,11 @@
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
-    Frame,
 };
 
 use crate::app::{FollowUpAction, FollowUpItem, Popup, View};
@@ -105,12 +105,12 @@ impl MergeResolveState {
         self.ai_resolved_content = None;
         self.follow_ups.clear();
 
-        if let Some(file) = self.conflicted_files.get(self.selected_file) {
-            if let Ok(conflict_file) = git::merge::get_conflict_file(&file.path) {
-                self.raw_conflict_content = Some(conflict_file.raw_content);
-                self.conflict_regions = conflict_file.regions;
-                self.total_lines = conflict_file.total_lines;
-            }
+        if let Some(file) = self.conflicted_files.get(self.selected_file)
+            && let Ok(conflict_file) = git::merge::get_conflict_file(&file.path)
+        {
+            self.raw_conflict_content = Some(conflict_file.raw_content);
+            self.conflict_regions = conflict_file.regions;
+            self.total_lines = conflict_file.total_lines;
         }
     }
 }
@@ -671,42 +671,42 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 .conflicted_files
                 .get(app.merge_resolve_state.selected_file)
                 .map(|f| f.path.clone());
-            if let Some(path) = path {
-                if let Some(ref content) = app.merge_resolve_state.ai_resolved_content.clone() {
-                    match git::merge::resolve_file(&path, content) {
-                        Ok(()) => {
-                            app.set_status(format!("✓ Applied AI resolution to {}", path));
-                            app.merge_resolve_state.refresh();
-                            // Show follow-up
-                            if app.merge_resolve_state.conflicted_files.is_empty() {
-                                app.popup = Popup::FollowUp {
-                                    title: "🎉 All Conflicts Resolved!".to_string(),
-                                    context: "All merge conflicts have been resolved.".to_string(),
-                                    suggestions: vec![
-                                        FollowUpItem {
-                                            label: "Continue merge".to_string(),
-                                            description: "Finalize the merge operation".to_string(),
-                                            action: FollowUpAction::ContinueMerge,
-                                        },
-                                        FollowUpItem {
-                                            label: "Review changes".to_string(),
-                                            description: "Go to staging view to review".to_string(),
-                                            action: FollowUpAction::SwitchToView(View::Staging),
-                                        },
-                                        FollowUpItem {
-                                            label: "Commit now".to_string(),
-                                            description: "Go to commit view".to_string(),
-                                            action: FollowUpAction::CommitNow,
-                                        },
-                                    ],
-                                    selected: 0,
-                                };
-                            }
-                        }
-                        Err(e) => {
-                            app.set_status(format!("Error: {}", e));
+            if let Some(path) = path
+                && let Some(ref content) = app.merge_resolve_state.ai_resolved_content.clone()
+            {
+                match git::merge::resolve_file(&path, content) {
+                    Ok(()) => {
+                        app.set_status(format!("✓ Applied AI resolution to {}", path));
+                        app.merge_resolve_state.refresh();
+                        // Show follow-up
+                        if app.merge_resolve_state.conflicted_files.is_empty() {
+                            app.popup = Popup::FollowUp {
+                                title: "🎉 All Conflicts Resolved!".to_string(),
+                                context: "All merge conflicts have been resolved.".to_string(),
+                                suggestions: vec![
+                                    FollowUpItem {
+                                        label: "Continue merge".to_string(),
+                                        description: "Finalize the merge operation".to_string(),
+                                        action: FollowUpAction::ContinueMerge,
+                                    },
+                                    FollowUpItem {
+                                        label: "Review changes".to_string(),
+                                        description: "Go to staging view to review".to_string(),
+                                        action: FollowUpAction::SwitchToView(View::Staging),
+                                    },
+                                    FollowUpItem {
+                                        label: "Commit now".to_string(),
+                                        description: "Go to commit view".to_string(),
+                                        action: FollowUpAction::CommitNow,
+                                    },
+                                ],
+                                selected: 0,
+                            };
                         }
                     }
+                    Err(e) => {
+                        app.set_status(format!("Error: {}", e));
+                    }
                 }
             }
         }
@@ -896,76 +896,73 @@ fn resolve_current_region(app: &mut crate::app::App, choice: &str) -> anyhow::Re
         .get(state.selected_file)
         .map(|f| f.path.clone());
 
-    if let Some(path) = file_path {
-        if let Some(region) = state.conflict_regions.get(state.selected_region) {
-            let region = region.clone();
-            match git::merge::resolve_region(&path, &region, choice) {
-                Ok(new_content) => {
-                    // Write the resolved content
-                    match std::fs::write(&path, &new_content) {
-                        Ok(()) => {
-                            let label = if choice == "current" {
-                                "current"
-                            } else {
-                                "incoming"
-                            };
+    if let Some(path) = file_path
+        && let Some(region) = state.conflict_regions.get(state.selected_region)
+    {
+        let region = region.clone();
+        match git::merge::resolve_region(&path, &region, choice) {
+            Ok(new_content) => {
+                // Write the resolved content
+                match std::fs::write(&path, &new_content) {
+                    Ok(()) => {
+                        let label = if choice == "current" {
+                            "current"
+                        } else {
+                            "incoming"
+                        };
+                        app.set_status(format!(
+                            "✓ Accepted {} changes in region {} of {}",
+                            label,
+                            app.merge_resolve_state.selected_region + 1,
+                            path
+                        ));
+
+                        // Reload the file to check for remaining conflicts
+                        app.merge_resolve_state.load_selected_file();
+
+                        // If no more conflict regions in this file, stage it
+                        if app.merge_resolve_state.conflict_regions.is_empty() {
+                            let _ = git::run_git(&["add", &path]);
                             app.set_status(format!(
-                                "✓ Accepted {} changes in region {} of {}",
-                                label,
-                                app.merge_resolve_state.selected_region + 1,
+                                "✓ All conflicts resolved in {} — file staged",
                                 path
                             ));
+                            app.merge_resolve_state.refresh();
 
-                            // Reload the file to check for remaining conflicts
-                            app.merge_resolve_state.load_selected_file();
-
-                            // If no more conflict regions in this file, stage it
-                            if app.merge_resolve_state.conflict_regions.is_empty() {
-                                let _ = git::run_git(&["add", &path]);
-                                app.set_status(format!(
-                                    "✓ All conflicts resolved in {} — file staged",
-                                    path
-                                ));
-                                app.merge_resolve_state.refresh();
-
-                                // If all conflicts resolved, show follow-up
-                                if app.merge_resolve_state.conflicted_files.is_empty() {
-                                    app.popup = Popup::FollowUp {
-                                        title: "🎉 All Conflicts Resolved!".to_string(),
-                                        context: "All merge conflicts have been resolved."
-                                            .to_string(),
-                                        suggestions: vec![
-                                            FollowUpItem {
-                                                label: "Continue merge".to_string(),
-                                                description: "Finalize the merge operation"
-                                                    .to_string(),
-                                                action: FollowUpAction::ContinueMerge,
-                                            },
-                                            FollowUpItem {
-                                                label: "Review changes".to_string(),
-                                                description: "Go to staging view to review"
-                                                    .to_string(),
-                                                action: FollowUpAction::SwitchToView(View::Staging),
-                                            },
-                                            FollowUpItem {
-                                                label: "Commit now".to_string(),
-                                                description: "Go to commit view".to_string(),
-                                                action: FollowUpAction::CommitNow,
-                                            },
-                                        ],
-                                        selected: 0,
-                                    };
-                                }
+                            // If all conflicts resolved, show follow-up
+                            if app.merge_resolve_state.conflicted_files.is_empty() {
+                                app.popup = Popup::FollowUp {
+                                    title: "🎉 All Conflicts Resolved!".to_string(),
+                                    context: "All merge conflicts have been resolved.".to_string(),
+                                    suggestions: vec![
+                                        FollowUpItem {
+                                            label: "Continue merge".to_string(),
+                                            description: "Finalize the merge operation".to_string(),
+                                            action: FollowUpAction::ContinueMerge,
+                                        },
+                                        FollowUpItem {
+                                            label: "Review changes".to_string(),
+                                            description: "Go to staging view to review".to_string(),
+                                            action: FollowUpAction::SwitchToView(View::Staging),
+                                        },
+                                        FollowUpItem {
+                                            label: "Commit now".to_string(),
+                                            description: "Go to commit view".to_string(),
+                                            action: FollowUpAction::CommitNow,
+                                        },
+                                    ],
+                                    selected: 0,
+                                };
                             }
                         }
-                        Err(e) => {
-                            app.set_status(format!("Error writing file: {}", e));
-                        }
+                    }
+                    Err(e) => {
+                        app.set_status(format!("Error writing file: {}", e));
                     }
                 }
-                Err(e) => {
-                    app.set_status(format!("Error resolving region: {}", e));
-                }
+            }
+            Err(e) => {
+                app.set_status(format!("Error resolving region: {}", e));
             }
         }
     }
@@ -1,17 +1,28 @@
 pub mod agent;
 pub mod ai_mentor;
 pub mod bisect;
+pub mod blame;
 pub mod branches;
 pub mod cherry_pick;
 pub mod commit;
 pub mod dashboard;
+pub mod diff_view;
+pub mod file_history;
 pub mod github;
+pub mod graph;
 pub mod help;
+pub mod lfs;
 pub mod merge_resolve;
+pub mod rebase;
 pub mod reflog;
+pub mod secret_audit;
 pub mod staging;
 pub mod stash;
+pub mod submodules;
+pub mod syntax;
+pub mod tags;
 pub mod time_travel;
 pub mod timeline;
 pub mod utils;
 pub mod workflow_builder;
+pub mod worktrees;
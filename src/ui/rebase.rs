@@ -0,0 +1,637 @@
+//! Interactive rebase UI — choose a base, edit the todo (reorder, pick,
+//! reword, edit, squash, fixup, drop), preview the result and drive the
+//! rebase until it finishes.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::git;
+use crate::git::log::CommitEntry;
+use crate::git::rebase::{PreviewCommit, RebaseAction, TodoEntry};
+
+/// Commits offered as a base.
+const BASE_CANDIDATES: usize = 100;
+
+/// Sub-view within the Rebase screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RebaseMode {
+    /// Selecting the commit to rebase onto.
+    BaseSelect,
+    /// Editing the todo list.
+    EditTodo,
+    /// A rebase is running (stopped at an `edit` step or a conflict).
+    InProgress,
+}
+
+pub struct RebaseState {
+    mode: RebaseMode,
+    /// Recent commits on the current branch, newest first.
+    pub commits: Vec<CommitEntry>,
+    pub base_selected: usize,
+    pub base_list_state: ListState,
+
+    /// Commit the todo is replayed onto.
+    pub base: Option<String>,
+    pub todo: Vec<TodoEntry>,
+    pub selected: usize,
+    pub list_state: ListState,
+    /// Predicted history after the rebase, oldest first.
+    pub preview: Vec<PreviewCommit>,
+    /// Why the todo cannot run as it stands.
+    pub problem: Option<String>,
+
+    /// Steps applied / total while a rebase is running.
+    pub progress: Option<(usize, usize)>,
+    pub conflicts: Vec<String>,
+}
+
+impl Default for RebaseState {
+    fn default() -> Self {
+        Self {
+            mode: RebaseMode::BaseSelect,
+            commits: Vec::new(),
+            base_selected: 0,
+            base_list_state: ListState::default(),
+            base: None,
+            todo: Vec::new(),
+            selected: 0,
+            list_state: ListState::default(),
+            preview: Vec::new(),
+            problem: None,
+            progress: None,
+            conflicts: Vec::new(),
+        }
+    }
+}
+
+impl RebaseState {
+    pub fn refresh(&mut self) {
+        if git::rebase::in_progress() {
+            self.mode = RebaseMode::InProgress;
+            self.progress = git::rebase::progress();
+            self.conflicts = git::status::get_status()
+                .map(|s| s.conflicts.into_iter().map(|f| f.path).collect())
+                .unwrap_or_default();
+            return;
+        }
+        if self.mode == RebaseMode::InProgress {
+            // The rebase finished or was aborted elsewhere
+            self.mode = RebaseMode::BaseSelect;
+        }
+        if self.mode == RebaseMode::BaseSelect {
+            self.commits = git::log::get_range("HEAD", BASE_CANDIDATES).unwrap_or_default();
+            if self.base_selected >= self.commits.len() {
+                self.base_selected = self.commits.len().saturating_sub(1);
+            }
+            self.base_list_state.select(if self.commits.is_empty() {
+                None
+            } else {
+                Some(self.base_selected)
+            });
+        }
+    }
+
+    /// Load the todo for rebasing onto `base` and switch to editing it.
+    pub fn plan(&mut self, base: &str) -> anyhow::Result<()> {
+        let todo = git::rebase::todo_for(Some(base))?;
+        if todo.is_empty() {
+            anyhow::bail!("No commits after {} to rebase", &base[..7.min(base.len())]);
+        }
+        self.base = Some(base.to_string());
+        self.todo = todo;
+        self.selected = 0;
+        self.list_state.select(Some(0));
+        self.mode = RebaseMode::EditTodo;
+        self.update_preview();
+        Ok(())
+    }
+
+    /// Drop the todo and go back to choosing a base.
+    pub fn reset(&mut self) {
+        self.mode = RebaseMode::BaseSelect;
+        self.todo.clear();
+        self.preview.clear();
+        self.problem = None;
+        self.base = None;
+        self.refresh();
+    }
+
+    pub fn selected_entry_mut(&mut self) -> Option<&mut TodoEntry> {
+        self.todo.get_mut(self.selected)
+    }
+
+    pub fn update_preview(&mut self) {
+        self.preview = git::rebase::preview(&self.todo);
+        self.problem = git::rebase::validate(&self.todo)
+            .err()
+            .map(|e| e.to_string());
+    }
+
+    fn set_action(&mut self, action: RebaseAction) {
+        if let Some(entry) = self.todo.get_mut(self.selected) {
+            entry.action = action;
+            if action != RebaseAction::Reword {
+                entry.new_message = None;
+            }
+        }
+        self.update_preview();
+    }
+
+    /// Move the selected commit one step earlier (`up`) or later.
+    fn move_selected(&mut self, up: bool) {
+        let target = if up {
+            self.selected.checked_sub(1)
+        } else {
+            Some(self.selected + 1).filter(|i| *i < self.todo.len())
+        };
+        if let Some(target) = target {
+            self.todo.swap(self.selected, target);
+            self.selected = target;
+            self.list_state.select(Some(target));
+            self.update_preview();
+        }
+    }
+}
+
+fn action_color(action: RebaseAction) -> Color {
+    match action {
+        RebaseAction::Pick => Color::Green,
+        RebaseAction::Reword => Color::Cyan,
+        RebaseAction::Edit => Color::Yellow,
+        RebaseAction::Squash | RebaseAction::Fixup => Color::Magenta,
+        RebaseAction::Drop => Color::Red,
+    }
+}
+
+pub fn render(f: &mut Frame, area: Rect, state: &mut RebaseState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(5),    // Content
+            Constraint::Length(3), // Keys
+        ])
+        .split(area);
+
+    let (header_text, keys) = match state.mode {
+        RebaseMode::BaseSelect => (
+            "  Select the commit to rebase onto — every commit above it can be edited".to_string(),
+            vec![("↑/↓", "Navigate"), ("Enter", "Rebase onto this commit")],
+        ),
+        RebaseMode::EditTodo => (
+            format!(
+                "  {} commit(s) onto {} → {} after rebase",
+                state.todo.len(),
+                state
+                    .base
+                    .as_deref()
+                    .map(|b| &b[..7.min(b.len())])
+                    .unwrap_or("root"),
+                state.preview.len()
+            ),
+            vec![
+                ("p/r/e/s/f/d", "Action"),
+                ("Space", "Cycle"),
+                ("J/K", "Move"),
+                ("Enter", "Start"),
+                ("Esc", "Back"),
+            ],
+        ),
+        RebaseMode::InProgress => (
+            match state.progress {
+                Some((done, total)) => format!("  Rebase in progress — step {}/{}", done, total),
+                None => "  Rebase in progress".to_string(),
+            },
+            vec![
+                ("c", "Continue"),
+                ("s", "Skip step"),
+                ("A", "Abort"),
+                ("m", "Resolve conflicts"),
+            ],
+        ),
+    };
+
+    let header = Paragraph::new(Line::from(Span::styled(
+        header_text,
+        Style::default().fg(Color::White),
+    )))
+    .block(
+        Block::default()
+            .title(Span::styled(
+                " Interactive Rebase ",
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+    f.render_widget(header, chunks[0]);
+
+    match state.mode {
+        RebaseMode::BaseSelect => render_base_select(f, chunks[1], state),
+        RebaseMode::EditTodo => render_todo(f, chunks[1], state),
+        RebaseMode::InProgress => render_in_progress(f, chunks[1], state),
+    }
+
+    let mut key_spans = Vec::new();
+    for (key, label) in keys.into_iter().chain([("q", "Dashboard")]) {
+        key_spans.push(Span::styled(
+            format!(" [{}]", key),
+            Style::default().fg(Color::Cyan),
+        ));
+        key_spans.push(Span::raw(format!(" {} ", label)));
+    }
+    let keys = Paragraph::new(Line::from(key_spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(keys, chunks[2]);
+}
+
+fn render_base_select(f: &mut Frame, area: Rect, state: &mut RebaseState) {
+    let items: Vec<ListItem> = state
+        .commits
+        .iter()
+        .map(|c| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {} ", c.short_hash),
+                    Style::default().fg(Color::Magenta),
+                ),
+                Span::styled(&c.message, Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("  {} · {}", c.author, c.date),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    " Base commit ",
+                    Style::default().fg(Color::White),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(list, area, &mut state.base_list_state);
+}
+
+fn render_todo(f: &mut Frame, area: Rect, state: &mut RebaseState) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(55), // Todo
+            Constraint::Percentage(45), // Preview
+        ])
+        .split(area);
+
+    let items: Vec<ListItem> = state
+        .todo
+        .iter()
+        .map(|entry| {
+            let summary = match entry.new_message {
+                Some(ref msg) => {
+                    format!("{} ← {}", msg.lines().next().unwrap_or(""), entry.summary)
+                }
+                None => entry.summary.clone(),
+            };
+            let summary_style = if entry.action == RebaseAction::Drop {
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::CROSSED_OUT)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {:<7}", entry.action.as_str()),
+                    Style::default()
+                        .fg(action_color(entry.action))
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("{} ", entry.short_hash),
+                    Style::default().fg(Color::Magenta),
+                ),
+                Span::styled(summary, summary_style),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    " Todo (oldest first) ",
+                    Style::default().fg(Color::White),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(list, chunks[0], &mut state.list_state);
+
+    let mut lines: Vec<Line> = Vec::new();
+    if let Some(ref problem) = state.problem {
+        lines.push(Line::from(Span::styled(
+            format!(" ⚠ {}", problem),
+            Style::default().fg(Color::Red),
+        )));
+        lines.push(Line::from(""));
+    }
+    // Newest first, like the log
+    for commit in state.preview.iter().rev() {
+        let mut spans = vec![
+            Span::styled(" ● ", Style::default().fg(Color::Green)),
+            Span::styled(&commit.summary, Style::default().fg(Color::White)),
+            Span::styled(
+                format!("  ({})", commit.sources.join("+")),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+        if commit.stops {
+            spans.push(Span::styled(
+                "  ⏸ stop to amend",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(Span::styled(
+        format!(
+            " ○ {}",
+            state
+                .base
+                .as_deref()
+                .map(|b| &b[..7.min(b.len())])
+                .unwrap_or("root")
+        ),
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let preview = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    " Result preview ",
+                    Style::default().fg(Color::White),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(preview, chunks[1]);
+}
+
+fn render_in_progress(f: &mut Frame, area: Rect, state: &RebaseState) {
+    let mut lines = Vec::new();
+    if state.conflicts.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  Stopped for amending. Make your changes, stage them and press [c] to continue.",
+            Style::default().fg(Color::Yellow),
+        )));
+        lines.push(Line::from(Span::styled(
+            "  Staged changes are folded into the current commit.",
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  This step has {} conflicted file(s). Press [m] to resolve them.",
+                state.conflicts.len()
+            ),
+            Style::default().fg(Color::Red),
+        )));
+        lines.push(Line::from(""));
+        for path in &state.conflicts {
+            lines.push(Line::from(Span::styled(
+                format!("    ✗ {}", path),
+                Style::default().fg(Color::Red),
+            )));
+        }
+    }
+
+    let body = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(body, area);
+}
+
+pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    match app.rebase_state.mode {
+        RebaseMode::BaseSelect => handle_base_select(app, key),
+        RebaseMode::EditTodo => handle_edit_todo(app, key),
+        RebaseMode::InProgress => handle_in_progress(app, key),
+    }
+}
+
+fn handle_base_select(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    let state = &mut app.rebase_state;
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') if state.base_selected > 0 => {
+            state.base_selected -= 1;
+            state.base_list_state.select(Some(state.base_selected));
+        }
+        KeyCode::Down | KeyCode::Char('j') if state.base_selected + 1 < state.commits.len() => {
+            state.base_selected += 1;
+            state.base_list_state.select(Some(state.base_selected));
+        }
+        KeyCode::Enter => {
+            if let Some(base) = state
+                .commits
+                .get(state.base_selected)
+                .map(|c| c.hash.clone())
+                && let Err(e) = state.plan(&base)
+            {
+                app.set_status(format!("Error: {}", e));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_edit_todo(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    let state = &mut app.rebase_state;
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+            state.selected -= 1;
+            state.list_state.select(Some(state.selected));
+        }
+        KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < state.todo.len() => {
+            state.selected += 1;
+            state.list_state.select(Some(state.selected));
+        }
+        KeyCode::Char('K') => state.move_selected(true),
+        KeyCode::Char('J') => state.move_selected(false),
+        KeyCode::Char('p') => state.set_action(RebaseAction::Pick),
+        KeyCode::Char('e') => state.set_action(RebaseAction::Edit),
+        KeyCode::Char('s') => state.set_action(RebaseAction::Squash),
+        KeyCode::Char('f') => state.set_action(RebaseAction::Fixup),
+        KeyCode::Char('d') => state.set_action(RebaseAction::Drop),
+        KeyCode::Char(' ') => {
+            if let Some(action) = state.todo.get(state.selected).map(|e| e.action.next()) {
+                state.set_action(action);
+            }
+        }
+        KeyCode::Char('r') => {
+            if let Some(entry) = state.todo.get(state.selected) {
+                let current = entry
+                    .new_message
+                    .clone()
+                    .unwrap_or_else(|| entry.summary.clone());
+                app.popup = crate::app::Popup::Input {
+                    title: format!("Reword {}", entry.short_hash),
+                    prompt: "New message: ".to_string(),
+                    value: current,
+                    on_submit: crate::app::InputAction::RebaseReword,
+                };
+            }
+        }
+        KeyCode::Enter => {
+            if let Some(ref problem) = state.problem {
+                let msg = format!("Cannot start: {}", problem);
+                app.set_status(msg);
+                return Ok(());
+            }
+            let dropped = state
+                .todo
+                .iter()
+                .filter(|e| e.action == RebaseAction::Drop)
+                .count();
+            let warning = if dropped > 0 {
+                format!("\n\n⚠ {} commit(s) will be dropped.", dropped)
+            } else {
+                String::new()
+            };
+            app.popup = crate::app::Popup::Confirm {
+                title: "Start Rebase".to_string(),
+                message: format!(
+                    "Rewrite {} commit(s) into {}?{}\n\nThe original history stays in the reflog.\n\n[y] Yes  [n] No",
+                    state.todo.len(),
+                    state.preview.len(),
+                    warning
+                ),
+                on_confirm: crate::app::ConfirmAction::StartRebase,
+            };
+        }
+        KeyCode::Esc => {
+            state.mode = RebaseMode::BaseSelect;
+            state.refresh();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_in_progress(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    match key.code {
+        KeyCode::Char('c') => {
+            let outcome = git::rebase::continue_rebase();
+            app.handle_rebase_outcome(outcome);
+        }
+        KeyCode::Char('s') => {
+            let outcome = git::rebase::skip();
+            app.handle_rebase_outcome(outcome);
+        }
+        KeyCode::Char('A') => {
+            app.popup = crate::app::Popup::Confirm {
+                title: "Abort Rebase".to_string(),
+                message: "Abort the rebase and restore the original branch?\n\n[y] Yes  [n] No"
+                    .to_string(),
+                on_confirm: crate::app::ConfirmAction::AbortMerge,
+            };
+        }
+        KeyCode::Char('m') => {
+            app.view = crate::app::View::MergeResolve;
+            app.merge_resolve_state.refresh();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with(shorts: &[&str]) -> RebaseState {
+        let mut state = RebaseState {
+            todo: shorts
+                .iter()
+                .map(|s| TodoEntry {
+                    action: RebaseAction::Pick,
+                    hash: s.to_string(),
+                    short_hash: s.to_string(),
+                    summary: format!("commit {}", s),
+                    new_message: None,
+                })
+                .collect(),
+            mode: RebaseMode::EditTodo,
+            ..RebaseState::default()
+        };
+        state.update_preview();
+        state
+    }
+
+    #[test]
+    fn test_move_selected() {
+        let mut state = state_with(&["a", "b", "c"]);
+        state.move_selected(true);
+        assert_eq!(state.selected, 0, "top entry cannot move up");
+        state.move_selected(false);
+        let order: Vec<&str> = state.todo.iter().map(|e| e.short_hash.as_str()).collect();
+        assert_eq!(order, ["b", "a", "c"]);
+        assert_eq!(state.selected, 1);
+        assert_eq!(state.preview[1].sources, ["a"]);
+    }
+
+    #[test]
+    fn test_set_action_updates_preview_and_problem() {
+        let mut state = state_with(&["a", "b"]);
+        state.selected = 1;
+        state.set_action(RebaseAction::Fixup);
+        assert_eq!(state.preview.len(), 1);
+        assert!(state.problem.is_none());
+
+        state.selected = 0;
+        state.set_action(RebaseAction::Squash);
+        assert!(state.problem.is_some());
+    }
+
+    #[test]
+    fn test_leaving_reword_clears_message() {
+        let mut state = state_with(&["a"]);
+        state.set_action(RebaseAction::Reword);
+        state.todo[0].new_message = Some("New".to_string());
+        state.set_action(RebaseAction::Pick);
+        assert!(state.todo[0].new_message.is_none());
+    }
+}
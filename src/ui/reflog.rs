@@ -1,13 +1,14 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
-    Frame,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
 };
 
 use crate::git;
+use crate::ui::diff_view;
 
 #[derive(Default)]
 pub struct ReflogState {
@@ -16,8 +17,9 @@ pub struct ReflogState {
     pub table_state: TableState,
     pub filter_op: Option<String>,
     pub show_diff: bool,
-    pub detail_diff: Vec<git::DiffLine>,
+    pub detail_diff: Vec<git::diff::FileDiff>,
     pub detail_scroll: u16,
+    pub diff_mode: diff_view::DiffMode,
 }
 
 impl ReflogState {
@@ -48,13 +50,7 @@ impl ReflogState {
         self.detail_diff.clear();
         self.detail_scroll = 0;
         if let Some(entry) = self.entries.get(self.selected) {
-            if let Ok(diffs) = git::diff::get_commit_diff(&entry.hash) {
-                for fd in &diffs {
-                    for hunk in &fd.hunks {
-                        self.detail_diff.extend(hunk.lines.clone());
-                    }
-                }
-            }
+            self.detail_diff = git::diff::get_commit_diff(&entry.hash).unwrap_or_default();
         }
     }
 }
@@ -158,40 +154,30 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut ReflogState) {
 }
 
 fn render_detail(f: &mut Frame, area: Rect, state: &ReflogState) {
-    let diff_lines: Vec<Line> = state
-        .detail_diff
-        .iter()
-        .map(|dl| {
-            let color = match dl.line_type {
-                git::DiffLineType::Added => Color::Green,
-                git::DiffLineType::Removed => Color::Red,
-                git::DiffLineType::Header => Color::Cyan,
-                git::DiffLineType::Context => Color::DarkGray,
-            };
-            Line::from(Span::styled(&dl.content, Style::default().fg(color)))
-        })
-        .collect();
-
     let title = if let Some(entry) = state.entries.get(state.selected) {
         format!(
-            " Reflog #{} — {} {} ",
-            entry.index, entry.operation, entry.short_hash
+            " Reflog #{} — {} {} ({}) ",
+            entry.index,
+            entry.operation,
+            entry.short_hash,
+            state.diff_mode.label()
         )
     } else {
         " Reflog Detail ".to_string()
     };
 
-    let diff = Paragraph::new(diff_lines)
-        .block(
-            Block::default()
-                .title(Span::styled(title, Style::default().fg(Color::White)))
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
-        )
-        .scroll((state.detail_scroll, 0))
-        .wrap(Wrap { trim: false });
-
-    f.render_widget(diff, area);
+    let block = Block::default()
+        .title(Span::styled(title, Style::default().fg(Color::White)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    diff_view::render_files(
+        f,
+        area,
+        block,
+        &state.detail_diff,
+        state.diff_mode,
+        state.detail_scroll,
+    );
 }
 
 pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
@@ -208,37 +194,35 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
             }
             KeyCode::PageDown => state.detail_scroll = state.detail_scroll.saturating_add(20),
             KeyCode::PageUp => state.detail_scroll = state.detail_scroll.saturating_sub(20),
+            KeyCode::Char('v') => {
+                state.diff_mode = state.diff_mode.toggle();
+                state.detail_scroll = 0;
+            }
             _ => {}
         }
         return Ok(());
     }
 
     match key.code {
-        KeyCode::Up | KeyCode::Char('k') => {
-            if state.selected > 0 {
-                state.selected -= 1;
-                state.table_state.select(Some(state.selected));
-            }
+        KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+            state.selected -= 1;
+            state.table_state.select(Some(state.selected));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if state.selected + 1 < state.entries.len() {
-                state.selected += 1;
-                state.table_state.select(Some(state.selected));
-            }
+        KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < state.entries.len() => {
+            state.selected += 1;
+            state.table_state.select(Some(state.selected));
         }
         KeyCode::Enter => {
             state.load_diff();
             state.show_diff = true;
         }
-        KeyCode::Char('b') => {
-            if state.entries.get(state.selected).is_some() {
-                app.popup = crate::app::Popup::Input {
-                    title: "Create Branch from Reflog".to_string(),
-                    prompt: "Branch name: ".to_string(),
-                    value: String::new(),
-                    on_submit: crate::app::InputAction::CreateBranch,
-                };
-            }
+        KeyCode::Char('b') if state.entries.get(state.selected).is_some() => {
+            app.popup = crate::app::Popup::Input {
+                title: "Create Branch from Reflog".to_string(),
+                prompt: "Branch name: ".to_string(),
+                value: String::new(),
+                on_submit: crate::app::InputAction::CreateBranch,
+            };
         }
         KeyCode::Char('f') => {
             // Cycle through operation filters
@@ -0,0 +1,444 @@
+//! Secret audit — secrets introduced anywhere in history, found by
+//! scanning every commit's patch on a background thread.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+
+use crate::app::View;
+use crate::config::SecretsConfig;
+use crate::git::secret_audit::{self, HistoryAudit, HistoryFinding};
+use crate::git::secrets;
+
+/// Sent from the scanning thread.
+enum AuditMessage {
+    Progress(usize, usize),
+    Done(Result<HistoryAudit, String>),
+}
+
+#[derive(Default)]
+pub struct SecretAuditState {
+    /// Revision range to scan; empty for all refs.
+    pub range: String,
+    pub audit: Option<HistoryAudit>,
+    pub selected: usize,
+    pub list_state: ListState,
+    /// (scanned, total) commits while a scan is running.
+    pub progress: Option<(usize, usize)>,
+    pub error: Option<String>,
+    receiver: Option<mpsc::Receiver<AuditMessage>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl SecretAuditState {
+    pub fn is_scanning(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// Start scanning `range` in the background, replacing any running scan.
+    pub fn start(&mut self, config: &SecretsConfig) {
+        self.stop();
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.cancel = cancel.clone();
+        self.receiver = Some(rx);
+        self.progress = Some((0, 0));
+        self.error = None;
+
+        let rules = secrets::rules_for(config);
+        let allowlist = config.allowlist.clone();
+        let range = self.range.trim().to_string();
+        std::thread::spawn(move || {
+            let range = Some(range.as_str()).filter(|r| !r.is_empty());
+            let progress_tx = tx.clone();
+            let result = secret_audit::audit_history(range, &rules, &allowlist, |done, total| {
+                let _ = progress_tx.send(AuditMessage::Progress(done, total));
+                !cancel.load(Ordering::Relaxed)
+            });
+            let _ = tx.send(AuditMessage::Done(result.map_err(|e| e.to_string())));
+        });
+    }
+
+    /// Cancel a running scan; the previous results stay.
+    pub fn stop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        self.receiver = None;
+        self.progress = None;
+    }
+
+    /// Drain messages from the scanning thread. Returns a status line when
+    /// the scan finishes.
+    pub fn poll(&mut self) -> Option<String> {
+        let receiver = self.receiver.as_ref()?;
+        let mut done = None;
+        while done.is_none() {
+            match receiver.try_recv() {
+                Ok(AuditMessage::Progress(scanned, total)) => {
+                    self.progress = Some((scanned, total))
+                }
+                Ok(AuditMessage::Done(result)) => done = Some(result),
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    done = Some(Err("scan stopped unexpectedly".to_string()))
+                }
+            }
+        }
+        let result = done?;
+        self.receiver = None;
+        self.progress = None;
+        match result {
+            Ok(audit) => {
+                let status = format!(
+                    "Secret audit: {} finding(s), {} still at HEAD, {} commit(s) scanned",
+                    audit.findings.len(),
+                    audit.at_head(),
+                    audit.commits_scanned
+                );
+                self.audit = Some(audit);
+                self.selected = 0;
+                self.list_state.select(Some(0));
+                Some(status)
+            }
+            Err(e) => {
+                let status = format!("Secret audit: {}", e);
+                self.error = Some(e);
+                Some(status)
+            }
+        }
+    }
+
+    fn findings(&self) -> &[HistoryFinding] {
+        self.audit.as_ref().map_or(&[], |a| &a.findings)
+    }
+
+    pub fn selected_finding(&self) -> Option<&HistoryFinding> {
+        self.findings().get(self.selected)
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        let last = self.findings().len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+        self.list_state.select(Some(self.selected));
+    }
+}
+
+/// Forward results of a background scan. Call on every tick.
+pub fn tick(app: &mut crate::app::App) {
+    if let Some(status) = app.secret_audit_state.poll() {
+        app.set_status(status);
+    }
+}
+
+pub fn render(f: &mut Frame, area: Rect, state: &mut SecretAuditState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+    render_summary(f, chunks[0], state);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(chunks[1]);
+
+    // Borrow the field directly so `list_state` stays free
+    let findings: &[HistoryFinding] = state.audit.as_ref().map_or(&[], |a| &a.findings);
+    let items: Vec<ListItem> = findings
+        .iter()
+        .map(|h| {
+            let (marker, marker_color) = if h.at_head {
+                ("● ", Color::Red)
+            } else {
+                ("○ ", Color::DarkGray)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(marker_color)),
+                Span::styled(
+                    format!("{} ", h.short_hash()),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(
+                    format!("{}:{} ", h.finding.file, h.finding.line),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(&h.finding.rule_name, Style::default().fg(Color::Cyan)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" Secrets in History ({}) ", findings.len()),
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Magenta)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(list, body[0], &mut state.list_state);
+
+    render_detail(f, body[1], state);
+}
+
+fn render_summary(f: &mut Frame, area: Rect, state: &SecretAuditState) {
+    let range = if state.range.trim().is_empty() {
+        "all refs"
+    } else {
+        state.range.trim()
+    };
+    let mut spans = vec![
+        Span::styled(" Range: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(range.to_string(), Style::default().fg(Color::White)),
+        Span::raw("   "),
+    ];
+    if let Some((scanned, total)) = state.progress {
+        spans.push(Span::styled(
+            format!("⏳ Scanning… {}/{} commits", scanned, total),
+            Style::default().fg(Color::Yellow),
+        ));
+    } else if let Some(error) = &state.error {
+        spans.push(Span::styled(
+            format!("Error: {}", error),
+            Style::default().fg(Color::Red),
+        ));
+    } else if let Some(audit) = &state.audit {
+        let color = if audit.findings.is_empty() {
+            Color::Green
+        } else {
+            Color::Red
+        };
+        spans.push(Span::styled(
+            format!(
+                "{} secret(s), {} still at HEAD, {} commit(s) scanned",
+                audit.findings.len(),
+                audit.at_head(),
+                audit.commits_scanned
+            ),
+            Style::default().fg(color),
+        ));
+    }
+
+    let summary = Paragraph::new(Line::from(spans)).block(
+        Block::default()
+            .title(Span::styled(
+                " 🛡 Secret Audit ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(summary, area);
+}
+
+fn render_detail(f: &mut Frame, area: Rect, state: &SecretAuditState) {
+    let label = |name: &str| {
+        Span::styled(
+            format!("{:<11}", name),
+            Style::default().fg(Color::DarkGray),
+        )
+    };
+    let lines = match state.selected_finding() {
+        Some(h) => {
+            let mut lines = vec![
+                Line::from(vec![
+                    label("Commit"),
+                    Span::styled(&h.commit, Style::default().fg(Color::Yellow)),
+                ]),
+                Line::from(vec![label("Author"), Span::raw(&h.author)]),
+                Line::from(vec![label("Date"), Span::raw(&h.date)]),
+                Line::from(vec![label("Summary"), Span::raw(&h.summary)]),
+                Line::from(""),
+                Line::from(vec![
+                    label("File"),
+                    Span::raw(format!("{}:{}", h.finding.file, h.finding.line)),
+                ]),
+                Line::from(vec![
+                    label("Rule"),
+                    Span::styled(&h.finding.rule_name, Style::default().fg(Color::Cyan)),
+                ]),
+                Line::from(vec![label("Preview"), Span::raw(&h.finding.preview)]),
+                Line::from(vec![
+                    label("Confidence"),
+                    Span::raw(h.finding.confidence.to_string()),
+                ]),
+                Line::from(""),
+            ];
+            if h.at_head {
+                lines.push(Line::from(Span::styled(
+                    "Still present at HEAD — remove it and rotate the credential.",
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                )));
+            } else {
+                lines.push(Line::from(Span::styled(
+                    "Only in history — rotate the credential; it stays readable in every clone until history is rewritten.",
+                    Style::default().fg(Color::Yellow),
+                )));
+            }
+            lines
+        }
+        None if state.is_scanning() => vec![Line::from(Span::styled(
+            "Scanning history…",
+            Style::default().fg(Color::DarkGray),
+        ))],
+        None if state.audit.is_some() => vec![Line::from(Span::styled(
+            "✓ No secrets found in history",
+            Style::default().fg(Color::Green),
+        ))],
+        None => vec![Line::from(Span::styled(
+            "Press s to scan history",
+            Style::default().fg(Color::DarkGray),
+        ))],
+    };
+
+    let detail = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(Span::styled(
+                " Introduced In ",
+                Style::default().fg(Color::White),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(detail, area);
+}
+
+pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    tick(app);
+    let state = &mut app.secret_audit_state;
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => state.move_by(-1),
+        KeyCode::Down | KeyCode::Char('j') => state.move_by(1),
+        KeyCode::PageUp => state.move_by(-10),
+        KeyCode::PageDown => state.move_by(10),
+        KeyCode::Char('s') => {
+            state.start(&app.config.secrets);
+            app.set_status("Scanning history for secrets…");
+        }
+        KeyCode::Char('x') if state.is_scanning() => {
+            state.stop();
+            app.set_status("Secret audit cancelled");
+        }
+        KeyCode::Char('r') => {
+            let range = state.range.clone();
+            app.popup = crate::app::Popup::Input {
+                title: "Audit Range (empty for all refs)".to_string(),
+                prompt: "Range: ".to_string(),
+                value: range,
+                on_submit: crate::app::InputAction::SecretAuditRange,
+            };
+        }
+        KeyCode::Enter => {
+            if let Some(h) = state.selected_finding() {
+                let (path, hash, line) = (h.finding.file.clone(), h.commit.clone(), h.finding.line);
+                app.open_blame(&path, Some(&hash));
+                if app.view == View::Blame {
+                    let last = app.blame_state.blame.lines.len().saturating_sub(1);
+                    app.blame_state.selected = line.saturating_sub(1).min(last);
+                }
+            }
+        }
+        KeyCode::Char('t') => {
+            if let Some(hash) = state.selected_finding().map(|h| h.commit.clone()) {
+                app.view = View::Timeline;
+                if let Err(e) = app.timeline_state.jump_to(&hash) {
+                    app.set_status(format!("Error: {}", e));
+                }
+            }
+        }
+        KeyCode::Char('y') => {
+            if let Some(hash) = state.selected_finding().map(|h| h.short_hash().to_string()) {
+                match cli_clipboard::set_contents(hash.clone()) {
+                    Ok(()) => app.set_status(format!("✓ Copied to clipboard: {}", hash)),
+                    Err(_) => app.set_status(format!("Copied (display only): {}", hash)),
+                }
+            }
+        }
+        KeyCode::Esc => {
+            app.view = View::Dashboard;
+            app.dashboard_state.refresh();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::secrets::{Confidence, SecretFinding};
+
+    fn finding(commit: &str, at_head: bool) -> HistoryFinding {
+        HistoryFinding {
+            commit: commit.to_string(),
+            author: "Ada".to_string(),
+            date: "2024-03-01".to_string(),
+            summary: "add config".to_string(),
+            finding: SecretFinding {
+                file: "app.env".to_string(),
+                line: 3,
+                rule_name: "AWS Access Key ID".to_string(),
+                preview: "AKIA****".to_string(),
+                confidence: Confidence::High,
+                secret_hash: String::new(),
+            },
+            at_head,
+        }
+    }
+
+    #[test]
+    fn test_poll_applies_finished_scan() {
+        let mut state = SecretAuditState::default();
+        let (tx, rx) = mpsc::channel();
+        state.receiver = Some(rx);
+        tx.send(AuditMessage::Progress(1, 2)).unwrap();
+        assert!(state.poll().is_none());
+        assert_eq!(state.progress, Some((1, 2)));
+
+        let audit = HistoryAudit {
+            findings: vec![finding("aaaaaaaaaa", false), finding("bbbbbbbbbb", true)],
+            commits_scanned: 2,
+        };
+        tx.send(AuditMessage::Done(Ok(audit))).unwrap();
+        let status = state.poll().unwrap();
+        assert!(status.contains("2 finding(s), 1 still at HEAD"));
+        assert!(!state.is_scanning());
+        assert_eq!(state.progress, None);
+
+        state.move_by(5);
+        assert_eq!(state.selected_finding().unwrap().short_hash(), "bbbbbbb");
+    }
+
+    #[test]
+    fn test_poll_keeps_previous_results_on_error() {
+        let mut state = SecretAuditState {
+            audit: Some(HistoryAudit::default()),
+            ..SecretAuditState::default()
+        };
+        let (tx, rx) = mpsc::channel();
+        state.receiver = Some(rx);
+        tx.send(AuditMessage::Done(Err("bad revision".to_string())))
+            .unwrap();
+        assert!(state.poll().unwrap().contains("bad revision"));
+        assert!(state.audit.is_some());
+        assert_eq!(state.error.as_deref(), Some("bad revision"));
+    }
+}
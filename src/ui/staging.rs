@@ -1,19 +1,25 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
-    Frame,
 };
 
+use std::collections::HashSet;
+
 use crate::git;
+use crate::ui::diff_view;
 
 #[derive(Debug, Clone)]
 pub struct StagingFile {
     pub path: String,
     pub status: git::FileStatus,
     pub is_staged: bool,
+    pub submodule: Option<git::status::SubmoduleState>,
+    /// Stored through Git LFS per `.gitattributes`.
+    pub lfs: bool,
 }
 
 #[derive(Default)]
@@ -28,11 +34,22 @@ pub struct StagingState {
     pub hunk_mode: bool,
     pub hunk_index: usize,
     pub file_hunks: Vec<git::diff::Hunk>,
+    pub diff_mode: diff_view::DiffMode,
+    /// Line-level selection inside the current hunk
+    pub line_mode: bool,
+    /// Cursor position, as an index into the current hunk's lines
+    pub line_cursor: usize,
+    /// Selected added/removed lines of the current hunk
+    pub selected_lines: Vec<usize>,
 }
 
 impl StagingState {
     pub fn refresh(&mut self) {
         let mut files = Vec::new();
+        let lfs_paths: HashSet<String> = git::lfs::lfs_paths()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
 
         if let Ok(status) = git::status::get_status() {
             for f in &status.staged {
@@ -40,6 +57,8 @@ impl StagingState {
                     path: f.path.clone(),
                     status: f.status.clone(),
                     is_staged: true,
+                    submodule: f.submodule,
+                    lfs: lfs_paths.contains(&f.path),
                 });
             }
             for f in &status.unstaged {
@@ -49,6 +68,8 @@ impl StagingState {
                         path: f.path.clone(),
                         status: f.status.clone(),
                         is_staged: false,
+                        submodule: f.submodule,
+                        lfs: lfs_paths.contains(&f.path),
                     });
                 }
             }
@@ -57,6 +78,8 @@ impl StagingState {
                     path: f.path.clone(),
                     status: f.status.clone(),
                     is_staged: false,
+                    submodule: None,
+                    lfs: lfs_paths.contains(&f.path),
                 });
             }
         }
@@ -72,6 +95,23 @@ impl StagingState {
         });
     }
 
+    /// Select `path`, preferring its unstaged entry. Returns `false` if it
+    /// has no changes.
+    pub fn select_path(&mut self, path: &str) -> bool {
+        let found = self
+            .files
+            .iter()
+            .position(|f| f.path == path && !f.is_staged)
+            .or_else(|| self.files.iter().position(|f| f.path == path));
+        let Some(i) = found else {
+            return false;
+        };
+        self.selected = i;
+        self.list_state.select(Some(i));
+        self.update_diff();
+        true
+    }
+
     fn filtered_files(&self) -> Vec<(usize, &StagingFile)> {
         self.files
             .iter()
@@ -88,8 +128,25 @@ impl StagingState {
         self.diff_scroll = 0;
         self.file_hunks.clear();
         self.hunk_index = 0;
+        self.exit_line_mode();
 
         if let Some(file) = self.files.get(self.selected) {
+            // Submodules: show the commits the pointer moved across
+            if let Some(sub) = file.submodule {
+                self.diff_lines = git::submodule::pointer_diff_lines(&file.path, file.is_staged)
+                    .unwrap_or_default();
+                if !file.is_staged && (sub.modified_content || sub.untracked_content) {
+                    self.diff_lines.push(git::DiffLine {
+                        line_type: git::DiffLineType::Context,
+                        content: format!(
+                            "  ({} inside the submodule — commit there first)",
+                            sub.describe()
+                        ),
+                    });
+                }
+                return;
+            }
+
             let diffs = if file.is_staged {
                 git::diff::get_staged_diff_for_file(&file.path).unwrap_or_default()
             } else {
@@ -97,7 +154,10 @@ impl StagingState {
             };
 
             if let Some(fd) = diffs.first() {
-                self.file_hunks = fd.hunks.clone();
+                // LFS objects are staged whole; there are no hunks to pick
+                if !fd.lfs {
+                    self.file_hunks = fd.hunks.clone();
+                }
                 for hunk in &fd.hunks {
                     self.diff_lines.extend(hunk.lines.clone());
                 }
@@ -116,25 +176,154 @@ impl StagingState {
 
     /// Exit hunk mode.
     fn exit_hunk_mode(&mut self) {
+        self.exit_line_mode();
         self.hunk_mode = false;
         self.hunk_index = 0;
         self.diff_scroll = 0;
     }
 
+    /// Enter line mode on the current hunk, with the cursor on its first change.
+    fn enter_line_mode(&mut self) {
+        let Some(first) = self.change_lines().first().copied() else {
+            return;
+        };
+        self.line_mode = true;
+        self.line_cursor = first;
+        self.selected_lines.clear();
+        self.scroll_to_line();
+    }
+
+    fn exit_line_mode(&mut self) {
+        self.line_mode = false;
+        self.line_cursor = 0;
+        self.selected_lines.clear();
+    }
+
+    /// Indices of the added/removed lines in the current hunk.
+    fn change_lines(&self) -> Vec<usize> {
+        self.file_hunks
+            .get(self.hunk_index)
+            .map(|hunk| {
+                hunk.lines
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, l)| {
+                        matches!(
+                            l.line_type,
+                            git::DiffLineType::Added | git::DiffLineType::Removed
+                        )
+                    })
+                    .map(|(i, _)| i)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Move the line cursor to the previous (`forward == false`) or next change line.
+    fn move_line_cursor(&mut self, forward: bool) {
+        let changes = self.change_lines();
+        let next = if forward {
+            changes.into_iter().find(|&i| i > self.line_cursor)
+        } else {
+            changes.into_iter().rev().find(|&i| i < self.line_cursor)
+        };
+        if let Some(i) = next {
+            self.line_cursor = i;
+            self.scroll_to_line();
+        }
+    }
+
+    fn toggle_line(&mut self) {
+        let cursor = self.line_cursor;
+        if let Some(pos) = self.selected_lines.iter().position(|&i| i == cursor) {
+            self.selected_lines.remove(pos);
+        } else {
+            self.selected_lines.push(cursor);
+        }
+    }
+
+    /// Lines to act on: the selection, or the cursor line when nothing is selected.
+    fn lines_to_apply(&self) -> Vec<usize> {
+        if self.selected_lines.is_empty() {
+            vec![self.line_cursor]
+        } else {
+            let mut lines = self.selected_lines.clone();
+            lines.sort_unstable();
+            lines
+        }
+    }
+
+    /// Split the current hunk at its context gaps. Returns how many pieces it became.
+    fn split_current_hunk(&mut self) -> usize {
+        let Some(hunk) = self.file_hunks.get(self.hunk_index) else {
+            return 0;
+        };
+        let pieces = git::diff::split_hunk(hunk);
+        let count = pieces.len();
+        if count > 1 {
+            self.file_hunks
+                .splice(self.hunk_index..=self.hunk_index, pieces);
+            self.diff_lines = self
+                .file_hunks
+                .iter()
+                .flat_map(|h| h.lines.iter().cloned())
+                .collect();
+            self.exit_line_mode();
+            self.scroll_to_hunk();
+        }
+        count
+    }
+
+    /// Reload status and the selected file's hunks after applying a patch,
+    /// keeping the same file and hunk position where they still exist.
+    pub fn reload_hunks(&mut self) {
+        let current = self
+            .files
+            .get(self.selected)
+            .map(|f| (f.path.clone(), f.is_staged));
+        let hunk_index = self.hunk_index;
+        self.refresh();
+        if let Some((path, is_staged)) = current
+            && let Some(i) = self
+                .files
+                .iter()
+                .position(|f| f.path == path && f.is_staged == is_staged)
+        {
+            self.selected = i;
+            self.list_state.select(Some(i));
+        }
+        self.update_diff();
+        self.exit_line_mode();
+        if self.file_hunks.is_empty() {
+            self.exit_hunk_mode();
+        } else {
+            self.hunk_index = hunk_index.min(self.file_hunks.len() - 1);
+            self.scroll_to_hunk();
+        }
+    }
+
+    /// Offset of the current hunk's first line within `diff_lines`.
+    fn hunk_offset(&self) -> usize {
+        self.file_hunks
+            .iter()
+            .take(self.hunk_index)
+            .map(|h| h.lines.len())
+            .sum()
+    }
+
+    /// Scroll so the line cursor stays in view, a few lines below the top.
+    fn scroll_to_line(&mut self) {
+        let offset = self.hunk_offset();
+        let target = (offset + self.line_cursor).saturating_sub(5).max(offset);
+        self.diff_scroll = target as u16;
+    }
+
     /// Scroll the diff view so the current hunk header is visible.
     fn scroll_to_hunk(&mut self) {
         if self.hunk_index >= self.file_hunks.len() {
             return;
         }
-        // Count lines before the current hunk
-        let mut line_offset: u16 = 0;
-        for (i, hunk) in self.file_hunks.iter().enumerate() {
-            if i == self.hunk_index {
-                break;
-            }
-            line_offset += hunk.lines.len() as u16;
-        }
-        self.diff_scroll = line_offset;
+        self.diff_scroll = self.hunk_offset() as u16;
     }
 }
 
@@ -191,6 +380,20 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut StagingState) {
                     Style::default().fg(icon_color).add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(&file.path, Style::default().fg(Color::White)),
+                Span::styled(
+                    match file.submodule {
+                        Some(sub) if !file.is_staged => {
+                            format!("  [submodule: {}]", sub.describe())
+                        }
+                        Some(_) => "  [submodule]".to_string(),
+                        None => String::new(),
+                    },
+                    Style::default().fg(Color::Magenta),
+                ),
+                Span::styled(
+                    if file.lfs { "  [LFS]" } else { "" },
+                    Style::default().fg(Color::Blue),
+                ),
             ]))
         })
         .collect();
@@ -217,22 +420,20 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut StagingState) {
 
     f.render_stateful_widget(list, chunks[0], &mut state.list_state);
 
-    // Diff preview
-    let diff_items: Vec<Line> = state
-        .diff_lines
-        .iter()
-        .map(|dl| {
-            let (color, _prefix) = match dl.line_type {
-                git::DiffLineType::Added => (Color::Green, "+"),
-                git::DiffLineType::Removed => (Color::Red, "-"),
-                git::DiffLineType::Header => (Color::Cyan, "@"),
-                git::DiffLineType::Context => (Color::DarkGray, " "),
-            };
-            Line::from(Span::styled(&dl.content, Style::default().fg(color)))
-        })
-        .collect();
-
-    let diff_title = if state.hunk_mode {
+    // Diff preview — in line mode the current hunk gets a cursor/selection gutter
+    let path = state
+        .files
+        .get(state.selected)
+        .map(|f| f.path.clone())
+        .unwrap_or_default();
+    let diff_title = if state.line_mode {
+        format!(
+            " Lines — hunk {}/{} ({} selected) ",
+            state.hunk_index + 1,
+            state.file_hunks.len(),
+            state.selected_lines.len()
+        )
+    } else if state.hunk_mode {
         let total = state.file_hunks.len();
         let current = state.hunk_index + 1;
         if let Some(file) = state.files.get(state.selected) {
@@ -241,18 +442,69 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut StagingState) {
             format!(" Hunk {}/{} ", current, total)
         }
     } else if let Some(file) = state.files.get(state.selected) {
-        format!(" Diff: {} ", file.path)
+        format!(" Diff: {} ({}) ", file.path, state.diff_mode.label())
     } else {
         " Diff Preview ".to_string()
     };
 
+    let block = Block::default()
+        .title(Span::styled(diff_title, Style::default().fg(Color::White)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+
+    if !state.hunk_mode {
+        diff_view::render_lines(
+            f,
+            chunks[1],
+            block,
+            &path,
+            &state.diff_lines,
+            state.diff_mode,
+            state.diff_scroll,
+        );
+        return;
+    }
+
+    // Hunk and line modes stay unified so hunk offsets map 1:1 to rows
+    let visible = state.diff_scroll as usize + chunks[1].height as usize;
+    let line_range = if state.line_mode {
+        let start = state.hunk_offset();
+        let len = state
+            .file_hunks
+            .get(state.hunk_index)
+            .map_or(0, |h| h.lines.len());
+        Some(start..start + len)
+    } else {
+        None
+    };
+    let diff_items: Vec<Line> = diff_view::unified_lines(&path, &state.diff_lines, 0..visible)
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let Some(range) = &line_range else {
+                return line;
+            };
+            let local = i.checked_sub(range.start).filter(|_| range.contains(&i));
+            let selected = local.is_some_and(|l| state.selected_lines.contains(&l));
+            let at_cursor = local == Some(state.line_cursor);
+            let gutter = format!(
+                "{}{}",
+                if at_cursor { "▶" } else { " " },
+                if selected { "●" } else { " " }
+            );
+            let mut spans = vec![Span::styled(gutter, Style::default().fg(Color::Yellow))];
+            spans.extend(line.spans);
+            let line = Line::from(spans);
+            if at_cursor {
+                line.style(Style::default().add_modifier(Modifier::BOLD))
+            } else {
+                line
+            }
+        })
+        .collect();
+
     let diff = Paragraph::new(diff_items)
-        .block(
-            Block::default()
-                .title(Span::styled(diff_title, Style::default().fg(Color::White)))
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
-        )
+        .block(block)
         .scroll((state.diff_scroll, 0))
         .wrap(Wrap { trim: false });
 
@@ -272,55 +524,127 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
         ScanAll(Vec<String>),
     }
     let mut deferred_stage = DeferredStage::None;
+    // (path, hunk, lines) to discard once the confirm popup can be opened
+    let mut discard: Option<(String, git::diff::Hunk, Vec<usize>)> = None;
 
     {
         let state = &mut app.staging_state;
 
-        // Hunk mode key handling
-        if state.hunk_mode {
+        // Line mode key handling
+        if state.line_mode {
             match key.code {
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if state.hunk_index > 0 {
-                        state.hunk_index -= 1;
-                        state.scroll_to_hunk();
+                KeyCode::Up | KeyCode::Char('k') => state.move_line_cursor(false),
+                KeyCode::Down | KeyCode::Char('j') => state.move_line_cursor(true),
+                KeyCode::Char(' ') | KeyCode::Char('v') => state.toggle_line(),
+                KeyCode::Char('a') => {
+                    // Select every change line, or clear when all are selected
+                    let changes = state.change_lines();
+                    if state.selected_lines.len() == changes.len() {
+                        state.selected_lines.clear();
+                    } else {
+                        state.selected_lines = changes;
+                    }
+                }
+                KeyCode::Enter => {
+                    // Stage/unstage the selected lines
+                    if let Some(file) = state.files.get(state.selected).cloned()
+                        && let Some(hunk) = state.file_hunks.get(state.hunk_index).cloned()
+                    {
+                        let lines = state.lines_to_apply();
+                        let result = if file.is_staged {
+                            git::diff::unstage_lines(&file.path, &hunk, &lines)
+                        } else {
+                            git::diff::stage_lines(&file.path, &hunk, &lines)
+                        };
+                        match result {
+                            Ok(_) => {
+                                let action = if file.is_staged { "Unstaged" } else { "Staged" };
+                                let noun = if lines.len() == 1 { "line" } else { "lines" };
+                                status_msg = Some(format!("{} {} {}", action, lines.len(), noun));
+                            }
+                            Err(e) => {
+                                let err_str = e.to_string();
+                                status_msg = Some(format!("Line staging error: {}", err_str));
+                                ai_error = Some(err_str);
+                            }
+                        }
+                        state.reload_hunks();
                     }
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if state.hunk_index + 1 < state.file_hunks.len() {
-                        state.hunk_index += 1;
-                        state.scroll_to_hunk();
+                KeyCode::Char('d') => {
+                    if let Some(file) = state.files.get(state.selected)
+                        && !file.is_staged
+                        && let Some(hunk) = state.file_hunks.get(state.hunk_index)
+                    {
+                        discard = Some((file.path.clone(), hunk.clone(), state.lines_to_apply()));
                     }
                 }
+                KeyCode::Esc => {
+                    state.exit_line_mode();
+                    state.scroll_to_hunk();
+                }
+                KeyCode::PageDown => {
+                    state.diff_scroll = state.diff_scroll.saturating_add(10);
+                }
+                KeyCode::PageUp => {
+                    state.diff_scroll = state.diff_scroll.saturating_sub(10);
+                }
+                _ => {}
+            }
+        } else if state.hunk_mode {
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') if state.hunk_index > 0 => {
+                    state.hunk_index -= 1;
+                    state.scroll_to_hunk();
+                }
+                KeyCode::Down | KeyCode::Char('j')
+                    if state.hunk_index + 1 < state.file_hunks.len() =>
+                {
+                    state.hunk_index += 1;
+                    state.scroll_to_hunk();
+                }
                 KeyCode::Char(' ') => {
                     // Stage/unstage current hunk
-                    if let Some(file) = state.files.get(state.selected).cloned() {
-                        if let Some(hunk) = state.file_hunks.get(state.hunk_index).cloned() {
-                            let result = if file.is_staged {
-                                git::diff::unstage_hunk(&file.path, &hunk)
-                            } else {
-                                git::diff::stage_hunk(&file.path, &hunk)
-                            };
-                            match result {
-                                Ok(_) => {
-                                    let action = if file.is_staged { "Unstaged" } else { "Staged" };
-                                    status_msg =
-                                        Some(format!("{} hunk {}", action, state.hunk_index + 1));
-                                }
-                                Err(e) => {
-                                    let err_str = e.to_string();
-                                    status_msg = Some(format!("Hunk error: {}", err_str));
-                                    ai_error = Some(err_str);
-                                }
+                    if let Some(file) = state.files.get(state.selected).cloned()
+                        && let Some(hunk) = state.file_hunks.get(state.hunk_index).cloned()
+                    {
+                        let result = if file.is_staged {
+                            git::diff::unstage_hunk(&file.path, &hunk)
+                        } else {
+                            git::diff::stage_hunk(&file.path, &hunk)
+                        };
+                        match result {
+                            Ok(_) => {
+                                let action = if file.is_staged { "Unstaged" } else { "Staged" };
+                                status_msg =
+                                    Some(format!("{} hunk {}", action, state.hunk_index + 1));
                             }
-                            state.refresh();
-                            // Stay in hunk mode if there are still hunks
-                            if state.file_hunks.is_empty() {
-                                state.exit_hunk_mode();
-                            } else if state.hunk_index >= state.file_hunks.len() {
-                                state.hunk_index = state.file_hunks.len() - 1;
-                                state.scroll_to_hunk();
+                            Err(e) => {
+                                let err_str = e.to_string();
+                                status_msg = Some(format!("Hunk error: {}", err_str));
+                                ai_error = Some(err_str);
                             }
                         }
+                        state.reload_hunks();
+                    }
+                }
+                KeyCode::Char('s') => {
+                    let pieces = state.split_current_hunk();
+                    status_msg = Some(if pieces > 1 {
+                        format!("Split hunk into {}", pieces)
+                    } else {
+                        "Hunk has no context gap to split at".to_string()
+                    });
+                }
+                KeyCode::Enter | KeyCode::Char('l') => {
+                    state.enter_line_mode();
+                }
+                KeyCode::Char('d') => {
+                    if let Some(file) = state.files.get(state.selected)
+                        && !file.is_staged
+                        && let Some(hunk) = state.file_hunks.get(state.hunk_index)
+                    {
+                        discard = Some((file.path.clone(), hunk.clone(), state.change_lines()));
                     }
                 }
                 KeyCode::Esc | KeyCode::Char('h') => {
@@ -336,19 +660,15 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
             }
         } else {
             match key.code {
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if state.selected > 0 {
-                        state.selected -= 1;
-                        state.list_state.select(Some(state.selected));
-                        state.update_diff();
-                    }
+                KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+                    state.selected -= 1;
+                    state.list_state.select(Some(state.selected));
+                    state.update_diff();
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if state.selected + 1 < state.files.len() {
-                        state.selected += 1;
-                        state.list_state.select(Some(state.selected));
-                        state.update_diff();
-                    }
+                KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < state.files.len() => {
+                    state.selected += 1;
+                    state.list_state.select(Some(state.selected));
+                    state.update_diff();
                 }
                 KeyCode::Char(' ') => {
                     // Toggle stage/unstage
@@ -455,6 +775,10 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 KeyCode::PageUp => {
                     state.diff_scroll = state.diff_scroll.saturating_sub(10);
                 }
+                KeyCode::Char('v') => {
+                    state.diff_mode = state.diff_mode.toggle();
+                    state.diff_scroll = 0;
+                }
                 _ => {}
             }
         } // close else block for non-hunk mode
@@ -463,28 +787,28 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
     // ── Deferred secret scanning & staging ───────────────────────────
     match deferred_stage {
         DeferredStage::ScanFile(path) => {
-            if app.config.secrets.enabled && !git::secrets::is_binary(&path) {
-                if let Ok(content) = std::fs::read_to_string(&path) {
-                    let rules = git::secrets::default_rules();
-                    let findings = git::secrets::scan_content(&path, &content, &rules);
-                    let findings: Vec<_> = findings
-                        .into_iter()
-                        .filter(|f| {
-                            !app.config.secrets.allowlist.iter().any(|a| {
-                                f.preview.contains(a)
-                                    || f.file.contains(a)
-                                    || f.rule_name.contains(a)
-                            })
+            if app.config.secrets.enabled
+                && !git::secrets::is_binary(&path)
+                && let Ok(content) = std::fs::read_to_string(&path)
+            {
+                let rules = git::secrets::rules_for(&app.config.secrets);
+                let findings = git::secrets::scan_content(&path, &content, &rules);
+                let mut findings: Vec<_> = findings
+                    .into_iter()
+                    .filter(|f| {
+                        !app.config.secrets.allowlist.iter().any(|a| {
+                            f.preview.contains(a) || f.file.contains(a) || f.rule_name.contains(a)
                         })
-                        .collect();
-                    if !findings.is_empty() {
-                        app.popup = crate::app::Popup::SecretWarning {
-                            findings,
-                            pending_action: crate::app::SecretPendingAction::StageFile(path),
-                            selected: 0,
-                        };
-                        return Ok(());
-                    }
+                    })
+                    .collect();
+                git::secret_baseline::retain_unknown(&mut findings);
+                if !findings.is_empty() {
+                    app.popup = crate::app::Popup::SecretWarning {
+                        findings,
+                        pending_action: crate::app::SecretPendingAction::StageFile(path),
+                        selected: 0,
+                    };
+                    return Ok(());
                 }
             }
             // No secrets or scanning disabled — stage normally
@@ -497,7 +821,7 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
         }
         DeferredStage::ScanAll(paths) => {
             if app.config.secrets.enabled {
-                let rules = git::secrets::default_rules();
+                let rules = git::secrets::rules_for(&app.config.secrets);
                 let mut all_findings = Vec::new();
                 for p in &paths {
                     if git::secrets::is_binary(p) {
@@ -512,6 +836,7 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                         f.preview.contains(a) || f.file.contains(a) || f.rule_name.contains(a)
                     })
                 });
+                git::secret_baseline::retain_unknown(&mut all_findings);
                 if !all_findings.is_empty() {
                     app.popup = crate::app::Popup::SecretWarning {
                         findings: all_findings,
@@ -535,6 +860,21 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
         DeferredStage::None => {}
     }
 
+    if let Some((path, hunk, lines)) = discard {
+        let noun = if lines.len() == 1 { "line" } else { "lines" };
+        app.popup = crate::app::Popup::Confirm {
+            title: "Discard Lines".to_string(),
+            message: format!(
+                "Discard {} changed {} in '{}'? This cannot be undone.",
+                lines.len(),
+                noun,
+                path
+            ),
+            on_confirm: crate::app::ConfirmAction::DiscardLines { path, hunk, lines },
+        };
+        return Ok(());
+    }
+
     // Handle actions that need full App access (no staging_state borrow)
     match key.code {
         KeyCode::Char('/') => {
@@ -546,6 +886,28 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 on_submit: crate::app::InputAction::SearchFiles,
             };
         }
+        KeyCode::Char('L') if !app.staging_state.hunk_mode => {
+            if let Some(file) = app.staging_state.files.get(app.staging_state.selected) {
+                let path = file.path.clone();
+                app.open_file_history(&path);
+            }
+        }
+        KeyCode::Char('t') if !app.staging_state.hunk_mode => {
+            if let Some(file) = app.staging_state.files.get(app.staging_state.selected) {
+                app.popup = crate::app::Popup::Input {
+                    title: "Track with Git LFS".to_string(),
+                    prompt: "Pattern: ".to_string(),
+                    value: lfs_pattern_for(&file.path),
+                    on_submit: crate::app::InputAction::LfsTrack,
+                };
+            }
+        }
+        KeyCode::Char('b') if !app.staging_state.hunk_mode => {
+            if let Some(file) = app.staging_state.files.get(app.staging_state.selected) {
+                let path = file.path.clone();
+                app.open_blame(&path, None);
+            }
+        }
         KeyCode::Char('c') => {
             app.view = crate::app::View::Commit;
             app.commit_state.refresh();
@@ -553,18 +915,15 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
         }
         KeyCode::Char('d') => {
             // Discard changes for the selected unstaged file
-            if let Some(file) = app.staging_state.files.get(app.staging_state.selected) {
-                if !file.is_staged {
-                    let path = file.path.clone();
-                    app.popup = crate::app::Popup::Confirm {
-                        title: "Discard Changes".to_string(),
-                        message: format!(
-                            "Discard all changes to '{}'? This cannot be undone.",
-                            path
-                        ),
-                        on_confirm: crate::app::ConfirmAction::DiscardFile(path),
-                    };
-                }
+            if let Some(file) = app.staging_state.files.get(app.staging_state.selected)
+                && !file.is_staged
+            {
+                let path = file.path.clone();
+                app.popup = crate::app::Popup::Confirm {
+                    title: "Discard Changes".to_string(),
+                    message: format!("Discard all changes to '{}'? This cannot be undone.", path),
+                    on_confirm: crate::app::ConfirmAction::DiscardFile(path),
+                };
             }
         }
         _ => {}
@@ -584,3 +943,103 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
     Ok(())
 }
+
+/// Pattern suggested for tracking `path` with LFS: every file with its
+/// extension, or the path itself when it has none.
+fn lfs_pattern_for(path: &str) -> String {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() && !ext.is_empty() => format!("*.{}", ext),
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::{DiffLine, DiffLineType};
+
+    #[test]
+    fn test_lfs_pattern_for() {
+        assert_eq!(lfs_pattern_for("assets/hero.psd"), "*.psd");
+        assert_eq!(lfs_pattern_for("data/dump"), "data/dump");
+        assert_eq!(lfs_pattern_for("config/.env"), "config/.env");
+    }
+
+    fn line(line_type: DiffLineType, content: &str) -> DiffLine {
+        DiffLine {
+            line_type,
+            content: content.to_string(),
+        }
+    }
+
+    fn state_with_hunk() -> StagingState {
+        let header = "@@ -1,5 +1,5 @@".to_string();
+        let lines = vec![
+            line(DiffLineType::Header, &header),
+            line(DiffLineType::Removed, "-a"),
+            line(DiffLineType::Added, "+A"),
+            line(DiffLineType::Context, " b"),
+            line(DiffLineType::Context, " c"),
+            line(DiffLineType::Removed, "-d"),
+            line(DiffLineType::Added, "+D"),
+            line(DiffLineType::Context, " e"),
+        ];
+        let hunk = git::diff::Hunk {
+            header,
+            old_start: 1,
+            old_count: 5,
+            new_start: 1,
+            new_count: 5,
+            lines: lines.clone(),
+        };
+        StagingState {
+            diff_lines: lines,
+            file_hunks: vec![hunk],
+            hunk_mode: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_line_cursor_skips_context() {
+        let mut state = state_with_hunk();
+        state.enter_line_mode();
+        assert_eq!(state.line_cursor, 1);
+        state.move_line_cursor(true);
+        state.move_line_cursor(true);
+        assert_eq!(state.line_cursor, 5);
+        state.move_line_cursor(true);
+        state.move_line_cursor(true);
+        assert_eq!(state.line_cursor, 6);
+        state.move_line_cursor(false);
+        assert_eq!(state.line_cursor, 5);
+    }
+
+    #[test]
+    fn test_lines_to_apply_defaults_to_cursor() {
+        let mut state = state_with_hunk();
+        state.enter_line_mode();
+        assert_eq!(state.lines_to_apply(), vec![1]);
+        state.move_line_cursor(true);
+        state.move_line_cursor(true);
+        state.toggle_line();
+        state.move_line_cursor(false);
+        state.toggle_line();
+        assert_eq!(state.lines_to_apply(), vec![2, 5]);
+        state.toggle_line();
+        assert_eq!(state.lines_to_apply(), vec![5]);
+    }
+
+    #[test]
+    fn test_split_current_hunk_rebuilds_diff() {
+        let mut state = state_with_hunk();
+        assert_eq!(state.split_current_hunk(), 2);
+        assert_eq!(state.file_hunks.len(), 2);
+        // Shared context lines appear in both pieces
+        assert_eq!(state.diff_lines.len(), 5 + 6);
+        state.hunk_index = 1;
+        assert_eq!(state.hunk_offset(), 5);
+        assert_eq!(state.split_current_hunk(), 1);
+    }
+}
@@ -2,22 +2,24 @@
 
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
-    Frame,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
 };
 
 use crate::git;
+use crate::ui::diff_view;
 
 #[derive(Default)]
 pub struct StashState {
     pub entries: Vec<git::stash::StashEntry>,
     pub selected: usize,
     pub list_state: ListState,
-    pub diff_text: String,
+    pub diff: Vec<git::diff::FileDiff>,
     pub diff_scroll: u16,
+    pub diff_mode: diff_view::DiffMode,
 }
 
 impl StashState {
@@ -35,13 +37,13 @@ impl StashState {
     }
 
     fn update_diff(&mut self) {
-        self.diff_text.clear();
+        self.diff.clear();
         self.diff_scroll = 0;
 
-        if let Some(entry) = self.entries.get(self.selected) {
-            if let Ok(diff) = git::stash::stash_show(entry.index) {
-                self.diff_text = diff;
-            }
+        if let Some(entry) = self.entries.get(self.selected)
+            && let Ok(diff) = git::stash::stash_show(entry.index)
+        {
+            self.diff = git::diff::parse_diff_output(&diff);
         }
     }
 }
@@ -97,33 +99,25 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut StashState) {
 
     f.render_stateful_widget(list, chunks[0], &mut state.list_state);
 
-    // Diff preview — use structured diff coloring
-    let diff_lines: Vec<Line> = state
-        .diff_text
-        .lines()
-        .map(|line| {
-            let color = crate::ui::utils::diff_line_color(line);
-            Line::from(Span::styled(line, Style::default().fg(color)))
-        })
-        .collect();
-
+    // Diff preview
     let diff_title = if let Some(entry) = state.entries.get(state.selected) {
         format!(" stash@{{{}}} ", entry.index)
     } else {
         " Stash Diff ".to_string()
     };
 
-    let diff = Paragraph::new(diff_lines)
-        .block(
-            Block::default()
-                .title(Span::styled(diff_title, Style::default().fg(Color::White)))
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
-        )
-        .scroll((state.diff_scroll, 0))
-        .wrap(Wrap { trim: false });
-
-    f.render_widget(diff, chunks[1]);
+    let block = Block::default()
+        .title(Span::styled(diff_title, Style::default().fg(Color::White)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    diff_view::render_files(
+        f,
+        chunks[1],
+        block,
+        &state.diff,
+        state.diff_mode,
+        state.diff_scroll,
+    );
 
     // Keybinding hints at bottom if area is big enough
     if area.height > 5 && state.entries.is_empty() {
@@ -157,19 +151,15 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
         let state = &mut app.stash_state;
 
         match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
-                if state.selected > 0 {
-                    state.selected -= 1;
-                    state.list_state.select(Some(state.selected));
-                    state.update_diff();
-                }
+            KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+                state.selected -= 1;
+                state.list_state.select(Some(state.selected));
+                state.update_diff();
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if state.selected + 1 < state.entries.len() {
-                    state.selected += 1;
-                    state.list_state.select(Some(state.selected));
-                    state.update_diff();
-                }
+            KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < state.entries.len() => {
+                state.selected += 1;
+                state.list_state.select(Some(state.selected));
+                state.update_diff();
             }
             KeyCode::Char('p') => {
                 // Pop stash
@@ -225,6 +215,10 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
             KeyCode::PageUp => {
                 state.diff_scroll = state.diff_scroll.saturating_sub(10);
             }
+            KeyCode::Char('v') => {
+                state.diff_mode = state.diff_mode.toggle();
+                state.diff_scroll = 0;
+            }
             _ => {}
         }
     } // release mutable borrow
@@ -239,17 +233,15 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 on_submit: crate::app::InputAction::StashPush,
             };
         }
-        KeyCode::Char('D') => {
-            if !app.stash_state.entries.is_empty() {
-                app.popup = crate::app::Popup::Confirm {
-                    title: "Clear All Stashes".to_string(),
-                    message: format!(
-                        "Drop all {} stash entries? This cannot be undone.",
-                        app.stash_state.entries.len()
-                    ),
-                    on_confirm: crate::app::ConfirmAction::ClearStash,
-                };
-            }
+        KeyCode::Char('D') if !app.stash_state.entries.is_empty() => {
+            app.popup = crate::app::Popup::Confirm {
+                title: "Clear All Stashes".to_string(),
+                message: format!(
+                    "Drop all {} stash entries? This cannot be undone.",
+                    app.stash_state.entries.len()
+                ),
+                on_confirm: crate::app::ConfirmAction::ClearStash,
+            };
         }
         _ => {}
     }
@@ -0,0 +1,309 @@
+//! Submodule panel — list submodules, init/update/sync them, and show the
+//! commit range each pointer moved across.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::git;
+use crate::git::status::SubmoduleState;
+use crate::git::submodule::{Submodule, SubmoduleSync};
+
+#[derive(Default)]
+pub struct SubmodulesState {
+    pub submodules: Vec<Submodule>,
+    /// Working-tree state of changed submodules, from `git status`.
+    pub changes: Vec<(String, SubmoduleState)>,
+    pub selected: usize,
+    pub list_state: ListState,
+    pub detail: Vec<git::DiffLine>,
+    pub detail_scroll: u16,
+}
+
+impl SubmodulesState {
+    pub fn refresh(&mut self) {
+        self.submodules = git::submodule::list().unwrap_or_default();
+        self.changes = git::status::get_status()
+            .map(|s| s.submodule_changes())
+            .unwrap_or_default();
+        if self.selected >= self.submodules.len() && !self.submodules.is_empty() {
+            self.selected = self.submodules.len() - 1;
+        }
+        self.list_state.select(if self.submodules.is_empty() {
+            None
+        } else {
+            Some(self.selected)
+        });
+        self.update_detail();
+    }
+
+    fn change_for(&self, path: &str) -> Option<SubmoduleState> {
+        self.changes
+            .iter()
+            .find(|(p, _)| p == path)
+            .map(|(_, state)| *state)
+    }
+
+    fn update_detail(&mut self) {
+        self.detail.clear();
+        self.detail_scroll = 0;
+
+        let Some(sub) = self.submodules.get(self.selected) else {
+            return;
+        };
+        let section = |title: &str| git::DiffLine {
+            line_type: git::DiffLineType::Context,
+            content: title.to_string(),
+        };
+
+        let at_head = git::submodule::recorded_commit(&sub.path, true).ok();
+        let in_index = git::submodule::recorded_commit(&sub.path, false).ok();
+
+        // Pointer change staged for the next commit
+        if at_head != in_index {
+            self.detail
+                .push(section("Staged pointer change (HEAD → index):"));
+            self.detail.extend(git::submodule::range_lines(
+                &sub.path,
+                at_head.as_deref(),
+                in_index.as_deref(),
+            ));
+            self.detail.push(section(""));
+        }
+
+        // Checkout that differs from what the superproject records
+        match sub.sync {
+            SubmoduleSync::OutOfSync => {
+                self.detail
+                    .push(section("Checked out vs recorded (index → checkout):"));
+                self.detail.extend(git::submodule::range_lines(
+                    &sub.path,
+                    in_index.as_deref(),
+                    Some(&sub.commit),
+                ));
+            }
+            SubmoduleSync::Uninitialized => {
+                self.detail
+                    .push(section("Not initialized — press u to clone and check out."));
+            }
+            SubmoduleSync::Conflict => {
+                self.detail
+                    .push(section("Pointer is conflicted — resolve the merge first."));
+            }
+            SubmoduleSync::InSync if self.detail.is_empty() => {
+                self.detail
+                    .push(section("Checked out at the recorded commit."));
+            }
+            SubmoduleSync::InSync => {}
+        }
+
+        if let Some(state) = self.change_for(&sub.path)
+            && (state.modified_content || state.untracked_content)
+        {
+            self.detail.push(section(""));
+            self.detail
+                .push(section(&format!("Working tree: {}", state.describe())));
+        }
+    }
+}
+
+pub fn render(f: &mut Frame, area: Rect, state: &mut SubmodulesState) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(40), // Submodule list
+            Constraint::Percentage(60), // Detail
+        ])
+        .split(area);
+
+    let items: Vec<ListItem> = state
+        .submodules
+        .iter()
+        .map(|sub| {
+            let (icon, color) = match sub.sync {
+                SubmoduleSync::InSync => ("✓", Color::Green),
+                SubmoduleSync::OutOfSync => ("+", Color::Yellow),
+                SubmoduleSync::Uninitialized => ("-", Color::DarkGray),
+                SubmoduleSync::Conflict => ("!", Color::Red),
+            };
+            let dirty = state
+                .change_for(&sub.path)
+                .filter(|s| s.modified_content || s.untracked_content)
+                .map(|_| " *")
+                .unwrap_or("");
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {} ", icon),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(&sub.path, Style::default().fg(Color::White)),
+                Span::styled(dirty, Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    format!("  {}", &sub.commit[..7.min(sub.commit.len())]),
+                    Style::default().fg(Color::Magenta),
+                ),
+                Span::styled(
+                    sub.describe
+                        .as_ref()
+                        .map(|d| format!(" ({})", d))
+                        .unwrap_or_default(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" Submodules ({}) ", state.submodules.len()),
+                    Style::default().fg(Color::White),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Magenta)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+
+    f.render_stateful_widget(list, chunks[0], &mut state.list_state);
+
+    let detail_lines: Vec<Line> = state
+        .detail
+        .iter()
+        .map(|dl| {
+            let color = match dl.line_type {
+                git::DiffLineType::Added => Color::Green,
+                git::DiffLineType::Removed => Color::Red,
+                git::DiffLineType::Header => Color::Cyan,
+                git::DiffLineType::Context => Color::Gray,
+            };
+            Line::from(Span::styled(&dl.content, Style::default().fg(color)))
+        })
+        .collect();
+
+    let detail_title = state
+        .submodules
+        .get(state.selected)
+        .map(|s| format!(" {} ", s.path))
+        .unwrap_or_else(|| " Submodule ".to_string());
+
+    let detail = Paragraph::new(detail_lines)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    detail_title,
+                    Style::default().fg(Color::White),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .scroll((state.detail_scroll, 0))
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(detail, chunks[1]);
+
+    if area.height > 5 && state.submodules.is_empty() {
+        let hint = Paragraph::new(Line::from(Span::styled(
+            " This repository has no submodules.",
+            Style::default().fg(Color::DarkGray),
+        )));
+        let hint_area = Rect {
+            x: chunks[0].x + 1,
+            y: chunks[0].y + 2,
+            width: chunks[0].width.saturating_sub(2),
+            height: 1,
+        };
+        f.render_widget(hint, hint_area);
+    }
+}
+
+pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    let mut status_msg: Option<String> = None;
+    let mut ai_error: Option<String> = None;
+
+    {
+        let state = &mut app.submodules_state;
+        let selected = state.submodules.get(state.selected).map(|s| s.path.clone());
+
+        // Run a submodule command and report its outcome
+        let mut run = |label: &str, result: anyhow::Result<String>| match result {
+            Ok(_) => status_msg = Some(label.to_string()),
+            Err(e) => {
+                let err_str = e.to_string();
+                status_msg = Some(format!("Error: {}", err_str));
+                ai_error = Some(err_str);
+            }
+        };
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+                state.selected -= 1;
+                state.list_state.select(Some(state.selected));
+                state.update_detail();
+            }
+            KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < state.submodules.len() => {
+                state.selected += 1;
+                state.list_state.select(Some(state.selected));
+                state.update_detail();
+            }
+            KeyCode::Char('i') => {
+                if let Some(path) = selected {
+                    run(
+                        &format!("Initialized {}", path),
+                        git::submodule::init(&path),
+                    );
+                    state.refresh();
+                }
+            }
+            KeyCode::Char('u') => {
+                if let Some(path) = selected {
+                    run(
+                        &format!("Updated {}", path),
+                        git::submodule::update(Some(&path)),
+                    );
+                    state.refresh();
+                }
+            }
+            KeyCode::Char('U') => {
+                run("Updated all submodules", git::submodule::update(None));
+                state.refresh();
+            }
+            KeyCode::Char('s') => {
+                if let Some(path) = selected {
+                    run(
+                        &format!("Synced URL for {}", path),
+                        git::submodule::sync(&path),
+                    );
+                    state.refresh();
+                }
+            }
+            KeyCode::PageDown => {
+                state.detail_scroll = state.detail_scroll.saturating_add(10);
+            }
+            KeyCode::PageUp => {
+                state.detail_scroll = state.detail_scroll.saturating_sub(10);
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(msg) = status_msg {
+        app.set_status(&msg);
+    }
+
+    if let Some(err) = ai_error {
+        app.start_ai_error_explain(err);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,432 @@
+//! Lightweight syntax colouring for diff lines.
+//!
+//! Each line is tokenized on its own (diff hunks rarely start at a clean
+//! lexical boundary anyway), so block comments and multi-line strings are
+//! only recognized on the lines where they open and close.
+
+use ratatui::style::{Color, Modifier, Style};
+use std::ops::Range;
+
+/// Kind of token found on a line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    Plain,
+    Keyword,
+    /// Capitalized identifiers — types, constructors, constants.
+    Type,
+    String,
+    Number,
+    Comment,
+}
+
+impl TokenKind {
+    pub fn style(self) -> Style {
+        match self {
+            TokenKind::Plain => Style::default(),
+            TokenKind::Keyword => Style::default().fg(Color::Magenta),
+            TokenKind::Type => Style::default().fg(Color::LightBlue),
+            TokenKind::String => Style::default().fg(Color::Yellow),
+            TokenKind::Number => Style::default().fg(Color::LightCyan),
+            TokenKind::Comment => Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        }
+    }
+}
+
+/// Lexical rules for a language family.
+#[derive(Debug)]
+pub struct Language {
+    pub name: &'static str,
+    extensions: &'static [&'static str],
+    keywords: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+}
+
+const C_LIKE_QUOTES: &[char] = &['"', '\''];
+
+const LANGUAGES: &[Language] = &[
+    Language {
+        name: "Rust",
+        extensions: &["rs"],
+        keywords: &[
+            "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+            "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+            "move", "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait",
+            "true", "type", "unsafe", "use", "where", "while",
+        ],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        // Single quotes are lifetimes as often as chars
+        quotes: &['"'],
+    },
+    Language {
+        name: "Python",
+        extensions: &["py", "pyi"],
+        keywords: &[
+            "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+            "elif", "else", "except", "False", "finally", "for", "from", "global", "if", "import",
+            "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return",
+            "True", "try", "while", "with", "yield",
+        ],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: C_LIKE_QUOTES,
+    },
+    Language {
+        name: "JavaScript",
+        extensions: &["js", "jsx", "mjs", "cjs", "ts", "tsx"],
+        keywords: &[
+            "async",
+            "await",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "continue",
+            "default",
+            "delete",
+            "else",
+            "export",
+            "extends",
+            "false",
+            "finally",
+            "for",
+            "from",
+            "function",
+            "if",
+            "import",
+            "in",
+            "instanceof",
+            "interface",
+            "let",
+            "new",
+            "null",
+            "of",
+            "return",
+            "static",
+            "switch",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "type",
+            "typeof",
+            "undefined",
+            "var",
+            "void",
+            "while",
+            "yield",
+        ],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\'', '`'],
+    },
+    Language {
+        name: "Go",
+        extensions: &["go"],
+        keywords: &[
+            "break",
+            "case",
+            "chan",
+            "const",
+            "continue",
+            "default",
+            "defer",
+            "else",
+            "false",
+            "for",
+            "func",
+            "go",
+            "goto",
+            "if",
+            "import",
+            "interface",
+            "map",
+            "nil",
+            "package",
+            "range",
+            "return",
+            "select",
+            "struct",
+            "switch",
+            "true",
+            "type",
+            "var",
+        ],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\'', '`'],
+    },
+    Language {
+        name: "C",
+        extensions: &[
+            "c", "h", "cc", "cpp", "cxx", "hpp", "hh", "java", "kt", "cs", "swift", "scala",
+        ],
+        keywords: &[
+            "auto",
+            "bool",
+            "break",
+            "case",
+            "catch",
+            "char",
+            "class",
+            "const",
+            "continue",
+            "default",
+            "delete",
+            "do",
+            "double",
+            "else",
+            "enum",
+            "extends",
+            "false",
+            "final",
+            "float",
+            "for",
+            "fun",
+            "func",
+            "if",
+            "import",
+            "int",
+            "interface",
+            "let",
+            "long",
+            "namespace",
+            "new",
+            "null",
+            "nullptr",
+            "override",
+            "package",
+            "private",
+            "protected",
+            "public",
+            "return",
+            "short",
+            "static",
+            "struct",
+            "switch",
+            "template",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "typedef",
+            "unsigned",
+            "using",
+            "val",
+            "var",
+            "virtual",
+            "void",
+            "while",
+        ],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: C_LIKE_QUOTES,
+    },
+    Language {
+        name: "Shell",
+        extensions: &["sh", "bash", "zsh", "fish"],
+        keywords: &[
+            "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+            "in", "local", "return", "then", "until", "while",
+        ],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: C_LIKE_QUOTES,
+    },
+    Language {
+        name: "Ruby",
+        extensions: &["rb"],
+        keywords: &[
+            "begin", "class", "def", "do", "else", "elsif", "end", "ensure", "false", "if",
+            "module", "nil", "require", "rescue", "return", "self", "true", "unless", "until",
+            "when", "while", "yield",
+        ],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: C_LIKE_QUOTES,
+    },
+    Language {
+        name: "Config",
+        extensions: &["toml", "yaml", "yml", "ini", "cfg", "conf"],
+        keywords: &["true", "false", "null", "yes", "no", "on", "off"],
+        line_comments: &["#", ";"],
+        block_comment: None,
+        quotes: C_LIKE_QUOTES,
+    },
+    Language {
+        name: "JSON",
+        extensions: &["json", "jsonc"],
+        keywords: &["true", "false", "null"],
+        line_comments: &["//"],
+        block_comment: None,
+        quotes: &['"'],
+    },
+    Language {
+        name: "SQL",
+        extensions: &["sql"],
+        keywords: &[
+            "and", "as", "by", "create", "delete", "from", "group", "insert", "into", "join",
+            "not", "null", "on", "or", "order", "select", "set", "table", "update", "values",
+            "where", "AND", "AS", "BY", "CREATE", "DELETE", "FROM", "GROUP", "INSERT", "INTO",
+            "JOIN", "NOT", "NULL", "ON", "OR", "ORDER", "SELECT", "SET", "TABLE", "UPDATE",
+            "VALUES", "WHERE",
+        ],
+        line_comments: &["--"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['\''],
+    },
+];
+
+/// Pick a language from a file path's extension.
+pub fn language_for(path: &str) -> Option<&'static Language> {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let (_, ext) = file_name.rsplit_once('.')?;
+    let ext = ext.to_ascii_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|lang| lang.extensions.contains(&ext.as_str()))
+}
+
+/// Split `text` into contiguous, non-overlapping token ranges covering the whole line.
+pub fn tokenize(lang: &Language, text: &str) -> Vec<(Range<usize>, TokenKind)> {
+    let mut tokens: Vec<(Range<usize>, TokenKind)> = Vec::new();
+    let mut push = |range: Range<usize>, kind: TokenKind| {
+        if range.is_empty() {
+            return;
+        }
+        // Merge adjacent plain runs so callers get fewer spans
+        if let Some((last, last_kind)) = tokens.last_mut()
+            && *last_kind == kind
+            && kind == TokenKind::Plain
+            && last.end == range.start
+        {
+            last.end = range.end;
+            return;
+        }
+        tokens.push((range, kind));
+    };
+
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+
+        if lang.line_comments.iter().any(|c| rest.starts_with(c)) {
+            push(i..text.len(), TokenKind::Comment);
+            break;
+        }
+        if let Some((open, close)) = lang.block_comment
+            && rest.starts_with(open)
+        {
+            let end = rest[open.len()..]
+                .find(close)
+                .map_or(text.len(), |p| i + open.len() + p + close.len());
+            push(i..end, TokenKind::Comment);
+            i = end;
+            continue;
+        }
+
+        let c = rest.chars().next().unwrap_or(' ');
+        if lang.quotes.contains(&c) {
+            let end = string_end(text, i, c);
+            push(i..end, TokenKind::String);
+            i = end;
+        } else if c.is_ascii_digit() {
+            let end = i + rest
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_' || ch == '.'))
+                .unwrap_or(rest.len());
+            push(i..end, TokenKind::Number);
+            i = end;
+        } else if c.is_alphabetic() || c == '_' {
+            let end = i + rest
+                .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                .unwrap_or(rest.len());
+            let word = &text[i..end];
+            let kind = if lang.keywords.contains(&word) {
+                TokenKind::Keyword
+            } else if word.starts_with(char::is_uppercase) {
+                TokenKind::Type
+            } else {
+                TokenKind::Plain
+            };
+            push(i..end, kind);
+            i = end;
+        } else {
+            let end = i + c.len_utf8();
+            push(i..end, TokenKind::Plain);
+            i = end;
+        }
+    }
+    tokens
+}
+
+/// Byte offset just past the string starting at `start`, honouring backslash escapes.
+/// Unterminated strings run to the end of the line.
+fn string_end(text: &str, start: usize, quote: char) -> usize {
+    let mut escaped = false;
+    for (offset, ch) in text[start + quote.len_utf8()..].char_indices() {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == quote {
+            return start + quote.len_utf8() + offset + ch.len_utf8();
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds<'a>(text: &'a str, tokens: &[(Range<usize>, TokenKind)]) -> Vec<(&'a str, TokenKind)> {
+        tokens.iter().map(|(r, k)| (&text[r.clone()], *k)).collect()
+    }
+
+    #[test]
+    fn test_language_for_extension() {
+        assert_eq!(language_for("src/main.rs").unwrap().name, "Rust");
+        assert_eq!(language_for("web/App.TSX").unwrap().name, "JavaScript");
+        assert_eq!(language_for("Cargo.toml").unwrap().name, "Config");
+        assert!(language_for("README").is_none());
+        assert!(language_for("notes.txt").is_none());
+    }
+
+    #[test]
+    fn test_tokenize_rust_line() {
+        let lang = language_for("a.rs").unwrap();
+        let text = "let x: Vec<u8> = \"hi\\\"\"; // done";
+        let tokens = tokenize(lang, text);
+        let kinds = kinds(text, &tokens);
+        assert_eq!(kinds[0], ("let", TokenKind::Keyword));
+        assert!(kinds.contains(&("Vec", TokenKind::Type)));
+        assert!(kinds.contains(&("\"hi\\\"\"", TokenKind::String)));
+        assert_eq!(*kinds.last().unwrap(), ("// done", TokenKind::Comment));
+        // Tokens cover the whole line without gaps
+        let covered: usize = tokens.iter().map(|(r, _)| r.len()).sum();
+        assert_eq!(covered, text.len());
+    }
+
+    #[test]
+    fn test_tokenize_numbers_and_block_comment() {
+        let lang = language_for("a.c").unwrap();
+        let text = "int n = 42; /* x */ return n;";
+        let kinds = kinds(text, &tokenize(lang, text));
+        assert!(kinds.contains(&("42", TokenKind::Number)));
+        assert!(kinds.contains(&("/* x */", TokenKind::Comment)));
+        assert!(kinds.contains(&("return", TokenKind::Keyword)));
+    }
+
+    #[test]
+    fn test_tokenize_unterminated_string_and_unicode() {
+        let lang = language_for("a.py").unwrap();
+        let text = "s = 'héllo";
+        let kinds = kinds(text, &tokenize(lang, text));
+        assert_eq!(*kinds.last().unwrap(), ("'héllo", TokenKind::String));
+    }
+}
@@ -0,0 +1,466 @@
+//! Tag and release view — list, create, delete and push tags, draft release
+//! notes with AI and publish a GitHub Release.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::app::{ConfirmAction, InputAction, Popup};
+use crate::git;
+use crate::git::tag::{Tag, TagKind};
+
+/// Commits listed per range; long histories are cut off.
+pub const RANGE_LIMIT: usize = 200;
+
+#[derive(Default)]
+pub struct TagsState {
+    pub tags: Vec<Tag>,
+    pub selected: usize,
+    pub list_state: ListState,
+    /// Latest tag reachable from HEAD and the commits made since.
+    pub latest: Option<String>,
+    pub unreleased: Vec<git::CommitEntry>,
+    /// Tag preceding the selected one and the commits the selected tag adds.
+    pub previous: Option<String>,
+    pub range: Vec<git::CommitEntry>,
+    /// AI-drafted release notes as `(tag, notes)`.
+    pub release_notes: Option<(String, String)>,
+    pub notes_loading: bool,
+    pub detail_scroll: u16,
+    /// Tag being created while the name and message inputs are open.
+    pub pending_kind: Option<TagKind>,
+    pub pending_name: Option<String>,
+}
+
+impl TagsState {
+    pub fn refresh(&mut self) {
+        self.tags = git::tag::list().unwrap_or_default();
+        self.latest = git::tag::latest();
+        let since = git::tag::range_spec(self.latest.as_deref(), "HEAD");
+        self.unreleased = git::log::get_range(&since, RANGE_LIMIT).unwrap_or_default();
+
+        if self.selected >= self.tags.len() && !self.tags.is_empty() {
+            self.selected = self.tags.len() - 1;
+        }
+        self.list_state.select(if self.tags.is_empty() {
+            None
+        } else {
+            Some(self.selected)
+        });
+        self.update_range();
+    }
+
+    /// Select the tag called `name`, if listed.
+    pub fn select_name(&mut self, name: &str) {
+        if let Some(i) = self.tags.iter().position(|t| t.name == name) {
+            self.selected = i;
+            self.list_state.select(Some(i));
+            self.update_range();
+        }
+    }
+
+    pub fn selected_tag(&self) -> Option<&Tag> {
+        self.tags.get(self.selected)
+    }
+
+    /// Release notes drafted for `tag`, if any.
+    pub fn notes_for(&self, tag: &str) -> Option<&str> {
+        self.release_notes
+            .as_ref()
+            .filter(|(t, _)| t == tag)
+            .map(|(_, notes)| notes.as_str())
+    }
+
+    fn update_range(&mut self) {
+        self.detail_scroll = 0;
+        let Some(tag) = self.tags.get(self.selected) else {
+            self.previous = None;
+            self.range.clear();
+            return;
+        };
+        self.previous = git::tag::previous(&tag.name);
+        let spec = git::tag::range_spec(self.previous.as_deref(), &tag.name);
+        self.range = git::log::get_range(&spec, RANGE_LIMIT).unwrap_or_default();
+    }
+}
+
+/// Plain release body used when no AI notes were drafted.
+pub fn default_release_body(previous: Option<&str>, commits: &[git::CommitEntry]) -> String {
+    let mut body = match previous {
+        Some(prev) => format!("## Changes since {}\n\n", prev),
+        None => "## Changes\n\n".to_string(),
+    };
+    for c in commits {
+        body.push_str(&format!("- {} ({})\n", c.message, c.short_hash));
+    }
+    body
+}
+
+pub fn render(f: &mut Frame, area: Rect, state: &mut TagsState) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(40), // Tag list
+            Constraint::Percentage(60), // Unreleased + selected tag
+        ])
+        .split(area);
+
+    let items: Vec<ListItem> = state
+        .tags
+        .iter()
+        .map(|tag| {
+            let (icon, color) = match tag.kind {
+                TagKind::Lightweight => ("○", Color::DarkGray),
+                TagKind::Annotated => ("●", Color::Yellow),
+                TagKind::Signed => ("✓", Color::Green),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {} ", icon), Style::default().fg(color)),
+                Span::styled(
+                    &tag.name,
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("  {}", tag.target),
+                    Style::default().fg(Color::Magenta),
+                ),
+                Span::styled(
+                    format!("  {}", tag.date),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" Tags ({}) ", state.tags.len()),
+                    Style::default().fg(Color::White),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Magenta)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+
+    f.render_stateful_widget(list, chunks[0], &mut state.list_state);
+
+    if area.height > 5 && state.tags.is_empty() {
+        let hint = Paragraph::new(Line::from(Span::styled(
+            " No tags yet — press n, a or s to tag HEAD.",
+            Style::default().fg(Color::DarkGray),
+        )));
+        let hint_area = Rect {
+            x: chunks[0].x + 1,
+            y: chunks[0].y + 2,
+            width: chunks[0].width.saturating_sub(2),
+            height: 1,
+        };
+        f.render_widget(hint, hint_area);
+    }
+
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(chunks[1]);
+
+    render_unreleased(f, right[0], state);
+    render_release(f, right[1], state);
+}
+
+fn commit_line(c: &git::CommitEntry) -> Line<'_> {
+    Line::from(vec![
+        Span::styled(
+            format!(" {} ", c.short_hash),
+            Style::default().fg(Color::Magenta),
+        ),
+        Span::styled(&c.message, Style::default().fg(Color::White)),
+        Span::styled(
+            format!("  {}", c.author),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])
+}
+
+fn render_unreleased(f: &mut Frame, area: Rect, state: &TagsState) {
+    let title = match state.latest {
+        Some(ref latest) => format!(" Since {} ({}) ", latest, state.unreleased.len()),
+        None => format!(" Unreleased ({}) ", state.unreleased.len()),
+    };
+    let lines: Vec<Line> = if state.unreleased.is_empty() {
+        vec![Line::from(Span::styled(
+            " HEAD is tagged — nothing unreleased.",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        state.unreleased.iter().map(commit_line).collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(Span::styled(title, Style::default().fg(Color::White)))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn render_release(f: &mut Frame, area: Rect, state: &TagsState) {
+    let Some(tag) = state.selected_tag() else {
+        let empty = Paragraph::new("").block(
+            Block::default()
+                .title(" Release ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(" Type: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(tag.kind.label(), Style::default().fg(Color::Yellow)),
+        ]),
+        Line::from(vec![
+            Span::styled(" Message: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(&tag.subject, Style::default().fg(Color::White)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            match state.previous {
+                Some(ref prev) => format!(" {} commit(s) since {}", state.range.len(), prev),
+                None => format!(" {} commit(s) — first tag", state.range.len()),
+            },
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+    ];
+    lines.extend(state.range.iter().map(commit_line));
+
+    lines.push(Line::from(""));
+    if state.notes_loading {
+        lines.push(Line::from(Span::styled(
+            " ⏳ Drafting release notes...",
+            Style::default().fg(Color::Yellow),
+        )));
+    } else if let Some(notes) = state.notes_for(&tag.name) {
+        lines.push(Line::from(Span::styled(
+            " 🤖 Release notes (used by R):",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(notes.lines().map(|l| {
+            Line::from(Span::styled(
+                format!(" {}", l),
+                Style::default().fg(Color::Gray),
+            ))
+        }));
+    } else {
+        lines.push(Line::from(Span::styled(
+            " i: draft release notes with AI · R: create GitHub Release",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let detail = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} ", tag.name),
+                    Style::default().fg(Color::White),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .scroll((state.detail_scroll, 0))
+        .wrap(Wrap { trim: false });
+    f.render_widget(detail, area);
+}
+
+pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    let mut status_msg: Option<String> = None;
+    let mut ai_error: Option<String> = None;
+
+    {
+        let state = &mut app.tags_state;
+        let selected = state.selected_tag().map(|t| t.name.clone());
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+                state.selected -= 1;
+                state.list_state.select(Some(state.selected));
+                state.update_range();
+            }
+            KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < state.tags.len() => {
+                state.selected += 1;
+                state.list_state.select(Some(state.selected));
+                state.update_range();
+            }
+            KeyCode::Char(c @ ('n' | 'a' | 's')) => {
+                let kind = match c {
+                    'n' => TagKind::Lightweight,
+                    'a' => TagKind::Annotated,
+                    _ => TagKind::Signed,
+                };
+                state.pending_kind = Some(kind);
+                state.pending_name = None;
+                app.popup = Popup::Input {
+                    title: format!("New {} tag on HEAD", kind.label()),
+                    prompt: "Tag name: ".to_string(),
+                    value: String::new(),
+                    on_submit: InputAction::CreateTag,
+                };
+                return Ok(());
+            }
+            KeyCode::Char('d') => {
+                if let Some(name) = selected {
+                    app.popup = Popup::Confirm {
+                        title: "Delete Tag".to_string(),
+                        message: format!(
+                            "Delete local tag '{}'?\n\nA tag already pushed stays on the remote.\n\n[y] Yes  [n] No",
+                            name
+                        ),
+                        on_confirm: ConfirmAction::DeleteTag(name),
+                    };
+                }
+                return Ok(());
+            }
+            KeyCode::Char('p') => {
+                if let Some(name) = selected {
+                    match git::tag::push("origin", &name) {
+                        Ok(_) => status_msg = Some(format!("Pushed tag '{}' to origin", name)),
+                        Err(e) => {
+                            let err_str = e.to_string();
+                            status_msg = Some(format!("Error: {}", err_str));
+                            ai_error = Some(err_str);
+                        }
+                    }
+                }
+            }
+            KeyCode::Char('P') => match git::tag::push_all("origin") {
+                Ok(_) => status_msg = Some("Pushed all tags to origin".to_string()),
+                Err(e) => {
+                    let err_str = e.to_string();
+                    status_msg = Some(format!("Error: {}", err_str));
+                    ai_error = Some(err_str);
+                }
+            },
+            KeyCode::Char('i') => {
+                if let Some(name) = selected {
+                    let previous = state.previous.clone();
+                    let commits = state.range.clone();
+                    app.start_ai_release_notes(name, previous, commits);
+                }
+                return Ok(());
+            }
+            KeyCode::Char('R') => {
+                let Some(name) = selected else {
+                    return Ok(());
+                };
+                if !super::github::logged_in(&app.config.github) {
+                    app.set_status("Not logged in to GitHub — press g on the dashboard to log in");
+                    return Ok(());
+                }
+                let notes = if state.notes_for(&name).is_some() {
+                    "AI-drafted release notes"
+                } else {
+                    "the commit list (press i to draft notes with AI)"
+                };
+                let prerelease = if git::tag::is_prerelease(&name) {
+                    " as a pre-release"
+                } else {
+                    ""
+                };
+                app.popup = Popup::Confirm {
+                    title: "Create GitHub Release".to_string(),
+                    message: format!(
+                        "Push '{}' to origin and publish a GitHub Release{}?\n\nBody: {}\n\n[y] Yes  [n] No",
+                        name, prerelease, notes
+                    ),
+                    on_confirm: ConfirmAction::CreateRelease(name),
+                };
+                return Ok(());
+            }
+            KeyCode::PageDown => {
+                state.detail_scroll = state.detail_scroll.saturating_add(10);
+            }
+            KeyCode::PageUp => {
+                state.detail_scroll = state.detail_scroll.saturating_sub(10);
+            }
+            _ => {}
+        }
+
+        if status_msg.is_some() {
+            state.refresh();
+        }
+    }
+
+    if let Some(msg) = status_msg {
+        app.set_status(&msg);
+    }
+
+    if let Some(err) = ai_error {
+        app.start_ai_error_explain(err);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(short: &str, message: &str) -> git::CommitEntry {
+        git::CommitEntry {
+            hash: String::new(),
+            short_hash: short.to_string(),
+            message: message.to_string(),
+            author: "dev".to_string(),
+            date: String::new(),
+            date_iso: String::new(),
+            parents: Vec::new(),
+            refs: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_default_release_body() {
+        let commits = vec![
+            commit("abc1234", "Add tags view"),
+            commit("def5678", "Fix push"),
+        ];
+        let body = default_release_body(Some("v1.0.0"), &commits);
+        assert!(body.starts_with("## Changes since v1.0.0"));
+        assert!(body.contains("- Add tags view (abc1234)\n"));
+        assert!(body.contains("- Fix push (def5678)\n"));
+
+        let body = default_release_body(None, &[]);
+        assert_eq!(body, "## Changes\n\n");
+    }
+
+    #[test]
+    fn test_notes_for_matches_tag() {
+        let state = TagsState {
+            release_notes: Some(("v1.1.0".to_string(), "## Fixes".to_string())),
+            ..TagsState::default()
+        };
+        assert_eq!(state.notes_for("v1.1.0"), Some("## Fixes"));
+        assert!(state.notes_for("v1.0.0").is_none());
+    }
+}
@@ -1,10 +1,10 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
-    Frame,
 };
 
 use crate::git;
@@ -21,7 +21,7 @@ pub struct TimeTravelState {
 
 impl TimeTravelState {
     pub fn refresh(&mut self) {
-        match git::log::get_log(50, 0, None) {
+        match git::log::get_log(50, 0, None, &git::log::LogQuery::default()) {
             Ok(commits) => {
                 self.commits = commits;
                 if self.selected >= self.commits.len() && !self.commits.is_empty() {
@@ -222,17 +222,15 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
     }
 
     match key.code {
-        KeyCode::Up | KeyCode::Char('k') if state.ai_suggestion.is_none() => {
-            if state.selected > 0 {
-                state.selected -= 1;
-                state.list_state.select(Some(state.selected));
-            }
+        KeyCode::Up | KeyCode::Char('k') if state.ai_suggestion.is_none() && state.selected > 0 => {
+            state.selected -= 1;
+            state.list_state.select(Some(state.selected));
         }
-        KeyCode::Down | KeyCode::Char('j') if state.ai_suggestion.is_none() => {
-            if state.selected + 1 < state.commits.len() {
-                state.selected += 1;
-                state.list_state.select(Some(state.selected));
-            }
+        KeyCode::Down | KeyCode::Char('j')
+            if state.ai_suggestion.is_none() && state.selected + 1 < state.commits.len() =>
+        {
+            state.selected += 1;
+            state.list_state.select(Some(state.selected));
         }
         KeyCode::Char('i') => {
             // AI reset insight
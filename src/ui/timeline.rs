@@ -1,85 +1,256 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
-    Frame,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
 };
 
+use std::collections::HashSet;
+use std::ops::Range;
+
 use crate::git;
+use crate::ui::diff_view;
+use crate::ui::graph::{GraphLayout, GraphRow};
+
+/// Commits fetched per lazy load.
+const CHUNK: usize = 50;
+/// Rows kept loaded below the selection before fetching more.
+const LOOKAHEAD: usize = 10;
+/// Shortest run of plain linear commits that folds into one row.
+const FOLD_MIN: usize = 8;
+
+/// A line of the commit list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimelineRow {
+    Commit(usize),
+    /// Commits (indices into `commits`) folded into a single `⋯` row.
+    Folded(Range<usize>),
+}
 
 #[derive(Default)]
 pub struct TimelineState {
     pub commits: Vec<git::CommitEntry>,
+    /// Lane graph for `commits`; empty while a search is active.
+    pub graph: GraphLayout,
+    pub rows: Vec<TimelineRow>,
+    /// Selected row (index into `rows`).
     pub selected: usize,
     pub list_state: ListState,
+    /// No more commits to load below the last one.
+    pub exhausted: bool,
+    /// Show every commit instead of folding long linear runs.
+    pub show_all: bool,
+    /// First commits of runs the user unfolded.
+    unfolded: HashSet<String>,
     pub detail_commit: Option<git::CommitEntry>,
-    pub detail_diff: Vec<git::DiffLine>,
+    pub detail_diff: Vec<git::diff::FileDiff>,
     pub detail_scroll: u16,
+    pub diff_mode: diff_view::DiffMode,
     pub search_query: String,
-    pub page: usize,
+    /// Parsed `search_query`; results load lazily like the full log.
+    pub query: git::log::LogQuery,
     pub show_detail: bool,
 }
 
 impl TimelineState {
+    /// Reload everything loaded so far, keeping the selection.
     pub fn refresh(&mut self) {
-        let count = 100;
-        let skip = self.page * count;
-        match git::log::get_log(count, skip, None) {
+        let anchor = self.anchor();
+        let count = self.commits.len().max(CHUNK);
+        match git::log::get_log(count, 0, None, &self.query) {
             Ok(commits) => {
-                self.commits = commits;
-                if self.selected >= self.commits.len() && !self.commits.is_empty() {
-                    self.selected = self.commits.len() - 1;
-                }
-                self.list_state.select(if self.commits.is_empty() {
-                    None
+                self.exhausted = commits.len() < count;
+                self.graph = if self.query.is_empty() {
+                    GraphLayout::new(&commits)
                 } else {
-                    Some(self.selected)
-                });
+                    GraphLayout::default()
+                };
+                self.commits = commits;
             }
             Err(_) => {
                 self.commits = Vec::new();
+                self.graph = GraphLayout::default();
+                self.exhausted = true;
             }
         }
+        self.rebuild_rows(anchor);
     }
 
-    pub fn do_search(&mut self) {
-        if self.search_query.is_empty() {
-            self.refresh();
+    /// Fetch up to `count` commits after the loaded ones and extend the graph.
+    fn load_more(&mut self, count: usize) {
+        if self.exhausted {
             return;
         }
-        if let Ok(commits) = git::log::search_commits(&self.search_query, 100) {
-            self.commits = commits;
-            self.selected = 0;
-            self.list_state.select(if self.commits.is_empty() {
-                None
-            } else {
-                Some(0)
-            });
+        let anchor = self.anchor();
+        match git::log::get_log(count, self.commits.len(), None, &self.query) {
+            Ok(more) => {
+                self.exhausted = more.len() < count;
+                if self.query.is_empty() {
+                    self.graph.extend(&more);
+                }
+                self.commits.extend(more);
+            }
+            Err(_) => self.exhausted = true,
         }
+        self.rebuild_rows(anchor);
     }
 
-    fn load_detail(&mut self) {
-        if let Some(commit) = self.commits.get(self.selected) {
-            if commit.hash.is_empty() {
-                self.detail_commit = None;
-                self.detail_diff.clear();
-                return;
+    /// Load more commits while the selection is near the end of the list.
+    fn ensure_loaded(&mut self) {
+        while !self.exhausted && self.selected + LOOKAHEAD >= self.rows.len() {
+            self.load_more(CHUNK);
+        }
+    }
+
+    /// Move the selection by `delta` rows, loading further commits as needed.
+    pub fn move_selection(&mut self, delta: isize) {
+        let last = self.rows.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+        self.ensure_loaded();
+        self.list_state.select(Some(self.selected));
+    }
+
+    pub fn selected_commit(&self) -> Option<&git::CommitEntry> {
+        match self.rows.get(self.selected)? {
+            TimelineRow::Commit(i) => self.commits.get(*i),
+            TimelineRow::Folded(_) => None,
+        }
+    }
+
+    /// Commit that identifies the selected row across reloads.
+    fn anchor(&self) -> Option<String> {
+        let index = match self.rows.get(self.selected)? {
+            TimelineRow::Commit(i) => *i,
+            TimelineRow::Folded(range) => range.start,
+        };
+        self.commits.get(index).map(|c| c.hash.clone())
+    }
+
+    fn foldable(&self, index: usize) -> bool {
+        self.graph.rows.get(index).is_some_and(|r| r.linear) && self.commits[index].refs.is_empty()
+    }
+
+    /// Rebuild `rows` from `commits`, folding closed linear runs, then select
+    /// the row holding `anchor`.
+    fn rebuild_rows(&mut self, anchor: Option<String>) {
+        self.rows.clear();
+        let n = self.commits.len();
+        let mut i = 0;
+        while i < n {
+            if self.show_all || !self.foldable(i) {
+                self.rows.push(TimelineRow::Commit(i));
+                i += 1;
+                continue;
+            }
+            let mut end = i;
+            while end < n && self.foldable(end) {
+                end += 1;
             }
+            // A run touching the last loaded commit may still grow
+            let closed = end < n || self.exhausted;
+            if closed && end - i >= FOLD_MIN && !self.unfolded.contains(&self.commits[i].hash) {
+                self.rows.push(TimelineRow::Commit(i));
+                self.rows.push(TimelineRow::Folded(i + 1..end - 1));
+                self.rows.push(TimelineRow::Commit(end - 1));
+            } else {
+                self.rows.extend((i..end).map(TimelineRow::Commit));
+            }
+            i = end;
+        }
 
-            self.detail_commit = Some(commit.clone());
-            self.detail_diff.clear();
-            self.detail_scroll = 0;
+        let anchor_index = anchor.and_then(|hash| self.commits.iter().position(|c| c.hash == hash));
+        self.selected = match anchor_index {
+            Some(index) => self.row_of(index).unwrap_or(0),
+            None => self.selected.min(self.rows.len().saturating_sub(1)),
+        };
+        self.list_state.select(if self.rows.is_empty() {
+            None
+        } else {
+            Some(self.selected)
+        });
+    }
+
+    fn row_of(&self, index: usize) -> Option<usize> {
+        self.rows.iter().position(|row| match row {
+            TimelineRow::Commit(i) => *i == index,
+            TimelineRow::Folded(range) => range.contains(&index),
+        })
+    }
+
+    /// Expand the folded run containing commit `index`.
+    fn unfold(&mut self, index: usize) {
+        let Some(TimelineRow::Folded(range)) = self.row_of(index).map(|r| self.rows[r].clone())
+        else {
+            return;
+        };
+        // Runs are keyed by their first (visible) commit
+        let first = self.commits[range.start - 1].hash.clone();
+        self.unfolded.insert(first);
+        self.rebuild_rows(Some(self.commits[index].hash.clone()));
+    }
+
+    /// Apply `search_query` from the top. An empty query shows the full log again.
+    pub fn do_search(&mut self) -> anyhow::Result<()> {
+        self.query = git::log::LogQuery::parse(&self.search_query)?;
+        self.reset();
+        Ok(())
+    }
 
-            if let Ok(diffs) = git::diff::get_commit_diff(&commit.hash) {
-                for fd in &diffs {
-                    for hunk in &fd.hunks {
-                        self.detail_diff.extend(hunk.lines.clone());
-                    }
+    fn reset(&mut self) {
+        self.commits.clear();
+        self.rows.clear();
+        self.unfolded.clear();
+        self.exhausted = false;
+        self.selected = 0;
+        self.refresh();
+    }
+
+    /// Select `hash`, loading the log down to it when HEAD contains it, and
+    /// open its detail.
+    pub fn jump_to(&mut self, hash: &str) -> anyhow::Result<()> {
+        let found = match git::log::commit_position(hash)? {
+            Some(position) => {
+                if !self.query.is_empty() {
+                    self.search_query.clear();
+                    self.query = git::log::LogQuery::default();
+                    self.reset();
+                }
+                if position >= self.commits.len() {
+                    self.load_more(position + CHUNK - self.commits.len());
                 }
+                self.commits.iter().position(|c| c.hash.starts_with(hash))
+            }
+            None => None,
+        };
+        match found {
+            Some(index) => {
+                self.unfold(index);
+                self.selected = self.row_of(index).unwrap_or(0);
+                self.list_state.select(Some(self.selected));
+                self.load_detail();
+            }
+            None => {
+                // Not reachable from HEAD: show the commit on its own
+                let Some(commit) = git::log::get_range(hash, 1)?.into_iter().next() else {
+                    anyhow::bail!("commit {} not found", hash);
+                };
+                self.detail_diff = git::diff::get_commit_diff(&commit.hash).unwrap_or_default();
+                self.detail_commit = Some(commit);
+                self.detail_scroll = 0;
             }
         }
+        self.show_detail = true;
+        Ok(())
+    }
+
+    fn load_detail(&mut self) {
+        if let Some(commit) = self.selected_commit().cloned() {
+            self.detail_scroll = 0;
+            self.detail_diff = git::diff::get_commit_diff(&commit.hash).unwrap_or_default();
+            self.detail_commit = Some(commit);
+        }
     }
 }
 
@@ -91,56 +262,41 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut TimelineState) {
 
     // Commit list
     let items: Vec<ListItem> = state
-        .commits
+        .rows
         .iter()
-        .map(|c| {
-            let graph_span = if c.graph.is_empty() {
-                Span::raw("")
-            } else {
-                Span::styled(&c.graph, Style::default().fg(Color::Magenta))
-            };
-
-            if c.hash.is_empty() {
-                // Graph-only line
-                return ListItem::new(Line::from(vec![graph_span]));
-            }
-
-            let hash_span = Span::styled(
-                format!("{} ", c.short_hash),
-                Style::default().fg(Color::Yellow),
-            );
-
-            let refs_span = if c.refs.is_empty() {
-                Span::raw("")
-            } else {
-                Span::styled(
-                    format!("({}) ", c.refs),
+        .map(|row| match row {
+            TimelineRow::Commit(i) => commit_item(&state.commits[*i], state.graph.rows.get(*i)),
+            TimelineRow::Folded(range) => {
+                let mut spans = state
+                    .graph
+                    .rows
+                    .get(range.start - 1)
+                    .map(|r| r.filler_spans())
+                    .unwrap_or_default();
+                spans.push(Span::styled(
+                    format!("⋯ {} commits", range.len()),
                     Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD),
-                )
-            };
-
-            let msg_span = Span::styled(&c.message, Style::default().fg(Color::White));
-
-            let meta_span = Span::styled(
-                format!("  {} · {}", c.author, c.date),
-                Style::default().fg(Color::DarkGray),
-            );
-
-            ListItem::new(Line::from(vec![
-                graph_span, hash_span, refs_span, msg_span, meta_span,
-            ]))
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::ITALIC),
+                ));
+                ListItem::new(Line::from(spans))
+            }
         })
         .collect();
 
+    let more = if state.exhausted { "" } else { "+" };
     let title = if state.search_query.is_empty() {
-        format!(" Commit Timeline (page {}) ", state.page + 1)
+        format!(
+            " Commit Timeline ({}{} commits) ",
+            state.commits.len(),
+            more
+        )
     } else {
         format!(
-            " Search: '{}' ({} results) ",
+            " Search: '{}' ({}{} results) ",
             state.search_query,
-            state.commits.len()
+            state.commits.len(),
+            more
         )
     };
 
@@ -166,6 +322,28 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut TimelineState) {
     f.render_stateful_widget(list, area, &mut state.list_state);
 }
 
+fn commit_item<'a>(c: &'a git::CommitEntry, graph: Option<&GraphRow>) -> ListItem<'a> {
+    let mut spans = graph.map(GraphRow::spans).unwrap_or_default();
+    spans.push(Span::styled(
+        format!("{} ", c.short_hash),
+        Style::default().fg(Color::Yellow),
+    ));
+    if !c.refs.is_empty() {
+        spans.push(Span::styled(
+            format!("({}) ", c.refs),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    spans.push(Span::styled(&c.message, Style::default().fg(Color::White)));
+    spans.push(Span::styled(
+        format!("  {} · {}", c.author, c.date),
+        Style::default().fg(Color::DarkGray),
+    ));
+    ListItem::new(Line::from(spans))
+}
+
 fn render_detail(f: &mut Frame, area: Rect, state: &TimelineState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -211,31 +389,21 @@ fn render_detail(f: &mut Frame, area: Rect, state: &TimelineState) {
         f.render_widget(info, chunks[0]);
     }
 
-    let diff_lines: Vec<Line> = state
-        .detail_diff
-        .iter()
-        .map(|dl| {
-            let color = match dl.line_type {
-                git::DiffLineType::Added => Color::Green,
-                git::DiffLineType::Removed => Color::Red,
-                git::DiffLineType::Header => Color::Cyan,
-                git::DiffLineType::Context => Color::DarkGray,
-            };
-            Line::from(Span::styled(&dl.content, Style::default().fg(color)))
-        })
-        .collect();
-
-    let diff = Paragraph::new(diff_lines)
-        .block(
-            Block::default()
-                .title(Span::styled(" Diff ", Style::default().fg(Color::White)))
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
-        )
-        .scroll((state.detail_scroll, 0))
-        .wrap(Wrap { trim: false });
-
-    f.render_widget(diff, chunks[1]);
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" Diff ({}) ", state.diff_mode.label()),
+            Style::default().fg(Color::White),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    diff_view::render_files(
+        f,
+        chunks[1],
+        block,
+        &state.detail_diff,
+        state.diff_mode,
+        state.detail_scroll,
+    );
 }
 
 pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
@@ -260,34 +428,38 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 app.timeline_state.detail_scroll =
                     app.timeline_state.detail_scroll.saturating_sub(20);
             }
+            KeyCode::Char('v') => {
+                app.timeline_state.diff_mode = app.timeline_state.diff_mode.toggle();
+                app.timeline_state.detail_scroll = 0;
+            }
             _ => {}
         }
         return Ok(());
     }
 
     match key.code {
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.timeline_state.selected > 0 {
-                app.timeline_state.selected -= 1;
-                let sel = app.timeline_state.selected;
-                app.timeline_state.list_state.select(Some(sel));
-            }
-        }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.timeline_state.selected + 1 < app.timeline_state.commits.len() {
-                app.timeline_state.selected += 1;
-                let sel = app.timeline_state.selected;
-                app.timeline_state.list_state.select(Some(sel));
-            }
-        }
+        KeyCode::Up | KeyCode::Char('k') => app.timeline_state.move_selection(-1),
+        KeyCode::Down | KeyCode::Char('j') => app.timeline_state.move_selection(1),
+        KeyCode::PageUp => app.timeline_state.move_selection(-20),
+        KeyCode::PageDown => app.timeline_state.move_selection(20),
         KeyCode::Enter => {
-            if let Some(commit) = app.timeline_state.commits.get(app.timeline_state.selected) {
-                if !commit.hash.is_empty() {
-                    app.timeline_state.load_detail();
-                    app.timeline_state.show_detail = true;
+            let state = &mut app.timeline_state;
+            match state.rows.get(state.selected).cloned() {
+                Some(TimelineRow::Commit(_)) => {
+                    state.load_detail();
+                    state.show_detail = true;
                 }
+                Some(TimelineRow::Folded(range)) => state.unfold(range.start),
+                None => {}
             }
         }
+        KeyCode::Char('z') => {
+            let state = &mut app.timeline_state;
+            state.show_all = !state.show_all;
+            state.unfolded.clear();
+            let anchor = state.anchor();
+            state.rebuild_rows(anchor);
+        }
         KeyCode::Char('/') => {
             let query = app.timeline_state.search_query.clone();
             app.popup = crate::app::Popup::Input {
@@ -297,29 +469,32 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 on_submit: crate::app::InputAction::SearchCommits,
             };
         }
-        KeyCode::Char('y') => {
-            // Copy hash to clipboard
-            let selected = app.timeline_state.selected;
-            if let Some(commit) = app.timeline_state.commits.get(selected) {
-                if !commit.hash.is_empty() {
-                    let hash = commit.short_hash.clone();
-                    match cli_clipboard::set_contents(hash.clone()) {
-                        Ok(()) => app.set_status(format!("✓ Copied to clipboard: {}", hash)),
-                        Err(_) => app.set_status(format!("Copied (display only): {}", hash)),
-                    }
+        KeyCode::Char('F') => {
+            app.popup = crate::app::Popup::Input {
+                title: "File History".to_string(),
+                prompt: "Path: ".to_string(),
+                value: String::new(),
+                on_submit: crate::app::InputAction::FileHistory,
+            };
+        }
+        KeyCode::Char('R') => {
+            // Interactive rebase of the commits above the selected one
+            if let Some(commit) = app.timeline_state.selected_commit() {
+                let base = commit.hash.clone();
+                match app.rebase_state.plan(&base) {
+                    Ok(()) => app.view = crate::app::View::Rebase,
+                    Err(e) => app.set_status(format!("Error: {}", e)),
                 }
             }
         }
-        KeyCode::PageDown => {
-            app.timeline_state.page += 1;
-            app.timeline_state.selected = 0;
-            app.timeline_state.refresh();
-        }
-        KeyCode::PageUp => {
-            if app.timeline_state.page > 0 {
-                app.timeline_state.page -= 1;
-                app.timeline_state.selected = 0;
-                app.timeline_state.refresh();
+        KeyCode::Char('y') => {
+            // Copy hash to clipboard
+            if let Some(commit) = app.timeline_state.selected_commit() {
+                let hash = commit.short_hash.clone();
+                match cli_clipboard::set_contents(hash.clone()) {
+                    Ok(()) => app.set_status(format!("✓ Copied to clipboard: {}", hash)),
+                    Err(_) => app.set_status(format!("Copied (display only): {}", hash)),
+                }
             }
         }
         _ => {}
@@ -327,3 +502,90 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A linear history `c0` (newest) .. `c{n-1}`; the last is a root when `root`.
+    fn linear(n: usize, root: bool) -> Vec<git::CommitEntry> {
+        (0..n)
+            .map(|i| git::CommitEntry {
+                hash: format!("c{}", i),
+                short_hash: format!("c{}", i),
+                message: String::new(),
+                author: String::new(),
+                date: String::new(),
+                date_iso: String::new(),
+                parents: if root && i + 1 == n {
+                    vec![]
+                } else {
+                    vec![format!("c{}", i + 1)]
+                },
+                refs: String::new(),
+            })
+            .collect()
+    }
+
+    fn state(commits: Vec<git::CommitEntry>, exhausted: bool) -> TimelineState {
+        let mut state = TimelineState {
+            graph: GraphLayout::new(&commits),
+            commits,
+            exhausted,
+            ..Default::default()
+        };
+        state.rebuild_rows(None);
+        state
+    }
+
+    #[test]
+    fn test_long_linear_run_folds() {
+        let state = state(linear(12, true), true);
+        assert_eq!(
+            state.rows,
+            vec![
+                TimelineRow::Commit(0),
+                TimelineRow::Commit(1),
+                TimelineRow::Folded(2..10),
+                TimelineRow::Commit(10),
+                TimelineRow::Commit(11),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_at_end_of_loaded_commits_stays_open() {
+        // More commits may continue the run, so it is not folded yet
+        let state = state(linear(12, false), false);
+        assert_eq!(state.rows.len(), 12);
+    }
+
+    #[test]
+    fn test_decorated_commit_breaks_run() {
+        let mut commits = linear(12, true);
+        commits[5].refs = "tag: v1.0".to_string();
+        let state = state(commits, true);
+        assert!(
+            !state
+                .rows
+                .iter()
+                .any(|r| matches!(r, TimelineRow::Folded(_)))
+        );
+    }
+
+    #[test]
+    fn test_unfold_keeps_selection_on_commit() {
+        let mut state = state(linear(12, true), true);
+        state.unfold(6);
+        assert_eq!(state.rows.len(), 12);
+        assert_eq!(state.selected_commit().unwrap().hash, "c6");
+    }
+
+    #[test]
+    fn test_show_all_disables_folding() {
+        let mut state = state(linear(12, true), true);
+        state.show_all = true;
+        state.rebuild_rows(None);
+        assert_eq!(state.rows.len(), 12);
+    }
+}
@@ -1,5 +1,4 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::Color;
 
 /// Create a centered rectangle within a given area, using percentage-based sizing.
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -22,24 +21,6 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-/// Return the appropriate color for a unified-diff line.
-///
-/// Handles `+++`/`---` header lines (Yellow), `@@` hunk headers (Cyan),
-/// `+` additions (Green), `-` deletions (Red), and context (DarkGray).
-pub fn diff_line_color(line: &str) -> Color {
-    if line.starts_with("+++") || line.starts_with("---") {
-        Color::Yellow
-    } else if line.starts_with('+') {
-        Color::Green
-    } else if line.starts_with('-') {
-        Color::Red
-    } else if line.starts_with("@@") {
-        Color::Cyan
-    } else {
-        Color::DarkGray
-    }
-}
-
 /// Navigate a list selection by delta, clamping to bounds.
 /// Returns the new selected index.
 #[allow(dead_code)]
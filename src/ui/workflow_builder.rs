@@ -1,10 +1,10 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
-    Frame,
 };
 
 // ─── Data Model ────────────────────────────────────────────
@@ -641,20 +641,16 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
 fn handle_navigate_key(state: &mut WorkflowBuilderState, key: KeyEvent) {
     match key.code {
-        KeyCode::Left | KeyCode::Char('h') => {
-            if state.selected > 0 {
-                state.selected -= 1;
-            }
+        KeyCode::Left | KeyCode::Char('h') if state.selected > 0 => {
+            state.selected -= 1;
         }
-        KeyCode::Right | KeyCode::Char('l') => {
-            if !state.nodes.is_empty() && state.selected + 1 < state.nodes.len() {
-                state.selected += 1;
-            }
+        KeyCode::Right | KeyCode::Char('l')
+            if !state.nodes.is_empty() && state.selected + 1 < state.nodes.len() =>
+        {
+            state.selected += 1;
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if state.scroll > 0 {
-                state.scroll -= 1;
-            }
+        KeyCode::Up | KeyCode::Char('k') if state.scroll > 0 => {
+            state.scroll -= 1;
         }
         KeyCode::Down | KeyCode::Char('j') => {
             state.scroll += 1;
@@ -665,43 +661,33 @@ fn handle_navigate_key(state: &mut WorkflowBuilderState, key: KeyEvent) {
             state.mode = BuilderMode::EditName;
             state.input_buffer.clear();
         }
-        KeyCode::Enter => {
-            // Edit selected node — cycle name → uses → run
-            if !state.nodes.is_empty() {
-                state.adding_new = false;
-                state.mode = BuilderMode::EditName;
-                state.input_buffer = state.nodes[state.selected].name.clone();
-            }
-        }
-        KeyCode::Char('u') => {
-            // Edit uses directly
-            if !state.nodes.is_empty() {
-                state.mode = BuilderMode::EditUses;
-                state.input_buffer = state.nodes[state.selected].uses.clone().unwrap_or_default();
-            }
-        }
-        KeyCode::Char('r') => {
-            // Edit run directly
-            if !state.nodes.is_empty() {
-                state.mode = BuilderMode::EditRun;
-                state.input_buffer = state.nodes[state.selected].run.clone().unwrap_or_default();
-            }
-        }
-        KeyCode::Char('d') => {
-            // Delete selected node
-            if !state.nodes.is_empty() {
-                let name = state.nodes[state.selected].name.clone();
-                state.remove_selected();
-                state.status = Some(format!("Removed '{}'", name));
-            }
-        }
-        KeyCode::Char('c') => {
-            // Start connecting
-            if !state.nodes.is_empty() {
-                state.mode = BuilderMode::Connect;
-                state.connect_from = Some(state.nodes[state.selected].id);
-                state.status = Some("Select target node and press [c] to connect".to_string());
-            }
+        // Edit selected node — cycle name → uses → run
+        KeyCode::Enter if !state.nodes.is_empty() => {
+            state.adding_new = false;
+            state.mode = BuilderMode::EditName;
+            state.input_buffer = state.nodes[state.selected].name.clone();
+        }
+        // Edit uses directly
+        KeyCode::Char('u') if !state.nodes.is_empty() => {
+            state.mode = BuilderMode::EditUses;
+            state.input_buffer = state.nodes[state.selected].uses.clone().unwrap_or_default();
+        }
+        // Edit run directly
+        KeyCode::Char('r') if !state.nodes.is_empty() => {
+            state.mode = BuilderMode::EditRun;
+            state.input_buffer = state.nodes[state.selected].run.clone().unwrap_or_default();
+        }
+        // Delete selected node
+        KeyCode::Char('d') if !state.nodes.is_empty() => {
+            let name = state.nodes[state.selected].name.clone();
+            state.remove_selected();
+            state.status = Some(format!("Removed '{}'", name));
+        }
+        // Start connecting
+        KeyCode::Char('c') if !state.nodes.is_empty() => {
+            state.mode = BuilderMode::Connect;
+            state.connect_from = Some(state.nodes[state.selected].id);
+            state.status = Some("Select target node and press [c] to connect".to_string());
         }
         KeyCode::Char('g') => {
             // Generate YAML
@@ -769,22 +755,22 @@ fn handle_edit_key(state: &mut WorkflowBuilderState, key: KeyEvent) {
                             state.nodes[state.selected].uses.clone().unwrap_or_default();
                     }
                 }
-                BuilderMode::EditUses => {
-                    if !state.nodes.is_empty() && state.selected < state.nodes.len() {
-                        state.nodes[state.selected].uses =
-                            if value.is_empty() { None } else { Some(value) };
-                        state.mode = BuilderMode::EditRun;
-                        state.input_buffer =
-                            state.nodes[state.selected].run.clone().unwrap_or_default();
-                    }
+                BuilderMode::EditUses
+                    if !state.nodes.is_empty() && state.selected < state.nodes.len() =>
+                {
+                    state.nodes[state.selected].uses =
+                        if value.is_empty() { None } else { Some(value) };
+                    state.mode = BuilderMode::EditRun;
+                    state.input_buffer =
+                        state.nodes[state.selected].run.clone().unwrap_or_default();
                 }
-                BuilderMode::EditRun => {
-                    if !state.nodes.is_empty() && state.selected < state.nodes.len() {
-                        state.nodes[state.selected].run =
-                            if value.is_empty() { None } else { Some(value) };
-                        state.mode = BuilderMode::Navigate;
-                        state.status = Some("✓ Step updated".to_string());
-                    }
+                BuilderMode::EditRun
+                    if !state.nodes.is_empty() && state.selected < state.nodes.len() =>
+                {
+                    state.nodes[state.selected].run =
+                        if value.is_empty() { None } else { Some(value) };
+                    state.mode = BuilderMode::Navigate;
+                    state.status = Some("✓ Step updated".to_string());
                 }
                 _ => {}
             }
@@ -852,15 +838,13 @@ fn handle_connect_key(state: &mut WorkflowBuilderState, key: KeyEvent) {
             state.connect_from = None;
             state.status = None;
         }
-        KeyCode::Left | KeyCode::Char('h') => {
-            if state.selected > 0 {
-                state.selected -= 1;
-            }
+        KeyCode::Left | KeyCode::Char('h') if state.selected > 0 => {
+            state.selected -= 1;
         }
-        KeyCode::Right | KeyCode::Char('l') => {
-            if !state.nodes.is_empty() && state.selected + 1 < state.nodes.len() {
-                state.selected += 1;
-            }
+        KeyCode::Right | KeyCode::Char('l')
+            if !state.nodes.is_empty() && state.selected + 1 < state.nodes.len() =>
+        {
+            state.selected += 1;
         }
         KeyCode::Char('c') | KeyCode::Enter => {
             if let Some(from_id) = state.connect_from {
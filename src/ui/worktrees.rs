@@ -0,0 +1,274 @@
+//! Worktree manager UI — list linked worktrees, create, remove, lock, prune, and jump into one.
+
+use crate::git;
+use crate::git::worktree::Worktree;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Cell, Row, Table, TableState},
+};
+
+#[derive(Default)]
+pub struct WorktreesState {
+    pub worktrees: Vec<Worktree>,
+    /// Changed-file count per worktree; `None` when it could not be read
+    /// (bare, missing directory).
+    pub dirty: Vec<Option<usize>>,
+    pub selected: usize,
+    pub table_state: TableState,
+    /// Index of the worktree zit is currently running in.
+    pub current: Option<usize>,
+}
+
+impl WorktreesState {
+    pub fn refresh(&mut self) {
+        self.worktrees = git::worktree::list().unwrap_or_default();
+        self.dirty = self
+            .worktrees
+            .iter()
+            .map(|wt| {
+                if wt.is_bare || wt.prunable.is_some() {
+                    None
+                } else {
+                    git::worktree::dirty_count(&wt.path).ok()
+                }
+            })
+            .collect();
+        // Compare canonical paths: git may report symlinked or relative forms
+        let root = git::repo::current()
+            .ok()
+            .and_then(|r| std::fs::canonicalize(r.root()).ok());
+        self.current = root.and_then(|root| {
+            self.worktrees
+                .iter()
+                .position(|wt| std::fs::canonicalize(&wt.path).is_ok_and(|p| p == root))
+        });
+
+        if self.selected >= self.worktrees.len() && !self.worktrees.is_empty() {
+            self.selected = self.worktrees.len() - 1;
+        }
+        self.table_state.select(if self.worktrees.is_empty() {
+            None
+        } else {
+            Some(self.selected)
+        });
+    }
+
+    /// Select the worktree at `path`, if listed.
+    pub fn select_path(&mut self, path: &std::path::Path) {
+        if let Some(i) = self.worktrees.iter().position(|wt| wt.path == path) {
+            self.selected = i;
+            self.table_state.select(Some(i));
+        }
+    }
+
+    fn is_current(&self, index: usize) -> bool {
+        self.current == Some(index)
+    }
+}
+
+pub fn render(f: &mut Frame, area: Rect, state: &mut WorktreesState) {
+    let header_cells = ["", "Path", "Branch", "HEAD", "State"].iter().map(|h| {
+        Cell::from(*h).style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+    });
+    let header = Row::new(header_cells).height(1);
+
+    let rows: Vec<Row> = state
+        .worktrees
+        .iter()
+        .zip(&state.dirty)
+        .enumerate()
+        .map(|(i, (wt, dirty))| {
+            let is_current = state.is_current(i);
+            let marker = if is_current { "●" } else { " " };
+            let path_style = if is_current {
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+
+            let (state_text, state_color) = worktree_state(wt, *dirty);
+            let mut path = wt.path.display().to_string();
+            if wt.is_main {
+                path.push_str(" (main)");
+            }
+
+            Row::new(vec![
+                Cell::from(marker).style(Style::default().fg(Color::Green)),
+                Cell::from(path).style(path_style),
+                Cell::from(wt.label()).style(Style::default().fg(Color::Magenta)),
+                Cell::from(wt.head.chars().take(7).collect::<String>())
+                    .style(Style::default().fg(Color::Yellow)),
+                Cell::from(state_text).style(Style::default().fg(state_color)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(2),
+            Constraint::Percentage(45),
+            Constraint::Percentage(20),
+            Constraint::Length(9),
+            Constraint::Percentage(25),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(Span::styled(
+                format!(" Worktrees ({}) ", state.worktrees.len()),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    )
+    .row_highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol("▶ ");
+
+    f.render_stateful_widget(table, area, &mut state.table_state);
+}
+
+/// Short description and colour of a worktree's state.
+fn worktree_state(wt: &Worktree, dirty: Option<usize>) -> (String, Color) {
+    let mut parts = Vec::new();
+    let mut color = Color::Green;
+
+    if wt.prunable.is_some() {
+        parts.push("prunable".to_string());
+        color = Color::Red;
+    } else if wt.is_bare {
+        parts.push("bare".to_string());
+        color = Color::DarkGray;
+    } else {
+        match dirty {
+            Some(0) => parts.push("clean".to_string()),
+            Some(n) => {
+                parts.push(format!("{} changed", n));
+                color = Color::Yellow;
+            }
+            None => {
+                parts.push("unknown".to_string());
+                color = Color::DarkGray;
+            }
+        }
+    }
+    if wt.locked.is_some() {
+        parts.push("🔒 locked".to_string());
+    }
+    (parts.join(" · "), color)
+}
+
+pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    let state = &mut app.worktrees_state;
+
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+            state.selected -= 1;
+            state.table_state.select(Some(state.selected));
+        }
+        KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < state.worktrees.len() => {
+            state.selected += 1;
+            state.table_state.select(Some(state.selected));
+        }
+        KeyCode::Enter => {
+            let Some(wt) = state.worktrees.get(state.selected).cloned() else {
+                return Ok(());
+            };
+            if state.is_current(state.selected) {
+                app.set_status("Already in this worktree");
+            } else if wt.is_bare || wt.prunable.is_some() {
+                app.set_status("This worktree has no checkout to enter");
+            } else {
+                app.enter_worktree(&wt.path);
+            }
+        }
+        KeyCode::Char('n') => {
+            app.popup = crate::app::Popup::Input {
+                title: "New Worktree".to_string(),
+                prompt: "New branch name: ".to_string(),
+                value: String::new(),
+                on_submit: crate::app::InputAction::AddWorktree,
+            };
+        }
+        KeyCode::Char('d') => {
+            let Some(wt) = state.worktrees.get(state.selected) else {
+                return Ok(());
+            };
+            if wt.is_main {
+                app.set_status("Cannot remove the main worktree");
+                return Ok(());
+            }
+            if state.is_current(state.selected) {
+                app.set_status("Cannot remove the worktree zit is running in");
+                return Ok(());
+            }
+            if wt.locked.is_some() {
+                app.set_status("Worktree is locked — unlock it first (l)");
+                return Ok(());
+            }
+            let dirty = state.dirty.get(state.selected).copied().flatten();
+            let warning = match dirty {
+                Some(n) if n > 0 => format!("\n\n⚠ {} uncommitted change(s) will be lost.", n),
+                _ => String::new(),
+            };
+            app.popup = crate::app::Popup::Confirm {
+                title: "Remove Worktree".to_string(),
+                message: format!(
+                    "Remove worktree '{}' ({})?{}\n\n[y] Yes  [n] No",
+                    wt.path.display(),
+                    wt.label(),
+                    warning
+                ),
+                on_confirm: crate::app::ConfirmAction::RemoveWorktree {
+                    path: wt.path.clone(),
+                    force: dirty.is_some_and(|n| n > 0),
+                },
+            };
+        }
+        KeyCode::Char('l') => {
+            let Some(wt) = state.worktrees.get(state.selected) else {
+                return Ok(());
+            };
+            if wt.is_main {
+                app.set_status("The main worktree cannot be locked");
+                return Ok(());
+            }
+            let (result, done) = if wt.locked.is_some() {
+                (git::worktree::unlock(&wt.path), "Unlocked")
+            } else {
+                (git::worktree::lock(&wt.path, None), "Locked")
+            };
+            let msg = match result {
+                Ok(()) => format!("{} {}", done, wt.path.display()),
+                Err(e) => format!("Error: {}", e),
+            };
+            state.refresh();
+            app.set_status(msg);
+        }
+        KeyCode::Char('p') => {
+            let msg = match git::worktree::prune() {
+                Ok(pruned) if pruned.is_empty() => "Nothing to prune".to_string(),
+                Ok(pruned) => format!("Pruned {} stale worktree(s)", pruned.len()),
+                Err(e) => format!("Error: {}", e),
+            };
+            state.refresh();
+            app.set_status(msg);
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
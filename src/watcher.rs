@@ -0,0 +1,326 @@
+//! Filesystem watcher that turns repository changes into `AppEvent::RepoChanged`.
+//!
+//! Watches `.git/index`, `.git/HEAD`, `.git/refs` and every non-ignored
+//! directory of the worktree. Bursts of changes (a checkout, a build writing
+//! files) are debounced into a single event.
+
+use anyhow::Result;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::event::AppEvent;
+use crate::git::repo::Repository;
+
+/// Quiet period after the last change before a refresh is sent.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Upper bound on how long a continuous stream of changes can delay a refresh.
+const MAX_DELAY: Duration = Duration::from_secs(1);
+
+/// Files directly inside the git dir whose changes affect what zit shows.
+const GIT_STATE_FILES: &[&str] = &[
+    "index",
+    "HEAD",
+    "packed-refs",
+    "MERGE_HEAD",
+    "CHERRY_PICK_HEAD",
+    "REBASE_HEAD",
+    "BISECT_LOG",
+];
+
+type SharedWatcher = Arc<Mutex<RecommendedWatcher>>;
+
+/// Keeps the underlying OS watcher alive. Dropping it stops the events.
+pub struct RepoWatcher {
+    _watcher: SharedWatcher,
+}
+
+impl RepoWatcher {
+    /// Start watching `repo`, sending `AppEvent::RepoChanged` on `tx`.
+    pub fn start(repo: &Repository, tx: mpsc::Sender<AppEvent>) -> Result<Self> {
+        let (raw_tx, raw_rx) = mpsc::channel();
+        let watcher: SharedWatcher = Arc::new(Mutex::new(notify::recommended_watcher(raw_tx)?));
+
+        {
+            let mut w = watcher.lock().unwrap_or_else(|e| e.into_inner());
+            w.watch(repo.git_dir(), RecursiveMode::NonRecursive)?;
+            w.watch(&repo.common_dir().join("refs"), RecursiveMode::Recursive)?;
+            if repo.common_dir() != repo.git_dir() {
+                w.watch(repo.common_dir(), RecursiveMode::NonRecursive)?;
+            }
+        }
+
+        let filter = ChangeFilter::new(repo.root(), repo.git_dir(), repo.common_dir());
+        let weak = Arc::downgrade(&watcher);
+        let root = repo.root().to_path_buf();
+
+        // Walking a large worktree can take a while — do it off the UI thread
+        thread::spawn(move || {
+            watch_tree(&weak, &root);
+            debounce_loop(raw_rx, filter, &weak, tx);
+        });
+
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+/// Add non-recursive watches for `dir` and every non-ignored directory below it.
+fn watch_tree(watcher: &Weak<Mutex<RecommendedWatcher>>, dir: &Path) {
+    let Some(watcher) = watcher.upgrade() else {
+        return;
+    };
+    let mut w = watcher.lock().unwrap_or_else(|e| e.into_inner());
+
+    let walker = ignore::WalkBuilder::new(dir)
+        .hidden(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+    for entry in walker.flatten() {
+        if entry.file_type().is_some_and(|t| t.is_dir())
+            && let Err(e) = w.watch(entry.path(), RecursiveMode::NonRecursive)
+        {
+            log::debug!("Cannot watch {}: {}", entry.path().display(), e);
+        }
+    }
+}
+
+/// Collapse raw filesystem events into debounced `RepoChanged` events.
+fn debounce_loop(
+    raw_rx: mpsc::Receiver<notify::Result<notify::Event>>,
+    mut filter: ChangeFilter,
+    watcher: &Weak<Mutex<RecommendedWatcher>>,
+    tx: mpsc::Sender<AppEvent>,
+) {
+    // Start of the current burst and time of its latest relevant change
+    let mut pending: Option<(Instant, Instant)> = None;
+
+    loop {
+        let received = match pending {
+            None => raw_rx
+                .recv()
+                .map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+            Some((first, last)) => {
+                let deadline = (last + DEBOUNCE).min(first + MAX_DELAY);
+                raw_rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+        };
+
+        match received {
+            Ok(Ok(event)) => {
+                for dir in filter.new_dirs(&event) {
+                    watch_tree(watcher, &dir);
+                }
+                if filter.is_relevant_event(&event) {
+                    let now = Instant::now();
+                    pending = Some(pending.map_or((now, now), |(first, _)| (first, now)));
+                }
+            }
+            Ok(Err(e)) => log::debug!("Watcher error: {}", e),
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                pending = None;
+                if tx.send(AppEvent::RepoChanged).is_err() {
+                    return;
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+/// Decides which changed paths matter for the repository state.
+pub struct ChangeFilter {
+    root: PathBuf,
+    git_dir: PathBuf,
+    common_dir: PathBuf,
+    ignore: Gitignore,
+}
+
+impl ChangeFilter {
+    pub fn new(root: &Path, git_dir: &Path, common_dir: &Path) -> Self {
+        let mut filter = Self {
+            root: root.to_path_buf(),
+            git_dir: git_dir.to_path_buf(),
+            common_dir: common_dir.to_path_buf(),
+            ignore: Gitignore::empty(),
+        };
+        filter.reload_ignores();
+        filter
+    }
+
+    /// Rebuild the ignore matcher from `.gitignore` and `.git/info/exclude`.
+    fn reload_ignores(&mut self) {
+        let mut builder = GitignoreBuilder::new(&self.root);
+        for file in [
+            self.root.join(".gitignore"),
+            self.common_dir.join("info/exclude"),
+        ] {
+            if file.exists()
+                && let Some(e) = builder.add(&file)
+            {
+                log::debug!("Bad ignore file {}: {}", file.display(), e);
+            }
+        }
+        self.ignore = builder.build().unwrap_or_else(|_| Gitignore::empty());
+    }
+
+    /// Whether an event should trigger a refresh. Reloads ignore rules when
+    /// `.gitignore` itself changes.
+    fn is_relevant_event(&mut self, event: &notify::Event) -> bool {
+        if matches!(event.kind, EventKind::Access(_)) {
+            return false;
+        }
+        if event
+            .paths
+            .iter()
+            .any(|p| p == &self.root.join(".gitignore"))
+        {
+            self.reload_ignores();
+        }
+        event.need_rescan() || event.paths.iter().any(|p| self.is_relevant(p))
+    }
+
+    /// Directories created by this event that need watches of their own.
+    fn new_dirs(&self, event: &notify::Event) -> Vec<PathBuf> {
+        if !matches!(event.kind, EventKind::Create(_)) {
+            return Vec::new();
+        }
+        event
+            .paths
+            .iter()
+            .filter(|p| p.is_dir() && self.is_relevant(p))
+            .cloned()
+            .collect()
+    }
+
+    /// Whether a change to `path` can affect status, branches or history.
+    pub fn is_relevant(&self, path: &Path) -> bool {
+        if path.extension().is_some_and(|ext| ext == "lock") {
+            return false;
+        }
+
+        for dir in [&self.git_dir, &self.common_dir] {
+            if let Ok(rel) = path.strip_prefix(dir) {
+                return rel.starts_with("refs")
+                    || GIT_STATE_FILES.iter().any(|f| rel == Path::new(f));
+            }
+        }
+
+        let Ok(rel) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        // Nested repositories (submodules) manage their own .git
+        if rel.components().any(|c| c.as_os_str() == ".git") {
+            return false;
+        }
+        !self
+            .ignore
+            .matched_path_or_any_parents(rel, path.is_dir())
+            .is_ignore()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn setup(gitignore: &str) -> (tempfile::TempDir, ChangeFilter) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join(".git/refs/heads")).unwrap();
+        fs::write(root.join(".gitignore"), gitignore).unwrap();
+        let git_dir = root.join(".git");
+        let filter = ChangeFilter::new(root, &git_dir, &git_dir);
+        (dir, filter)
+    }
+
+    #[test]
+    fn test_worktree_files_are_relevant() {
+        let (dir, filter) = setup("");
+        assert!(filter.is_relevant(&dir.path().join("src/main.rs")));
+        assert!(filter.is_relevant(&dir.path().join("README.md")));
+    }
+
+    #[test]
+    fn test_gitignored_paths_are_skipped() {
+        let (dir, filter) = setup("target/\n*.log\n");
+        fs::create_dir_all(dir.path().join("target/debug")).unwrap();
+        assert!(!filter.is_relevant(&dir.path().join("target/debug/zit")));
+        assert!(!filter.is_relevant(&dir.path().join("build.log")));
+        assert!(filter.is_relevant(&dir.path().join("src/lib.rs")));
+    }
+
+    #[test]
+    fn test_git_state_files_are_relevant() {
+        let (dir, filter) = setup("");
+        let git = dir.path().join(".git");
+        assert!(filter.is_relevant(&git.join("index")));
+        assert!(filter.is_relevant(&git.join("HEAD")));
+        assert!(filter.is_relevant(&git.join("refs/heads/main")));
+        assert!(filter.is_relevant(&git.join("MERGE_HEAD")));
+    }
+
+    #[test]
+    fn test_git_internals_are_skipped() {
+        let (dir, filter) = setup("");
+        let git = dir.path().join(".git");
+        assert!(!filter.is_relevant(&git.join("index.lock")));
+        assert!(!filter.is_relevant(&git.join("refs/heads/main.lock")));
+        assert!(!filter.is_relevant(&git.join("objects/ab/cdef")));
+        assert!(!filter.is_relevant(&git.join("COMMIT_EDITMSG")));
+        assert!(!filter.is_relevant(&dir.path().join("vendor/lib/.git/index")));
+    }
+
+    #[test]
+    fn test_paths_outside_repo_are_skipped() {
+        let (_dir, filter) = setup("");
+        assert!(!filter.is_relevant(Path::new("/definitely/not/in/repo.txt")));
+    }
+
+    #[test]
+    fn test_gitignore_change_reloads_rules() {
+        let (dir, mut filter) = setup("");
+        let log_file = dir.path().join("debug.log");
+        assert!(filter.is_relevant(&log_file));
+
+        fs::write(dir.path().join(".gitignore"), "*.log\n").unwrap();
+        let event = notify::Event::new(EventKind::Modify(notify::event::ModifyKind::Any))
+            .add_path(dir.path().join(".gitignore"));
+        assert!(filter.is_relevant_event(&event));
+        assert!(!filter.is_relevant(&log_file));
+    }
+
+    #[test]
+    fn test_access_events_are_ignored() {
+        let (dir, mut filter) = setup("");
+        let event = notify::Event::new(EventKind::Access(notify::event::AccessKind::Any))
+            .add_path(dir.path().join("src/main.rs"));
+        assert!(!filter.is_relevant_event(&event));
+    }
+
+    #[test]
+    fn test_watcher_reports_worktree_change() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let status = std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let repo = Repository::discover(dir.path()).unwrap();
+        let (tx, rx) = mpsc::channel();
+        let _watcher = RepoWatcher::start(&repo, tx).unwrap();
+
+        // Give the background walk time to register the worktree watch
+        thread::sleep(Duration::from_millis(300));
+        fs::write(dir.path().join("new.txt"), "hello").unwrap();
+
+        let event = rx.recv_timeout(Duration::from_secs(5));
+        assert!(matches!(event, Ok(AppEvent::RepoChanged)));
+    }
+}